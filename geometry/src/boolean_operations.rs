@@ -2,6 +2,7 @@ use super::*;
 use crate::simplex_overlap::simplex_interior_overlap;
 use crate::{
     ambient_space::common_space,
+    bounding_box::BoundingBoxTree,
     convex_hull::ConvexHull,
     partial_simplicial_complex::{LabelledPartialSimplicialComplex, PartialSimplicialComplex},
     simplex::Simplex,
@@ -106,6 +107,11 @@ where
         other: &LabelledSimplicialDisjointUnion<'f, FS, S>,
     ) -> LabelledSimplicialDisjointUnion<'f, FS, T> {
         let ambient_space = common_space(self.ambient_space(), other.ambient_space()).unwrap();
+        // only simplexes with overlapping bounding boxes can overlap
+        let other_simplexes = BoundingBoxTree::from_simplexes(
+            ambient_space,
+            other.simplexes().into_iter().map(|spx| (spx, spx)),
+        );
 
         Self::new_labelled_unchecked(
             ambient_space,
@@ -115,7 +121,9 @@ where
                 .into_par_iter()
                 .map(|(self_spx, self_spx_label)| {
                     let mut self_leftover = HashSet::from([self_spx.clone()]);
-                    for other_spx in other.simplexes() {
+                    for other_spx in self_spx.bounding_box().map_or(vec![], |bounding_box| {
+                        other_simplexes.query_box(&bounding_box)
+                    }) {
                         self_leftover = self_leftover
                             .into_iter()
                             .flat_map(|self_leftover_spx| {
@@ -140,10 +148,21 @@ where
         other: &LabelledSimplicialDisjointUnion<'f, FS, S>,
     ) -> LabelledSimplicialDisjointUnion<'f, FS, (T, S)> {
         let ambient_space = common_space(self.ambient_space(), other.ambient_space()).unwrap();
+        // only simplexes with overlapping bounding boxes can overlap
+        let other_simplexes = BoundingBoxTree::from_simplexes(
+            ambient_space,
+            other
+                .labelled_simplexes()
+                .into_iter()
+                .map(|(spx, label)| (spx, (spx, label))),
+        );
         LabelledSimplicialDisjointUnion::new_labelled_unchecked(ambient_space, {
             let mut simplexes = HashMap::new();
             for (self_spx, self_spx_label) in self.labelled_simplexes() {
-                for (other_spx, other_spx_label) in other.labelled_simplexes() {
+                let Some(bounding_box) = self_spx.bounding_box() else {
+                    continue;
+                };
+                for &(other_spx, other_spx_label) in other_simplexes.query_box(&bounding_box) {
                     for spx in simplex_venn(self_spx, other_spx)
                         .subset_by_label(&VennLabel::Middle)
                         .into_simplexes()
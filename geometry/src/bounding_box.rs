@@ -0,0 +1,371 @@
+use super::*;
+use crate::{ambient_space::AffineSpace, simplex::Simplex, vector::Vector};
+use std::cmp::Ordering;
+
+/// A closed axis-aligned box given by its minimum and maximum corners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundingBox<'f, FS: OrderedRingSignature + FieldSignature> {
+    min: Vector<'f, FS>,
+    max: Vector<'f, FS>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> BoundingBox<'f, FS> {
+    /// The smallest box containing the points, or `None` if there are no points.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Vector<'f, FS>>) -> Option<Self>
+    where
+        'f: 'a,
+        FS: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounding_box = Self {
+            min: first.clone(),
+            max: first.clone(),
+        };
+        for point in points {
+            bounding_box.extend_by_point(point);
+        }
+        Some(bounding_box)
+    }
+
+    fn field(&self) -> &'f FS {
+        self.min.ambient_space().field()
+    }
+
+    fn extend_by_point(&mut self, point: &Vector<'f, FS>) {
+        assert_eq!(point.ambient_space(), self.ambient_space());
+        let field = self.field();
+        let space = self.ambient_space();
+        self.min = Vector::construct(space, |i| {
+            match field.ring_cmp(point.coordinate(i), self.min.coordinate(i)) {
+                Ordering::Less => point.coordinate(i).clone(),
+                _ => self.min.coordinate(i).clone(),
+            }
+        });
+        self.max = Vector::construct(space, |i| {
+            match field.ring_cmp(point.coordinate(i), self.max.coordinate(i)) {
+                Ordering::Greater => point.coordinate(i).clone(),
+                _ => self.max.coordinate(i).clone(),
+            }
+        });
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.min.ambient_space()
+    }
+
+    pub fn min(&self) -> &Vector<'f, FS> {
+        &self.min
+    }
+
+    pub fn max(&self) -> &Vector<'f, FS> {
+        &self.max
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut bounding_box = self.clone();
+        bounding_box.extend_by_point(&other.min);
+        bounding_box.extend_by_point(&other.max);
+        bounding_box
+    }
+
+    pub fn contains_point(&self, point: &Vector<'f, FS>) -> bool {
+        assert_eq!(point.ambient_space(), self.ambient_space());
+        let field = self.field();
+        (0..self.ambient_space().linear_dimension().unwrap()).all(|i| {
+            field.ring_cmp(self.min.coordinate(i), point.coordinate(i)) != Ordering::Greater
+                && field.ring_cmp(point.coordinate(i), self.max.coordinate(i)) != Ordering::Greater
+        })
+    }
+
+    /// Whether the closed boxes have a point in common.
+    pub fn intersects(&self, other: &Self) -> bool {
+        assert_eq!(other.ambient_space(), self.ambient_space());
+        let field = self.field();
+        (0..self.ambient_space().linear_dimension().unwrap()).all(|i| {
+            field.ring_cmp(self.min.coordinate(i), other.max.coordinate(i)) != Ordering::Greater
+                && field.ring_cmp(other.min.coordinate(i), self.max.coordinate(i))
+                    != Ordering::Greater
+        })
+    }
+
+    // twice the centre along axis i, which is enough for comparing centres
+    fn double_centre(&self, i: usize) -> FS::Set {
+        self.field()
+            .add(self.min.coordinate(i), self.max.coordinate(i))
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> Simplex<'f, FS> {
+    /// The smallest box containing the simplex, or `None` for the null simplex.
+    pub fn bounding_box(&self) -> Option<BoundingBox<'f, FS>> {
+        BoundingBox::from_points(self.points())
+    }
+}
+
+const LEAF_SIZE: usize = 8;
+
+#[derive(Debug, Clone)]
+enum BoundingBoxTreeNode<'f, FS: OrderedRingSignature + FieldSignature, V> {
+    Leaf(Vec<(BoundingBox<'f, FS>, V)>),
+    Branch(Box<[BoundedNode<'f, FS, V>; 2]>),
+}
+
+// a node together with the bounding box of everything in it
+type BoundedNode<'f, FS, V> = (BoundingBox<'f, FS>, BoundingBoxTreeNode<'f, FS, V>);
+
+impl<'f, FS: OrderedRingSignature + FieldSignature, V> BoundingBoxTreeNode<'f, FS, V> {
+    fn build(mut items: Vec<(BoundingBox<'f, FS>, V)>) -> BoundedNode<'f, FS, V> {
+        debug_assert!(!items.is_empty());
+        let bounding_box = items
+            .iter()
+            .skip(1)
+            .fold(items[0].0.clone(), |total, (item, _)| total.union(item));
+        if items.len() <= LEAF_SIZE {
+            return (bounding_box, Self::Leaf(items));
+        }
+        // split at the median along the axis where the centres of the boxes are most spread out
+        let field = bounding_box.field();
+        let spread = |k: usize| {
+            let centres = items
+                .iter()
+                .map(|(item, _)| item.double_centre(k))
+                .collect::<Vec<_>>();
+            let lowest = centres.iter().min_by(|a, b| field.ring_cmp(a, b)).unwrap();
+            let highest = centres.iter().max_by(|a, b| field.ring_cmp(a, b)).unwrap();
+            field.sub(highest, lowest)
+        };
+        let spreads = (0..bounding_box.ambient_space().linear_dimension().unwrap())
+            .map(spread)
+            .collect::<Vec<_>>();
+        // no axis separates the items when their centres all coincide, as always happens in a 0-dimensional space
+        let Some(axis) = (0..spreads.len())
+            .max_by(|i, j| field.ring_cmp(&spreads[*i], &spreads[*j]))
+            .filter(|axis| field.ring_cmp(&spreads[*axis], &field.zero()) == Ordering::Greater)
+        else {
+            return (bounding_box, Self::Leaf(items));
+        };
+        items.sort_by(|(a, _), (b, _)| {
+            field.ring_cmp(&a.double_centre(axis), &b.double_centre(axis))
+        });
+        let upper = items.split_off(items.len() / 2);
+        (
+            bounding_box,
+            Self::Branch(Box::new([Self::build(items), Self::build(upper)])),
+        )
+    }
+
+    fn query<'a>(
+        &'a self,
+        overlaps: &impl Fn(&BoundingBox<'f, FS>) -> bool,
+        found: &mut Vec<&'a V>,
+    ) {
+        match self {
+            Self::Leaf(items) => {
+                found.extend(
+                    items
+                        .iter()
+                        .filter(|(item, _)| overlaps(item))
+                        .map(|(_, value)| value),
+                );
+            }
+            Self::Branch(children) => {
+                for (child_box, child) in children.iter() {
+                    if overlaps(child_box) {
+                        child.query(overlaps, found);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A bounding volume hierarchy of boxes for finding the items whose boxes contain a point or meet a box
+/// without checking every item.
+#[derive(Debug, Clone)]
+pub struct BoundingBoxTree<'f, FS: OrderedRingSignature + FieldSignature, V> {
+    ambient_space: AffineSpace<'f, FS>,
+    root: Option<BoundedNode<'f, FS, V>>,
+    len: usize,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature, V> BoundingBoxTree<'f, FS, V> {
+    pub fn new(ambient_space: AffineSpace<'f, FS>, items: Vec<(BoundingBox<'f, FS>, V)>) -> Self {
+        for (item, _) in &items {
+            assert_eq!(item.ambient_space(), ambient_space);
+        }
+        let len = items.len();
+        Self {
+            ambient_space,
+            root: if items.is_empty() {
+                None
+            } else {
+                Some(BoundingBoxTreeNode::build(items))
+            },
+            len,
+        }
+    }
+
+    /// A tree of the boxes of some simplices, where null simplices are left out.
+    pub fn from_simplexes<'a>(
+        ambient_space: AffineSpace<'f, FS>,
+        simplexes: impl IntoIterator<Item = (&'a Simplex<'f, FS>, V)>,
+    ) -> Self
+    where
+        'f: 'a,
+        FS: 'a,
+    {
+        Self::new(
+            ambient_space,
+            simplexes
+                .into_iter()
+                .filter_map(|(spx, value)| Some((spx.bounding_box()?, value)))
+                .collect(),
+        )
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.ambient_space
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn query(&self, overlaps: impl Fn(&BoundingBox<'f, FS>) -> bool) -> Vec<&V> {
+        let mut found = vec![];
+        if let Some((root_box, root)) = &self.root
+            && overlaps(root_box)
+        {
+            root.query(&overlaps, &mut found);
+        }
+        found
+    }
+
+    /// The items whose boxes contain the point.
+    pub fn query_point(&self, point: &Vector<'f, FS>) -> Vec<&V> {
+        assert_eq!(point.ambient_space(), self.ambient_space);
+        self.query(|item| item.contains_point(point))
+    }
+
+    /// The items whose boxes meet the given box.
+    pub fn query_box(&self, bounding_box: &BoundingBox<'f, FS>) -> Vec<&V> {
+        assert_eq!(bounding_box.ambient_space(), self.ambient_space);
+        self.query(|item| item.intersects(bounding_box))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algebraeon_nzq::Rational;
+
+    #[test]
+    fn test_bounding_box() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let triangle = space
+            .simplex(vec![
+                space.vector([0, 3]),
+                space.vector([2, -1]),
+                space.vector([-1, 1]),
+            ])
+            .unwrap();
+        let bounding_box = triangle.bounding_box().unwrap();
+        assert_eq!(bounding_box.min(), &space.vector([-1, -1]));
+        assert_eq!(bounding_box.max(), &space.vector([2, 3]));
+        assert!(bounding_box.contains_point(&space.vector([2, 3])));
+        assert!(!bounding_box.contains_point(&space.vector([3, 0])));
+
+        let other =
+            BoundingBox::from_points(&[space.vector([2, 3]), space.vector([5, 5])]).unwrap();
+        assert!(bounding_box.intersects(&other));
+        let other = BoundingBox::from_points(&[space.vector([3, 0])]).unwrap();
+        assert!(!bounding_box.intersects(&other));
+        assert_eq!(bounding_box.union(&other).max(), &space.vector([3, 3]));
+        assert!(space.simplex(vec![]).unwrap().bounding_box().is_none());
+    }
+
+    #[test]
+    fn test_bounding_box_tree_without_separating_axis() {
+        //in a 0-dimensional space every box is the single point
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 0);
+        let point = space.origin().unwrap();
+        let point_box = BoundingBox::from_points([&point]).unwrap();
+        let tree = BoundingBoxTree::new(space, (0..9).map(|i| (point_box.clone(), i)).collect());
+        assert_eq!(tree.len(), 9);
+        assert_eq!(tree.query_point(&point).len(), 9);
+
+        //nine copies of the same box in the plane
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let unit_box =
+            BoundingBox::from_points(&[space.vector([0, 0]), space.vector([1, 1])]).unwrap();
+        let tree = BoundingBoxTree::new(space, (0..9).map(|i| (unit_box.clone(), i)).collect());
+        assert_eq!(tree.query_point(&space.vector([1, 0])).len(), 9);
+        assert!(tree.query_point(&space.vector([2, 0])).is_empty());
+    }
+
+    #[test]
+    fn test_bounding_box_tree() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let mut simplexes = vec![];
+        for x in 0..10 {
+            for y in 0..10 {
+                simplexes.push(
+                    space
+                        .simplex(vec![
+                            space.vector([x, y]),
+                            space.vector([x + 1 + (x * y) % 3, y]),
+                            space.vector([x, y + 1 + (x + y) % 2]),
+                        ])
+                        .unwrap(),
+                );
+            }
+        }
+        let tree = BoundingBoxTree::from_simplexes(
+            space,
+            simplexes.iter().enumerate().map(|(i, spx)| (spx, i)),
+        );
+        assert_eq!(tree.len(), 100);
+
+        for point in [
+            space.vector([0, 0]),
+            space.vector([Rational::from_integers(7, 2), Rational::from(5)]),
+            space.vector([11, 11]),
+            space.vector([-1, 4]),
+        ] {
+            let mut found = tree
+                .query_point(&point)
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>();
+            found.sort();
+            let expected = (0..simplexes.len())
+                .filter(|i| simplexes[*i].bounding_box().unwrap().contains_point(&point))
+                .collect::<Vec<_>>();
+            assert_eq!(found, expected);
+        }
+
+        let query =
+            BoundingBox::from_points(&[space.vector([2, 3]), space.vector([4, 4])]).unwrap();
+        let mut found = tree
+            .query_box(&query)
+            .into_iter()
+            .copied()
+            .collect::<Vec<_>>();
+        found.sort();
+        let expected = (0..simplexes.len())
+            .filter(|i| simplexes[*i].bounding_box().unwrap().intersects(&query))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected);
+        assert!(!found.is_empty());
+
+        let empty = BoundingBoxTree::<_, usize>::new(space, vec![]);
+        assert!(empty.is_empty());
+        assert!(empty.query_point(&space.vector([0, 0])).is_empty());
+    }
+}
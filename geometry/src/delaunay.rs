@@ -0,0 +1,480 @@
+use super::*;
+use crate::{
+    ambient_space::AffineSpace,
+    convex_hull::ConvexHull,
+    linear_programming::LinearInequality,
+    oriented_simplex::{OrientationSide, OrientedSimplex},
+    polytope::HalfSpaceRepresentation,
+    simplex::Simplex,
+    simplex_collection::LabelledSimplexCollection,
+    simplicial_complex::SimplicialComplex,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_rings::matrix::{Matrix, MatrixStructure};
+use std::collections::HashSet;
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> Simplex<'f, FS> {
+    /// The point in the affine span of the simplex equidistant from all of its points, or `None` for the null simplex.
+    pub fn circumcentre(&self) -> Option<Vector<'f, FS>> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let root = self.points().first()?;
+        let edges = (1..self.n())
+            .map(|i| self.point(i) - root)
+            .collect::<Vec<_>>();
+        let k = edges.len();
+        // the circumcentre is root + Σ λ_j e_j where Σ_j (e_i·e_j) λ_j = (e_i·e_i) / 2
+        let two = field.from_nat(2u32);
+        let gram = Matrix::construct(k, k, |r, c| edges[r].dot(&edges[c]));
+        let rhs = edges
+            .iter()
+            .map(|e| field.div(&e.dot(e), &two).unwrap())
+            .collect::<Vec<_>>();
+        let lambda = MatrixStructure::new(field.clone())
+            .col_solve(gram, &rhs)
+            .unwrap();
+        let mut centre = root.clone();
+        for (e, l) in edges.iter().zip(lambda.iter()) {
+            centre += &e.scalar_mul(l);
+        }
+        Some(centre)
+    }
+
+    /// The square of the radius of the circumscribed sphere, or `None` for the null simplex.
+    pub fn circumradius_squared(&self) -> Option<FS::Set> {
+        let d = &self.circumcentre()? - self.point(0);
+        Some(d.dot(&d))
+    }
+}
+
+fn squared_distance<'f, FS: FieldSignature>(a: &Vector<'f, FS>, b: &Vector<'f, FS>) -> FS::Set {
+    let d = a - b;
+    d.dot(&d)
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> AffineSpace<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// A Delaunay triangulation of some points whose affine span is the whole space.
+    ///
+    /// The points are lifted onto the paraboloid `x_{d+1} = |x|^2` one dimension up and the full dimensional
+    /// simplices of the triangulation are the projections of the simplices on the lower boundary of the convex hull
+    /// of the lifted points. No point lies strictly inside the circumscribed sphere of any simplex.
+    /// When more than `d + 1` points lie on a common empty sphere the triangulation is not unique and any one is returned.
+    pub fn delaunay_triangulation(
+        &self,
+        points: Vec<Vector<'f, FS>>,
+    ) -> Result<SimplicialComplex<'f, FS>, &'static str> {
+        let field = self.field();
+        let d = self.linear_dimension().unwrap();
+        for point in &points {
+            assert_eq!(point.ambient_space(), *self);
+        }
+        let points = points
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let spans = !points.is_empty() && {
+            let differences = points.iter().map(|p| p - &points[0]).collect::<Vec<_>>();
+            self.rank(differences.iter().collect()) == d
+        };
+        if !spans {
+            return Err("Points must affinely span the space");
+        }
+
+        let lifted_space = AffineSpace::new_linear(field, d + 1);
+        let lift = |p: &Vector<'f, FS>| {
+            Vector::construct(lifted_space, |i| {
+                if i < d {
+                    p.coordinate(i).clone()
+                } else {
+                    p.dot(p)
+                }
+            })
+        };
+        let project = |p: &Vector<'f, FS>| Vector::construct(*self, |i| p.coordinate(i).clone());
+        let lifted = points.iter().map(lift).collect::<Vec<_>>();
+        let up = Vector::construct(
+            lifted_space,
+            |i| {
+                if i < d { field.zero() } else { field.one() }
+            },
+        );
+
+        let mut simplexes = HashSet::new();
+        for spx in lifted_space
+            .convex_hull(lifted.clone())
+            .to_simplicial_complex()
+            .into_simplexes()
+        {
+            if spx.n() != d + 1 {
+                continue;
+            }
+            // the simplex is on the lower boundary when every lifted point is on or above its hyperplane
+            // and it is not vertical, which happens exactly when the point directly above it is strictly above
+            let Ok(hyperplane) = OrientedSimplex::new_with_positive_point(
+                lifted_space,
+                spx.points().clone(),
+                &(spx.point(0) + &up),
+            ) else {
+                continue;
+            };
+            if lifted
+                .iter()
+                .all(|p| hyperplane.classify_point(p) != OrientationSide::Negative)
+            {
+                let projected = self
+                    .simplex(spx.points().iter().map(project).collect())
+                    .unwrap();
+                for sub in projected.sub_simplices_not_null() {
+                    simplexes.insert(sub);
+                }
+            }
+        }
+        Ok(SimplicialComplex::new_unchecked(*self, simplexes))
+    }
+
+    /// The Voronoi diagram of some distinct sites.
+    pub fn voronoi_diagram(
+        &self,
+        sites: Vec<Vector<'f, FS>>,
+    ) -> Result<VoronoiDiagram<'f, FS>, &'static str> {
+        VoronoiDiagram::new(*self, sites)
+    }
+}
+
+/// The Voronoi diagram of a finite set of sites, which divides space into one convex cell for each site
+/// consisting of the points at least as close to that site as to any other.
+///
+/// The cells are computed from the Delaunay triangulation: the cell of a site is bounded by the perpendicular
+/// bisectors between it and its neighbours in the triangulation, and the vertices of the diagram are the
+/// circumcentres of the full dimensional Delaunay simplices.
+#[derive(Debug, Clone)]
+pub struct VoronoiDiagram<'f, FS: OrderedRingSignature + FieldSignature>
+where
+    FS::Set: Hash,
+{
+    ambient_space: AffineSpace<'f, FS>,
+    sites: Vec<Vector<'f, FS>>,
+    cells: Vec<HalfSpaceRepresentation<'f, FS>>,
+    vertices: Vec<Vector<'f, FS>>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> VoronoiDiagram<'f, FS>
+where
+    FS::Set: Hash,
+{
+    fn new(
+        ambient_space: AffineSpace<'f, FS>,
+        sites: Vec<Vector<'f, FS>>,
+    ) -> Result<Self, &'static str> {
+        let field = ambient_space.field();
+        let n = sites.len();
+        if sites.iter().collect::<HashSet<_>>().len() != n {
+            return Err("Voronoi sites must be distinct");
+        }
+        let index = |p: &Vector<'f, FS>| sites.iter().position(|s| s == p).unwrap();
+
+        // when the sites do not span the space every pair of sites are treated as neighbours
+        let mut neighbours = vec![HashSet::new(); n];
+        let mut vertices = vec![];
+        match ambient_space.delaunay_triangulation(sites.clone()) {
+            Ok(triangulation) => {
+                for spx in triangulation.simplexes() {
+                    if spx.n() == 2 {
+                        let (i, j) = (index(spx.point(0)), index(spx.point(1)));
+                        neighbours[i].insert(j);
+                        neighbours[j].insert(i);
+                    }
+                    if spx.n() == ambient_space.affine_dimension() {
+                        vertices.push(spx.circumcentre().unwrap());
+                    }
+                }
+            }
+            Err(_) => {
+                for (i, nbs) in neighbours.iter_mut().enumerate() {
+                    nbs.extend((0..n).filter(|j| *j != i));
+                }
+            }
+        }
+        vertices.sort();
+        vertices.dedup();
+
+        // x is at least as close to p as to q when 2(q - p)·x <= |q|^2 - |p|^2
+        let two = field.from_nat(2u32);
+        let cells = (0..n)
+            .map(|i| {
+                let p = &sites[i];
+                let mut nbs = neighbours[i].iter().cloned().collect::<Vec<_>>();
+                nbs.sort_unstable();
+                HalfSpaceRepresentation::new(
+                    ambient_space,
+                    vec![],
+                    nbs.into_iter()
+                        .map(|j| {
+                            let q = &sites[j];
+                            LinearInequality::new(
+                                (q - p).scalar_mul(&two),
+                                field.sub(&q.dot(q), &p.dot(p)),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        Ok(Self {
+            ambient_space,
+            sites,
+            cells,
+            vertices,
+        })
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.ambient_space
+    }
+
+    pub fn sites(&self) -> &Vec<Vector<'f, FS>> {
+        &self.sites
+    }
+
+    /// The cell of the `i`-th site as an intersection of half-spaces.
+    pub fn cell(&self, i: usize) -> &HalfSpaceRepresentation<'f, FS> {
+        &self.cells[i]
+    }
+
+    pub fn cells(&self) -> &Vec<HalfSpaceRepresentation<'f, FS>> {
+        &self.cells
+    }
+
+    /// The points where `d + 1` or more cells meet.
+    pub fn vertices(&self) -> &Vec<Vector<'f, FS>> {
+        &self.vertices
+    }
+
+    /// The cell of the `i`-th site if it is bounded.
+    pub fn bounded_cell(&self, i: usize) -> Option<ConvexHull<'f, FS>> {
+        self.cells[i].to_convex_hull().ok()
+    }
+
+    /// The intersection of the cell of the `i`-th site with a convex region, which is always bounded.
+    pub fn clipped_cell(&self, i: usize, region: &ConvexHull<'f, FS>) -> ConvexHull<'f, FS> {
+        assert_eq!(region.ambient_space(), self.ambient_space);
+        let region = region.to_half_spaces();
+        let mut inequalities = region.inequalities().clone();
+        inequalities.extend(self.cells[i].inequalities().iter().cloned());
+        HalfSpaceRepresentation::new(self.ambient_space, region.equations().clone(), inequalities)
+            .to_convex_hull()
+            .unwrap()
+    }
+
+    /// The index of a site closest to a point.
+    pub fn nearest_site(&self, point: &Vector<'f, FS>) -> Option<usize> {
+        let field = self.ambient_space.field();
+        (0..self.sites.len()).min_by(|i, j| {
+            field.ring_cmp(
+                &squared_distance(&self.sites[*i], point),
+                &squared_distance(&self.sites[*j], point),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measure::SimplexCollectionMeasure;
+    use algebraeon_nzq::Rational;
+
+    fn check_delaunay<'f>(
+        points: &[Vector<'f, algebraeon_nzq::RationalCanonicalStructure>],
+        triangulation: &SimplicialComplex<'f, algebraeon_nzq::RationalCanonicalStructure>,
+    ) {
+        let space = triangulation.ambient_space();
+        //no point lies strictly inside the circumscribed sphere of any full dimensional simplex
+        for spx in triangulation.simplexes() {
+            if spx.n() == space.affine_dimension() {
+                let centre = spx.circumcentre().unwrap();
+                let r2 = spx.circumradius_squared().unwrap();
+                for p in points {
+                    assert!(squared_distance(p, &centre) >= r2);
+                }
+            }
+        }
+        //the simplices cover the convex hull
+        assert_eq!(
+            triangulation.volume(),
+            space
+                .convex_hull(points.to_vec())
+                .to_simplicial_complex()
+                .volume()
+        );
+    }
+
+    #[test]
+    fn test_circumcentre() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let triangle = space
+            .simplex(vec![
+                space.vector([0, 0, 5]),
+                space.vector([2, 0, 5]),
+                space.vector([0, 2, 5]),
+            ])
+            .unwrap();
+        assert_eq!(triangle.circumcentre(), Some(space.vector([1, 1, 5])));
+        assert_eq!(triangle.circumradius_squared(), Some(Rational::from(2)));
+        let segment = space
+            .simplex(vec![space.vector([0, 0, 0]), space.vector([2, 4, 6])])
+            .unwrap();
+        assert_eq!(segment.circumcentre(), Some(space.vector([1, 2, 3])));
+        assert!(space.simplex(vec![]).unwrap().circumcentre().is_none());
+    }
+
+    #[test]
+    fn test_delaunay_triangulation() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+
+        let points = vec![
+            space.vector([0, 0]),
+            space.vector([2, 0]),
+            space.vector([0, 2]),
+            space.vector([2, 2]),
+            space.vector([1, 1]),
+        ];
+        let triangulation = space.delaunay_triangulation(points.clone()).unwrap();
+        check_delaunay(&points, &triangulation);
+        let triangles = triangulation
+            .simplexes()
+            .into_iter()
+            .filter(|spx| spx.n() == 3)
+            .count();
+        assert_eq!(triangles, 4);
+
+        //four points on a circle can be triangulated either way
+        let square = vec![
+            space.vector([0, 0]),
+            space.vector([1, 0]),
+            space.vector([0, 1]),
+            space.vector([1, 1]),
+        ];
+        let triangulation = space.delaunay_triangulation(square.clone()).unwrap();
+        check_delaunay(&square, &triangulation);
+
+        //a thin triangle must not be used when a point is inside its circumcircle
+        let points = vec![
+            space.vector([0, 0]),
+            space.vector([4, 0]),
+            space.vector([2, 1]),
+            space.vector([2, -3]),
+        ];
+        let triangulation = space.delaunay_triangulation(points.clone()).unwrap();
+        check_delaunay(&points, &triangulation);
+        assert!(
+            triangulation.simplexes().contains(
+                &space
+                    .simplex(vec![space.vector([2, 1]), space.vector([2, -3])])
+                    .unwrap()
+            )
+        );
+
+        assert!(
+            space
+                .delaunay_triangulation(vec![space.vector([0, 0]), space.vector([1, 1])])
+                .is_err()
+        );
+        assert!(space.delaunay_triangulation(vec![]).is_err());
+
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let points = vec![
+            space.vector([0, 0, 0]),
+            space.vector([3, 0, 1]),
+            space.vector([0, 2, 0]),
+            space.vector([1, 1, 3]),
+            space.vector([2, 3, 2]),
+            space.vector([1, 1, 1]),
+            space.vector([-1, 2, 2]),
+        ];
+        let triangulation = space.delaunay_triangulation(points.clone()).unwrap();
+        check_delaunay(&points, &triangulation);
+    }
+
+    #[test]
+    fn test_voronoi_diagram() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let sites = vec![
+            space.vector([0, 0]),
+            space.vector([2, 0]),
+            space.vector([0, 2]),
+            space.vector([2, 2]),
+            space.vector([1, 1]),
+        ];
+        let voronoi = space.voronoi_diagram(sites.clone()).unwrap();
+        assert_eq!(
+            voronoi.vertices(),
+            &vec![
+                space.vector([0, 1]),
+                space.vector([1, 0]),
+                space.vector([1, 2]),
+                space.vector([2, 1]),
+            ]
+        );
+        //the centre cell is a diamond and the others are unbounded
+        let centre = voronoi.bounded_cell(4).unwrap();
+        assert_eq!(centre.vertices(), voronoi.vertices().clone());
+        assert_eq!(centre.to_simplicial_complex().volume(), Rational::from(2));
+        for i in 0..4 {
+            assert!(voronoi.bounded_cell(i).is_none());
+            assert!(voronoi.cell(i).contains_point(&sites[i]));
+            assert!(!voronoi.cell(i).contains_point(&sites[4]));
+        }
+
+        //clipping the cells to the square of the sites divides it up
+        let square = space.convex_hull(sites.clone());
+        let mut total = Rational::ZERO;
+        for i in 0..5 {
+            total += voronoi
+                .clipped_cell(i, &square)
+                .to_simplicial_complex()
+                .volume();
+        }
+        assert_eq!(total, Rational::from(4));
+
+        assert_eq!(
+            voronoi.nearest_site(&space.vector([
+                Rational::from_integers(1, 5),
+                Rational::from_integers(1, 10)
+            ])),
+            Some(0)
+        );
+        assert_eq!(voronoi.nearest_site(&space.vector([1, 2])), Some(2));
+
+        assert!(
+            space
+                .voronoi_diagram(vec![space.vector([0, 0]), space.vector([0, 0])])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_voronoi_diagram_of_collinear_sites() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let voronoi = space
+            .voronoi_diagram(vec![
+                space.vector([0, 0]),
+                space.vector([1, 0]),
+                space.vector([3, 0]),
+            ])
+            .unwrap();
+        assert!(voronoi.vertices().is_empty());
+        let middle = voronoi.cell(1);
+        assert!(
+            middle
+                .contains_point(&space.vector([Rational::from_integers(1, 2), Rational::from(7)]))
+        );
+        assert!(middle.contains_point(&space.vector([2, -7])));
+        assert!(!middle.contains_point(&space.vector([3, 0])));
+        assert!(voronoi.bounded_cell(1).is_none());
+    }
+}
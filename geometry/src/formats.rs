@@ -0,0 +1,594 @@
+use crate::{
+    ambient_space::AffineSpace,
+    simplex::Simplex,
+    simplex_collection::LabelledSimplexCollection,
+    simplicial_complex::SimplicialComplex,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_nzq::{Rational, RationalCanonicalStructure};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+type Point<'f> = Vector<'f, RationalCanonicalStructure>;
+
+fn cross<'f>(a: &Point<'f>, b: &Point<'f>) -> Point<'f> {
+    let c =
+        |i: usize, j: usize| a.coordinate(i) * b.coordinate(j) - a.coordinate(j) * b.coordinate(i);
+    a.ambient_space().vector([c(1, 2), c(2, 0), c(0, 1)])
+}
+
+//coordinates are written as the nearest floating point number
+fn format_coordinates(point: &Point) -> String {
+    point
+        .clone()
+        .into_coordinates()
+        .iter()
+        .map(|x| f64::from(x).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse an integer, a fraction such as `-3/4` or a decimal such as `1.25` or `-2.5e-3` exactly.
+pub(crate) fn parse_number(s: &str) -> Option<Rational> {
+    if s.contains('/') {
+        return Rational::from_str(s).ok();
+    }
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], i64::from_str(&s[i + 1..]).ok()?),
+        None => (s, 0),
+    };
+    if exponent.abs() > 1000 {
+        return None;
+    }
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", mantissa.strip_prefix('+').unwrap_or(mantissa)),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let shift = exponent - fraction.len() as i64;
+    let zeros = "0".repeat(shift.unsigned_abs() as usize);
+    let (numerator, denominator) = if shift >= 0 {
+        (format!("{sign}0{whole}{fraction}{zeros}"), "1".to_string())
+    } else {
+        (format!("{sign}0{whole}{fraction}"), format!("1{zeros}"))
+    };
+    Rational::from_str(&format!("{numerator}/{denominator}")).ok()
+}
+
+/// Exporting collections of simplices with rational coordinates to common file formats.
+pub trait SimplexCollectionFormats<'f, T: Eq + Clone + Send + Sync>:
+    LabelledSimplexCollection<'f, RationalCanonicalStructure, T>
+{
+    /// An SVG image of a 2D shape where each simplex is drawn in the colour given by its label,
+    /// or is not drawn if the colour is `None`. Colours can be any SVG colour such as `"red"` or `"#ff8800"`.
+    fn to_svg_with(&self, colour: impl Fn(&T) -> Option<String>) -> Result<String, &'static str> {
+        if self.ambient_space().linear_dimension() != Some(2) {
+            return Err("SVG export requires a 2D shape");
+        }
+        let mut simplexes = self
+            .labelled_simplexes()
+            .into_iter()
+            .filter_map(|(spx, label)| Some((spx, colour(label)?)))
+            .collect::<Vec<_>>();
+        // draw triangles below edges below points, each in a deterministic order
+        simplexes.sort_by(|(a, _), (b, _)| b.n().cmp(&a.n()).then(a.points().cmp(b.points())));
+
+        let coords = |p: &Point| (f64::from(p.coordinate(0)), -f64::from(p.coordinate(1)));
+        let all = simplexes
+            .iter()
+            .flat_map(|(spx, _)| spx.points().iter().map(coords))
+            .collect::<Vec<_>>();
+        let min = |f: fn(&(f64, f64)) -> f64| all.iter().map(f).fold(f64::INFINITY, f64::min);
+        let max = |f: fn(&(f64, f64)) -> f64| all.iter().map(f).fold(f64::NEG_INFINITY, f64::max);
+        let (x0, x1, y0, y1) = if all.is_empty() {
+            (0.0, 1.0, 0.0, 1.0)
+        } else {
+            (min(|p| p.0), max(|p| p.0), min(|p| p.1), max(|p| p.1))
+        };
+        let size = (x1 - x0).max(y1 - y0);
+        let width = if size > 0.0 { size / 200.0 } else { 0.01 };
+        let margin = 4.0 * width;
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+            x0 - margin,
+            y0 - margin,
+            x1 - x0 + 2.0 * margin,
+            y1 - y0 + 2.0 * margin
+        )
+        .unwrap();
+        for (spx, colour) in simplexes {
+            let pts = spx.points().iter().map(coords).collect::<Vec<_>>();
+            match pts.len() {
+                1 => writeln!(
+                    svg,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{colour}\"/>",
+                    pts[0].0,
+                    pts[0].1,
+                    2.0 * width
+                ),
+                2 => writeln!(
+                    svg,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{colour}\" stroke-width=\"{width}\" stroke-linecap=\"round\"/>",
+                    pts[0].0, pts[0].1, pts[1].0, pts[1].1
+                ),
+                _ => writeln!(
+                    svg,
+                    "<polygon points=\"{}\" fill=\"{colour}\"/>",
+                    pts.iter()
+                        .map(|(x, y)| format!("{x},{y}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+            }
+            .unwrap();
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// An SVG image of a 2D shape drawn in black.
+    fn to_svg(&self) -> Result<String, &'static str> {
+        self.to_svg_with(|_| Some("black".to_string()))
+    }
+
+    /// The triangles of the surface of a 3D shape.
+    ///
+    /// These are the boundary triangles of the solid part, oriented anticlockwise when viewed from outside,
+    /// together with the triangles of the shape not on a solid part, oriented arbitrarily.
+    fn surface_triangles(&self) -> Result<Vec<[Point<'f>; 3]>, &'static str> {
+        if self.ambient_space().linear_dimension() != Some(3) {
+            return Err("Surface export requires a 3D shape");
+        }
+        // use a partial simplicial complex so that neighbouring tetrahedra meet along whole faces
+        let simplexes = self.to_partial_simplicial_complex().into_simplexes();
+        let mut facet_count: HashMap<Simplex<'f, RationalCanonicalStructure>, Vec<[Point<'f>; 3]>> =
+            HashMap::new();
+        for spx in &simplexes {
+            if spx.n() == 4 {
+                for k in 0..4 {
+                    let [a, b, c] = [0, 1, 2, 3]
+                        .into_iter()
+                        .filter(|i| *i != k)
+                        .map(|i| spx.point(i).clone())
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap();
+                    let inward = spx.point(k) - &a;
+                    let outward_triangle =
+                        if cross(&(&b - &a), &(&c - &a)).dot(&inward) > Rational::ZERO {
+                            [a, c, b]
+                        } else {
+                            [a, b, c]
+                        };
+                    facet_count
+                        .entry(spx.facet(k))
+                        .or_default()
+                        .push(outward_triangle);
+                }
+            }
+        }
+        let mut triangles = vec![];
+        for spx in &simplexes {
+            if spx.n() == 3 && !facet_count.contains_key(spx) {
+                triangles.push([
+                    spx.point(0).clone(),
+                    spx.point(1).clone(),
+                    spx.point(2).clone(),
+                ]);
+            }
+        }
+        for (_, mut oriented) in facet_count {
+            if oriented.len() == 1 {
+                triangles.push(oriented.pop().unwrap());
+            }
+        }
+        triangles.sort();
+        Ok(triangles)
+    }
+
+    /// The surface of a 3D shape in the OFF format.
+    /// Coordinates are written as the nearest floating point numbers.
+    fn to_off(&self) -> Result<String, &'static str> {
+        let (vertices, faces) = indexed_mesh(self.surface_triangles()?);
+        let mut off = format!("OFF\n{} {} 0\n", vertices.len(), faces.len());
+        for v in &vertices {
+            writeln!(off, "{}", format_coordinates(v)).unwrap();
+        }
+        for [a, b, c] in faces {
+            writeln!(off, "3 {a} {b} {c}").unwrap();
+        }
+        Ok(off)
+    }
+
+    /// The surface of a 3D shape in the Wavefront OBJ format.
+    /// Coordinates are written as the nearest floating point numbers.
+    fn to_obj(&self) -> Result<String, &'static str> {
+        let (vertices, faces) = indexed_mesh(self.surface_triangles()?);
+        let mut obj = String::new();
+        for v in &vertices {
+            writeln!(obj, "v {}", format_coordinates(v)).unwrap();
+        }
+        for [a, b, c] in faces {
+            writeln!(obj, "f {} {} {}", a + 1, b + 1, c + 1).unwrap();
+        }
+        Ok(obj)
+    }
+
+    /// The surface of a 3D shape in the ASCII STL format.
+    /// Coordinates and normals are written as the nearest floating point numbers.
+    fn to_stl(&self, name: &str) -> Result<String, &'static str> {
+        let mut stl = format!("solid {name}\n");
+        for [a, b, c] in self.surface_triangles()? {
+            let normal = cross(&(&b - &a), &(&c - &a))
+                .into_coordinates()
+                .iter()
+                .map(f64::from)
+                .collect::<Vec<_>>();
+            let length = normal.iter().map(|x| x * x).sum::<f64>().sqrt();
+            writeln!(
+                stl,
+                "facet normal {} {} {}",
+                normal[0] / length,
+                normal[1] / length,
+                normal[2] / length
+            )
+            .unwrap();
+            stl.push_str("  outer loop\n");
+            for v in [a, b, c] {
+                writeln!(stl, "    vertex {}", format_coordinates(&v)).unwrap();
+            }
+            stl.push_str("  endloop\nendfacet\n");
+        }
+        writeln!(stl, "endsolid {name}").unwrap();
+        Ok(stl)
+    }
+}
+
+impl<
+    'f,
+    T: Eq + Clone + Send + Sync,
+    S: LabelledSimplexCollection<'f, RationalCanonicalStructure, T>,
+> SimplexCollectionFormats<'f, T> for S
+{
+}
+
+//a list of distinct vertices and triangles indexing into it
+fn indexed_mesh(triangles: Vec<[Point; 3]>) -> (Vec<Point>, Vec<[usize; 3]>) {
+    let mut vertices = vec![];
+    let mut index = HashMap::new();
+    let faces = triangles
+        .into_iter()
+        .map(|triangle| {
+            triangle.map(|v| {
+                *index.entry(v.clone()).or_insert_with(|| {
+                    vertices.push(v);
+                    vertices.len() - 1
+                })
+            })
+        })
+        .collect();
+    (vertices, faces)
+}
+
+impl<'f> AffineSpace<'f, RationalCanonicalStructure> {
+    //the simplicial complex of the triangles of a mesh, with polygonal faces split into fans of triangles
+    fn mesh_to_simplicial_complex(
+        &self,
+        vertices: Vec<Point<'f>>,
+        faces: Vec<Vec<usize>>,
+    ) -> Result<SimplicialComplex<'f, RationalCanonicalStructure>, &'static str> {
+        let mut simplexes = HashSet::new();
+        for face in faces {
+            if face.len() < 3 {
+                return Err("Faces must have at least 3 vertices");
+            }
+            for i in 1..face.len() - 1 {
+                let triangle = self.simplex(
+                    [face[0], face[i], face[i + 1]]
+                        .into_iter()
+                        .map(|v| vertices.get(v).cloned().ok_or("Vertex index out of range"))
+                        .collect::<Result<_, _>>()?,
+                )?;
+                simplexes.extend(triangle.sub_simplices_not_null());
+            }
+        }
+        for v in vertices {
+            simplexes.insert(self.simplex(vec![v]).unwrap());
+        }
+        SimplicialComplex::try_new(*self, simplexes)
+    }
+
+    fn parse_point(&self, coordinates: &[&str]) -> Result<Point<'f>, &'static str> {
+        if coordinates.len() != 3 {
+            return Err("Vertices must have 3 coordinates");
+        }
+        Ok(self.vector(
+            coordinates
+                .iter()
+                .map(|x| parse_number(x).ok_or("Invalid coordinate"))
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+
+    /// Read the triangles of a mesh in the OFF format into this 3D space.
+    /// Coordinates may be integers, fractions or decimals, all read exactly.
+    /// Polygonal faces are split into triangles from their first vertex, so they should be convex and planar.
+    pub fn read_off(
+        &self,
+        text: &str,
+    ) -> Result<SimplicialComplex<'f, RationalCanonicalStructure>, &'static str> {
+        if self.linear_dimension() != Some(3) {
+            return Err("OFF import requires a 3D space");
+        }
+        let mut lines = text
+            .lines()
+            .map(|line| line.split('#').next().unwrap().trim())
+            .filter(|line| !line.is_empty());
+        if lines.next() != Some("OFF") {
+            return Err("Expected the OFF header");
+        }
+        let counts = lines
+            .next()
+            .ok_or("Missing the number of vertices and faces")?
+            .split_whitespace()
+            .map(usize::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "Invalid number of vertices or faces")?;
+        let [num_vertices, num_faces, ..] = counts[..] else {
+            return Err("Missing the number of vertices and faces");
+        };
+        let mut vertices = vec![];
+        for _ in 0..num_vertices {
+            let line = lines.next().ok_or("Missing vertex")?;
+            vertices.push(self.parse_point(&line.split_whitespace().collect::<Vec<_>>())?);
+        }
+        let mut faces = vec![];
+        for _ in 0..num_faces {
+            let numbers = lines
+                .next()
+                .ok_or("Missing face")?
+                .split_whitespace()
+                .map(usize::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| "Invalid face")?;
+            let (count, rest) = numbers.split_first().ok_or("Invalid face")?;
+            // any numbers after the vertex indices give the colour of the face
+            faces.push(rest.get(..*count).ok_or("Missing face vertices")?.to_vec());
+        }
+        self.mesh_to_simplicial_complex(vertices, faces)
+    }
+
+    /// Read the triangles of a mesh in the Wavefront OBJ format into this 3D space.
+    /// Only vertices and faces are read. Coordinates may be integers, fractions or decimals, all read exactly.
+    /// Polygonal faces are split into triangles from their first vertex, so they should be convex and planar.
+    pub fn read_obj(
+        &self,
+        text: &str,
+    ) -> Result<SimplicialComplex<'f, RationalCanonicalStructure>, &'static str> {
+        if self.linear_dimension() != Some(3) {
+            return Err("OBJ import requires a 3D space");
+        }
+        let mut vertices = vec![];
+        let mut faces = vec![];
+        for line in text.lines() {
+            let mut words = line.split('#').next().unwrap().split_whitespace();
+            match words.next() {
+                Some("v") => {
+                    let coordinates = words.collect::<Vec<_>>();
+                    // an optional fourth coordinate is a weight
+                    vertices.push(self.parse_point(&coordinates[..coordinates.len().min(3)])?);
+                }
+                Some("f") => {
+                    let face = words
+                        .map(|word| {
+                            // a vertex may be given as v, v/vt, v//vn or v/vt/vn and negative indices count from the end
+                            let v = i64::from_str(word.split('/').next().unwrap())
+                                .map_err(|_| "Invalid face")?;
+                            match v {
+                                1.. => Ok(v as usize - 1),
+                                ..0 => (vertices.len() as i64 + v)
+                                    .try_into()
+                                    .map_err(|_| "Vertex index out of range"),
+                                0 => Err("Vertex index out of range"),
+                            }
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    faces.push(face);
+                }
+                _ => {}
+            }
+        }
+        self.mesh_to_simplicial_complex(vertices, faces)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::partial_simplicial_complex::LabelledPartialSimplicialComplex;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("3"), Some(Rational::from(3)));
+        assert_eq!(parse_number("-3/4"), Rational::from_str("-3/4").ok());
+        assert_eq!(parse_number("1.25"), Rational::from_str("5/4").ok());
+        assert_eq!(parse_number("-.5"), Rational::from_str("-1/2").ok());
+        assert_eq!(parse_number("2."), Some(Rational::from(2)));
+        assert_eq!(parse_number("1.5e2"), Some(Rational::from(150)));
+        assert_eq!(parse_number("+25E-3"), Rational::from_str("1/40").ok());
+        assert_eq!(parse_number("0.1"), Rational::from_str("1/10").ok());
+        assert_eq!(parse_number("."), None);
+        assert_eq!(parse_number("1.2.3"), None);
+        assert_eq!(parse_number("x"), None);
+    }
+
+    #[test]
+    fn test_cube_round_trip() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let mut corners = vec![];
+        for x in [0, 2] {
+            for y in [0, 2] {
+                for z in [0, 2] {
+                    corners.push(space.vector([x, y, z]));
+                }
+            }
+        }
+        let cube = space.convex_hull(corners).to_simplicial_complex();
+
+        let surface = cube.surface_triangles().unwrap();
+        assert_eq!(surface.len(), 12);
+        //the triangles are oriented outwards so the divergence theorem gives 6 times the volume
+        let mut total = Rational::ZERO;
+        for [a, b, c] in &surface {
+            total += cross(b, c).dot(a);
+        }
+        assert_eq!(total, Rational::from(48));
+
+        let off = cube.to_off().unwrap();
+        assert!(off.starts_with("OFF\n8 12 0\n"));
+        let obj = cube.to_obj().unwrap();
+        assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 8);
+        assert_eq!(
+            obj.lines().filter(|line| line.starts_with("f ")).count(),
+            12
+        );
+        let stl = cube.to_stl("cube").unwrap();
+        assert_eq!(stl.matches("facet normal").count(), 12);
+        assert!(stl.ends_with("endsolid cube\n"));
+
+        for imported in [space.read_off(&off).unwrap(), space.read_obj(&obj).unwrap()] {
+            let triangles = imported.surface_triangles().unwrap();
+            assert_eq!(triangles.len(), 12);
+            assert_eq!(
+                triangles
+                    .iter()
+                    .map(|t| space.simplex(t.to_vec()).unwrap())
+                    .collect::<HashSet<_>>(),
+                surface
+                    .iter()
+                    .map(|t| space.simplex(t.to_vec()).unwrap())
+                    .collect::<HashSet<_>>()
+            );
+        }
+
+        let square = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        assert!(
+            square
+                .convex_hull(vec![square.vector([0, 0]), square.vector([1, 1])])
+                .to_simplicial_complex()
+                .to_off()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_read_mesh() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+
+        let obj = "# a unit square and a triangle\n\
+                   v 0 0 0\nv 1.0 0 0\nv 1 1 0\nv 0 1/1 0\nv 0.5 0.5 1e0\n\
+                   vt 0 0\n\
+                   f 1/1 2/1 3/1 4/1\n\
+                   f -5//1 -4//1 -1//1\n";
+        let mesh = space.read_obj(obj).unwrap();
+        assert_eq!(
+            mesh.simplexes().iter().filter(|spx| spx.n() == 3).count(),
+            3
+        );
+        assert!(
+            mesh.simplexes().contains(
+                &space
+                    .simplex(vec![space.vector([
+                        Rational::from_str("1/2").unwrap(),
+                        Rational::from_str("1/2").unwrap(),
+                        Rational::ONE
+                    ])])
+                    .unwrap()
+            )
+        );
+
+        let off = "OFF\n# comment\n4 2 0\n0 0 0\n1 0 0\n0 1 0\n0 0 1\n3 0 1 2\n3 0 1 3 255 0 0\n";
+        let mesh = space.read_off(off).unwrap();
+        assert_eq!(
+            mesh.simplexes().iter().filter(|spx| spx.n() == 3).count(),
+            2
+        );
+        assert_eq!(
+            mesh.simplexes().iter().filter(|spx| spx.n() == 2).count(),
+            5
+        );
+
+        assert!(space.read_off("OFF\n1 1 0\n0 0 0\n3 0 0 0\n").is_err());
+        assert!(
+            space
+                .read_off("OFF\n3 1 0\n0 0 0\n1 0 0\n2 0 0\n3 0 1 2\n")
+                .is_err()
+        );
+        assert!(space.read_off("OFF\n1 0 0\n0 0\n").is_err());
+        assert!(space.read_obj("v 0 0 0\nf 1 2 3\n").is_err());
+        assert!(space.read_obj("v 0 0 zero\n").is_err());
+        let plane = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        assert!(plane.read_obj("").is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let triangle = space
+            .simplex(vec![
+                space.vector([0, 0]),
+                space.vector([4, 0]),
+                space.vector([0, 4]),
+            ])
+            .unwrap();
+        let edge = space
+            .simplex(vec![space.vector([5, 5]), space.vector([6, 5])])
+            .unwrap();
+        let point = space.simplex(vec![space.vector([8, 0])]).unwrap();
+        let shape = LabelledPartialSimplicialComplex::try_new_labelled(
+            space,
+            [(triangle, 0), (edge, 1), (point, 2)].into_iter().collect(),
+        )
+        .unwrap();
+
+        let svg = shape.to_svg().unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polygon").count(), 1);
+        assert_eq!(svg.matches("<line").count(), 1);
+        assert_eq!(svg.matches("<circle").count(), 1);
+        //triangles are drawn first so that edges and points appear above them
+        assert!(svg.find("<polygon").unwrap() < svg.find("<line").unwrap());
+
+        let svg = shape
+            .to_svg_with(|label| match *label {
+                0 => Some("red".to_string()),
+                1 => Some("#0000ff".to_string()),
+                _ => None,
+            })
+            .unwrap();
+        assert!(svg.contains("fill=\"red\""));
+        assert!(svg.contains("stroke=\"#0000ff\""));
+        assert!(!svg.contains("<circle"));
+
+        let space3 = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        assert!(
+            space3
+                .convex_hull(vec![space3.vector([0, 0, 0])])
+                .to_simplicial_complex()
+                .to_svg()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use crate::{
+    convex_hull::ConvexHull,
+    linear_programming::LinearInequality,
+    polytope::HalfSpaceRepresentation,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_nzq::{
+    Integer, Natural, Rational, RationalCanonicalStructure,
+    traits::{Ceil, Floor, Fraction},
+};
+use algebraeon_rings::polynomial::Polynomial;
+
+/// The integer points of the polyhedron `h` lying in the box given by inclusive `lower` and `upper` bounds.
+/// When `strict` only points strictly satisfying every inequality are included.
+fn lattice_points_in_box<'f>(
+    h: &HalfSpaceRepresentation<'f, RationalCanonicalStructure>,
+    lower: &[Integer],
+    upper: &[Integer],
+    strict: bool,
+) -> Vec<Vector<'f, RationalCanonicalStructure>> {
+    let space = h.ambient_space();
+    let d = lower.len();
+    let mut points = vec![];
+    if (0..d).any(|i| lower[i] > upper[i]) {
+        return points;
+    }
+    let mut current = lower.to_vec();
+    'outer: loop {
+        let point = space.vector(current.iter().map(Rational::from));
+        if h.contains_point(&point)
+            && (!strict
+                || h.inequalities()
+                    .iter()
+                    .all(|c| &c.normal().dot(&point) < c.bound()))
+        {
+            points.push(point);
+        }
+        //step to the next point of the box in lexicographic order
+        for i in (0..d).rev() {
+            if current[i] < upper[i] {
+                current[i] += Integer::ONE;
+                continue 'outer;
+            }
+            current[i] = lower[i].clone();
+        }
+        break;
+    }
+    points
+}
+
+/// The polyhedron `h` dilated by a factor of `t`.
+fn dilate<'f>(
+    h: &HalfSpaceRepresentation<'f, RationalCanonicalStructure>,
+    t: &Rational,
+) -> HalfSpaceRepresentation<'f, RationalCanonicalStructure> {
+    HalfSpaceRepresentation::new(
+        h.ambient_space(),
+        h.equations()
+            .iter()
+            .map(|(normal, value)| (normal.clone(), value * t))
+            .collect(),
+        h.inequalities()
+            .iter()
+            .map(|c| LinearInequality::new(c.normal().clone(), c.bound() * t))
+            .collect(),
+    )
+}
+
+/// The Ehrhart quasi-polynomial `L(t)` counting the integer points in the `t`-th dilate of a rational polytope.
+///
+/// It is given by a list of polynomials, its constituents, such that `L(t)` is the `t mod p`-th constituent
+/// evaluated at `t` where `p` is the period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EhrhartQuasiPolynomial {
+    constituents: Vec<Polynomial<Rational>>,
+}
+
+impl EhrhartQuasiPolynomial {
+    pub fn period(&self) -> usize {
+        self.constituents.len()
+    }
+
+    pub fn constituents(&self) -> &Vec<Polynomial<Rational>> {
+        &self.constituents
+    }
+
+    /// The constituent used to evaluate at `t`.
+    pub fn constituent(&self, t: usize) -> &Polynomial<Rational> {
+        &self.constituents[t % self.period()]
+    }
+
+    pub fn evaluate(&self, t: usize) -> Rational {
+        self.constituent(t).evaluate(&Rational::from(t))
+    }
+
+    /// Whether every constituent is the same, in which case it is a polynomial.
+    pub fn is_polynomial(&self) -> bool {
+        self.constituents.iter().all(|p| p == &self.constituents[0])
+    }
+}
+
+/// The integer points in the dilate by a factor of `t` of the polytope with the given `vertices` and
+/// half-space representation `h`, searching the box bounding the dilated vertices.
+fn dilated_lattice_points<'f>(
+    vertices: &[Vector<'f, RationalCanonicalStructure>],
+    h: &HalfSpaceRepresentation<'f, RationalCanonicalStructure>,
+    t: &Rational,
+    strict: bool,
+) -> Vec<Vector<'f, RationalCanonicalStructure>> {
+    if vertices.is_empty() {
+        return vec![];
+    }
+    let d = h.ambient_space().linear_dimension().unwrap();
+    let coordinate_range = |i: usize| {
+        let values = vertices.iter().map(|v| v.coordinate(i) * t);
+        (
+            values.clone().min().unwrap().ceil(),
+            values.max().unwrap().floor(),
+        )
+    };
+    let (lower, upper): (Vec<_>, Vec<_>) = (0..d).map(coordinate_range).unzip();
+    lattice_points_in_box(&dilate(h, t), &lower, &upper, strict)
+}
+
+/// The smallest positive integer `q` such that dilating the `vertices` by `q` gives integer coordinates.
+fn denominator(vertices: &[Vector<'_, RationalCanonicalStructure>]) -> Natural {
+    vertices
+        .iter()
+        .flat_map(|v| v.clone().into_coordinates())
+        .fold(Natural::ONE, |q, x| algebraeon_nzq::lcm(q, x.denominator()))
+}
+
+impl<'f> ConvexHull<'f, RationalCanonicalStructure> {
+    /// The points with integer coordinates.
+    pub fn lattice_points(&self) -> Vec<Vector<'f, RationalCanonicalStructure>> {
+        dilated_lattice_points(
+            &self.vertices(),
+            &self.to_half_spaces(),
+            &Rational::ONE,
+            false,
+        )
+    }
+
+    pub fn lattice_point_count(&self) -> usize {
+        self.lattice_points().len()
+    }
+
+    /// The number of points with integer coordinates in the relative interior.
+    pub fn relative_interior_lattice_point_count(&self) -> usize {
+        dilated_lattice_points(
+            &self.vertices(),
+            &self.to_half_spaces(),
+            &Rational::ONE,
+            true,
+        )
+        .len()
+    }
+
+    /// The smallest positive integer `q` such that dilating by `q` produces a polytope with integer vertices.
+    pub fn denominator(&self) -> Natural {
+        denominator(&self.vertices())
+    }
+
+    /// Compute the Ehrhart quasi-polynomial by counting integer points in enough dilates to interpolate each constituent.
+    ///
+    /// The period is taken to be the denominator of the polytope, which is a multiple of the minimal period.
+    /// Each constituent has degree at most the dimension of the polytope.
+    pub fn ehrhart_quasi_polynomial(&self) -> EhrhartQuasiPolynomial {
+        if self.is_empty() {
+            return EhrhartQuasiPolynomial {
+                constituents: vec![Polynomial::from_coeffs(Vec::<Rational>::new())],
+            };
+        }
+        //the vertices and half-spaces are found once and dilated for each count
+        let vertices = self.vertices();
+        let h = self.to_half_spaces();
+        let period: usize = (&denominator(&vertices)).try_into().unwrap();
+        let dim = self.affine_span_dimension() - 1;
+        let constituents = (0..period)
+            .map(|r| {
+                //use dilates t = r + kq for dim + 1 values of k, avoiding t = 0
+                let start = if r == 0 { 1 } else { 0 };
+                let points = (start..start + dim + 1)
+                    .map(|k| {
+                        let t = Rational::from(r + k * period);
+                        let count = dilated_lattice_points(&vertices, &h, &t, false).len();
+                        (t, Rational::from(count))
+                    })
+                    .collect();
+                Polynomial::interpolate_by_lagrange_basis(&points).unwrap()
+            })
+            .collect();
+        EhrhartQuasiPolynomial { constituents }
+    }
+
+    /// The Ehrhart polynomial, or `None` if the Ehrhart quasi-polynomial is not a polynomial.
+    /// This is always a polynomial when the vertices have integer coordinates.
+    pub fn ehrhart_polynomial(&self) -> Option<Polynomial<Rational>> {
+        let quasi = self.ehrhart_quasi_polynomial();
+        if quasi.is_polynomial() {
+            Some(quasi.constituents.into_iter().next().unwrap())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ambient_space::AffineSpace;
+
+    #[test]
+    fn test_lattice_points() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let triangle = space.convex_hull(vec![
+            space.vector([0, 0]),
+            space.vector([4, 0]),
+            space.vector([0, 4]),
+        ]);
+        assert_eq!(triangle.lattice_point_count(), 15);
+        assert_eq!(triangle.relative_interior_lattice_point_count(), 3);
+        assert!(triangle.lattice_points().contains(&space.vector([1, 3])));
+        assert!(!triangle.lattice_points().contains(&space.vector([2, 3])));
+
+        let thin = space.convex_hull(vec![
+            space.vector([Rational::from_integers(1, 3), Rational::from(0)]),
+            space.vector([Rational::from_integers(2, 3), Rational::from(5)]),
+        ]);
+        assert_eq!(thin.lattice_point_count(), 0);
+
+        let segment = space.convex_hull(vec![space.vector([-1, -2]), space.vector([2, 4])]);
+        assert_eq!(
+            segment.lattice_points(),
+            vec![
+                space.vector([-1, -2]),
+                space.vector([0, 0]),
+                space.vector([1, 2]),
+                space.vector([2, 4])
+            ]
+        );
+        assert_eq!(segment.relative_interior_lattice_point_count(), 2);
+
+        assert_eq!(space.convex_hull(vec![]).lattice_point_count(), 0);
+    }
+
+    #[test]
+    fn test_ehrhart_polynomial() {
+        //the unit cube has Ehrhart polynomial (t + 1)^3
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let mut points = vec![];
+        for x in [0, 1] {
+            for y in [0, 1] {
+                for z in [0, 1] {
+                    points.push(space.vector([x, y, z]));
+                }
+            }
+        }
+        let cube = space.convex_hull(points);
+        assert_eq!(cube.denominator(), Natural::ONE);
+        assert_eq!(
+            cube.ehrhart_polynomial().unwrap(),
+            Polynomial::from_coeffs(vec![1, 3, 3, 1])
+        );
+
+        //the leading coefficient of the Ehrhart polynomial of a lattice polygon is its area
+        //and the second is half the number of boundary points
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let polygon = space.convex_hull(vec![
+            space.vector([0, 0]),
+            space.vector([3, 0]),
+            space.vector([1, 2]),
+            space.vector([0, 1]),
+        ]);
+        let p = polygon.ehrhart_polynomial().unwrap();
+        assert_eq!(
+            p,
+            Polynomial::from_coeffs(vec![
+                Rational::ONE,
+                Rational::from_integers(7, 2),
+                Rational::from_integers(7, 2)
+            ])
+        );
+        //Ehrhart reciprocity: L(-1) = interior points
+        assert_eq!(
+            p.evaluate(&Rational::from(-1)),
+            Rational::from(polygon.relative_interior_lattice_point_count())
+        );
+
+        //a lattice segment in the plane
+        let segment = space.convex_hull(vec![space.vector([0, 0]), space.vector([2, 4])]);
+        assert_eq!(
+            segment.ehrhart_polynomial().unwrap(),
+            Polynomial::from_coeffs(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_ehrhart_quasi_polynomial() {
+        //the segment [0, 1/2] has L(t) = floor(t / 2) + 1
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 1);
+        let segment = space.convex_hull(vec![
+            space.vector([Rational::ZERO]),
+            space.vector([Rational::from_integers(1, 2)]),
+        ]);
+        let quasi = segment.ehrhart_quasi_polynomial();
+        assert_eq!(quasi.period(), 2);
+        assert!(!quasi.is_polynomial());
+        assert_eq!(segment.ehrhart_polynomial(), None);
+        assert_eq!(
+            quasi.constituents(),
+            &vec![
+                Polynomial::from_coeffs(vec![Rational::ONE, Rational::from_integers(1, 2)]),
+                Polynomial::from_coeffs(vec![
+                    Rational::from_integers(1, 2),
+                    Rational::from_integers(1, 2)
+                ]),
+            ]
+        );
+        for t in 0..10 {
+            assert_eq!(quasi.evaluate(t), Rational::from(t / 2 + 1));
+        }
+
+        //the triangle with vertices (0, 0), (1, 0), (0, 1/2) has L(t) = (t^2 + 4t + 4)/4 for even t
+        //and (t^2 + 4t + 3)/4 for odd t
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let triangle = space.convex_hull(vec![
+            space.vector([Rational::ZERO, Rational::ZERO]),
+            space.vector([Rational::ONE, Rational::ZERO]),
+            space.vector([Rational::ZERO, Rational::from_integers(1, 2)]),
+        ]);
+        let quasi = triangle.ehrhart_quasi_polynomial();
+        assert_eq!(quasi.period(), 2);
+        assert_eq!(
+            quasi.constituent(0),
+            &Polynomial::from_coeffs(vec![
+                Rational::ONE,
+                Rational::ONE,
+                Rational::from_integers(1, 4)
+            ])
+        );
+        assert_eq!(
+            quasi.constituent(1),
+            &Polynomial::from_coeffs(vec![
+                Rational::from_integers(3, 4),
+                Rational::ONE,
+                Rational::from_integers(1, 4)
+            ])
+        );
+
+        //the triangle with vertices (0, 0), (1, 1/2), (2, 0) has denominator 2 but its Ehrhart quasi-polynomial
+        //is a polynomial, an example of period collapse
+        let collapse = space.convex_hull(vec![
+            space.vector([Rational::ZERO, Rational::ZERO]),
+            space.vector([Rational::ONE, Rational::from_integers(1, 2)]),
+            space.vector([Rational::from(2), Rational::ZERO]),
+        ]);
+        assert_eq!(collapse.denominator(), Natural::from(2u32));
+        let p = collapse.ehrhart_polynomial().unwrap();
+        let (vertices, h) = (collapse.vertices(), collapse.to_half_spaces());
+        for t in 1..8 {
+            assert_eq!(
+                p.evaluate(&Rational::from(t)),
+                Rational::from(
+                    dilated_lattice_points(&vertices, &h, &Rational::from(t), false).len()
+                )
+            );
+        }
+    }
+}
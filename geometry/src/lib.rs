@@ -7,14 +7,24 @@ use std::hash::Hash;
 pub mod affine_subspace;
 pub mod ambient_space;
 pub mod boolean_operations;
+pub mod bounding_box;
 pub mod convex_hull;
+pub mod delaunay;
+pub mod formats;
+pub mod lattice_points;
+pub mod linear_programming;
+pub mod measure;
 pub mod minkowski_sum;
 pub mod oriented_simplex;
 pub mod parse;
 pub mod partial_simplicial_complex;
+pub mod point_location;
+pub mod polytope;
 pub mod simplex;
 pub mod simplex_collection;
 pub mod simplex_overlap;
 pub mod simplicial_complex;
 pub mod simplicial_disjoint_union;
+pub mod simplicial_homology;
+pub mod transformation;
 pub mod vector;
@@ -0,0 +1,630 @@
+use super::*;
+use crate::{
+    ambient_space::AffineSpace,
+    oriented_simplex::{OrientationSide, OrientedHyperplane},
+    vector::{DotProduct, Vector},
+};
+use algebraeon_rings::structure::AdditiveMonoidEqSignature;
+
+/// The half-space of points `x` satisfying `normal·x <= bound`.
+#[derive(Debug, Clone)]
+pub struct LinearInequality<'f, FS: OrderedRingSignature + FieldSignature> {
+    normal: Vector<'f, FS>,
+    bound: FS::Set,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> LinearInequality<'f, FS> {
+    pub fn new(normal: Vector<'f, FS>, bound: FS::Set) -> Self {
+        Self { normal, bound }
+    }
+
+    pub fn normal(&self) -> &Vector<'f, FS> {
+        &self.normal
+    }
+
+    pub fn bound(&self) -> &FS::Set {
+        &self.bound
+    }
+
+    pub fn is_satisfied_by(&self, point: &Vector<'f, FS>) -> bool {
+        let field = self.normal.ambient_space().field();
+        field.ring_cmp(&self.normal.dot(point), &self.bound) != std::cmp::Ordering::Greater
+    }
+}
+
+/// The outcome of solving a linear program together with a certificate which can be checked independently.
+#[derive(Debug, Clone)]
+pub enum LinearProgramResult<'f, FS: OrderedRingSignature + FieldSignature> {
+    /// `point` is an optimal point with objective `value`.
+    /// The `dual` multipliers are non-negative, one for each constraint, and satisfy
+    /// `Σ dual_i normal_i = objective` and `Σ dual_i bound_i = value`, proving that no feasible point does better.
+    Optimal {
+        point: Vector<'f, FS>,
+        value: FS::Set,
+        dual: Vec<FS::Set>,
+    },
+    /// `point + t * direction` is feasible for all `t >= 0` and the objective increases along `direction`.
+    Unbounded {
+        point: Vector<'f, FS>,
+        direction: Vector<'f, FS>,
+    },
+    /// The `farkas` multipliers are non-negative, one for each constraint, and satisfy
+    /// `Σ farkas_i normal_i = 0` and `Σ farkas_i bound_i < 0`, proving that no point satisfies every constraint.
+    Infeasible { farkas: Vec<FS::Set> },
+}
+
+/// Maximize `objective·x` over the points `x` of an affine space satisfying a list of linear inequalities.
+///
+/// Solved exactly by the two phase simplex method using Bland's rule, so it terminates over any ordered field.
+#[derive(Debug, Clone)]
+pub struct LinearProgram<'f, FS: OrderedRingSignature + FieldSignature> {
+    ambient_space: AffineSpace<'f, FS>,
+    objective: Vector<'f, FS>,
+    constraints: Vec<LinearInequality<'f, FS>>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> LinearProgram<'f, FS> {
+    pub fn new(ambient_space: AffineSpace<'f, FS>, objective: Vector<'f, FS>) -> Self {
+        assert_eq!(objective.ambient_space(), ambient_space);
+        Self {
+            ambient_space,
+            objective,
+            constraints: vec![],
+        }
+    }
+
+    /// A linear program with no objective, for testing feasibility.
+    pub fn new_feasibility(ambient_space: AffineSpace<'f, FS>) -> Self {
+        Self::new(ambient_space, Vector::zero(ambient_space))
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.ambient_space
+    }
+
+    pub fn objective(&self) -> &Vector<'f, FS> {
+        &self.objective
+    }
+
+    pub fn constraints(&self) -> &Vec<LinearInequality<'f, FS>> {
+        &self.constraints
+    }
+
+    pub fn add_constraint(&mut self, constraint: LinearInequality<'f, FS>) {
+        assert_eq!(constraint.normal.ambient_space(), self.ambient_space);
+        self.constraints.push(constraint);
+    }
+
+    /// Add the constraint `normal·x <= bound`.
+    pub fn add_inequality(&mut self, normal: Vector<'f, FS>, bound: FS::Set) {
+        self.add_constraint(LinearInequality::new(normal, bound));
+    }
+
+    /// Add the constraint `normal·x = bound`.
+    pub fn add_equality(&mut self, normal: Vector<'f, FS>, bound: FS::Set) {
+        let field = self.ambient_space.field();
+        self.add_inequality(-&normal, field.neg(&bound));
+        self.add_inequality(normal, bound);
+    }
+
+    /// Restrict to the closed half-space on the given side of an oriented hyperplane.
+    /// Choosing [`OrientationSide::Neutral`] restricts to the hyperplane itself.
+    pub fn add_half_space(
+        &mut self,
+        hyperplane: &OrientedHyperplane<'f, FS>,
+        side: OrientationSide,
+    ) {
+        let field = self.ambient_space.field();
+        let (normal, offset) = hyperplane.linear_functional();
+        match side {
+            OrientationSide::Positive => self.add_inequality(-&normal, field.neg(&offset)),
+            OrientationSide::Neutral => self.add_equality(normal, offset),
+            OrientationSide::Negative => self.add_inequality(normal, offset),
+        }
+    }
+
+    pub fn is_feasible_point(&self, point: &Vector<'f, FS>) -> bool {
+        self.constraints.iter().all(|c| c.is_satisfied_by(point))
+    }
+
+    fn tableau(&self) -> Tableau<'f, FS> {
+        Tableau::new(self.ambient_space, &self.constraints)
+    }
+
+    /// Some point satisfying every constraint, or `None` if there is no such point.
+    pub fn feasible_point(&self) -> Option<Vector<'f, FS>> {
+        let mut tableau = self.tableau();
+        match tableau.phase_one() {
+            Ok(()) => Some(tableau.point()),
+            Err(_) => None,
+        }
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.feasible_point().is_some()
+    }
+
+    pub fn solve(&self) -> LinearProgramResult<'f, FS> {
+        let mut tableau = self.tableau();
+        if let Err(farkas) = tableau.phase_one() {
+            return LinearProgramResult::Infeasible { farkas };
+        }
+        match tableau.phase_two(&self.objective) {
+            Ok(dual) => {
+                let point = tableau.point();
+                LinearProgramResult::Optimal {
+                    value: self.objective.dot(&point),
+                    point,
+                    dual,
+                }
+            }
+            Err(direction) => LinearProgramResult::Unbounded {
+                point: tableau.point(),
+                direction,
+            },
+        }
+    }
+}
+
+/// A dense simplex tableau for the standard form problem
+/// `A(x⁺ - x⁻) + s = b` with `x⁺, x⁻, s >= 0`, where each row is negated if necessary
+/// so that the right hand side is non-negative and an artificial variable is added to each row.
+///
+/// The columns are ordered `x⁺, x⁻, s, artificial`. The artificial columns hold the inverse of the current basis.
+struct Tableau<'f, FS: OrderedRingSignature + FieldSignature> {
+    ambient_space: AffineSpace<'f, FS>,
+    dim: usize,
+    rows: usize,
+    negated: Vec<bool>,
+    entries: Vec<Vec<FS::Set>>,
+    rhs: Vec<FS::Set>,
+    basis: Vec<usize>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> Tableau<'f, FS> {
+    fn new(ambient_space: AffineSpace<'f, FS>, constraints: &[LinearInequality<'f, FS>]) -> Self {
+        let field = ambient_space.field();
+        let dim = ambient_space.linear_dimension().unwrap();
+        let rows = constraints.len();
+        let cols = 2 * dim + 2 * rows;
+        let mut negated = vec![];
+        let mut entries = vec![];
+        let mut rhs = vec![];
+        for (i, constraint) in constraints.iter().enumerate() {
+            let neg = field.ring_cmp(&constraint.bound, &field.zero()) == std::cmp::Ordering::Less;
+            let sign = |x: FS::Set| if neg { field.neg(&x) } else { x };
+            let mut row = (0..cols).map(|_| field.zero()).collect::<Vec<_>>();
+            for k in 0..dim {
+                row[k] = sign(constraint.normal.coordinate(k).clone());
+                row[dim + k] = field.neg(&row[k]);
+            }
+            row[2 * dim + i] = sign(field.one());
+            row[2 * dim + rows + i] = field.one();
+            negated.push(neg);
+            entries.push(row);
+            rhs.push(sign(constraint.bound.clone()));
+        }
+        Self {
+            ambient_space,
+            dim,
+            rows,
+            negated,
+            entries,
+            rhs,
+            basis: (0..rows).map(|i| 2 * dim + rows + i).collect(),
+        }
+    }
+
+    fn field(&self) -> &'f FS {
+        self.ambient_space.field()
+    }
+
+    fn artificial_start(&self) -> usize {
+        2 * self.dim + self.rows
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let field = self.field();
+        let inv = field.inv(&self.entries[row][col]).unwrap();
+        for x in &mut self.entries[row] {
+            field.mul_mut(x, &inv);
+        }
+        field.mul_mut(&mut self.rhs[row], &inv);
+        for i in 0..self.rows {
+            if i != row && !field.is_zero(&self.entries[i][col]) {
+                let factor = self.entries[i][col].clone();
+                for j in 0..self.entries[i].len() {
+                    let delta = field.neg(&field.mul(&factor, &self.entries[row][j]));
+                    field.add_mut(&mut self.entries[i][j], &delta);
+                }
+                let delta = field.neg(&field.mul(&factor, &self.rhs[row]));
+                field.add_mut(&mut self.rhs[i], &delta);
+            }
+        }
+        self.basis[row] = col;
+    }
+
+    /// The row vector `c_B B⁻¹` of simplex multipliers for the given column costs.
+    fn multipliers(&self, costs: &[FS::Set]) -> Vec<FS::Set> {
+        let field = self.field();
+        let start = self.artificial_start();
+        (0..self.rows)
+            .map(|k| {
+                field.sum(
+                    (0..self.rows)
+                        .map(|i| field.mul(&costs[self.basis[i]], &self.entries[i][start + k]))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The simplex multipliers with the sign of each negated row undone, so that they multiply the original constraints.
+    ///
+    /// At an optimum the reduced cost of each slack column is non-positive, which makes these multipliers non-negative.
+    fn constraint_multipliers(&self, costs: &[FS::Set]) -> Vec<FS::Set> {
+        let field = self.field();
+        self.multipliers(costs)
+            .into_iter()
+            .zip(&self.negated)
+            .map(|(y, neg)| if *neg { field.neg(&y) } else { y })
+            .collect()
+    }
+
+    /// Maximize `costs` over the current tableau, only allowing the columns before `allowed` to enter the basis.
+    /// On unboundedness return the column which can be increased without limit.
+    fn optimize(&mut self, costs: &[FS::Set], allowed: usize) -> Result<(), usize> {
+        let field = self.field();
+        loop {
+            // Bland's rule: the entering column is the first with positive reduced cost
+            let entering = (0..allowed).find(|&j| {
+                !self.basis.contains(&j) && {
+                    let reduced = field.sub(
+                        &costs[j],
+                        &field.sum(
+                            (0..self.rows)
+                                .map(|i| field.mul(&costs[self.basis[i]], &self.entries[i][j]))
+                                .collect(),
+                        ),
+                    );
+                    field.ring_cmp(&reduced, &field.zero()) == std::cmp::Ordering::Greater
+                }
+            });
+            let Some(col) = entering else {
+                return Ok(());
+            };
+            // ratio test, breaking ties by the smallest basic variable
+            let mut leaving: Option<(usize, FS::Set)> = None;
+            for i in 0..self.rows {
+                if field.ring_cmp(&self.entries[i][col], &field.zero())
+                    == std::cmp::Ordering::Greater
+                {
+                    let ratio = field.div(&self.rhs[i], &self.entries[i][col]).unwrap();
+                    let better = match &leaving {
+                        None => true,
+                        Some((best, best_ratio)) => match field.ring_cmp(&ratio, best_ratio) {
+                            std::cmp::Ordering::Less => true,
+                            std::cmp::Ordering::Equal => self.basis[i] < self.basis[*best],
+                            std::cmp::Ordering::Greater => false,
+                        },
+                    };
+                    if better {
+                        leaving = Some((i, ratio));
+                    }
+                }
+            }
+            match leaving {
+                Some((row, _)) => self.pivot(row, col),
+                None => return Err(col),
+            }
+        }
+    }
+
+    /// Find a basic feasible solution, or return Farkas multipliers proving there is none.
+    fn phase_one(&mut self) -> Result<(), Vec<FS::Set>> {
+        let field = self.field();
+        let start = self.artificial_start();
+        let costs = (0..start + self.rows)
+            .map(|j| {
+                if j < start {
+                    field.zero()
+                } else {
+                    field.neg(&field.one())
+                }
+            })
+            .collect::<Vec<_>>();
+        self.optimize(&costs, start).unwrap();
+        let infeasibility = field.sum(
+            (0..self.rows)
+                .filter(|i| self.basis[*i] >= start)
+                .map(|i| self.rhs[i].clone())
+                .collect(),
+        );
+        if !field.is_zero(&infeasibility) {
+            return Err(self.constraint_multipliers(&costs));
+        }
+        // pivot out any artificial variables left in the basis at level zero
+        // if a row has no other non-zero entry then it is redundant and the artificial variable stays at zero
+        for i in 0..self.rows {
+            if self.basis[i] >= start
+                && let Some(j) = (0..start).find(|j| !field.is_zero(&self.entries[i][*j]))
+            {
+                self.pivot(i, j);
+            }
+        }
+        Ok(())
+    }
+
+    /// Maximize `objective·x` from a basic feasible solution.
+    /// Return the dual multipliers at an optimum or an unbounded direction.
+    fn phase_two(&mut self, objective: &Vector<'f, FS>) -> Result<Vec<FS::Set>, Vector<'f, FS>> {
+        let field = self.field();
+        let start = self.artificial_start();
+        let costs = (0..start + self.rows)
+            .map(|j| {
+                if j < self.dim {
+                    objective.coordinate(j).clone()
+                } else if j < 2 * self.dim {
+                    field.neg(objective.coordinate(j - self.dim))
+                } else {
+                    field.zero()
+                }
+            })
+            .collect::<Vec<_>>();
+        match self.optimize(&costs, start) {
+            Ok(()) => Ok(self.constraint_multipliers(&costs)),
+            Err(col) => {
+                let mut values = (0..start).map(|_| field.zero()).collect::<Vec<_>>();
+                values[col] = field.one();
+                for i in 0..self.rows {
+                    if self.basis[i] < start {
+                        values[self.basis[i]] = field.neg(&self.entries[i][col]);
+                    }
+                }
+                Err(self.point_from_standard_form(&values))
+            }
+        }
+    }
+
+    fn point_from_standard_form(&self, values: &[FS::Set]) -> Vector<'f, FS> {
+        let field = self.field();
+        Vector::construct(self.ambient_space, |k| {
+            field.sub(&values[k], &values[self.dim + k])
+        })
+    }
+
+    /// The point of the current basic solution.
+    fn point(&self) -> Vector<'f, FS> {
+        let field = self.field();
+        let start = self.artificial_start();
+        let mut values = (0..start).map(|_| field.zero()).collect::<Vec<_>>();
+        for i in 0..self.rows {
+            if self.basis[i] < start {
+                values[self.basis[i]] = self.rhs[i].clone();
+            }
+        }
+        self.point_from_standard_form(&values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oriented_simplex::OrientedSimplex;
+    use algebraeon_nzq::Rational;
+    use algebraeon_rings::isolated_algebraic::RealAlgebraic;
+    use algebraeon_rings::structure::{AdditiveMonoidSignature, MetaPositiveRealNthRoot};
+
+    fn check_result<'f, FS: OrderedRingSignature + FieldSignature>(
+        lp: &LinearProgram<'f, FS>,
+        result: &LinearProgramResult<'f, FS>,
+    ) {
+        let space = lp.ambient_space();
+        let field = space.field();
+        let combination = |multipliers: &Vec<FS::Set>| {
+            assert_eq!(multipliers.len(), lp.constraints().len());
+            let mut normal = Vector::zero(space);
+            let mut bound = field.zero();
+            for (y, constraint) in multipliers.iter().zip(lp.constraints()) {
+                assert_ne!(field.ring_cmp(y, &field.zero()), std::cmp::Ordering::Less);
+                normal += &constraint.normal().scalar_mul(y);
+                field.add_mut(&mut bound, &field.mul(y, constraint.bound()));
+            }
+            (normal, bound)
+        };
+        match result {
+            LinearProgramResult::Optimal { point, value, dual } => {
+                assert!(lp.is_feasible_point(point));
+                assert!(field.equal(&lp.objective().dot(point), value));
+                let (normal, bound) = combination(dual);
+                assert_eq!(&normal, lp.objective());
+                assert!(field.equal(&bound, value));
+            }
+            LinearProgramResult::Unbounded { point, direction } => {
+                assert!(lp.is_feasible_point(point));
+                assert!(lp.is_feasible_point(&(point + direction)));
+                assert_eq!(
+                    field.ring_cmp(&lp.objective().dot(direction), &field.zero()),
+                    std::cmp::Ordering::Greater
+                );
+                for constraint in lp.constraints() {
+                    assert_ne!(
+                        field.ring_cmp(&constraint.normal().dot(direction), &field.zero()),
+                        std::cmp::Ordering::Greater
+                    );
+                }
+            }
+            LinearProgramResult::Infeasible { farkas } => {
+                let (normal, bound) = combination(farkas);
+                assert_eq!(normal, Vector::zero(space));
+                assert_eq!(
+                    field.ring_cmp(&bound, &field.zero()),
+                    std::cmp::Ordering::Less
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_optimal() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let mut lp = LinearProgram::new(space, space.vector([1, 1]));
+        lp.add_inequality(space.vector([1, 0]), Rational::from(2));
+        lp.add_inequality(space.vector([0, 1]), Rational::from(3));
+        lp.add_inequality(space.vector([1, 2]), Rational::from(6));
+        lp.add_inequality(space.vector([-1, 0]), Rational::from(0));
+        lp.add_inequality(space.vector([0, -1]), Rational::from(0));
+        let result = lp.solve();
+        check_result(&lp, &result);
+        match result {
+            LinearProgramResult::Optimal { point, value, .. } => {
+                assert_eq!(point, space.vector([2, 2]));
+                assert_eq!(value, Rational::from(4));
+            }
+            _ => panic!(),
+        }
+
+        //no constraints and no objective
+        let lp = LinearProgram::new_feasibility(space);
+        let result = lp.solve();
+        check_result(&lp, &result);
+        assert!(matches!(result, LinearProgramResult::Optimal { .. }));
+    }
+
+    #[test]
+    fn test_unbounded_and_infeasible() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let mut lp = LinearProgram::new(space, space.vector([1, -1]));
+        lp.add_inequality(space.vector([0, 1]), Rational::from(1));
+        lp.add_inequality(space.vector([0, -1]), Rational::from(1));
+        lp.add_inequality(space.vector([-1, 0]), Rational::from(-5));
+        let result = lp.solve();
+        check_result(&lp, &result);
+        assert!(matches!(result, LinearProgramResult::Unbounded { .. }));
+        assert!(lp.is_feasible());
+
+        lp.add_inequality(space.vector([1, 1]), Rational::from(3));
+        let result = lp.solve();
+        check_result(&lp, &result);
+        assert!(matches!(result, LinearProgramResult::Infeasible { .. }));
+        assert!(!lp.is_feasible());
+        assert_eq!(lp.feasible_point(), None);
+    }
+
+    #[test]
+    fn test_degenerate_cycling_example() {
+        //Beale's example, on which the simplex method cycles using the largest coefficient rule
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 4);
+        let q = |n: i64, d: i64| Rational::from_integers(n, d);
+        let mut lp =
+            LinearProgram::new(space, space.vector([q(3, 4), q(-20, 1), q(1, 2), q(-6, 1)]));
+        lp.add_inequality(
+            space.vector([q(1, 4), q(-8, 1), q(-1, 1), q(9, 1)]),
+            Rational::ZERO,
+        );
+        lp.add_inequality(
+            space.vector([q(1, 2), q(-12, 1), q(-1, 2), q(3, 1)]),
+            Rational::ZERO,
+        );
+        lp.add_inequality(space.vector([0, 0, 1, 0]), Rational::ONE);
+        for i in 0..4 {
+            lp.add_inequality(
+                Vector::construct(space, |j| {
+                    if i == j {
+                        -Rational::ONE
+                    } else {
+                        Rational::ZERO
+                    }
+                }),
+                Rational::ZERO,
+            );
+        }
+        let result = lp.solve();
+        check_result(&lp, &result);
+        match result {
+            LinearProgramResult::Optimal { value, .. } => assert_eq!(value, q(5, 4)),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_half_spaces() {
+        //the triangle with vertices (0, 0), (4, 0), (0, 4) as the intersection of half-spaces
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let a = space.vector([0, 0]);
+        let b = space.vector([4, 0]);
+        let c = space.vector([0, 4]);
+        let mut lp = LinearProgram::new(space, space.vector([1, 3]));
+        let mut hyperplanes = vec![];
+        for (p, q, r) in [(&a, &b, &c), (&b, &c, &a), (&c, &a, &b)] {
+            let hyperplane =
+                OrientedSimplex::new_with_positive_point(space, vec![p.clone(), q.clone()], r)
+                    .unwrap()
+                    .into_oriented_hyperplane();
+            lp.add_half_space(&hyperplane, OrientationSide::Positive);
+            hyperplanes.push(hyperplane);
+        }
+        let result = lp.solve();
+        check_result(&lp, &result);
+        match result {
+            LinearProgramResult::Optimal { point, value, .. } => {
+                assert_eq!(point, c);
+                assert_eq!(value, Rational::from(12));
+            }
+            _ => panic!(),
+        }
+        let inside = lp.feasible_point().unwrap();
+        for hyperplane in &hyperplanes {
+            assert_ne!(
+                hyperplane.classify_point(&inside),
+                OrientationSide::Negative
+            );
+        }
+
+        //restrict to the edge from a to b
+        lp.add_half_space(&hyperplanes[0], OrientationSide::Neutral);
+        let result = lp.solve();
+        check_result(&lp, &result);
+        match result {
+            LinearProgramResult::Optimal { point, value, .. } => {
+                assert_eq!(point, b);
+                assert_eq!(value, Rational::from(4));
+            }
+            _ => panic!(),
+        }
+
+        //and then to the far side of the line through b and c
+        lp.add_half_space(&hyperplanes[1], OrientationSide::Negative);
+        lp.add_inequality(space.vector([1, 1]), Rational::from(3));
+        assert!(!lp.is_feasible());
+        check_result(&lp, &lp.solve());
+    }
+
+    #[test]
+    fn test_real_algebraic() {
+        let two = RealAlgebraic::Rational(Rational::from(2));
+        let sqrt_two = two.nth_root(2).unwrap();
+        let one = RealAlgebraic::Rational(Rational::ONE);
+        let zero = RealAlgebraic::Rational(Rational::ZERO);
+        let minus_one = RealAlgebraic::Rational(-Rational::ONE);
+        let space = AffineSpace::new_linear(RealAlgebraic::structure_ref(), 2);
+        //maximize x + sqrt(2) y over the unit square
+        let mut lp = LinearProgram::new(space, space.vector([one.clone(), sqrt_two.clone()]));
+        lp.add_inequality(space.vector([one.clone(), zero.clone()]), one.clone());
+        lp.add_inequality(space.vector([zero.clone(), one.clone()]), one.clone());
+        lp.add_inequality(
+            space.vector([minus_one.clone(), zero.clone()]),
+            zero.clone(),
+        );
+        lp.add_inequality(space.vector([zero.clone(), minus_one]), zero.clone());
+        let result = lp.solve();
+        check_result(&lp, &result);
+        match result {
+            LinearProgramResult::Optimal { point, value, .. } => {
+                assert_eq!(point, space.vector([one.clone(), one.clone()]));
+                assert_eq!(value, RealAlgebraic::structure_ref().add(&one, &sqrt_two));
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use super::*;
+use crate::{
+    simplex::Simplex,
+    simplex_collection::LabelledSimplexCollection,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_rings::matrix::{Matrix, MatrixStructure};
+use algebraeon_rings::structure::AdditiveMonoidEqSignature;
+
+fn factorial<FS: FieldSignature>(field: &FS, k: usize) -> FS::Set {
+    let mut f = field.one();
+    for i in 2..=k {
+        field.mul_mut(&mut f, &field.from_nat(i));
+    }
+    f
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> Simplex<'f, FS> {
+    fn edge_vectors(&self) -> Vec<Vector<'f, FS>> {
+        (1..self.n())
+            .map(|i| self.point(i) - self.point(0))
+            .collect()
+    }
+
+    fn is_full_dimensional(&self) -> bool {
+        self.n() == self.ambient_space().affine_dimension()
+    }
+
+    /// The square of the `k`-dimensional volume of a simplex with `k+1` points.
+    ///
+    /// The `k`-dimensional volume itself need not lie in the field, for example the length of a diagonal line segment.
+    pub fn squared_volume(&self) -> FS::Set {
+        let field = self.ambient_space().field();
+        let edges = self.edge_vectors();
+        let k = edges.len();
+        if k == 0 {
+            return field.one();
+        }
+        let gram = Matrix::construct(k, k, |r, c| edges[r].dot(&edges[c]));
+        let det = MatrixStructure::new(field.clone()).det(gram).unwrap();
+        let f = factorial(field, k);
+        field.div(&det, &field.mul(&f, &f)).unwrap()
+    }
+
+    /// The volume in the dimension of the ambient space, which is zero unless the simplex is full dimensional.
+    pub fn volume(&self) -> FS::Set {
+        let space = self.ambient_space();
+        let field = space.field();
+        if !self.is_full_dimensional() {
+            return field.zero();
+        }
+        let edges = self.edge_vectors();
+        if edges.is_empty() {
+            return field.one();
+        }
+        let det = space.determinant(edges.iter().collect());
+        field
+            .div(&field.abs(&det), &factorial(field, edges.len()))
+            .unwrap()
+    }
+
+    /// The average of the points of the simplex.
+    pub fn centroid(&self) -> Vector<'f, FS> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let mut total = Vector::zero(space);
+        for pt in self.points() {
+            total += pt;
+        }
+        total.scalar_mul(&field.inv(&field.from_nat(self.n())).unwrap())
+    }
+
+    /// The matrix of second moments `∫ x_i x_j` over the simplex, taken about the origin.
+    /// This is zero unless the simplex is full dimensional.
+    pub fn second_moment_matrix(&self) -> Matrix<FS::Set> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let d = space.linear_dimension().unwrap();
+        if !self.is_full_dimensional() {
+            return Matrix::construct(d, d, |_, _| field.zero());
+        }
+        // ∫ x x^T = vol / ((d+1)(d+2)) * (Σ v v^T + (Σ v)(Σ v)^T) summing over the points v
+        let mut sum = Vector::zero(space);
+        for pt in self.points() {
+            sum += pt;
+        }
+        let scale = field
+            .div(&self.volume(), &field.from_nat((d + 1) * (d + 2)))
+            .unwrap();
+        Matrix::construct(d, d, |r, c| {
+            let mut entry = field.mul(sum.coordinate(r), sum.coordinate(c));
+            for pt in self.points() {
+                field.add_mut(&mut entry, &field.mul(pt.coordinate(r), pt.coordinate(c)));
+            }
+            field.mul(&entry, &scale)
+        })
+    }
+}
+
+/// Exact measurements of a collection of disjoint simplices treated as a solid of uniform density.
+///
+/// Everything is measured in the dimension of the ambient space, so lower dimensional simplices contribute nothing.
+pub trait SimplexCollectionMeasure<
+    'f,
+    FS: OrderedRingSignature + FieldSignature + 'f,
+    T: Eq + Clone + Send + Sync,
+>: LabelledSimplexCollection<'f, FS, T> where
+    FS::Set: Hash,
+{
+    /// The length, area, volume, ... in the dimension of the ambient space.
+    fn volume(&self) -> FS::Set {
+        let field = self.ambient_space().field();
+        field.sum(
+            self.simplexes()
+                .into_iter()
+                .map(|spx| spx.volume())
+                .collect(),
+        )
+    }
+
+    /// The centre of mass, or `None` if the volume is zero.
+    fn centroid(&self) -> Option<Vector<'f, FS>> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let volume = self.volume();
+        if field.is_zero(&volume) {
+            return None;
+        }
+        let mut total = Vector::zero(space);
+        for spx in self.simplexes() {
+            total += &spx.centroid().scalar_mul(&spx.volume());
+        }
+        Some(total.scalar_mul(&field.inv(&volume).unwrap()))
+    }
+
+    /// The matrix of second moments `∫ x_i x_j`, taken about the origin.
+    fn second_moment_matrix(&self) -> Matrix<FS::Set> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let d = space.linear_dimension().unwrap();
+        let mut total = Matrix::construct(d, d, |_, _| field.zero());
+        for spx in self.simplexes() {
+            let m = spx.second_moment_matrix();
+            total = Matrix::construct(d, d, |r, c| {
+                field.add(total.at(r, c).unwrap(), m.at(r, c).unwrap())
+            });
+        }
+        total
+    }
+
+    /// The moment of inertia tensor `∫ (|y|^2 I - y y^T)` about the centroid where `y = x - centroid`,
+    /// or `None` if the volume is zero.
+    fn inertia_tensor(&self) -> Option<Matrix<FS::Set>> {
+        let field = self.ambient_space().field();
+        let d = self.ambient_space().linear_dimension().unwrap();
+        let centroid = self.centroid()?;
+        let volume = self.volume();
+        let moments = self.second_moment_matrix();
+        //the second moments about the centroid
+        let central = Matrix::construct(d, d, |r, c| {
+            field.sub(
+                moments.at(r, c).unwrap(),
+                &field.mul(
+                    &volume,
+                    &field.mul(centroid.coordinate(r), centroid.coordinate(c)),
+                ),
+            )
+        });
+        let trace = field.sum((0..d).map(|i| central.at(i, i).unwrap()).collect());
+        Some(Matrix::construct(d, d, |r, c| {
+            let entry = field.neg(central.at(r, c).unwrap());
+            if r == c {
+                field.add(&entry, &trace)
+            } else {
+                entry
+            }
+        }))
+    }
+}
+
+impl<
+    'f,
+    FS: OrderedRingSignature + FieldSignature + 'f,
+    T: Eq + Clone + Send + Sync,
+    S: LabelledSimplexCollection<'f, FS, T>,
+> SimplexCollectionMeasure<'f, FS, T> for S
+where
+    FS::Set: Hash,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ambient_space::AffineSpace, boolean_operations::Difference,
+        simplex_collection::InteriorOrBoundarySimplexCollection,
+    };
+    use algebraeon_nzq::Rational;
+
+    #[test]
+    fn test_simplex_measure() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let triangle = space
+            .simplex(vec![
+                space.vector([0, 0]),
+                space.vector([1, 0]),
+                space.vector([0, 1]),
+            ])
+            .unwrap();
+        assert_eq!(triangle.volume(), Rational::from_integers(1, 2));
+        assert_eq!(triangle.squared_volume(), Rational::from_integers(1, 4));
+        assert_eq!(
+            triangle.centroid(),
+            space.vector([Rational::from_integers(1, 3), Rational::from_integers(1, 3)])
+        );
+
+        let segment = space
+            .simplex(vec![space.vector([0, 0]), space.vector([3, 4])])
+            .unwrap();
+        assert_eq!(segment.volume(), Rational::ZERO);
+        assert_eq!(segment.squared_volume(), Rational::from(25));
+
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let tetrahedron = space
+            .simplex(vec![
+                space.vector([1, 1, 1]),
+                space.vector([2, 1, 1]),
+                space.vector([1, 3, 1]),
+                space.vector([1, 1, 4]),
+            ])
+            .unwrap();
+        assert_eq!(tetrahedron.volume(), Rational::from(1));
+        assert_eq!(tetrahedron.squared_volume(), Rational::from(1));
+        let face = tetrahedron.facet(0);
+        assert_eq!(face.squared_volume(), Rational::from_integers(49, 4));
+    }
+
+    #[test]
+    fn test_shape_measure() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let square = space
+            .convex_hull(vec![
+                space.vector([0, 0]),
+                space.vector([2, 0]),
+                space.vector([0, 2]),
+                space.vector([2, 2]),
+            ])
+            .to_simplicial_complex();
+        assert_eq!(square.volume(), Rational::from(4));
+        assert_eq!(square.interior().volume(), Rational::from(4));
+        assert_eq!(square.boundary().volume(), Rational::ZERO);
+        assert_eq!(square.boundary().centroid(), None);
+        assert_eq!(square.centroid(), Some(space.vector([1, 1])));
+        //∫x^2 = 16/3 and ∫xy = 4 over [0, 2]^2
+        let m = square.second_moment_matrix();
+        assert_eq!(m.at(0, 0).unwrap(), &Rational::from_integers(16, 3));
+        assert_eq!(m.at(0, 1).unwrap(), &Rational::from(4));
+
+        let hole = space
+            .convex_hull(vec![
+                space.vector([1, 1]),
+                space.vector([2, 1]),
+                space.vector([1, 2]),
+                space.vector([2, 2]),
+            ])
+            .to_simplicial_complex()
+            .forget_labels();
+        let notched = square
+            .forget_labels()
+            .to_simplicial_disjoint_union()
+            .difference(&hole.to_simplicial_disjoint_union());
+        assert_eq!(notched.volume(), Rational::from(3));
+        assert_eq!(
+            notched.centroid(),
+            Some(space.vector([Rational::from_integers(5, 6), Rational::from_integers(5, 6)]))
+        );
+    }
+
+    #[test]
+    fn test_inertia_tensor() {
+        //a cube of side 2 has moment of inertia 2 * 2^2 / 12 times its volume 8 about each axis
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let mut points = vec![];
+        for x in [1, 3] {
+            for y in [0, 2] {
+                for z in [-1, 1] {
+                    points.push(space.vector([x, y, z]));
+                }
+            }
+        }
+        let cube = space.convex_hull(points).to_simplicial_complex();
+        assert_eq!(cube.volume(), Rational::from(8));
+        assert_eq!(cube.centroid(), Some(space.vector([2, 1, 0])));
+        let inertia = cube.inertia_tensor().unwrap();
+        for r in 0..3 {
+            for c in 0..3 {
+                let expected = if r == c {
+                    Rational::from_integers(16, 3)
+                } else {
+                    Rational::ZERO
+                };
+                assert_eq!(inertia.at(r, c).unwrap(), &expected);
+            }
+        }
+    }
+}
@@ -162,6 +162,29 @@ impl<'f, FS: OrderedRingSignature + FieldSignature> OrientedSimplex<'f, FS> {
         }
     }
 
+    /// A vector `n` and a scalar `k` such that a point `x` is on the positive side when `n·x > k`,
+    /// on the negative side when `n·x < k` and on the hyperplane when `n·x = k`.
+    pub fn linear_functional(&self) -> (Vector<'f, FS>, FS::Set) {
+        let space = self.ambient_space();
+        let field = space.field();
+        match &self.orientation {
+            Some(OrientedSimplexOrientation {
+                flip,
+                plane_point,
+                positive_normal,
+            }) => {
+                let normal = if *flip {
+                    -positive_normal
+                } else {
+                    positive_normal.clone()
+                };
+                let offset = normal.dot(plane_point);
+                (normal, offset)
+            }
+            None => (Vector::zero(space), field.zero()),
+        }
+    }
+
     pub fn classify_point(&self, point: &Vector<'f, FS>) -> OrientationSide {
         let space = self.ambient_space();
         let field = space.field();
@@ -311,6 +334,11 @@ impl<'f, FS: OrderedRingSignature + FieldSignature> OrientedHyperplane<'f, FS> {
         }
     }
 
+    /// See [`OrientedSimplex::linear_functional`].
+    pub fn linear_functional(&self) -> (Vector<'f, FS>, FS::Set) {
+        self.oriented_simplex.linear_functional()
+    }
+
     pub fn flip(&mut self) {
         self.oriented_simplex.flip();
     }
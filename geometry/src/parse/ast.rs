@@ -1,14 +1,44 @@
+use super::{BALL_GRID_SIZE, MAX_BALL_DIMENSION, MAX_BOX_DIMENSION};
 use crate::{
     ambient_space::AffineSpace,
     boolean_operations::{Difference, Intersect, Union},
+    formats::parse_number,
     minkowski_sum::MinkowskiSum,
     partial_simplicial_complex::PartialSimplicialComplex,
     simplex_collection::{InteriorOrBoundarySimplexCollection, LabelledSimplexCollection},
+    transformation::AffineTransformation,
     vector::Vector,
 };
 use algebraeon_nzq::{Rational, RationalCanonicalStructure};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+// shapes are built over the rationals, which the numbers of the grammar are parsed into
+type Space<'f> = AffineSpace<'f, RationalCanonicalStructure>;
+type ShapeValue<'f> = PartialSimplicialComplex<'f, RationalCanonicalStructure>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+    pub span: Span,
+    pub message: String,
+}
+
+impl ShapeError {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Sign {
     Negative,
@@ -21,6 +51,17 @@ pub struct SignedValue {
     pub value: String,
 }
 
+impl SignedValue {
+    fn to_rational(&self) -> Rational {
+        // the lexer only accepts integers, fractions and decimals
+        let value = parse_number(&self.value).unwrap();
+        match self.sign {
+            Sign::Negative => -value,
+            Sign::Positive => value,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Point {
     pub coordinates: Vec<SignedValue>,
@@ -31,27 +72,24 @@ impl Point {
         self.coordinates.len()
     }
 
-    fn to_vector(
-        &self,
-        space: AffineSpace<'static, RationalCanonicalStructure>,
-    ) -> Vector<'static, RationalCanonicalStructure> {
-        space.vector(self.coordinates.iter().map(|ast_value| {
-            let mut value = Rational::from_str(&ast_value.value).unwrap();
-            match ast_value.sign {
-                Sign::Negative => value = -value,
-                Sign::Positive => {}
-            }
-            value
-        }))
+    fn to_vector<'f>(&self, space: Space<'f>) -> Vector<'f, RationalCanonicalStructure> {
+        space.vector(self.coordinates.iter().map(|value| value.to_rational()))
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaleFactor {
+    Uniform(SignedValue),
+    Axes(Point),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShapeExpression {
-    Union(Box<ShapeExpression>, Box<ShapeExpression>),
-    Intersect(Box<ShapeExpression>, Box<ShapeExpression>),
-    MinkowskiSum(Box<ShapeExpression>, Box<ShapeExpression>),
-    Difference(Box<ShapeExpression>, Box<ShapeExpression>),
+    Union(Box<Shape>, Box<Shape>),
+    Intersect(Box<Shape>, Box<Shape>),
+    MinkowskiSum(Box<Shape>, Box<Shape>),
+    Difference(Box<Shape>, Box<Shape>),
+    Name(String),
     Point(Point),
     ConvexHull(Vec<Point>),
     ConvexHullInterior(Vec<Point>),
@@ -60,91 +98,247 @@ pub enum ShapeExpression {
     Loop(Vec<Point>),
     Polygon(Vec<Point>),
     PolygonInterior(Vec<Point>),
+    Simplex(Vec<Point>),
+    Box(Point, Point),
+    Cube(String, SignedValue),
+    Ball(String, SignedValue),
+    Translate(Box<Shape>, Point),
+    Scale(Box<Shape>, ScaleFactor),
+    Rotate(Box<Shape>, Point, Option<(String, String)>),
 }
 
-impl ShapeExpression {
-    fn dimension(&self) -> Result<usize, ()> {
-        let mut same_dim = None;
-        for next_dim in match self {
-            ShapeExpression::Union(left, right) => {
-                vec![left.dimension(), right.dimension()]
-            }
-            ShapeExpression::Intersect(left, right) => {
-                vec![left.dimension(), right.dimension()]
-            }
-            ShapeExpression::MinkowskiSum(left, right) => {
-                vec![left.dimension(), right.dimension()]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    pub span: Span,
+    pub expression: ShapeExpression,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub name: String,
+    pub shape: Shape,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub bindings: Vec<Binding>,
+    pub shape: Shape,
+}
+
+fn convex_hull_shape<'f>(space: Space<'f>, points: &[Point]) -> ShapeValue<'f> {
+    space
+        .convex_hull(points.iter().map(|pt| pt.to_vector(space)).collect())
+        .to_simplicial_complex()
+        .into_forget_labels()
+        .into_partial_simplicial_complex()
+}
+
+// the union of the line segments between consecutive points
+fn path_shape<'f>(space: Space<'f>, points: &[Point], closed: bool) -> ShapeValue<'f> {
+    let mut shape = space.empty_subset().into_partial_simplicial_complex();
+    let n = points.len();
+    let segments = if closed { n } else { n.saturating_sub(1) };
+    for i in 0..segments {
+        let p0 = points[i].to_vector(space);
+        let p1 = points[(i + 1) % n].to_vector(space);
+        shape = shape.union(
+            &space
+                .convex_hull(vec![p0, p1])
+                .to_simplicial_complex()
+                .into_forget_labels(),
+        );
+    }
+    shape
+}
+
+// the box with opposite corners p and q
+fn box_shape<'f>(
+    space: Space<'f>,
+    p: &Vector<'f, RationalCanonicalStructure>,
+    q: &Vector<'f, RationalCanonicalStructure>,
+) -> ShapeValue<'f> {
+    let d = space.linear_dimension().unwrap();
+    let corners = (0..(1usize << d))
+        .map(|mask| {
+            Vector::construct(space, |i| {
+                if mask & (1 << i) == 0 {
+                    p.coordinate(i).clone()
+                } else {
+                    q.coordinate(i).clone()
+                }
+            })
+        })
+        .collect();
+    space
+        .convex_hull(corners)
+        .to_simplicial_complex()
+        .into_forget_labels()
+        .into_partial_simplicial_complex()
+}
+
+// the coordinates of the points of the grid used by ball_shape
+const BALL_GRID: [&str; BALL_GRID_SIZE] = ["-2", "-1", "-1/2", "0", "1/2", "1", "2"];
+
+// the convex hull of rational points on the sphere of radius r about the origin
+// obtained by inverse stereographic projection of a grid of points
+fn ball_shape<'f>(space: Space<'f>, radius: &Rational) -> ShapeValue<'f> {
+    let n = space.linear_dimension().unwrap();
+    let mut points = vec![];
+    if n == 0 {
+        points.push(space.origin().unwrap());
+    } else {
+        let north_pole = Vector::construct(space, |i| {
+            if i + 1 == n {
+                radius.clone()
+            } else {
+                Rational::ZERO
             }
-            ShapeExpression::Difference(left, right) => {
-                vec![left.dimension(), right.dimension()]
+        });
+        points.push(north_pole.clone());
+        points.push(-&north_pole);
+        let grid = BALL_GRID.map(|t| Rational::from_str(t).unwrap());
+        for t in (0..(n - 1)).map(|_| grid.iter()).multi_cartesian_product() {
+            let s = t.iter().fold(Rational::ZERO, |s, ti| s + *ti * *ti);
+            let scale = radius / (&s + Rational::ONE);
+            points.push(Vector::construct(space, |i| {
+                if i + 1 == n {
+                    (&s - Rational::ONE) * &scale
+                } else {
+                    Rational::from(2) * t[i] * &scale
+                }
+            }));
+        }
+    }
+    space
+        .convex_hull(points)
+        .to_simplicial_complex()
+        .into_forget_labels()
+        .into_partial_simplicial_complex()
+}
+
+fn parse_index(value: &str, span: Span) -> Result<usize, ShapeError> {
+    usize::from_str(value)
+        .map_err(|_| ShapeError::new(span, format!("expected a whole number but found `{value}`")))
+}
+
+// the dimension of a primitive, which must be at most the limit beyond which building it takes too long
+fn check_dimension(
+    primitive: &str,
+    dimension: usize,
+    limit: usize,
+    span: Span,
+) -> Result<usize, ShapeError> {
+    if dimension > limit {
+        return Err(ShapeError::new(
+            span,
+            format!("the dimension of a `{primitive}` must be at most {limit} but is {dimension}"),
+        ));
+    }
+    Ok(dimension)
+}
+
+fn parse_radius(radius: &SignedValue, span: Span) -> Result<Rational, ShapeError> {
+    let radius = radius.to_rational();
+    if radius <= Rational::ZERO {
+        return Err(ShapeError::new(span, "the radius must be positive"));
+    }
+    Ok(radius)
+}
+
+impl Shape {
+    // record the dimension of every part of the shape which determines one
+    fn collect_dimensions(
+        &self,
+        names: &HashSet<String>,
+        dimensions: &mut Vec<(usize, Span)>,
+    ) -> Result<(), ShapeError> {
+        let span = self.span;
+        match &self.expression {
+            ShapeExpression::Union(left, right)
+            | ShapeExpression::Intersect(left, right)
+            | ShapeExpression::MinkowskiSum(left, right)
+            | ShapeExpression::Difference(left, right) => {
+                left.collect_dimensions(names, dimensions)?;
+                right.collect_dimensions(names, dimensions)?;
             }
-            ShapeExpression::Point(point) => {
-                vec![Ok(point.dimension())]
+            ShapeExpression::Name(name) => {
+                if !names.contains(name) {
+                    return Err(ShapeError::new(span, format!("unknown shape `{name}`")));
+                }
             }
-            ShapeExpression::ConvexHull(points) => {
-                points.iter().map(|pt| Ok(pt.dimension())).collect()
+            ShapeExpression::Point(point) => dimensions.push((point.dimension(), span)),
+            ShapeExpression::ConvexHull(points)
+            | ShapeExpression::ConvexHullInterior(points)
+            | ShapeExpression::ConvexHullBoundary(points)
+            | ShapeExpression::Lines(points)
+            | ShapeExpression::Loop(points)
+            | ShapeExpression::Polygon(points)
+            | ShapeExpression::PolygonInterior(points)
+            | ShapeExpression::Simplex(points) => {
+                dimensions.extend(points.iter().map(|pt| (pt.dimension(), span)));
             }
-            ShapeExpression::ConvexHullInterior(points) => {
-                points.iter().map(|pt| Ok(pt.dimension())).collect()
+            ShapeExpression::Box(p, q) => {
+                dimensions.push((
+                    check_dimension("Box", p.dimension(), MAX_BOX_DIMENSION, span)?,
+                    span,
+                ));
+                dimensions.push((q.dimension(), span));
             }
-            ShapeExpression::ConvexHullBoundary(points) => {
-                points.iter().map(|pt| Ok(pt.dimension())).collect()
+            ShapeExpression::Cube(n, _) => {
+                let n = parse_index(n, span)?;
+                dimensions.push((check_dimension("Cube", n, MAX_BOX_DIMENSION, span)?, span));
             }
-            ShapeExpression::Lines(points) => points.iter().map(|pt| Ok(pt.dimension())).collect(),
-            ShapeExpression::Loop(points) => points.iter().map(|pt| Ok(pt.dimension())).collect(),
-            ShapeExpression::Polygon(points) => {
-                points.iter().map(|pt| Ok(pt.dimension())).collect()
+            ShapeExpression::Ball(n, _) => {
+                let n = parse_index(n, span)?;
+                dimensions.push((check_dimension("Ball", n, MAX_BALL_DIMENSION, span)?, span));
             }
-            ShapeExpression::PolygonInterior(points) => {
-                points.iter().map(|pt| Ok(pt.dimension())).collect()
+            ShapeExpression::Translate(shape, vector) => {
+                shape.collect_dimensions(names, dimensions)?;
+                dimensions.push((vector.dimension(), span));
             }
-        } {
-            if let Ok(next_dim) = next_dim {
-                if let Some(current_d) = same_dim {
-                    if current_d != next_dim {
-                        return Err(());
-                    }
-                } else {
-                    same_dim = Some(next_dim)
+            ShapeExpression::Scale(shape, factor) => {
+                shape.collect_dimensions(names, dimensions)?;
+                if let ScaleFactor::Axes(factors) = factor {
+                    dimensions.push((factors.dimension(), span));
                 }
-            } else {
-                return Err(());
+            }
+            ShapeExpression::Rotate(shape, _, _) => {
+                shape.collect_dimensions(names, dimensions)?;
             }
         }
-        if let Some(dim) = same_dim {
-            Ok(dim)
-        } else {
-            Err(())
-        }
+        Ok(())
     }
 
-    fn to_partial_simplicial_complex(
+    fn evaluate<'f>(
         &self,
-        space: AffineSpace<'static, RationalCanonicalStructure>,
-    ) -> PartialSimplicialComplex<'static, RationalCanonicalStructure> {
-        match self {
+        space: Space<'f>,
+        names: &HashMap<String, ShapeValue<'f>>,
+    ) -> Result<ShapeValue<'f>, ShapeError> {
+        let span = self.span;
+        let transform = |shape: &Shape, transformation: AffineTransformation<'f, _>| {
+            transformation
+                .apply_simplex_collection(&shape.evaluate(space, names)?)
+                .map_err(|message| ShapeError::new(span, message))
+        };
+        Ok(match &self.expression {
             ShapeExpression::Union(left, right) => left
-                .to_partial_simplicial_complex(space)
-                .union(&right.to_partial_simplicial_complex(space)),
+                .evaluate(space, names)?
+                .union(&right.evaluate(space, names)?),
             ShapeExpression::Intersect(left, right) => left
-                .to_partial_simplicial_complex(space)
-                .intersect(&right.to_partial_simplicial_complex(space)),
+                .evaluate(space, names)?
+                .intersect(&right.evaluate(space, names)?),
             ShapeExpression::MinkowskiSum(left, right) => left
-                .to_partial_simplicial_complex(space)
-                .minkowski_sum(&right.to_partial_simplicial_complex(space)),
+                .evaluate(space, names)?
+                .minkowski_sum(&right.evaluate(space, names)?),
             ShapeExpression::Difference(left, right) => left
-                .to_partial_simplicial_complex(space)
-                .difference(&right.to_partial_simplicial_complex(space)),
-            ShapeExpression::Point(point) => space
-                .convex_hull(vec![point.to_vector(space)])
-                .to_simplicial_complex()
-                .into_forget_labels()
-                .into_partial_simplicial_complex(),
-            ShapeExpression::ConvexHull(points) => space
-                .convex_hull(points.iter().map(|pt| pt.to_vector(space)).collect())
-                .to_simplicial_complex()
-                .into_forget_labels()
-                .into_partial_simplicial_complex(),
+                .evaluate(space, names)?
+                .difference(&right.evaluate(space, names)?),
+            ShapeExpression::Name(name) => names
+                .get(name)
+                .ok_or_else(|| ShapeError::new(span, format!("unknown shape `{name}`")))?
+                .clone(),
+            ShapeExpression::Point(point) => convex_hull_shape(space, std::slice::from_ref(point)),
+            ShapeExpression::ConvexHull(points) => convex_hull_shape(space, points),
             ShapeExpression::ConvexHullInterior(points) => space
                 .convex_hull(points.iter().map(|pt| pt.to_vector(space)).collect())
                 .to_simplicial_complex()
@@ -155,40 +349,18 @@ impl ShapeExpression {
                 .to_simplicial_complex()
                 .boundary()
                 .into_partial_simplicial_complex(),
-            ShapeExpression::Lines(points) => {
-                let mut shape = space.empty_subset().into_partial_simplicial_complex();
-                for i in 0..points.len() - 1 {
-                    let p0 = points[i].to_vector(space);
-                    let p1 = points[i + 1].to_vector(space);
-                    shape = shape.union(
-                        &space
-                            .convex_hull(vec![p0, p1])
-                            .to_simplicial_complex()
-                            .into_forget_labels(),
-                    );
-                }
-                shape
-            }
-            ShapeExpression::Loop(points) => {
-                let mut shape = space.empty_subset().into_partial_simplicial_complex();
-                let n = points.len();
-                for i in 0..n {
-                    let p0 = points[i].to_vector(space);
-                    let p1 = points[(i + 1) % n].to_vector(space);
-                    shape = shape.union(
-                        &space
-                            .convex_hull(vec![p0, p1])
-                            .to_simplicial_complex()
-                            .into_forget_labels(),
-                    );
-                }
-                shape
+            ShapeExpression::Lines(points) => path_shape(space, points, false),
+            ShapeExpression::Loop(points) => path_shape(space, points, true),
+            ShapeExpression::Polygon(points) => Shape {
+                span,
+                expression: ShapeExpression::PolygonInterior(points.clone()),
             }
-            ShapeExpression::Polygon(points) => ShapeExpression::PolygonInterior(points.clone())
-                .to_partial_simplicial_complex(space)
-                .union(&ShapeExpression::Loop(points.clone()).to_partial_simplicial_complex(space)),
+            .evaluate(space, names)?
+            .union(&path_shape(space, points, true)),
             ShapeExpression::PolygonInterior(points) => {
-                assert_eq!(space.linear_dimension(), Some(2));
+                if space.linear_dimension() != Some(2) {
+                    return Err(ShapeError::new(span, "polygons are only supported in 2D"));
+                }
 
                 let mut shape = space.empty_subset().into_partial_simplicial_complex();
 
@@ -221,14 +393,124 @@ impl ShapeExpression {
                     .interior()
                 }
             }
+            ShapeExpression::Simplex(points) => {
+                space
+                    .simplex(points.iter().map(|pt| pt.to_vector(space)).collect())
+                    .map_err(|_| {
+                        ShapeError::new(
+                            span,
+                            "the points of a simplex must be affinely independent",
+                        )
+                    })?;
+                convex_hull_shape(space, points)
+            }
+            ShapeExpression::Box(p, q) => {
+                box_shape(space, &p.to_vector(space), &q.to_vector(space))
+            }
+            ShapeExpression::Cube(_, radius) => {
+                let radius = parse_radius(radius, span)?;
+                let corner = Vector::construct(space, |_| radius.clone());
+                box_shape(space, &-&corner, &corner)
+            }
+            ShapeExpression::Ball(_, radius) => ball_shape(space, &parse_radius(radius, span)?),
+            ShapeExpression::Translate(shape, vector) => transform(
+                shape,
+                AffineTransformation::translation(&vector.to_vector(space)),
+            )?,
+            ShapeExpression::Scale(shape, factor) => {
+                let transformation = match factor {
+                    ScaleFactor::Uniform(factor) => {
+                        AffineTransformation::scaling(space, &factor.to_rational())
+                    }
+                    ScaleFactor::Axes(factors) => AffineTransformation::axis_scaling(
+                        space,
+                        factors
+                            .coordinates
+                            .iter()
+                            .map(|f| f.to_rational())
+                            .collect(),
+                    ),
+                };
+                if !transformation.is_invertible() {
+                    return Err(ShapeError::new(span, "cannot scale by zero"));
+                }
+                transform(shape, transformation)?
+            }
+            ShapeExpression::Rotate(shape, direction, axes) => {
+                let (i, j) = match axes {
+                    Some((i, j)) => (parse_index(i, span)?, parse_index(j, span)?),
+                    None => (0, 1),
+                };
+                let d = space.linear_dimension().unwrap();
+                if i == j || i >= d || j >= d {
+                    return Err(ShapeError::new(
+                        span,
+                        format!("cannot rotate in the plane of axes {i} and {j} in {d} dimensions"),
+                    ));
+                }
+                let [a, b] = direction
+                    .coordinates
+                    .iter()
+                    .map(|x| x.to_rational())
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .map_err(|_| {
+                        ShapeError::new(span, "the direction of a rotation must have 2 coordinates")
+                    })?;
+                let length = (&a * &a + &b * &b)
+                    .sqrt_if_square()
+                    .filter(|length| length != &Rational::ZERO)
+                    .ok_or_else(|| {
+                        ShapeError::new(
+                            span,
+                            "the direction of a rotation must have non-zero rational length, such as (3, 4)",
+                        )
+                    })?;
+                transform(
+                    shape,
+                    AffineTransformation::rotation(space, i, j, &(a / &length), &(b / &length))
+                        .unwrap(),
+                )?
+            }
+        })
+    }
+}
+
+impl Program {
+    /// The dimension shared by every part of the program, which must equal `expected` if given.
+    pub fn dimension(&self, expected: Option<usize>) -> Result<usize, ShapeError> {
+        let mut names = HashSet::new();
+        let mut dimensions = vec![];
+        for binding in &self.bindings {
+            binding.shape.collect_dimensions(&names, &mut dimensions)?;
+            names.insert(binding.name.clone());
+        }
+        self.shape.collect_dimensions(&names, &mut dimensions)?;
+        let dimension = expected
+            .or(dimensions.first().map(|(d, _)| *d))
+            .ok_or_else(|| {
+                ShapeError::new(
+                    self.shape.span,
+                    "could not determine the dimension of the shape",
+                )
+            })?;
+        for (d, span) in dimensions {
+            if d != dimension {
+                return Err(ShapeError::new(
+                    span,
+                    format!("expected dimension {dimension} but found dimension {d}"),
+                ));
+            }
         }
+        Ok(dimension)
     }
 
-    pub fn to_partial_simplicial_complex_root(
-        &self,
-    ) -> PartialSimplicialComplex<'static, RationalCanonicalStructure> {
-        let n = self.dimension().unwrap();
-        let space = AffineSpace::new_linear(Rational::structure_ref(), n);
-        self.to_partial_simplicial_complex(space)
+    pub fn evaluate<'f>(&self, space: Space<'f>) -> Result<ShapeValue<'f>, ShapeError> {
+        let mut names = HashMap::new();
+        for binding in &self.bindings {
+            let shape = binding.shape.evaluate(space, &names)?;
+            names.insert(binding.name.clone(), shape);
+        }
+        self.shape.evaluate(space, &names)
     }
 }
@@ -1,22 +1,202 @@
-use crate::partial_simplicial_complex::PartialSimplicialComplex;
-use algebraeon_nzq::RationalCanonicalStructure;
-use lalrpop_util::lalrpop_mod;
+use crate::{ambient_space::AffineSpace, partial_simplicial_complex::PartialSimplicialComplex};
+use algebraeon_nzq::{Rational, RationalCanonicalStructure};
+use lalrpop_util::{ParseError, lalrpop_mod};
+use std::ops::Range;
+
+// Shapes are parsed over the rationals only, since the grammar's numbers are decimals and
+// fractions whose exact values need not lie in any smaller field.
 
 lalrpop_mod!(shape_parser, "/parse/shape_grammar.rs");
 mod ast;
 
-pub fn parse_shape(string: &str) -> PartialSimplicialComplex<'static, RationalCanonicalStructure> {
-    shape_parser::ShapeParser::new()
+/// The largest dimension of a `Box` or `Cube`, since a box in dimension `n` has `2^n` corners
+/// and its triangulation has `n!` simplices.
+pub const MAX_BOX_DIMENSION: usize = 6;
+
+/// The largest dimension of a `Ball`, whose vertices come from a grid of
+/// `BALL_GRID_SIZE^(n-1)` points projected onto the sphere.
+pub const MAX_BALL_DIMENSION: usize = 4;
+
+/// The number of values taken by each coordinate of the grid projected onto the sphere to give
+/// the vertices of a `Ball`.
+pub const BALL_GRID_SIZE: usize = 7;
+
+/// An error from parsing or evaluating a shape, located by a byte range of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseShapeError {
+    span: Range<usize>,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseShapeError {
+    fn new(input: &str, span: Range<usize>, message: String) -> Self {
+        let before = &input[..span.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count())
+            + 1;
+        Self {
+            span,
+            line,
+            column,
+            message,
+        }
+    }
+
+    fn from_shape_error(input: &str, error: ast::ShapeError) -> Self {
+        Self::new(input, error.span.start..error.span.end, error.message)
+    }
+
+    fn from_syntax_error<T: std::fmt::Display>(
+        input: &str,
+        error: ParseError<usize, T, ast::ShapeError>,
+    ) -> Self {
+        // describe the tokens lalrpop expected, naming the regex tokens by what they match
+        let expected = |expected: Vec<String>| {
+            let mut descriptions = expected
+                .into_iter()
+                .map(|token| {
+                    if token.starts_with("r#\"[0-9]") {
+                        String::from("a number")
+                    } else if token.starts_with("r#\"[a-zA-Z_]") {
+                        String::from("a name")
+                    } else {
+                        token
+                    }
+                })
+                .collect::<Vec<_>>();
+            descriptions.dedup();
+            match descriptions.len() {
+                0 => String::new(),
+                1 => format!(", expected {}", descriptions[0]),
+                _ => format!(", expected one of {}", descriptions.join(", ")),
+            }
+        };
+        match error {
+            ParseError::InvalidToken { location } => {
+                let c = input[location..].chars().next().unwrap();
+                Self::new(
+                    input,
+                    location..location + c.len_utf8(),
+                    format!("unrecognised character `{c}`"),
+                )
+            }
+            ParseError::UnrecognizedEof {
+                location,
+                expected: tokens,
+            } => Self::new(
+                input,
+                location..location,
+                format!("unexpected end of input{}", expected(tokens)),
+            ),
+            ParseError::UnrecognizedToken {
+                token: (start, token, end),
+                expected: tokens,
+            } => Self::new(
+                input,
+                start..end,
+                format!("unexpected `{token}`{}", expected(tokens)),
+            ),
+            ParseError::ExtraToken {
+                token: (start, token, end),
+            } => Self::new(input, start..end, format!("unexpected `{token}`")),
+            ParseError::User { error } => Self::from_shape_error(input, error),
+        }
+    }
+
+    /// The byte range of the input where the error occurred.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The line of the start of the error, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the start of the error in characters, counting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ParseShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseShapeError {}
+
+fn parse_program(string: &str) -> Result<ast::Program, ParseShapeError> {
+    shape_parser::ProgramParser::new()
         .parse(string)
-        .unwrap()
-        .to_partial_simplicial_complex_root()
+        .map_err(|error| ParseShapeError::from_syntax_error(string, error))
+}
+
+/// Parse a shape in the space whose dimension is that of the points in the input.
+pub fn try_parse_shape(
+    string: &str,
+) -> Result<PartialSimplicialComplex<'static, RationalCanonicalStructure>, ParseShapeError> {
+    let program = parse_program(string)?;
+    let n = program
+        .dimension(None)
+        .map_err(|error| ParseShapeError::from_shape_error(string, error))?;
+    let space = AffineSpace::new_linear(Rational::structure_ref(), n);
+    program
+        .evaluate(space)
+        .map_err(|error| ParseShapeError::from_shape_error(string, error))
+}
+
+/// Parse a shape in a given space, which must not be the empty space.
+pub fn try_parse_shape_in<'f>(
+    space: AffineSpace<'f, RationalCanonicalStructure>,
+    string: &str,
+) -> Result<PartialSimplicialComplex<'f, RationalCanonicalStructure>, ParseShapeError> {
+    let program = parse_program(string)?;
+    let Some(n) = space.linear_dimension() else {
+        return Err(ParseShapeError::new(
+            string,
+            0..string.len(),
+            String::from("shapes cannot be parsed in the empty space"),
+        ));
+    };
+    program
+        .dimension(Some(n))
+        .map_err(|error| ParseShapeError::from_shape_error(string, error))?;
+    program
+        .evaluate(space)
+        .map_err(|error| ParseShapeError::from_shape_error(string, error))
+}
+
+/// Parse a shape, panicking if the input is not valid. See [`try_parse_shape`].
+pub fn parse_shape(string: &str) -> PartialSimplicialComplex<'static, RationalCanonicalStructure> {
+    match try_parse_shape(string) {
+        Ok(shape) => shape,
+        Err(error) => panic!("{error}"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parse::ast::{Point, SignedValue};
-
     use super::*;
+    use crate::{
+        measure::SimplexCollectionMeasure,
+        parse::ast::{Point, SignedValue},
+        point_location::SimplexCollectionPointLocation,
+    };
 
     #[test]
     fn test() {
@@ -108,12 +288,12 @@ mod tests {
         assert!(
             shape_parser::ShapeParser::new()
                 .parse("ConvexHull() | ConvexHull() & ConvexHull()")
-                .is_err()
+                .is_ok()
         );
         assert!(
             shape_parser::ShapeParser::new()
                 .parse("ConvexHull() & ConvexHull() | ConvexHull()")
-                .is_err()
+                .is_ok()
         );
         assert!(
             shape_parser::ShapeParser::new()
@@ -123,12 +303,12 @@ mod tests {
         assert!(
             shape_parser::ShapeParser::new()
                 .parse("ConvexHull() \\ ConvexHull() \\ ConvexHull()")
-                .is_err()
+                .is_ok()
         );
         assert!(
             shape_parser::ShapeParser::new()
                 .parse("ConvexHull() \\ ConvexHull() | ConvexHull()")
-                .is_err()
+                .is_ok()
         );
         assert!(
             shape_parser::ShapeParser::new()
@@ -138,7 +318,7 @@ mod tests {
         assert!(
             shape_parser::ShapeParser::new()
                 .parse("ConvexHull() \\ ConvexHull() & ConvexHull()")
-                .is_err()
+                .is_ok()
         );
         assert!(
             shape_parser::ShapeParser::new()
@@ -146,4 +326,124 @@ mod tests {
                 .is_ok()
         );
     }
+
+    #[test]
+    fn test_bindings_primitives_and_transforms() {
+        let volume = |string: &str| parse_shape(string).volume();
+
+        assert_eq!(
+            volume("let a = Box((0, 0), (2, 1)); let b = translate(a, (1, 0)); a | b"),
+            Rational::from(3)
+        );
+        // later bindings may shadow earlier ones
+        assert_eq!(
+            volume("let a = Cube(2, 1); let a = a \\ Box((0, 0), (1, 1)); a"),
+            Rational::from(3)
+        );
+        assert_eq!(volume("scale(Cube(2, 1), (1/2, 3))"), Rational::from(6));
+        assert_eq!(volume("scale(Box((0, 0), (1, 1)), -2)"), Rational::from(4));
+        assert_eq!(volume("rotate(Box((0, 0), (1, 1)), (3, 4))"), Rational::ONE);
+        assert_eq!(
+            volume("rotate(Box((0, 0, 0), (1, 2, 3)), (0, 1), 2, 0)"),
+            Rational::from(6)
+        );
+        assert_eq!(
+            volume("Simplex((0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1))"),
+            Rational::from_integers(1, 6)
+        );
+        assert_eq!(volume("Lines((0, 0))"), Rational::ZERO);
+
+        // the ball is a polytope inscribed in the round ball
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let ball = try_parse_shape_in(space, "Ball(2, 1)").unwrap();
+        let area = ball.volume();
+        assert!(Rational::from(2) < area && area < Rational::from_integers(22, 7));
+        assert!(ball.contains_point(&space.vector([0, 1])));
+        assert!(!ball.contains_point(&space.vector([1, 1])));
+        assert!(parse_shape("Ball(3, 2)").volume() > Rational::from(8));
+    }
+
+    #[test]
+    fn test_precedence() {
+        let volume = |string: &str| {
+            parse_shape(&format!(
+                "let a = Box((0, 0), (1, 1)); let b = Box((0, 0), (3, 1)); let c = Box((2, 0), (3, 1)); {string}"
+            ))
+            .volume()
+        };
+        assert_eq!(volume("a | b & c"), Rational::from(2));
+        assert_eq!(volume("(a | b) & c"), Rational::ONE);
+        assert_eq!(volume("b & c | a"), Rational::from(2));
+        assert_eq!(volume("b \\ a \\ c"), Rational::ONE);
+        assert_eq!(volume("b \\ (a \\ c)"), Rational::from(2));
+        assert_eq!(volume("b \\ a & c"), Rational::ONE);
+        assert_eq!(volume("a + (1, 0) \\ a"), Rational::ONE);
+        assert_eq!(volume("a \\ a + (1, 0)"), Rational::ONE);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = try_parse_shape("let a = Box((0, 0), (1, 1));\n  b | a").unwrap_err();
+        assert_eq!(error.span(), 31..32);
+        assert_eq!((error.line(), error.column()), (2, 3));
+        assert_eq!(error.to_string(), "line 2, column 3: unknown shape `b`");
+
+        let error = try_parse_shape("Box((0, 0), (1, 1)) | (0, 0, 0)").unwrap_err();
+        assert_eq!(error.span(), 22..31);
+        assert_eq!(
+            error.message(),
+            "expected dimension 2 but found dimension 3"
+        );
+
+        let error = try_parse_shape("ConvexHull((0, 0) (1, 1))").unwrap_err();
+        assert_eq!(error.span(), 18..19);
+        assert!(error.message().starts_with("unexpected `(`"));
+
+        let error = try_parse_shape("Box((0, 0), (1, 1)) |").unwrap_err();
+        assert!(error.message().starts_with("unexpected end of input"));
+
+        let error = try_parse_shape("Box((0, 0), (1, 1)) $").unwrap_err();
+        assert_eq!(error.message(), "unrecognised character `$`");
+
+        //primitives of too high a dimension are rejected rather than built
+        let error = try_parse_shape("Cube(1, 1) | Cube(70, 1)").unwrap_err();
+        assert_eq!(error.span(), 13..24);
+        assert_eq!(
+            error.message(),
+            "the dimension of a `Cube` must be at most 6 but is 70"
+        );
+        for input in ["Cube(25, 1)", "Ball(8, 1)", "Ball(5, 1)", "Cube(7, 1)"] {
+            assert!(try_parse_shape(input).is_err(), "{input}");
+        }
+        let error =
+            try_parse_shape("Box((0, 0, 0, 0, 0, 0, 0), (1, 1, 1, 1, 1, 1, 1))").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "the dimension of a `Box` must be at most 6 but is 7"
+        );
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 8);
+        assert!(try_parse_shape_in(space, "Ball(8, 1)").is_err());
+
+        assert!(try_parse_shape("Cube(2, -1)").is_err());
+        assert!(try_parse_shape("rotate(Cube(2, 1), (1, 1))").is_err());
+        assert!(try_parse_shape("rotate(Cube(2, 1), (1, 0), 0, 2)").is_err());
+        assert!(try_parse_shape("scale(Cube(2, 1), (1, 0))").is_err());
+        assert!(try_parse_shape("Simplex((0, 0), (1, 1), (2, 2))").is_err());
+        assert!(try_parse_shape("Polygon((0, 0, 0), (1, 0, 0), (0, 1, 0))").is_err());
+        assert!(try_parse_shape("a").is_err());
+
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        assert!(try_parse_shape_in(space, "Box((0, 0), (1, 1))").is_err());
+        let error = try_parse_shape_in(AffineSpace::new_empty(Rational::structure_ref()), "(  )")
+            .unwrap_err();
+        assert_eq!(error.span(), 0..4);
+        assert_eq!(
+            error.message(),
+            "shapes cannot be parsed in the empty space"
+        );
+        assert_eq!(
+            try_parse_shape_in(space, "Cube(3, 1)").unwrap().volume(),
+            Rational::from(8)
+        );
+    }
 }
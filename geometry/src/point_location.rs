@@ -0,0 +1,255 @@
+use super::*;
+use crate::{
+    ambient_space::AffineSpace,
+    bounding_box::BoundingBoxTree,
+    partial_simplicial_complex::LabelledPartialSimplicialComplex,
+    simplex::Simplex,
+    simplex_collection::{InteriorOrBoundary, LabelledSimplexCollection},
+    simplex_overlap::simplex_interior_overlap,
+    vector::Vector,
+};
+use std::collections::HashMap;
+
+/// Where a point lies relative to a shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    /// The shape contains a neighbourhood of the point.
+    Interior,
+    /// The point is in the shape but not in its interior.
+    Boundary,
+    /// The point is not in the shape.
+    Exterior,
+}
+
+/// Precomputed data for answering repeated point location queries against a fixed shape.
+#[derive(Debug, Clone)]
+pub struct PointLocator<'f, FS: OrderedRingSignature + FieldSignature> {
+    // the simplexes of the shape, each with whether its points are interior or boundary points of the shape
+    simplexes: BoundingBoxTree<'f, FS, (Simplex<'f, FS>, InteriorOrBoundary)>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> PointLocator<'f, FS>
+where
+    FS::Set: Hash,
+{
+    pub fn new<T: Eq + Clone + Send + Sync>(
+        shape: &LabelledPartialSimplicialComplex<'f, FS, T>,
+    ) -> Self {
+        /*
+        The simplexes of the closure of the shape have disjoint interiors, so a point lies in the interior of at most one of them.
+        A point in the interior of a simplex of the shape is in the interior of the shape iff
+         - the simplex is in the interior of the closure, so that the simplexes containing it cover a neighbourhood of the point, and
+         - every simplex of the closure containing it is part of the shape.
+        */
+        let closure = shape.into_labelled_simplicial_complex();
+        let labels = closure.labelled_simplexes();
+        let mut locations = closure
+            .forget_labels()
+            .interior_and_boundary()
+            .into_labelled_simplexes()
+            .into_iter()
+            .filter(|(spx, _)| labels.get(spx).unwrap().is_some())
+            .collect::<HashMap<_, _>>();
+        for (spx, label) in labels {
+            if label.is_none() {
+                for bdry in spx.proper_sub_simplices_not_null() {
+                    if let Some(location) = locations.get_mut(&bdry) {
+                        *location = InteriorOrBoundary::Boundary;
+                    }
+                }
+            }
+        }
+        Self {
+            simplexes: BoundingBoxTree::new(
+                shape.ambient_space(),
+                locations
+                    .into_iter()
+                    .map(|(spx, location)| (spx.bounding_box().unwrap(), (spx, location)))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.simplexes.ambient_space()
+    }
+
+    fn find(&self, point: &Vector<'f, FS>) -> Option<&(Simplex<'f, FS>, InteriorOrBoundary)> {
+        let point_simplex = self.ambient_space().simplex(vec![point.clone()]).unwrap();
+        self.simplexes
+            .query_point(point)
+            .into_iter()
+            .find(|(spx, _)| simplex_interior_overlap(spx, &point_simplex))
+    }
+
+    /// The simplex of the shape whose interior contains the point, if any.
+    pub fn containing_simplex(&self, point: &Vector<'f, FS>) -> Option<&Simplex<'f, FS>> {
+        self.find(point).map(|(spx, _)| spx)
+    }
+
+    pub fn locate_point(&self, point: &Vector<'f, FS>) -> PointLocation {
+        match self.find(point) {
+            Some((_, InteriorOrBoundary::Interior)) => PointLocation::Interior,
+            Some((_, InteriorOrBoundary::Boundary)) => PointLocation::Boundary,
+            None => PointLocation::Exterior,
+        }
+    }
+
+    pub fn contains_point(&self, point: &Vector<'f, FS>) -> bool {
+        self.locate_point(point) != PointLocation::Exterior
+    }
+}
+
+/// Point location queries for simplicial complexes, partial simplicial complexes and simplicial disjoint unions.
+///
+/// For many queries against the same shape, build a [`PointLocator`] once with [`Self::point_locator`].
+pub trait SimplexCollectionPointLocation<'f, FS: OrderedRingSignature + FieldSignature + 'f, T>:
+    LabelledSimplexCollection<'f, FS, T>
+where
+    FS::Set: Hash,
+    T: Eq + Clone + Send + Sync,
+{
+    fn point_locator(&self) -> PointLocator<'f, FS> {
+        PointLocator::new(&self.to_partial_simplicial_complex())
+    }
+
+    fn locate_point(&self, point: &Vector<'f, FS>) -> PointLocation {
+        self.point_locator().locate_point(point)
+    }
+
+    fn contains_point(&self, point: &Vector<'f, FS>) -> bool {
+        self.point_locator().contains_point(point)
+    }
+}
+
+impl<
+    'f,
+    FS: OrderedRingSignature + FieldSignature + 'f,
+    T: Eq + Clone + Send + Sync,
+    S: LabelledSimplexCollection<'f, FS, T>,
+> SimplexCollectionPointLocation<'f, FS, T> for S
+where
+    FS::Set: Hash,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean_operations::Difference;
+    use algebraeon_nzq::Rational;
+
+    #[test]
+    fn test_locate_point_in_simplicial_complex() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let square = space
+            .convex_hull(vec![
+                space.vector([0, 0]),
+                space.vector([2, 0]),
+                space.vector([0, 2]),
+                space.vector([2, 2]),
+            ])
+            .to_simplicial_complex()
+            .forget_labels();
+        let locator = square.point_locator();
+        assert_eq!(
+            locator.locate_point(&space.vector([1, 1])),
+            PointLocation::Interior
+        );
+        assert_eq!(
+            locator.locate_point(
+                &space.vector([Rational::from_integers(1, 3), Rational::from_integers(1, 2)])
+            ),
+            PointLocation::Interior
+        );
+        assert_eq!(
+            locator.locate_point(&space.vector([2, 1])),
+            PointLocation::Boundary
+        );
+        assert_eq!(
+            locator.locate_point(&space.vector([0, 0])),
+            PointLocation::Boundary
+        );
+        assert_eq!(
+            locator.locate_point(&space.vector([3, 1])),
+            PointLocation::Exterior
+        );
+        assert_eq!(
+            locator.locate_point(&space.vector([-1, -1])),
+            PointLocation::Exterior
+        );
+        assert_eq!(
+            locator.containing_simplex(&space.vector([0, 0])),
+            Some(&space.simplex(vec![space.vector([0, 0])]).unwrap())
+        );
+
+        // a shape of lower dimension has no interior
+        let segment = space
+            .convex_hull(vec![space.vector([0, 0]), space.vector([2, 2])])
+            .to_simplicial_complex();
+        assert_eq!(
+            segment.locate_point(&space.vector([1, 1])),
+            PointLocation::Boundary
+        );
+        assert_eq!(
+            segment.locate_point(&space.vector([1, 0])),
+            PointLocation::Exterior
+        );
+    }
+
+    #[test]
+    fn test_locate_point_in_partial_shapes() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let square = space
+            .convex_hull(vec![
+                space.vector([0, 0]),
+                space.vector([4, 0]),
+                space.vector([0, 4]),
+                space.vector([4, 4]),
+            ])
+            .to_simplicial_complex()
+            .forget_labels();
+        let hole = space
+            .convex_hull(vec![
+                space.vector([1, 1]),
+                space.vector([2, 1]),
+                space.vector([1, 2]),
+                space.vector([2, 2]),
+            ])
+            .to_simplicial_complex()
+            .forget_labels();
+
+        // the open square
+        let open_square = square.interior();
+        assert!(open_square.contains_point(&space.vector([3, 3])));
+        assert!(!open_square.contains_point(&space.vector([4, 3])));
+
+        // the square with a closed hole removed, so the edge of the hole is not part of the shape
+        let notched = square
+            .to_simplicial_disjoint_union()
+            .difference(&hole.to_simplicial_disjoint_union());
+        let points = [
+            (space.vector([3, 3]), PointLocation::Interior),
+            (space.vector([3, 1]), PointLocation::Interior),
+            (space.vector([4, 2]), PointLocation::Boundary),
+            (space.vector([0, 0]), PointLocation::Boundary),
+            (space.vector([2, 2]), PointLocation::Exterior),
+            (space.vector([1, 2]), PointLocation::Exterior),
+            (
+                space.vector([Rational::from_integers(3, 2), Rational::from_integers(3, 2)]),
+                PointLocation::Exterior,
+            ),
+            (space.vector([5, 2]), PointLocation::Exterior),
+        ];
+        let locator = notched.point_locator();
+        for (point, location) in &points {
+            assert_eq!(&locator.locate_point(point), location);
+        }
+
+        // the same shape as a simplicial disjoint union whose simplexes do not meet face to face
+        let union = notched.to_simplicial_disjoint_union();
+        for (point, location) in &points {
+            assert_eq!(&union.locate_point(point), location);
+        }
+    }
+}
@@ -0,0 +1,714 @@
+use super::*;
+use crate::{
+    ambient_space::AffineSpace,
+    convex_hull::ConvexHull,
+    linear_programming::LinearInequality,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_rings::structure::AdditiveMonoidEqSignature;
+use std::collections::{BTreeSet, HashMap};
+
+/// A ray of a polyhedral cone together with the indices of the constraints it lies on.
+struct TightRay<'f, FS: OrderedRingSignature + FieldSignature> {
+    ray: Vector<'f, FS>,
+    tight: BTreeSet<usize>,
+}
+
+/// Scale a vector by a positive scalar so that its first non-zero coordinate is `±1`.
+fn normalize_ray<'f, FS: OrderedRingSignature + FieldSignature>(
+    v: Vector<'f, FS>,
+) -> Vector<'f, FS> {
+    let space = v.ambient_space();
+    let field = space.field();
+    match (0..space.linear_dimension().unwrap()).find(|i| !field.is_zero(v.coordinate(*i))) {
+        Some(i) => {
+            let scale = field.inv(&field.abs(v.coordinate(i))).unwrap();
+            v.scalar_mul(&scale)
+        }
+        None => v,
+    }
+}
+
+/// The double description method.
+///
+/// Return a basis of the lineality space and the extreme rays modulo the lineality space of the cone
+/// `{y : a·y >= 0 for each a in constraints}`, together with the constraints each extreme ray lies on.
+///
+/// The constraints are added one at a time, updating the extreme rays by combining each pair of adjacent rays
+/// lying on opposite sides of the new constraint. Two rays are adjacent exactly when no other ray lies on all the
+/// constraints they have in common.
+fn double_description<'f, FS: OrderedRingSignature + FieldSignature>(
+    space: AffineSpace<'f, FS>,
+    constraints: &[Vector<'f, FS>],
+) -> (Vec<Vector<'f, FS>>, Vec<TightRay<'f, FS>>) {
+    let field = space.field();
+    let n = space.linear_dimension().unwrap();
+    let mut lineality = (0..n)
+        .map(|i| Vector::construct(space, |j| if i == j { field.one() } else { field.zero() }))
+        .collect::<Vec<_>>();
+    let mut rays: Vec<TightRay<'f, FS>> = vec![];
+
+    for (idx, a) in constraints.iter().enumerate() {
+        debug_assert_eq!(a.ambient_space(), space);
+        if let Some(k) = lineality.iter().position(|l| !field.is_zero(&a.dot(l))) {
+            // the constraint cuts the lineality space, which shrinks by one dimension
+            // and the half of the removed line on the positive side becomes a new ray
+            let l = lineality.remove(k);
+            let al = a.dot(&l);
+            let project = |v: &Vector<'f, FS>| {
+                let t = field.div(&a.dot(v), &al).unwrap();
+                v - &l.scalar_mul(&t)
+            };
+            lineality = lineality.iter().map(project).collect();
+            for r in &mut rays {
+                r.ray = normalize_ray(project(&r.ray));
+                r.tight.insert(idx);
+            }
+            let l = if field.ring_cmp(&al, &field.zero()) == std::cmp::Ordering::Greater {
+                l
+            } else {
+                -&l
+            };
+            rays.push(TightRay {
+                ray: normalize_ray(l),
+                tight: (0..idx).collect(),
+            });
+        } else {
+            let values = rays.iter().map(|r| a.dot(&r.ray)).collect::<Vec<_>>();
+            let sign = |i: usize| field.ring_cmp(&values[i], &field.zero());
+            let positive = (0..rays.len())
+                .filter(|i| sign(*i) == std::cmp::Ordering::Greater)
+                .collect::<Vec<_>>();
+            let negative = (0..rays.len())
+                .filter(|i| sign(*i) == std::cmp::Ordering::Less)
+                .collect::<Vec<_>>();
+            let mut new_rays = vec![];
+            for &p in &positive {
+                for &q in &negative {
+                    let common = rays[p]
+                        .tight
+                        .intersection(&rays[q].tight)
+                        .cloned()
+                        .collect::<BTreeSet<_>>();
+                    let adjacent = (0..rays.len())
+                        .all(|r| r == p || r == q || !common.is_subset(&rays[r].tight));
+                    if adjacent {
+                        let ray = &rays[q].ray.scalar_mul(&values[p])
+                            - &rays[p].ray.scalar_mul(&values[q]);
+                        let mut tight = common;
+                        tight.insert(idx);
+                        new_rays.push(TightRay {
+                            ray: normalize_ray(ray),
+                            tight,
+                        });
+                    }
+                }
+            }
+            let mut kept = vec![];
+            for (i, mut r) in rays.into_iter().enumerate() {
+                match sign(i) {
+                    std::cmp::Ordering::Less => {}
+                    std::cmp::Ordering::Equal => {
+                        r.tight.insert(idx);
+                        kept.push(r);
+                    }
+                    std::cmp::Ordering::Greater => {
+                        kept.push(r);
+                    }
+                }
+            }
+            kept.append(&mut new_rays);
+            rays = kept;
+        }
+    }
+    (lineality, rays)
+}
+
+/// A polyhedron given as the set of points satisfying some linear equations and some linear inequalities.
+#[derive(Debug, Clone)]
+pub struct HalfSpaceRepresentation<'f, FS: OrderedRingSignature + FieldSignature> {
+    ambient_space: AffineSpace<'f, FS>,
+    // each (normal, value) is the equation normal·x = value
+    equations: Vec<(Vector<'f, FS>, FS::Set)>,
+    inequalities: Vec<LinearInequality<'f, FS>>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> HalfSpaceRepresentation<'f, FS> {
+    pub fn new(
+        ambient_space: AffineSpace<'f, FS>,
+        equations: Vec<(Vector<'f, FS>, FS::Set)>,
+        inequalities: Vec<LinearInequality<'f, FS>>,
+    ) -> Self {
+        for (normal, _) in &equations {
+            assert_eq!(normal.ambient_space(), ambient_space);
+        }
+        for inequality in &inequalities {
+            assert_eq!(inequality.normal().ambient_space(), ambient_space);
+        }
+        Self {
+            ambient_space,
+            equations,
+            inequalities,
+        }
+    }
+
+    pub fn ambient_space(&self) -> AffineSpace<'f, FS> {
+        self.ambient_space
+    }
+
+    /// Each `(normal, value)` is the equation `normal·x = value`.
+    pub fn equations(&self) -> &Vec<(Vector<'f, FS>, FS::Set)> {
+        &self.equations
+    }
+
+    pub fn inequalities(&self) -> &Vec<LinearInequality<'f, FS>> {
+        &self.inequalities
+    }
+
+    pub fn contains_point(&self, point: &Vector<'f, FS>) -> bool {
+        let field = self.ambient_space.field();
+        self.equations
+            .iter()
+            .all(|(normal, value)| field.equal(&normal.dot(point), value))
+            && self.inequalities.iter().all(|c| c.is_satisfied_by(point))
+    }
+
+    /// The vertices of the polyhedron, or an error if it is unbounded.
+    pub fn vertices(&self) -> Result<Vec<Vector<'f, FS>>, &'static str> {
+        let space = self.ambient_space;
+        let field = space.field();
+        let d = space.linear_dimension().unwrap();
+        let cone_space = AffineSpace::new_linear(field, d + 1);
+        // the polyhedron is the slice t = 1 of the cone of (t, x) with t >= 0 and t b - a·x >= 0
+        let homogenize = |normal: &Vector<'f, FS>, bound: &FS::Set| {
+            Vector::construct(cone_space, |i| {
+                if i == 0 {
+                    bound.clone()
+                } else {
+                    field.neg(normal.coordinate(i - 1))
+                }
+            })
+        };
+        let mut constraints = vec![Vector::construct(cone_space, |i| {
+            if i == 0 { field.one() } else { field.zero() }
+        })];
+        for (normal, value) in &self.equations {
+            constraints.push(homogenize(normal, value));
+            constraints.push(homogenize(&-normal, &field.neg(value)));
+        }
+        for inequality in &self.inequalities {
+            constraints.push(homogenize(inequality.normal(), inequality.bound()));
+        }
+        let (lineality, rays) = double_description(cone_space, &constraints);
+        let mut vertices = vec![];
+        let mut bounded = lineality.is_empty();
+        for TightRay { ray, .. } in rays {
+            if field.is_zero(ray.coordinate(0)) {
+                bounded = false;
+            } else {
+                let t = field.inv(ray.coordinate(0)).unwrap();
+                vertices.push(Vector::construct(space, |i| {
+                    field.mul(ray.coordinate(i + 1), &t)
+                }));
+            }
+        }
+        if !vertices.is_empty() && !bounded {
+            return Err("polyhedron is unbounded");
+        }
+        vertices.sort();
+        Ok(vertices)
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> HalfSpaceRepresentation<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// The convex hull of the vertices, or an error if the polyhedron is unbounded.
+    pub fn to_convex_hull(&self) -> Result<ConvexHull<'f, FS>, &'static str> {
+        Ok(self.ambient_space.convex_hull(self.vertices()?))
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> AffineSpace<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// The convex hull of the points satisfying every inequality, or an error if they are unbounded.
+    pub fn convex_hull_from_inequalities(
+        &self,
+        inequalities: Vec<LinearInequality<'f, FS>>,
+    ) -> Result<ConvexHull<'f, FS>, &'static str> {
+        HalfSpaceRepresentation::new(*self, vec![], inequalities).to_convex_hull()
+    }
+}
+
+/// A non-empty face of a polytope, or the empty face.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Face {
+    dimension: isize,
+    vertices: Vec<usize>,
+    subfaces: Vec<usize>,
+    superfaces: Vec<usize>,
+}
+
+impl Face {
+    /// The dimension of the face, which is `-1` for the empty face.
+    pub fn dimension(&self) -> isize {
+        self.dimension
+    }
+
+    /// The indices of the vertices of the polytope belonging to this face.
+    pub fn vertices(&self) -> &Vec<usize> {
+        &self.vertices
+    }
+
+    /// The indices of the faces of one less dimension contained in this face.
+    pub fn subfaces(&self) -> &Vec<usize> {
+        &self.subfaces
+    }
+
+    /// The indices of the faces of one more dimension containing this face.
+    pub fn superfaces(&self) -> &Vec<usize> {
+        &self.superfaces
+    }
+}
+
+/// The lattice of faces of a polytope ordered by inclusion, from the polytope itself down to the empty face.
+#[derive(Debug, Clone)]
+pub struct FaceLattice<'f, FS: OrderedRingSignature + FieldSignature> {
+    vertices: Vec<Vector<'f, FS>>,
+    // sorted by decreasing dimension
+    faces: Vec<Face>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> FaceLattice<'f, FS> {
+    /// Build the face lattice from the vertices of a polytope and, for each facet, the indices of the vertices on it.
+    ///
+    /// The facets of each face are the maximal proper intersections of the face with the facets of the polytope.
+    fn new(vertices: Vec<Vector<'f, FS>>, facets: Vec<BTreeSet<usize>>, dimension: isize) -> Self {
+        let mut faces = vec![];
+        let mut level = if vertices.is_empty() {
+            vec![]
+        } else {
+            vec![(0..vertices.len()).collect::<BTreeSet<_>>()]
+        };
+        let mut dim = dimension;
+        let mut level_start = 0;
+        while !level.is_empty() {
+            let mut next_level: Vec<BTreeSet<usize>> = vec![];
+            let mut next_index = HashMap::new();
+            let next_start = level_start + level.len();
+            for (i, face) in level.iter().enumerate() {
+                let candidates = if dim == 0 {
+                    vec![BTreeSet::new()]
+                } else {
+                    let intersections = facets
+                        .iter()
+                        .filter(|facet| !face.is_subset(facet))
+                        .map(|facet| face.intersection(facet).cloned().collect::<BTreeSet<_>>())
+                        .collect::<BTreeSet<_>>();
+                    intersections
+                        .iter()
+                        .filter(|x| !intersections.iter().any(|y| x != &y && x.is_subset(y)))
+                        .cloned()
+                        .collect()
+                };
+                let mut subfaces = vec![];
+                for candidate in candidates {
+                    let j = *next_index.entry(candidate.clone()).or_insert_with(|| {
+                        next_level.push(candidate);
+                        next_start + next_level.len() - 1
+                    });
+                    subfaces.push(j);
+                }
+                debug_assert_eq!(faces.len(), level_start + i);
+                faces.push(Face {
+                    dimension: dim,
+                    vertices: face.iter().cloned().collect(),
+                    subfaces,
+                    superfaces: vec![],
+                });
+            }
+            level = next_level;
+            level_start = next_start;
+            dim -= 1;
+        }
+        if faces.is_empty() {
+            faces.push(Face {
+                dimension: -1,
+                vertices: vec![],
+                subfaces: vec![],
+                superfaces: vec![],
+            });
+        }
+        for i in 0..faces.len() {
+            for j in faces[i].subfaces.clone() {
+                faces[j].superfaces.push(i);
+            }
+        }
+        Self { vertices, faces }
+    }
+
+    pub fn vertices(&self) -> &Vec<Vector<'f, FS>> {
+        &self.vertices
+    }
+
+    /// All faces sorted by decreasing dimension, starting with the polytope and ending with the empty face.
+    pub fn faces(&self) -> &Vec<Face> {
+        &self.faces
+    }
+
+    /// The dimension of the polytope, which is `-1` when it is empty.
+    pub fn dimension(&self) -> isize {
+        self.faces[0].dimension
+    }
+
+    pub fn faces_of_dimension(&self, dimension: isize) -> Vec<&Face> {
+        self.faces
+            .iter()
+            .filter(|face| face.dimension == dimension)
+            .collect()
+    }
+
+    pub fn face_vertices(&self, face: &Face) -> Vec<&Vector<'f, FS>> {
+        face.vertices.iter().map(|i| &self.vertices[*i]).collect()
+    }
+
+    /// The f-vector `(f_0, f_1, ..., f_{d-1})` where `f_k` is the number of faces of dimension `k`
+    /// and `d` is the dimension of the polytope.
+    pub fn f_vector(&self) -> Vec<usize> {
+        (0..self.dimension().max(0))
+            .map(|k| self.faces_of_dimension(k).len())
+            .collect()
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> ConvexHull<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// The affine span as a list of equations and the facets as a list of inequalities.
+    /// The inequalities are irredundant and there is one for each facet.
+    pub fn to_half_spaces(&self) -> HalfSpaceRepresentation<'f, FS> {
+        let space = self.ambient_space();
+        let field = space.field();
+        let d = space.linear_dimension().unwrap();
+        let points = self.defining_points().into_iter().collect::<Vec<_>>();
+        if points.is_empty() {
+            return HalfSpaceRepresentation::new(
+                space,
+                vec![],
+                vec![LinearInequality::new(
+                    Vector::zero(space),
+                    field.neg(&field.one()),
+                )],
+            );
+        }
+        // the facets of the cone over the points (1, p) are the extreme rays of its dual cone of (b, a) with b + a·p >= 0
+        let cone_space = AffineSpace::new_linear(field, d + 1);
+        let constraints = points
+            .iter()
+            .map(|p| {
+                Vector::construct(cone_space, |i| {
+                    if i == 0 {
+                        field.one()
+                    } else {
+                        p.coordinate(i - 1).clone()
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let (lineality, rays) = double_description(cone_space, &constraints);
+        let split = |v: &Vector<'f, FS>| {
+            (
+                Vector::construct(space, |i| v.coordinate(i + 1).clone()),
+                v.coordinate(0).clone(),
+            )
+        };
+        let equations = lineality
+            .iter()
+            .map(|l| {
+                let (a, b) = split(l);
+                (a, field.neg(&b))
+            })
+            .collect();
+        let inequalities = rays
+            .iter()
+            // a ray on none of the points is the trivial inequality 0 <= 1 modulo the equations
+            .filter(|r| !r.tight.is_empty())
+            .map(|r| {
+                let (a, b) = split(&r.ray);
+                LinearInequality::new(-&a, b)
+            })
+            .collect();
+        HalfSpaceRepresentation::new(space, equations, inequalities)
+    }
+
+    /// The extreme points.
+    pub fn vertices(&self) -> Vec<Vector<'f, FS>> {
+        let h = self.to_half_spaces();
+        let field = self.ambient_space().field();
+        let points = self.defining_points().into_iter().collect::<Vec<_>>();
+        let tight = points
+            .iter()
+            .map(|p| {
+                h.inequalities()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| field.equal(&c.normal().dot(p), c.bound()))
+                    .map(|(i, _)| i)
+                    .collect::<BTreeSet<_>>()
+            })
+            .collect::<Vec<_>>();
+        // a point is a vertex when no other point lies on every facet it lies on
+        let mut vertices = (0..points.len())
+            .filter(|i| (0..points.len()).all(|j| *i == j || !tight[*i].is_subset(&tight[j])))
+            .map(|i| points[i].clone())
+            .collect::<Vec<_>>();
+        vertices.sort();
+        vertices
+    }
+
+    pub fn face_lattice(&self) -> FaceLattice<'f, FS> {
+        let field = self.ambient_space().field();
+        let vertices = self.vertices();
+        let facets = self
+            .to_half_spaces()
+            .inequalities()
+            .iter()
+            .map(|c| {
+                (0..vertices.len())
+                    .filter(|i| field.equal(&c.normal().dot(&vertices[*i]), c.bound()))
+                    .collect::<BTreeSet<_>>()
+            })
+            .collect();
+        FaceLattice::new(vertices, facets, self.affine_span_dimension() as isize - 1)
+    }
+
+    /// The pairs of vertices joined by an edge.
+    pub fn edges(&self) -> Vec<(Vector<'f, FS>, Vector<'f, FS>)> {
+        let lattice = self.face_lattice();
+        lattice
+            .faces_of_dimension(1)
+            .into_iter()
+            .map(|edge| {
+                let [a, b] = lattice.face_vertices(edge)[..] else {
+                    unreachable!()
+                };
+                (a.clone(), b.clone())
+            })
+            .collect()
+    }
+
+    /// See [`FaceLattice::f_vector`].
+    pub fn f_vector(&self) -> Vec<usize> {
+        self.face_lattice().f_vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algebraeon_nzq::Rational;
+
+    fn coordinate_inequality<'f>(
+        space: AffineSpace<'f, algebraeon_nzq::RationalCanonicalStructure>,
+        i: usize,
+        sign: i64,
+        bound: i64,
+    ) -> LinearInequality<'f, algebraeon_nzq::RationalCanonicalStructure> {
+        LinearInequality::new(
+            Vector::construct(space, |j| {
+                if i == j {
+                    Rational::from(sign)
+                } else {
+                    Rational::ZERO
+                }
+            }),
+            Rational::from(bound),
+        )
+    }
+
+    #[test]
+    fn test_cube() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let mut points = vec![];
+        for x in [0, 2] {
+            for y in [0, 2] {
+                for z in [0, 2] {
+                    points.push(space.vector([x, y, z]));
+                }
+            }
+        }
+        //points in the interior and on the boundary which are not vertices
+        points.push(space.vector([1, 1, 1]));
+        points.push(space.vector([1, 1, 2]));
+        points.push(space.vector([0, 1, 2]));
+        let cube = space.convex_hull(points);
+
+        let h = cube.to_half_spaces();
+        assert_eq!(h.equations().len(), 0);
+        assert_eq!(h.inequalities().len(), 6);
+        assert!(h.contains_point(&space.vector([2, 1, 0])));
+        assert!(!h.contains_point(&space.vector([3, 1, 1])));
+
+        assert_eq!(cube.vertices().len(), 8);
+        assert_eq!(cube.edges().len(), 12);
+        assert_eq!(cube.f_vector(), vec![8, 12, 6]);
+
+        let lattice = cube.face_lattice();
+        assert_eq!(lattice.dimension(), 3);
+        assert_eq!(lattice.faces().len(), 1 + 6 + 12 + 8 + 1);
+        for face in lattice.faces_of_dimension(2) {
+            assert_eq!(face.vertices().len(), 4);
+            assert_eq!(face.subfaces().len(), 4);
+            assert_eq!(face.superfaces().len(), 1);
+        }
+        for face in lattice.faces_of_dimension(0) {
+            assert_eq!(face.superfaces().len(), 3);
+        }
+
+        //and back again
+        let cube_again = h.to_convex_hull().unwrap();
+        assert_eq!(cube_again.vertices(), cube.vertices());
+    }
+
+    #[test]
+    fn test_from_inequalities() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        //the octahedron |x| + |y| + |z| <= 1
+        let mut inequalities = vec![];
+        for sx in [-1, 1] {
+            for sy in [-1, 1] {
+                for sz in [-1, 1] {
+                    inequalities.push(LinearInequality::new(
+                        space.vector([sx, sy, sz]),
+                        Rational::ONE,
+                    ));
+                }
+            }
+        }
+        let octahedron = space
+            .convex_hull_from_inequalities(inequalities.clone())
+            .unwrap();
+        assert_eq!(octahedron.vertices().len(), 6);
+        assert_eq!(octahedron.f_vector(), vec![6, 12, 8]);
+
+        //a redundant inequality changes nothing
+        let mut with_redundant = inequalities.clone();
+        with_redundant.push(coordinate_inequality(space, 0, 1, 5));
+        let same = space.convex_hull_from_inequalities(with_redundant).unwrap();
+        assert_eq!(same.vertices(), octahedron.vertices());
+        assert_eq!(same.to_half_spaces().inequalities().len(), 8);
+
+        //cutting off a vertex
+        let mut truncated = inequalities.clone();
+        truncated.push(LinearInequality::new(
+            space.vector([1, 0, 0]),
+            Rational::from_integers(1, 2),
+        ));
+        let truncated = space.convex_hull_from_inequalities(truncated).unwrap();
+        assert_eq!(truncated.f_vector(), vec![9, 16, 9]);
+
+        //unbounded
+        assert!(
+            space
+                .convex_hull_from_inequalities(vec![
+                    coordinate_inequality(space, 0, 1, 1),
+                    coordinate_inequality(space, 1, 1, 1),
+                    coordinate_inequality(space, 2, 1, 1),
+                ])
+                .is_err()
+        );
+
+        //empty
+        let empty = space
+            .convex_hull_from_inequalities(vec![
+                coordinate_inequality(space, 0, 1, 1),
+                coordinate_inequality(space, 0, -1, -2),
+            ])
+            .unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.face_lattice().dimension(), -1);
+        assert_eq!(empty.face_lattice().faces().len(), 1);
+        assert!(
+            !empty
+                .to_half_spaces()
+                .contains_point(&space.vector([0, 0, 0]))
+        );
+    }
+
+    #[test]
+    fn test_lower_dimensional() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let triangle = space.convex_hull(vec![
+            space.vector([1, 0, 0]),
+            space.vector([0, 1, 0]),
+            space.vector([0, 0, 1]),
+            space.vector([0, 1, 0]),
+        ]);
+        let h = triangle.to_half_spaces();
+        assert_eq!(h.equations().len(), 1);
+        assert_eq!(h.inequalities().len(), 3);
+        assert!(h.contains_point(&space.vector([
+            Rational::from_integers(1, 3),
+            Rational::from_integers(1, 3),
+            Rational::from_integers(1, 3)
+        ])));
+        assert!(!h.contains_point(&space.vector([0, 0, 0])));
+        assert_eq!(triangle.f_vector(), vec![3, 3]);
+        assert_eq!(h.to_convex_hull().unwrap().vertices(), triangle.vertices());
+
+        let point = space.convex_hull(vec![space.vector([1, 2, 3])]);
+        let h = point.to_half_spaces();
+        assert_eq!(h.equations().len(), 3);
+        assert_eq!(h.inequalities().len(), 0);
+        assert_eq!(h.vertices().unwrap(), vec![space.vector([1, 2, 3])]);
+        let lattice = point.face_lattice();
+        assert_eq!(lattice.dimension(), 0);
+        assert_eq!(lattice.faces().len(), 2);
+    }
+
+    #[test]
+    fn test_higher_dimensional() {
+        //the 4-dimensional cross polytope and hypercube
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 4);
+        let mut points = vec![];
+        for i in 0..4 {
+            for s in [-1, 1] {
+                points.push(Vector::construct(space, |j| {
+                    if i == j {
+                        Rational::from(s)
+                    } else {
+                        Rational::ZERO
+                    }
+                }));
+            }
+        }
+        let cross_polytope = space.convex_hull(points);
+        assert_eq!(cross_polytope.f_vector(), vec![8, 24, 32, 16]);
+
+        let mut inequalities = vec![];
+        for i in 0..4 {
+            inequalities.push(coordinate_inequality(space, i, 1, 1));
+            inequalities.push(coordinate_inequality(space, i, -1, 1));
+        }
+        let hypercube = space.convex_hull_from_inequalities(inequalities).unwrap();
+        let f = hypercube.f_vector();
+        assert_eq!(f, vec![16, 32, 24, 8]);
+        //Euler's relation
+        assert_eq!(f[0] + f[2], f[1] + f[3]);
+
+        //a 4-simplex
+        let simplex = space.convex_hull(vec![
+            space.vector([0, 0, 0, 0]),
+            space.vector([1, 0, 0, 0]),
+            space.vector([0, 1, 0, 0]),
+            space.vector([0, 0, 1, 0]),
+            space.vector([0, 0, 0, 1]),
+        ]);
+        assert_eq!(simplex.f_vector(), vec![5, 10, 10, 5]);
+        assert_eq!(simplex.face_lattice().faces().len(), 32);
+    }
+}
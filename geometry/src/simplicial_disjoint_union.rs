@@ -1,10 +1,12 @@
 use super::*;
 use crate::{
     ambient_space::AffineSpace,
+    bounding_box::BoundingBoxTree,
     convex_hull::ConvexHull,
     partial_simplicial_complex::LabelledPartialSimplicialComplex,
     simplex::Simplex,
     simplex_collection::{InteriorOrBoundarySimplexCollection, LabelledSimplexCollection},
+    simplex_overlap::simplex_interior_overlap,
 };
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::collections::{HashMap, HashSet};
@@ -108,8 +110,11 @@ where
                         &ConvexHull::from_simplex(spx_b.clone()),
                     );
 
+                    //the closures may meet in a full dimensional region while the open simplices are
+                    //disjoint, for example a short edge lying along an edge of a triangle
                     if !(overlap.affine_span_dimension() < spx_a.n()
                         && overlap.affine_span_dimension() < spx_b.n())
+                        && simplex_interior_overlap(spx_a, spx_b)
                     {
                         println!("spx_a = {spx_a:?}");
                         println!("spx_b = {spx_b:?}");
@@ -132,9 +137,23 @@ where
         //maintain a list of pairs of simplexes which may intersect on their boundary
         let mut pairs_todo: HashMap<Simplex<'f, FS>, HashSet<Simplex<'f, FS>>> = HashMap::new();
         let simplexes = self.simplexes().into_iter().collect::<Vec<_>>();
+        // only simplexes with overlapping bounding boxes can overlap
+        let simplex_indices = BoundingBoxTree::from_simplexes(
+            ambient_space,
+            simplexes.iter().enumerate().map(|(i, spx)| (*spx, i)),
+        );
 
         for (spx_i, spx_j) in (0..simplexes.len())
-            .flat_map(|i| ((i + 1)..simplexes.len()).map(move |j| (i, j)))
+            .flat_map(|i| {
+                simplexes[i]
+                    .bounding_box()
+                    .map_or(vec![], |bounding_box| {
+                        simplex_indices.query_box(&bounding_box)
+                    })
+                    .into_iter()
+                    .filter(move |j| i < **j)
+                    .map(move |j| (i, *j))
+            })
             .collect::<Vec<_>>()
             .into_par_iter()
             .filter_map(|(i, j)| {
@@ -0,0 +1,453 @@
+use super::*;
+use crate::{
+    simplex::Simplex, simplex_collection::LabelledSimplexCollection,
+    simplicial_complex::LabelledSimplicialComplex,
+};
+use algebraeon_nzq::{Integer, Natural};
+use algebraeon_rings::matrix::Matrix;
+use std::collections::HashMap;
+
+/// A finitely generated abelian group `Z^rank + Z/t_1 + ... + Z/t_k`
+/// where the torsion coefficients satisfy `1 < t_1 | t_2 | ... | t_k`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomologyGroup {
+    rank: usize,
+    torsion: Vec<Natural>,
+}
+
+impl HomologyGroup {
+    pub fn trivial() -> Self {
+        Self {
+            rank: 0,
+            torsion: vec![],
+        }
+    }
+
+    pub fn free(rank: usize) -> Self {
+        Self {
+            rank,
+            torsion: vec![],
+        }
+    }
+
+    /// The rank of the free part, which for a homology group is the Betti number.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn torsion(&self) -> &Vec<Natural> {
+        &self.torsion
+    }
+
+    pub fn is_trivial(&self) -> bool {
+        self.rank == 0 && self.torsion.is_empty()
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.torsion.is_empty()
+    }
+}
+
+impl std::fmt::Display for HomologyGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut summands = vec![];
+        match self.rank {
+            0 => {}
+            1 => summands.push("Z".to_string()),
+            r => summands.push(format!("Z^{r}")),
+        }
+        for t in &self.torsion {
+            summands.push(format!("Z/{t}"));
+        }
+        if summands.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", summands.join(" + "))
+        }
+    }
+}
+
+/// The rank and the invariant factors bigger than one of an integer matrix.
+fn rank_and_torsion(m: &Matrix<Integer>) -> (usize, Vec<Natural>) {
+    if m.rows() == 0 || m.cols() == 0 {
+        return (0, vec![]);
+    }
+    let (_u, s, _v, k) = m.smith_algorithm();
+    let torsion = (0..k)
+        .map(|i| Natural::try_from(s.at(i, i).unwrap().clone()).unwrap())
+        .filter(|t| t > &Natural::ONE)
+        .collect();
+    (k, torsion)
+}
+
+/// A chain complex `0 -> C_n -> ... -> C_1 -> C_0 -> 0` of finitely generated free abelian groups.
+#[derive(Debug, Clone)]
+pub struct ChainComplex {
+    ranks: Vec<usize>,
+    // boundary_maps[k] is the matrix of the boundary map from C_{k+1} to C_k
+    boundary_maps: Vec<Matrix<Integer>>,
+}
+
+impl ChainComplex {
+    /// `ranks[k]` is the rank of `C_k` and `boundary_maps[k]` is the `ranks[k]` by `ranks[k+1]` matrix
+    /// of the boundary map from `C_{k+1}` to `C_k`.
+    pub fn new(
+        ranks: Vec<usize>,
+        boundary_maps: Vec<Matrix<Integer>>,
+    ) -> Result<Self, &'static str> {
+        if boundary_maps.len() + 1 != ranks.len().max(1) {
+            return Err(
+                "There must be one boundary map between each pair of adjacent chain groups",
+            );
+        }
+        for (k, m) in boundary_maps.iter().enumerate() {
+            if m.rows() != ranks[k] || m.cols() != ranks[k + 1] {
+                return Err("Boundary map has the wrong shape");
+            }
+        }
+        for k in 1..boundary_maps.len() {
+            if Matrix::mul(&boundary_maps[k - 1], &boundary_maps[k]).unwrap()
+                != Matrix::zero(ranks[k - 1], ranks[k + 1])
+            {
+                return Err("The composition of two boundary maps must be zero");
+            }
+        }
+        Ok(Self {
+            ranks,
+            boundary_maps,
+        })
+    }
+
+    /// The ranks of the chain groups `C_0, C_1, ..., C_n`.
+    pub fn ranks(&self) -> &Vec<usize> {
+        &self.ranks
+    }
+
+    /// The matrix of the boundary map from `C_k` to `C_{k-1}`, for `1 <= k <= n`.
+    pub fn boundary_map(&self, k: usize) -> Option<&Matrix<Integer>> {
+        if k == 0 {
+            None
+        } else {
+            self.boundary_maps.get(k - 1)
+        }
+    }
+
+    /// The homology groups `H_0, H_1, ..., H_n`.
+    pub fn homology(&self) -> Vec<HomologyGroup> {
+        let boundaries = self
+            .boundary_maps
+            .iter()
+            .map(rank_and_torsion)
+            .collect::<Vec<_>>();
+        (0..self.ranks.len())
+            .map(|k| {
+                //H_k = ker(C_k -> C_{k-1}) / im(C_{k+1} -> C_k)
+                let incoming = boundaries.get(k);
+                let outgoing_rank = if k == 0 { 0 } else { boundaries[k - 1].0 };
+                let incoming_rank = incoming.map_or(0, |(r, _)| *r);
+                HomologyGroup {
+                    rank: self.ranks[k] - outgoing_rank - incoming_rank,
+                    torsion: incoming.map_or(vec![], |(_, t)| t.clone()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        self.homology().into_iter().map(|h| h.rank()).collect()
+    }
+
+    /// The alternating sum of the ranks of the chain groups, which is also the alternating sum of the Betti numbers.
+    pub fn euler_characteristic(&self) -> isize {
+        self.ranks
+            .iter()
+            .enumerate()
+            .map(|(k, r)| {
+                if k % 2 == 0 {
+                    *r as isize
+                } else {
+                    -(*r as isize)
+                }
+            })
+            .sum()
+    }
+}
+
+/// The simplicial chain complex of a simplicial complex, or of a pair of a simplicial complex and a subcomplex.
+///
+/// A simplex is oriented by the order of its points, so the `i`th facet appears in its boundary with sign `(-1)^i`.
+#[derive(Debug, Clone)]
+pub struct SimplicialChainComplex<'f, FS: OrderedRingSignature + FieldSignature> {
+    // simplexes[k] is the basis of C_k, consisting of the simplexes with k+1 points
+    simplexes: Vec<Vec<Simplex<'f, FS>>>,
+    chain_complex: ChainComplex,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> SimplicialChainComplex<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// `simplexes[k]` lists the `k` dimensional simplexes to use as the basis of `C_k`.
+    /// Facets not listed in the dimension below, such as those belonging to a subcomplex, are dropped from the boundary.
+    fn new(simplexes: Vec<Vec<Simplex<'f, FS>>>) -> Self {
+        let boundary_maps = (1..simplexes.len())
+            .map(|k| {
+                let rows = simplexes[k - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, spx)| (spx, i))
+                    .collect::<HashMap<_, _>>();
+                let mut m = Matrix::<Integer>::zero(simplexes[k - 1].len(), simplexes[k].len());
+                for (c, spx) in simplexes[k].iter().enumerate() {
+                    for i in 0..spx.n() {
+                        if let Some(r) = rows.get(&spx.facet(i)) {
+                            *m.at_mut(*r, c).unwrap() = if i % 2 == 0 {
+                                Integer::ONE
+                            } else {
+                                -Integer::ONE
+                            };
+                        }
+                    }
+                }
+                m
+            })
+            .collect();
+        let ranks = simplexes.iter().map(|s| s.len()).collect();
+        Self {
+            simplexes,
+            chain_complex: ChainComplex::new(ranks, boundary_maps).unwrap(),
+        }
+    }
+
+    /// The simplexes forming the basis of `C_k`, in the order used by the boundary matrices.
+    pub fn simplexes(&self, k: usize) -> &Vec<Simplex<'f, FS>> {
+        &self.simplexes[k]
+    }
+
+    pub fn chain_complex(&self) -> &ChainComplex {
+        &self.chain_complex
+    }
+
+    pub fn boundary_map(&self, k: usize) -> Option<&Matrix<Integer>> {
+        self.chain_complex.boundary_map(k)
+    }
+
+    pub fn homology(&self) -> Vec<HomologyGroup> {
+        self.chain_complex.homology()
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature, T: Eq + Clone + Send + Sync>
+    LabelledSimplicialComplex<'f, FS, T>
+where
+    FS::Set: Hash,
+{
+    //the simplexes of self, excluding those in exclude, grouped by dimension in a fixed order
+    fn simplexes_by_dimension(
+        &self,
+        exclude: impl Fn(&Simplex<'f, FS>) -> bool,
+    ) -> Vec<Vec<Simplex<'f, FS>>> {
+        let mut simplexes: Vec<Vec<Simplex<'f, FS>>> = vec![];
+        for spx in self.simplexes() {
+            let k = spx.n() - 1;
+            if simplexes.len() <= k {
+                simplexes.resize(k + 1, vec![]);
+            }
+            if !exclude(spx) {
+                simplexes[k].push(spx.clone());
+            }
+        }
+        for spxs in &mut simplexes {
+            spxs.sort_unstable_by(|a, b| a.points().cmp(b.points()));
+        }
+        simplexes
+    }
+
+    pub fn chain_complex(&self) -> SimplicialChainComplex<'f, FS> {
+        SimplicialChainComplex::new(self.simplexes_by_dimension(|_| false))
+    }
+
+    /// The chain complex of `self` relative to a subcomplex, whose chain groups are those of `self` modulo those of the subcomplex.
+    pub fn relative_chain_complex<S: Eq + Clone + Send + Sync>(
+        &self,
+        subcomplex: &LabelledSimplicialComplex<'f, FS, S>,
+    ) -> Result<SimplicialChainComplex<'f, FS>, &'static str> {
+        if self.ambient_space() != subcomplex.ambient_space() {
+            return Err("The subcomplex must live in the same space");
+        }
+        let simplexes = self.simplexes();
+        let sub_simplexes = subcomplex.simplexes();
+        if !sub_simplexes.iter().all(|spx| simplexes.contains(spx)) {
+            return Err("Not a subcomplex");
+        }
+        Ok(SimplicialChainComplex::new(
+            self.simplexes_by_dimension(|spx| sub_simplexes.contains(spx)),
+        ))
+    }
+
+    /// The integral homology groups `H_0, H_1, ..., H_n` where `n` is the largest dimension of a simplex.
+    pub fn homology(&self) -> Vec<HomologyGroup> {
+        self.chain_complex().homology()
+    }
+
+    /// The integral homology groups `H_k(self, subcomplex)` for `k = 0, 1, ..., n`.
+    pub fn relative_homology<S: Eq + Clone + Send + Sync>(
+        &self,
+        subcomplex: &LabelledSimplicialComplex<'f, FS, S>,
+    ) -> Result<Vec<HomologyGroup>, &'static str> {
+        Ok(self.relative_chain_complex(subcomplex)?.homology())
+    }
+
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        self.chain_complex().chain_complex().betti_numbers()
+    }
+
+    /// The number of even dimensional simplexes minus the number of odd dimensional simplexes.
+    pub fn euler_characteristic(&self) -> isize {
+        self.simplexes()
+            .into_iter()
+            .map(|spx| if spx.n() % 2 == 1 { 1 } else { -1 })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ambient_space::AffineSpace, simplicial_complex::SimplicialComplex};
+    use algebraeon_nzq::Rational;
+    use algebraeon_sets::structure::MetaType;
+    use std::collections::HashSet;
+
+    //the simplicial complex generated by the given facets on the vertices 0, e_1, ..., e_n of an n-simplex
+    fn abstract_complex(
+        space: AffineSpace<'static, <Rational as MetaType>::Signature>,
+        facets: &[Vec<usize>],
+    ) -> SimplicialComplex<'static, <Rational as MetaType>::Signature> {
+        let n = space.linear_dimension().unwrap();
+        let vertex = |i: usize| space.vector((0..n).map(|j| if j + 1 == i { 1 } else { 0 }));
+        let mut simplexes = HashSet::new();
+        for facet in facets {
+            let spx = space
+                .simplex(facet.iter().map(|i| vertex(*i)).collect())
+                .unwrap();
+            simplexes.extend(spx.sub_simplices_not_null());
+        }
+        SimplicialComplex::try_new(space, simplexes).unwrap()
+    }
+
+    fn z(rank: usize) -> HomologyGroup {
+        HomologyGroup::free(rank)
+    }
+
+    #[test]
+    fn test_chain_complex() {
+        //the cellular chain complex Z --2--> Z --0--> Z of the real projective plane
+        let c = ChainComplex::new(
+            vec![1, 1, 1],
+            vec![
+                Matrix::from_rows(vec![vec![Integer::ZERO]]),
+                Matrix::from_rows(vec![vec![Integer::from(2)]]),
+            ],
+        )
+        .unwrap();
+        let h = c.homology();
+        assert_eq!(h[0], z(1));
+        assert_eq!(h[1].torsion(), &vec![Natural::from(2u32)]);
+        assert_eq!(h[1].to_string(), "Z/2");
+        assert!(h[2].is_trivial());
+        assert_eq!(c.euler_characteristic(), 1);
+
+        assert!(
+            ChainComplex::new(
+                vec![1, 1, 1],
+                vec![
+                    Matrix::from_rows(vec![vec![Integer::ONE]]),
+                    Matrix::from_rows(vec![vec![Integer::ONE]]),
+                ],
+            )
+            .is_err()
+        );
+        assert!(ChainComplex::new(vec![1, 2], vec![]).is_err());
+        assert!(
+            ChainComplex::new(vec![], vec![])
+                .unwrap()
+                .homology()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_spheres_and_balls() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 3);
+        let ball = abstract_complex(space, &[vec![0, 1, 2, 3]]);
+        let sphere = ball.boundary();
+        assert_eq!(ball.homology(), vec![z(1), z(0), z(0), z(0)]);
+        assert_eq!(sphere.betti_numbers(), vec![1, 0, 1]);
+        assert_eq!(sphere.euler_characteristic(), 2);
+        assert_eq!(
+            ball.relative_homology(&sphere).unwrap(),
+            vec![z(0), z(0), z(0), z(1)]
+        );
+
+        let triangle = abstract_complex(space, &[vec![0, 1, 2]]);
+        let circle = abstract_complex(space, &[vec![0, 1], vec![1, 2], vec![0, 2]]);
+        assert_eq!(circle.betti_numbers(), vec![1, 1]);
+        assert_eq!(
+            triangle.relative_homology(&circle).unwrap(),
+            vec![z(0), z(0), z(1)]
+        );
+        assert!(circle.relative_homology(&triangle).is_err());
+
+        //two disjoint intervals glued at no point
+        let intervals = abstract_complex(space, &[vec![0, 1], vec![2, 3]]);
+        assert_eq!(intervals.betti_numbers(), vec![2, 0]);
+        let chains = intervals.chain_complex();
+        assert_eq!(chains.simplexes(0).len(), 4);
+        assert_eq!(chains.boundary_map(1).unwrap().rows(), 4);
+        assert!(chains.boundary_map(2).is_none());
+    }
+
+    #[test]
+    fn test_surfaces() {
+        //the 7 vertex torus
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 6);
+        let torus = abstract_complex(
+            space,
+            &(0..7)
+                .flat_map(|i| {
+                    [
+                        vec![i, (i + 1) % 7, (i + 3) % 7],
+                        vec![i, (i + 2) % 7, (i + 3) % 7],
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(torus.homology(), vec![z(1), z(2), z(1)]);
+        assert_eq!(torus.euler_characteristic(), 0);
+
+        //the 6 vertex real projective plane
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 5);
+        let rp2 = abstract_complex(
+            space,
+            &[
+                vec![0, 1, 2],
+                vec![0, 2, 3],
+                vec![0, 3, 4],
+                vec![0, 4, 5],
+                vec![0, 5, 1],
+                vec![1, 2, 4],
+                vec![2, 3, 5],
+                vec![3, 4, 1],
+                vec![4, 5, 2],
+                vec![5, 1, 3],
+            ],
+        );
+        let h = rp2.homology();
+        assert_eq!(h[0], z(1));
+        assert_eq!(h[1].rank(), 0);
+        assert_eq!(h[1].torsion(), &vec![Natural::from(2u32)]);
+        assert!(h[2].is_trivial());
+        assert_eq!(rp2.euler_characteristic(), 1);
+    }
+}
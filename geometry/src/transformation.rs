@@ -0,0 +1,600 @@
+use super::*;
+use crate::{
+    ambient_space::AffineSpace,
+    convex_hull::ConvexHull,
+    oriented_simplex::OrientedSimplex,
+    simplex::Simplex,
+    simplex_collection::LabelledSimplexCollection,
+    vector::{DotProduct, Vector},
+};
+use algebraeon_rings::{
+    matrix::{Matrix, MatrixStructure},
+    structure::{AdditiveMonoidEqSignature, PositiveRealNthRootSignature},
+};
+
+/// An affine map `x -> Ax + b` from one space to another, given by a matrix `A` and a translation `b`.
+#[derive(Debug, Clone)]
+pub struct AffineTransformation<'f, FS: OrderedRingSignature + FieldSignature> {
+    domain: AffineSpace<'f, FS>,
+    range: AffineSpace<'f, FS>,
+    // rows are indexed by the coordinates of range and columns by the coordinates of domain
+    matrix: Matrix<FS::Set>,
+    translation: Vector<'f, FS>,
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> AffineTransformation<'f, FS> {
+    pub fn new(
+        domain: AffineSpace<'f, FS>,
+        range: AffineSpace<'f, FS>,
+        matrix: Matrix<FS::Set>,
+        translation: Vector<'f, FS>,
+    ) -> Result<Self, &'static str> {
+        assert_eq!(translation.ambient_space(), range);
+        let (Some(n), Some(m)) = (domain.linear_dimension(), range.linear_dimension()) else {
+            return Err("Affine transformations require non-empty spaces");
+        };
+        if matrix.rows() != m || matrix.cols() != n {
+            return Err("Matrix dimensions do not match the spaces");
+        }
+        Ok(Self {
+            domain,
+            range,
+            matrix,
+            translation,
+        })
+    }
+
+    pub fn new_linear(
+        domain: AffineSpace<'f, FS>,
+        range: AffineSpace<'f, FS>,
+        matrix: Matrix<FS::Set>,
+    ) -> Result<Self, &'static str> {
+        Self::new(domain, range, matrix, Vector::zero(range))
+    }
+
+    // the transformation of space with the given square matrix and zero translation
+    fn from_square_matrix(
+        space: AffineSpace<'f, FS>,
+        matrix: impl Fn(usize, usize) -> FS::Set,
+    ) -> Self {
+        let n = space.linear_dimension().unwrap();
+        Self::new_linear(space, space, Matrix::construct(n, n, matrix)).unwrap()
+    }
+
+    pub fn identity(space: AffineSpace<'f, FS>) -> Self {
+        let field = space.field();
+        Self::from_square_matrix(
+            space,
+            |r, c| {
+                if r == c { field.one() } else { field.zero() }
+            },
+        )
+    }
+
+    pub fn translation(vector: &Vector<'f, FS>) -> Self {
+        let mut transformation = Self::identity(vector.ambient_space());
+        transformation.translation = vector.clone();
+        transformation
+    }
+
+    /// Scale about the origin by the same factor in every direction.
+    pub fn scaling(space: AffineSpace<'f, FS>, factor: &FS::Set) -> Self {
+        let field = space.field();
+        Self::from_square_matrix(
+            space,
+            |r, c| {
+                if r == c { factor.clone() } else { field.zero() }
+            },
+        )
+    }
+
+    /// Scale about the origin by a separate factor along each coordinate axis.
+    pub fn axis_scaling(space: AffineSpace<'f, FS>, factors: Vec<FS::Set>) -> Self {
+        assert_eq!(Some(factors.len()), space.linear_dimension());
+        let field = space.field();
+        Self::from_square_matrix(space, |r, c| {
+            if r == c {
+                factors[r].clone()
+            } else {
+                field.zero()
+            }
+        })
+    }
+
+    /// The shear adding `factor` times coordinate `j` to coordinate `i`.
+    pub fn shear(space: AffineSpace<'f, FS>, i: usize, j: usize, factor: &FS::Set) -> Self {
+        assert_ne!(i, j);
+        let field = space.field();
+        Self::from_square_matrix(space, |r, c| {
+            if r == c {
+                field.one()
+            } else if (r, c) == (i, j) {
+                factor.clone()
+            } else {
+                field.zero()
+            }
+        })
+    }
+
+    /// The rotation about the origin in the plane of coordinate axes `i` and `j` taking axis `i` towards axis `j`
+    /// through the angle with the given cosine and sine. Returns an error unless `cos² + sin² = 1`.
+    pub fn rotation(
+        space: AffineSpace<'f, FS>,
+        i: usize,
+        j: usize,
+        cos: &FS::Set,
+        sin: &FS::Set,
+    ) -> Result<Self, &'static str> {
+        assert_ne!(i, j);
+        let field = space.field();
+        if !field.equal(
+            &field.add(&field.mul(cos, cos), &field.mul(sin, sin)),
+            &field.one(),
+        ) {
+            return Err("The cosine and sine of a rotation must satisfy cos² + sin² = 1");
+        }
+        Ok(Self::from_square_matrix(space, |r, c| {
+            if (r, c) == (i, i) || (r, c) == (j, j) {
+                cos.clone()
+            } else if (r, c) == (j, i) {
+                sin.clone()
+            } else if (r, c) == (i, j) {
+                field.neg(sin)
+            } else if r == c {
+                field.one()
+            } else {
+                field.zero()
+            }
+        }))
+    }
+
+    /// The rotation as in [`Self::rotation`] through the angle `θ` with `tan(θ/2) = t`.
+    /// Since `cos(θ) = (1 - t²) / (1 + t²)` and `sin(θ) = 2t / (1 + t²)` this gives every rotation
+    /// with cosine and sine in the field apart from the half turn.
+    pub fn rotation_by_half_angle_tangent(
+        space: AffineSpace<'f, FS>,
+        i: usize,
+        j: usize,
+        t: &FS::Set,
+    ) -> Self {
+        let field = space.field();
+        let t2 = field.mul(t, t);
+        let denominator = field.add(&field.one(), &t2);
+        let cos = field
+            .div(&field.sub(&field.one(), &t2), &denominator)
+            .unwrap();
+        let sin = field.div(&field.add(t, t), &denominator).unwrap();
+        Self::rotation(space, i, j, &cos, &sin).unwrap()
+    }
+
+    /// Reflection in the hyperplane of points `x` with `normal·x = offset`.
+    pub fn reflection(normal: &Vector<'f, FS>, offset: &FS::Set) -> Result<Self, &'static str> {
+        let space = normal.ambient_space();
+        let field = space.field();
+        let length2 = normal.dot(normal);
+        if field.is_zero(&length2) {
+            return Err("The normal of a reflection must be non-zero");
+        }
+        // x -> x - 2 (normal·x - offset) / (normal·normal) normal
+        let scale = field.div(&field.from_nat(2u32), &length2).unwrap();
+        let mut transformation = Self::from_square_matrix(space, |r, c| {
+            let entry = field.neg(&field.mul(
+                &scale,
+                &field.mul(normal.coordinate(r), normal.coordinate(c)),
+            ));
+            if r == c {
+                field.add(&field.one(), &entry)
+            } else {
+                entry
+            }
+        });
+        transformation.translation = normal.scalar_mul(&field.mul(&scale, offset));
+        Ok(transformation)
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature + PositiveRealNthRootSignature>
+    AffineTransformation<'f, FS>
+{
+    /// The rotation as in [`Self::rotation`] through the angle in `[0, π]` with the given cosine,
+    /// using the square root in the field for its sine. Returns an error unless `-1 <= cos <= 1`.
+    pub fn rotation_by_cosine(
+        space: AffineSpace<'f, FS>,
+        i: usize,
+        j: usize,
+        cos: &FS::Set,
+    ) -> Result<Self, &'static str> {
+        let field = space.field();
+        let sin2 = field.sub(&field.one(), &field.mul(cos, cos));
+        if field.ring_cmp(&sin2, &field.zero()) == std::cmp::Ordering::Less {
+            return Err("The cosine of a rotation must lie between -1 and 1");
+        }
+        let sin = field.square_root(&sin2).unwrap();
+        Self::rotation(space, i, j, cos, &sin)
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> AffineTransformation<'f, FS> {
+    pub fn domain(&self) -> AffineSpace<'f, FS> {
+        self.domain
+    }
+
+    pub fn range(&self) -> AffineSpace<'f, FS> {
+        self.range
+    }
+
+    pub fn linear_part(&self) -> &Matrix<FS::Set> {
+        &self.matrix
+    }
+
+    pub fn translation_part(&self) -> &Vector<'f, FS> {
+        &self.translation
+    }
+
+    fn matrix_structure(&self) -> MatrixStructure<FS, &'f FS> {
+        MatrixStructure::new(self.range.field())
+    }
+
+    /// The transformation applying `other` and then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(other.range, self.domain);
+        let mat = self.matrix_structure();
+        let mut translation = self.apply_linear(&other.translation);
+        translation += &self.translation;
+        Self {
+            domain: other.domain,
+            range: self.range,
+            matrix: mat.mul(&self.matrix, &other.matrix).unwrap(),
+            translation,
+        }
+    }
+
+    /// The inverse transformation, if the matrix is square and invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let matrix = self.matrix_structure().inv(self.matrix.clone()).ok()?;
+        let linear_inverse = Self {
+            domain: self.range,
+            range: self.domain,
+            matrix,
+            translation: Vector::zero(self.domain),
+        };
+        let translation = -&linear_inverse.apply_linear(&self.translation);
+        Some(Self {
+            translation,
+            ..linear_inverse
+        })
+    }
+
+    /// The determinant of the matrix, or `None` if the domain and range have different dimensions.
+    pub fn determinant(&self) -> Option<FS::Set> {
+        self.matrix_structure().det(self.matrix.clone()).ok()
+    }
+
+    /// Whether the transformation maps the domain bijectively onto the range.
+    pub fn is_invertible(&self) -> bool {
+        self.determinant()
+            .is_some_and(|det| !self.range.field().is_zero(&det))
+    }
+
+    /// Whether the transformation is invertible and reverses orientation, such as a reflection.
+    pub fn is_orientation_reversing(&self) -> bool {
+        self.determinant().is_some_and(|det| {
+            let field = self.range.field();
+            field.ring_cmp(&det, &field.zero()) == std::cmp::Ordering::Less
+        })
+    }
+
+    /// Whether distinct points have distinct images, so that simplices are mapped to simplices of the same dimension.
+    pub fn is_injective(&self) -> bool {
+        self.matrix_structure().rank(self.matrix.clone()) == self.matrix.cols()
+    }
+
+    fn apply_linear(&self, vector: &Vector<'f, FS>) -> Vector<'f, FS> {
+        self.range.vector(
+            self.matrix_structure()
+                .apply_col(&self.matrix, &vector.clone().into_coordinates()),
+        )
+    }
+
+    pub fn apply_point(&self, point: &Vector<'f, FS>) -> Vector<'f, FS> {
+        assert_eq!(point.ambient_space(), self.domain);
+        let mut image = self.apply_linear(point);
+        image += &self.translation;
+        image
+    }
+
+    /// The image of a simplex, or an error if its image is degenerate.
+    pub fn apply_simplex(
+        &self,
+        simplex: &Simplex<'f, FS>,
+    ) -> Result<Simplex<'f, FS>, &'static str> {
+        self.range.simplex(
+            simplex
+                .points()
+                .iter()
+                .map(|point| self.apply_point(point))
+                .collect(),
+        )
+    }
+
+    /// The image of an oriented simplex whose positive side is the image of the positive side,
+    /// so the orientation is reversed when the transformation reverses orientation.
+    /// Returns an error unless the transformation is invertible.
+    pub fn apply_oriented_simplex(
+        &self,
+        oriented_simplex: &OrientedSimplex<'f, FS>,
+    ) -> Result<OrientedSimplex<'f, FS>, &'static str> {
+        if !self.is_invertible() {
+            return Err("Only invertible transformations can be applied to oriented simplices");
+        }
+        // a null oriented simplex has no sides, so the translation, a point of the range, will do
+        let positive_point = match oriented_simplex.positive_point() {
+            Some(point) => self.apply_point(&point),
+            None => self.translation.clone(),
+        };
+        OrientedSimplex::new_with_positive_point(
+            self.range,
+            oriented_simplex
+                .simplex()
+                .points()
+                .iter()
+                .map(|point| self.apply_point(point))
+                .collect(),
+            &positive_point,
+        )
+    }
+}
+
+impl<'f, FS: OrderedRingSignature + FieldSignature> AffineTransformation<'f, FS>
+where
+    FS::Set: Hash,
+{
+    /// The image of a convex hull, which is the convex hull of the images of its points.
+    pub fn apply_convex_hull(&self, convex_hull: &ConvexHull<'f, FS>) -> ConvexHull<'f, FS> {
+        assert_eq!(convex_hull.ambient_space(), self.domain);
+        self.range.convex_hull(
+            convex_hull
+                .defining_points()
+                .iter()
+                .map(|point| self.apply_point(point))
+                .collect(),
+        )
+    }
+
+    /// The image of a collection of simplices with each simplex keeping its label.
+    /// Returns an error unless the transformation is injective.
+    pub fn apply_simplex_collection<
+        T: Eq + Clone + Send + Sync,
+        C: LabelledSimplexCollection<'f, FS, T>,
+    >(
+        &self,
+        collection: &C,
+    ) -> Result<C, &'static str> {
+        assert_eq!(collection.ambient_space(), self.domain);
+        if !self.is_injective() {
+            return Err("Only injective transformations can be applied to simplex collections");
+        }
+        // injective affine maps preserve how simplices meet, so the image is valid whenever the collection is
+        Ok(C::new_labelled_unchecked(
+            self.range,
+            collection
+                .labelled_simplexes()
+                .into_iter()
+                .map(|(spx, label)| (self.apply_simplex(spx).unwrap(), label.clone()))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        measure::SimplexCollectionMeasure, oriented_simplex::OrientationSide,
+        simplicial_complex::LabelledSimplicialComplex,
+    };
+    use algebraeon_nzq::Rational;
+    use algebraeon_rings::isolated_algebraic::RealAlgebraic;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_standard_transformations() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+
+        let rotation = AffineTransformation::rotation_by_half_angle_tangent(
+            space,
+            0,
+            1,
+            &Rational::from_str("1/2").unwrap(),
+        );
+        assert_eq!(
+            rotation.apply_point(&space.vector([5, 0])),
+            space.vector([3, 4])
+        );
+        assert_eq!(rotation.determinant(), Some(Rational::ONE));
+        assert!(
+            AffineTransformation::rotation(space, 0, 1, &Rational::ONE, &Rational::ONE).is_err()
+        );
+
+        let reflection =
+            AffineTransformation::reflection(&space.vector([1, 1]), &Rational::from(2)).unwrap();
+        assert_eq!(
+            reflection.apply_point(&space.vector([0, 0])),
+            space.vector([2, 2])
+        );
+        assert_eq!(
+            reflection.apply_point(&space.vector([3, -1])),
+            space.vector([3, -1])
+        );
+        assert!(reflection.is_orientation_reversing());
+        assert!(!rotation.is_orientation_reversing());
+
+        let shear = AffineTransformation::shear(space, 0, 1, &Rational::from(2));
+        assert_eq!(
+            shear.apply_point(&space.vector([1, 3])),
+            space.vector([7, 3])
+        );
+
+        let translation = AffineTransformation::translation(&space.vector([1, -1]));
+        let scaling =
+            AffineTransformation::axis_scaling(space, vec![Rational::from(2), Rational::from(3)]);
+        let composite = scaling.compose(&translation).compose(&rotation);
+        let inverse = composite.inverse().unwrap();
+        for point in [space.vector([0, 0]), space.vector([2, 7])] {
+            assert_eq!(inverse.apply_point(&composite.apply_point(&point)), point);
+        }
+        assert_eq!(
+            composite.apply_point(&space.vector([5, 0])),
+            space.vector([8, 9])
+        );
+
+        let line = AffineSpace::new_linear(Rational::structure_ref(), 1);
+        let projection = AffineTransformation::new(
+            space,
+            line,
+            Matrix::from_rows(vec![vec![Rational::ONE, Rational::ONE]]),
+            line.vector([1]),
+        )
+        .unwrap();
+        assert_eq!(
+            projection.apply_point(&space.vector([2, 3])),
+            line.vector([6])
+        );
+        assert!(!projection.is_injective());
+        assert!(projection.inverse().is_none());
+        assert!(projection.determinant().is_none());
+    }
+
+    #[test]
+    fn test_rotation_by_cosine() {
+        let field = RealAlgebraic::structure_ref();
+        let space = AffineSpace::new_linear(field, 3);
+        let half = RealAlgebraic::Rational(Rational::from_str("1/2").unwrap());
+        let rotation = AffineTransformation::rotation_by_cosine(space, 1, 2, &half).unwrap();
+        let mut total = AffineTransformation::identity(space);
+        for _ in 0..3 {
+            total = total.compose(&rotation);
+        }
+        // three rotations by a sixth of a turn is a half turn
+        let point = space.vector([
+            RealAlgebraic::Rational(Rational::from(5)),
+            RealAlgebraic::Rational(Rational::ONE),
+            RealAlgebraic::Rational(Rational::from(2)),
+        ]);
+        assert_eq!(
+            total.apply_point(&point),
+            space.vector([
+                RealAlgebraic::Rational(Rational::from(5)),
+                RealAlgebraic::Rational(-Rational::ONE),
+                RealAlgebraic::Rational(Rational::from(-2)),
+            ])
+        );
+        assert!(
+            AffineTransformation::rotation_by_cosine(
+                space,
+                0,
+                1,
+                &RealAlgebraic::Rational(Rational::from(2))
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_transform_shapes() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let square = space.convex_hull(vec![
+            space.vector([0, 0]),
+            space.vector([1, 0]),
+            space.vector([0, 1]),
+            space.vector([1, 1]),
+        ]);
+        let transformation = AffineTransformation::shear(space, 0, 1, &Rational::from(5))
+            .compose(&AffineTransformation::scaling(space, &Rational::from(3)));
+
+        let image = transformation.apply_convex_hull(&square);
+        assert_eq!(image.to_simplicial_complex().volume(), Rational::from(9));
+
+        let labelled = LabelledSimplicialComplex::try_new_labelled(
+            space,
+            square
+                .to_simplicial_complex()
+                .into_simplexes()
+                .into_iter()
+                .map(|spx| {
+                    let n = spx.n();
+                    (spx, n)
+                })
+                .collect(),
+        )
+        .unwrap();
+        let image = transformation.apply_simplex_collection(&labelled).unwrap();
+        assert_eq!(image.ambient_space(), space);
+        assert_eq!(image.volume(), Rational::from(9));
+        for (spx, label) in image.labelled_simplexes() {
+            assert_eq!(spx.n(), *label);
+        }
+
+        let line = AffineSpace::new_linear(Rational::structure_ref(), 1);
+        let projection = AffineTransformation::new_linear(
+            space,
+            line,
+            Matrix::from_rows(vec![vec![Rational::ONE, Rational::ZERO]]),
+        )
+        .unwrap();
+        assert!(projection.apply_simplex_collection(&labelled).is_err());
+        assert_eq!(
+            projection
+                .apply_convex_hull(&square)
+                .defining_points()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_transform_oriented_simplex() {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let oriented = OrientedSimplex::new_with_positive_point(
+            space,
+            vec![space.vector([0, 0]), space.vector([1, 0])],
+            &space.vector([0, 1]),
+        )
+        .unwrap();
+        for transformation in [
+            AffineTransformation::reflection(&space.vector([0, 1]), &Rational::ZERO).unwrap(),
+            AffineTransformation::reflection(&space.vector([1, 0]), &Rational::ONE).unwrap(),
+            AffineTransformation::rotation_by_half_angle_tangent(space, 0, 1, &Rational::ONE),
+        ] {
+            let image = transformation.apply_oriented_simplex(&oriented).unwrap();
+            for point in [space.vector([3, 1]), space.vector([-1, -2])] {
+                assert_eq!(
+                    image.classify_point(&transformation.apply_point(&point)),
+                    oriented.classify_point(&point)
+                );
+            }
+        }
+        // the reflection in the line of the simplex swaps its sides
+        let image = AffineTransformation::reflection(&space.vector([0, 1]), &Rational::ZERO)
+            .unwrap()
+            .apply_oriented_simplex(&oriented)
+            .unwrap();
+        assert_eq!(
+            image.classify_point(&space.vector([0, 1])),
+            OrientationSide::Negative
+        );
+        assert!(
+            AffineTransformation::scaling(space, &Rational::ZERO)
+                .apply_oriented_simplex(&oriented)
+                .is_err()
+        );
+        // the null oriented simplex of a one point space
+        let point = AffineSpace::new_linear(Rational::structure_ref(), 0);
+        let null =
+            OrientedSimplex::new_with_positive_point(point, vec![], &Vector::zero(point)).unwrap();
+        let image = AffineTransformation::identity(point)
+            .apply_oriented_simplex(&null)
+            .unwrap();
+        assert!(image.simplex().points().is_empty());
+    }
+}
@@ -0,0 +1,788 @@
+use crate::structure::{FiniteGraphSignature, GraphSignature, UndirectedGraphSignature};
+use algebraeon_rings::structure::OrderedRingSignature;
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature, SetSignature};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+pub type Vertex<G> = <<G as GraphSignature>::Vertices as SetSignature>::Set;
+pub type Bipartition<G> = (Vec<Vertex<G>>, Vec<Vertex<G>>);
+
+fn vertex_position<G: FiniteGraphSignature + ?Sized>(
+    graph: &G,
+    elements: &[Vertex<G>],
+    v: &Vertex<G>,
+) -> Result<usize, String> {
+    graph
+        .vertices()
+        .is_element(v)
+        .map_err(|e| format!("Not an element of Vertices: {e}"))?;
+    Ok(elements
+        .iter()
+        .position(|u| graph.vertices().equal(u, v))
+        .unwrap())
+}
+
+//adjacency ignoring the direction of edges
+fn symmetric_adjacency(out_adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut adjacency = out_adjacency.to_vec();
+    for (u, targets) in out_adjacency.iter().enumerate() {
+        for v in targets {
+            adjacency[*v].push(u);
+        }
+    }
+    for targets in &mut adjacency {
+        targets.sort_unstable();
+        targets.dedup();
+    }
+    adjacency
+}
+
+//breadth first search returning the distance to and the predecessor of each vertex
+fn bfs(adjacency: &[Vec<usize>], start: usize) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let mut distance = vec![None; adjacency.len()];
+    let mut predecessor = vec![None; adjacency.len()];
+    distance[start] = Some(0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(u) = queue.pop_front() {
+        let d = distance[u].unwrap();
+        for v in &adjacency[u] {
+            if distance[*v].is_none() {
+                distance[*v] = Some(d + 1);
+                predecessor[*v] = Some(u);
+                queue.push_back(*v);
+            }
+        }
+    }
+    (distance, predecessor)
+}
+
+//follow predecessors back from target to the source
+fn trace_path(predecessor: &[Option<usize>], source: usize, target: usize) -> Vec<usize> {
+    let mut path = vec![target];
+    let mut v = target;
+    while v != source {
+        v = predecessor[v].unwrap();
+        path.push(v);
+    }
+    path.reverse();
+    path
+}
+
+/// Shortest paths from a single source in a graph with weighted edges.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S: SetSignature + EqSignature, W> {
+    vertices: S,
+    elements: Vec<S::Set>,
+    source: usize,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl<S: SetSignature + EqSignature, W> ShortestPaths<S, W> {
+    fn position(&self, v: &S::Set) -> Option<usize> {
+        self.elements.iter().position(|u| self.vertices.equal(u, v))
+    }
+
+    pub fn source(&self) -> &S::Set {
+        &self.elements[self.source]
+    }
+
+    /// The length of a shortest path to `target`, or `None` if it is unreachable.
+    pub fn distance(&self, target: &S::Set) -> Option<&W> {
+        self.distances[self.position(target)?].as_ref()
+    }
+
+    /// The vertices of a shortest path from the source to `target`, or `None` if it is unreachable.
+    pub fn path(&self, target: &S::Set) -> Option<Vec<S::Set>> {
+        let target = self.position(target)?;
+        self.distances[target].as_ref()?;
+        Some(
+            trace_path(&self.predecessors, self.source, target)
+                .into_iter()
+                .map(|i| self.elements[i].clone())
+                .collect(),
+        )
+    }
+}
+
+/// Algorithms which work for any graph whose vertices can be listed.
+///
+/// Unless stated otherwise, multiple edges are treated as a single edge.
+pub trait FiniteGraphAlgorithms: FiniteGraphSignature {
+    /// The vertices reachable from `start` in breadth first order.
+    fn bfs_order(&self, start: &Vertex<Self>) -> Result<Vec<Vertex<Self>>, String> {
+        Ok(self
+            .bfs_distances(start)?
+            .into_iter()
+            .map(|(v, _)| v)
+            .collect())
+    }
+
+    /// The vertices reachable from `start` in breadth first order together with their distance from `start`.
+    fn bfs_distances(&self, start: &Vertex<Self>) -> Result<Vec<(Vertex<Self>, usize)>, String> {
+        let elements = self.vertices().list_all_elements();
+        let start = vertex_position(self, &elements, start)?;
+        let (distance, _) = bfs(&self.out_adjacency(), start);
+        let mut reached = (0..elements.len())
+            .filter_map(|i| Some((i, distance[i]?)))
+            .collect::<Vec<_>>();
+        //a stable sort by distance keeps the order in which vertices were listed
+        reached.sort_by_key(|(_, d)| *d);
+        Ok(reached
+            .into_iter()
+            .map(|(i, d)| (elements[i].clone(), d))
+            .collect())
+    }
+
+    /// The vertices reachable from `start` in depth first preorder.
+    fn dfs_order(&self, start: &Vertex<Self>) -> Result<Vec<Vertex<Self>>, String> {
+        let elements = self.vertices().list_all_elements();
+        let start = vertex_position(self, &elements, start)?;
+        let adjacency = self.out_adjacency();
+        let mut visited = vec![false; elements.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(u) = stack.pop() {
+            if visited[u] {
+                continue;
+            }
+            visited[u] = true;
+            order.push(elements[u].clone());
+            for v in adjacency[u].iter().rev() {
+                if !visited[*v] {
+                    stack.push(*v);
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// The vertices of a path from `source` to `target` with as few edges as possible.
+    fn shortest_path(
+        &self,
+        source: &Vertex<Self>,
+        target: &Vertex<Self>,
+    ) -> Result<Option<Vec<Vertex<Self>>>, String> {
+        let elements = self.vertices().list_all_elements();
+        let source = vertex_position(self, &elements, source)?;
+        let target = vertex_position(self, &elements, target)?;
+        let (distance, predecessor) = bfs(&self.out_adjacency(), source);
+        if distance[target].is_none() {
+            return Ok(None);
+        }
+        Ok(Some(
+            trace_path(&predecessor, source, target)
+                .into_iter()
+                .map(|i| elements[i].clone())
+                .collect(),
+        ))
+    }
+
+    /// The connected components when the direction of edges is ignored.
+    fn connected_components(&self) -> Vec<Vec<Vertex<Self>>> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = symmetric_adjacency(&self.out_adjacency());
+        let mut component = vec![None; elements.len()];
+        let mut components = vec![];
+        for start in 0..elements.len() {
+            if component[start].is_some() {
+                continue;
+            }
+            let (distance, _) = bfs(&adjacency, start);
+            let members = (0..elements.len())
+                .filter(|i| distance[*i].is_some())
+                .collect::<Vec<_>>();
+            for i in &members {
+                component[*i] = Some(components.len());
+            }
+            components.push(members);
+        }
+        components
+            .into_iter()
+            .map(|members| members.into_iter().map(|i| elements[i].clone()).collect())
+            .collect()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+
+    /// The strongly connected components, computed using Tarjan's algorithm.
+    /// Components are listed in reverse topological order.
+    fn strongly_connected_components(&self) -> Vec<Vec<Vertex<Self>>> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = self.out_adjacency();
+        let n = elements.len();
+        let mut index = vec![None; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        let mut next_index = 0;
+        let mut components = vec![];
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+            //each frame is a vertex and the position of the next neighbour to visit
+            let mut call_stack = vec![(root, 0)];
+            index[root] = Some(next_index);
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some((u, i)) = call_stack.pop() {
+                if i < adjacency[u].len() {
+                    call_stack.push((u, i + 1));
+                    let v = adjacency[u][i];
+                    match index[v] {
+                        None => {
+                            index[v] = Some(next_index);
+                            lowlink[v] = next_index;
+                            next_index += 1;
+                            stack.push(v);
+                            on_stack[v] = true;
+                            call_stack.push((v, 0));
+                        }
+                        Some(v_index) => {
+                            if on_stack[v] {
+                                lowlink[u] = lowlink[u].min(v_index);
+                            }
+                        }
+                    }
+                } else {
+                    if let Some((parent, _)) = call_stack.last() {
+                        lowlink[*parent] = lowlink[*parent].min(lowlink[u]);
+                    }
+                    if Some(lowlink[u]) == index[u] {
+                        let mut members = vec![];
+                        loop {
+                            let w = stack.pop().unwrap();
+                            on_stack[w] = false;
+                            members.push(elements[w].clone());
+                            if w == u {
+                                break;
+                            }
+                        }
+                        components.push(members);
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    fn is_strongly_connected(&self) -> bool {
+        self.strongly_connected_components().len() <= 1
+    }
+
+    /// The greatest distance from one vertex to another, or `None` if some vertex cannot reach another or there are no vertices.
+    fn diameter(&self) -> Option<usize> {
+        let adjacency = self.out_adjacency();
+        let mut diameter = None;
+        for start in 0..adjacency.len() {
+            let (distance, _) = bfs(&adjacency, start);
+            for d in distance {
+                diameter = Some(diameter.unwrap_or(0).max(d?));
+            }
+        }
+        diameter
+    }
+
+    /// The length of a shortest cycle, or `None` if the graph is a forest.
+    /// A loop is a cycle of length 1.
+    fn girth(&self) -> Option<usize>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let adjacency = self.out_adjacency();
+        if (0..adjacency.len()).any(|u| adjacency[u].contains(&u)) {
+            return Some(1);
+        }
+        let mut girth: Option<usize> = None;
+        for start in 0..adjacency.len() {
+            let (distance, predecessor) = bfs(&adjacency, start);
+            for u in 0..adjacency.len() {
+                let Some(du) = distance[u] else {
+                    continue;
+                };
+                for v in &adjacency[u] {
+                    //a non-tree edge closes a cycle through start, or a shorter one elsewhere
+                    if predecessor[u] != Some(*v) && predecessor[*v] != Some(u) {
+                        let length = du + distance[*v].unwrap() + 1;
+                        girth = Some(girth.map_or(length, |g| g.min(length)));
+                    }
+                }
+            }
+        }
+        girth
+    }
+
+    /// The length of a shortest directed cycle, or `None` if there are no directed cycles.
+    /// A loop is a cycle of length 1.
+    fn directed_girth(&self) -> Option<usize> {
+        let adjacency = self.out_adjacency();
+        let mut girth: Option<usize> = None;
+        for u in 0..adjacency.len() {
+            //the shortest cycle through u returns to u along an edge v -> u
+            let (distance, _) = bfs(&adjacency, u);
+            for v in 0..adjacency.len() {
+                if let Some(d) = distance[v]
+                    && adjacency[v].contains(&u)
+                {
+                    girth = Some(girth.map_or(d + 1, |g| g.min(d + 1)));
+                }
+            }
+        }
+        girth
+    }
+
+    /// A partition of the vertices into two sets with every edge between them, ignoring the direction of edges.
+    fn bipartition(&self) -> Option<Bipartition<Self>> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = symmetric_adjacency(&self.out_adjacency());
+        let mut side = vec![None; elements.len()];
+        for start in 0..elements.len() {
+            if side[start].is_some() {
+                continue;
+            }
+            side[start] = Some(false);
+            let mut queue = VecDeque::from([start]);
+            while let Some(u) = queue.pop_front() {
+                let s = side[u].unwrap();
+                for v in &adjacency[u] {
+                    match side[*v] {
+                        None => {
+                            side[*v] = Some(!s);
+                            queue.push_back(*v);
+                        }
+                        Some(t) => {
+                            if s == t {
+                                return None;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let (left, right): (Vec<_>, Vec<_>) =
+            (0..elements.len()).partition(|i| side[*i] == Some(false));
+        Some((
+            left.into_iter().map(|i| elements[i].clone()).collect(),
+            right.into_iter().map(|i| elements[i].clone()).collect(),
+        ))
+    }
+
+    fn is_bipartite(&self) -> bool {
+        self.bipartition().is_some()
+    }
+
+    /// An ordering of the vertices such that every edge goes forwards, or `None` if there is a directed cycle.
+    fn topological_sort(&self) -> Option<Vec<Vertex<Self>>> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = self.out_adjacency();
+        let mut in_degree = vec![0; elements.len()];
+        for targets in &adjacency {
+            for v in targets {
+                in_degree[*v] += 1;
+            }
+        }
+        let mut ready = (0..elements.len())
+            .filter(|i| in_degree[*i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = vec![];
+        while let Some(u) = ready.pop_front() {
+            order.push(elements[u].clone());
+            for v in &adjacency[u] {
+                in_degree[*v] -= 1;
+                if in_degree[*v] == 0 {
+                    ready.push_back(*v);
+                }
+            }
+        }
+        if order.len() == elements.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// The vertices `v_0, v_1, ..., v_k` of a directed cycle with edges `v_i -> v_{i+1}` and `v_k -> v_0`, if there is one.
+    fn find_directed_cycle(&self) -> Option<Vec<Vertex<Self>>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            Unvisited,
+            Active,
+            Finished,
+        }
+        let elements = self.vertices().list_all_elements();
+        let adjacency = self.out_adjacency();
+        let mut state = vec![State::Unvisited; elements.len()];
+        let mut parent = vec![None; elements.len()];
+        for root in 0..elements.len() {
+            if state[root] != State::Unvisited {
+                continue;
+            }
+            state[root] = State::Active;
+            let mut call_stack = vec![(root, 0)];
+            while let Some((u, i)) = call_stack.pop() {
+                if i < adjacency[u].len() {
+                    call_stack.push((u, i + 1));
+                    let v = adjacency[u][i];
+                    match state[v] {
+                        State::Unvisited => {
+                            state[v] = State::Active;
+                            parent[v] = Some(u);
+                            call_stack.push((v, 0));
+                        }
+                        State::Active => {
+                            //v is an ancestor of u, so v -> ... -> u -> v is a cycle
+                            let mut cycle = vec![u];
+                            let mut w = u;
+                            while w != v {
+                                w = parent[w].unwrap();
+                                cycle.push(w);
+                            }
+                            cycle.reverse();
+                            return Some(cycle.into_iter().map(|i| elements[i].clone()).collect());
+                        }
+                        State::Finished => {}
+                    }
+                } else {
+                    state[u] = State::Finished;
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether there are no directed cycles.
+    fn is_acyclic(&self) -> bool {
+        self.find_directed_cycle().is_none()
+    }
+
+    /// Shortest paths from `source` using Dijkstra's algorithm.
+    /// Returns `Err` if an edge with negative weight is encountered.
+    fn dijkstra<R: OrderedRingSignature>(
+        &self,
+        source: &Vertex<Self>,
+        ring: &R,
+        weight: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Result<ShortestPaths<Self::Vertices, R::Set>, String> {
+        let elements = self.vertices().list_all_elements();
+        let source = vertex_position(self, &elements, source)?;
+        let adjacency = self.out_adjacency();
+        let n = elements.len();
+        let mut distances: Vec<Option<R::Set>> = vec![None; n];
+        let mut predecessors = vec![None; n];
+        let mut done = vec![false; n];
+        distances[source] = Some(ring.zero());
+        loop {
+            //the closest vertex not yet done
+            let mut closest: Option<usize> = None;
+            for i in 0..n {
+                if done[i] {
+                    continue;
+                }
+                if let Some(d) = &distances[i]
+                    && closest.is_none_or(|c| {
+                        ring.ring_cmp(d, distances[c].as_ref().unwrap()) == Ordering::Less
+                    })
+                {
+                    closest = Some(i);
+                }
+            }
+            let Some(u) = closest else {
+                break;
+            };
+            done[u] = true;
+            let du = distances[u].clone().unwrap();
+            for v in &adjacency[u] {
+                let w = weight(&elements[u], &elements[*v]);
+                if ring.ring_cmp(&w, &ring.zero()) == Ordering::Less {
+                    return Err("Dijkstra's algorithm requires non-negative weights".to_string());
+                }
+                let dv = ring.add(&du, &w);
+                if distances[*v]
+                    .as_ref()
+                    .is_none_or(|old| ring.ring_cmp(&dv, old) == Ordering::Less)
+                {
+                    distances[*v] = Some(dv);
+                    predecessors[*v] = Some(u);
+                }
+            }
+        }
+        Ok(ShortestPaths {
+            vertices: self.vertices().clone(),
+            elements,
+            source,
+            distances,
+            predecessors,
+        })
+    }
+
+    /// Shortest paths from `source` using the Bellman-Ford algorithm, which allows negative weights.
+    /// Returns `Err` if there is a cycle of negative weight reachable from `source`.
+    fn bellman_ford<R: OrderedRingSignature>(
+        &self,
+        source: &Vertex<Self>,
+        ring: &R,
+        weight: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Result<ShortestPaths<Self::Vertices, R::Set>, String> {
+        let elements = self.vertices().list_all_elements();
+        let source = vertex_position(self, &elements, source)?;
+        let adjacency = self.out_adjacency();
+        let n = elements.len();
+        let edges = (0..n)
+            .flat_map(|u| {
+                adjacency[u]
+                    .iter()
+                    .map(move |v| (u, *v))
+                    .collect::<Vec<_>>()
+            })
+            .map(|(u, v)| (u, v, weight(&elements[u], &elements[v])))
+            .collect::<Vec<_>>();
+        let mut distances: Vec<Option<R::Set>> = vec![None; n];
+        let mut predecessors = vec![None; n];
+        distances[source] = Some(ring.zero());
+        //after n rounds without settling there must be a negative cycle
+        for _ in 0..=n {
+            let mut changed = false;
+            for (u, v, w) in &edges {
+                let Some(du) = &distances[*u] else {
+                    continue;
+                };
+                let dv = ring.add(du, w);
+                if distances[*v]
+                    .as_ref()
+                    .is_none_or(|old| ring.ring_cmp(&dv, old) == Ordering::Less)
+                {
+                    distances[*v] = Some(dv);
+                    predecessors[*v] = Some(*u);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(ShortestPaths {
+                    vertices: self.vertices().clone(),
+                    elements,
+                    source,
+                    distances,
+                    predecessors,
+                });
+            }
+        }
+        Err("There is a negative cycle reachable from the source".to_string())
+    }
+
+    /// The distances between every pair of vertices using the Floyd-Warshall algorithm.
+    /// Rows and columns are indexed in the order given by `list_all_elements`, and unreachable pairs are `None`.
+    /// Returns `Err` if there is a cycle of negative weight.
+    fn floyd_warshall<R: OrderedRingSignature>(
+        &self,
+        ring: &R,
+        weight: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Result<Vec<Vec<Option<R::Set>>>, String> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = self.out_adjacency();
+        let n = elements.len();
+        let less = |a: &R::Set, b: &Option<R::Set>| {
+            b.as_ref()
+                .is_none_or(|b| ring.ring_cmp(a, b) == Ordering::Less)
+        };
+        let mut distances: Vec<Vec<Option<R::Set>>> = vec![vec![None; n]; n];
+        for u in 0..n {
+            distances[u][u] = Some(ring.zero());
+            for v in &adjacency[u] {
+                let w = weight(&elements[u], &elements[*v]);
+                if less(&w, &distances[u][*v]) {
+                    distances[u][*v] = Some(w);
+                }
+            }
+        }
+        for k in 0..n {
+            let row_k = distances[k].clone();
+            for row_i in &mut distances {
+                let Some(dik) = row_i[k].clone() else {
+                    continue;
+                };
+                for (dij, dkj) in row_i.iter_mut().zip(&row_k) {
+                    if let Some(dkj) = dkj {
+                        let d = ring.add(&dik, dkj);
+                        if less(&d, dij) {
+                            *dij = Some(d);
+                        }
+                    }
+                }
+            }
+        }
+        for (i, row) in distances.iter().enumerate() {
+            if ring.ring_cmp(row[i].as_ref().unwrap(), &ring.zero()) == Ordering::Less {
+                return Err("There is a negative cycle".to_string());
+            }
+        }
+        Ok(distances)
+    }
+}
+impl<G: FiniteGraphSignature> FiniteGraphAlgorithms for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{
+        CompleteDirectedGraph, CompleteUndirectedGraph, DirectedCycleGraph, UndirectedCycleGraph,
+        WheelGraph,
+    };
+    use crate::graph::{DirectedGraph, UndirectedGraph};
+    use algebraeon_nzq::Integer;
+    use algebraeon_sets::structure::{EnumeratedFiniteSetStructure, MetaType};
+
+    #[test]
+    fn test_closed_forms_of_families() {
+        for n in 3..10 {
+            let c = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(FiniteGraphAlgorithms::girth(&c), Some(c.girth()));
+            assert_eq!(FiniteGraphAlgorithms::diameter(&c), Some(c.diameter()));
+            assert_eq!(FiniteGraphAlgorithms::is_bipartite(&c), c.is_bipartite());
+            assert!(c.is_connected());
+
+            let d = DirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(d.directed_girth(), Some(d.girth()));
+            assert_eq!(FiniteGraphAlgorithms::diameter(&d), Some(d.diameter()));
+            assert_eq!(
+                FiniteGraphAlgorithms::is_strongly_connected(&d),
+                d.is_strongly_connected()
+            );
+            assert!(d.topological_sort().is_none());
+            assert_eq!(d.find_directed_cycle().unwrap().len(), n);
+        }
+        for n in 4..10 {
+            let w = WheelGraph::new(n).unwrap();
+            assert_eq!(FiniteGraphAlgorithms::girth(&w), Some(w.girth()));
+            assert_eq!(FiniteGraphAlgorithms::diameter(&w), Some(w.diameter()));
+            assert!(!w.is_bipartite());
+            assert!(w.is_connected());
+        }
+        let k5 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(5));
+        assert_eq!(k5.girth(), Some(3));
+        assert_eq!(k5.diameter(), Some(1));
+        let k4 = CompleteDirectedGraph::new(EnumeratedFiniteSetStructure::new(4));
+        assert_eq!(k4.directed_girth(), Some(2));
+        assert_eq!(k4.strongly_connected_components().len(), 1);
+    }
+
+    #[test]
+    fn test_traversals() {
+        //0 - 1 - 3
+        //|   |
+        //2 - 4   5
+        let g = UndirectedGraph::from_edges(6, [(0, 1), (0, 2), (1, 3), (1, 4), (2, 4)]).unwrap();
+        assert_eq!(g.bfs_order(&0).unwrap(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(g.dfs_order(&0).unwrap(), vec![0, 1, 3, 4, 2]);
+        assert_eq!(
+            g.bfs_distances(&3).unwrap(),
+            vec![(3, 0), (1, 1), (0, 2), (4, 2), (2, 3)]
+        );
+        assert_eq!(g.shortest_path(&3, &2).unwrap(), Some(vec![3, 1, 0, 2]));
+        assert_eq!(g.shortest_path(&3, &5).unwrap(), None);
+        assert!(g.shortest_path(&3, &6).is_err());
+        assert_eq!(g.connected_components(), vec![vec![0, 1, 2, 3, 4], vec![5]]);
+        assert!(!g.is_connected());
+        assert_eq!(g.diameter(), None);
+        assert_eq!(g.girth(), Some(4));
+        assert_eq!(g.bipartition(), Some((vec![0, 3, 4, 5], vec![1, 2])));
+
+        let tree = UndirectedGraph::from_edges(4, [(0, 1), (0, 2), (0, 3)]).unwrap();
+        assert_eq!(tree.girth(), None);
+        assert_eq!(tree.diameter(), Some(2));
+        let with_loop = UndirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        assert_eq!(with_loop.girth(), Some(1));
+        assert!(!with_loop.is_bipartite());
+    }
+
+    #[test]
+    fn test_directed_structure() {
+        //two 2-cycles joined by an edge, and an isolated vertex
+        let g = DirectedGraph::from_edges(5, [(0, 1), (1, 0), (1, 2), (2, 3), (3, 2)]).unwrap();
+        let mut sccs = g.strongly_connected_components();
+        for scc in &mut sccs {
+            scc.sort_unstable();
+        }
+        sccs.sort();
+        assert_eq!(sccs, vec![vec![0, 1], vec![2, 3], vec![4]]);
+        assert!(!g.is_strongly_connected());
+        assert_eq!(g.connected_components().len(), 2);
+        assert_eq!(g.directed_girth(), Some(2));
+        assert!(!g.is_acyclic());
+
+        let dag = DirectedGraph::from_edges(5, [(3, 1), (1, 0), (3, 2), (2, 0), (4, 3)]).unwrap();
+        assert!(dag.is_acyclic());
+        assert_eq!(dag.directed_girth(), None);
+        let order = dag.topological_sort().unwrap();
+        let position = |v: usize| order.iter().position(|u| *u == v).unwrap();
+        for (s, t) in dag.edges() {
+            assert!(position(s) < position(t));
+        }
+        assert_eq!(dag.strongly_connected_components().len(), 5);
+
+        let mut cyclic = dag.clone();
+        cyclic.add_edge(0, 4).unwrap();
+        let cycle = cyclic.find_directed_cycle().unwrap();
+        for i in 0..cycle.len() {
+            assert!(cyclic.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+    }
+
+    #[test]
+    fn test_weighted_shortest_paths() {
+        let edges = [
+            (0, 1, 4),
+            (0, 2, 1),
+            (2, 1, 2),
+            (1, 3, 1),
+            (2, 3, 5),
+            (3, 4, 3),
+        ];
+        let g = DirectedGraph::from_edges(6, edges.iter().map(|(u, v, _)| (*u, *v))).unwrap();
+        let weight = |u: &usize, v: &usize| {
+            Integer::from(edges.iter().find(|(s, t, _)| s == u && t == v).unwrap().2)
+        };
+        let ring = Integer::structure();
+
+        let paths = g.dijkstra(&0, &ring, weight).unwrap();
+        assert_eq!(paths.source(), &0);
+        assert_eq!(paths.distance(&3), Some(&Integer::from(4)));
+        assert_eq!(paths.path(&4), Some(vec![0, 2, 1, 3, 4]));
+        assert_eq!(paths.distance(&5), None);
+        assert_eq!(paths.path(&5), None);
+
+        let paths = g.bellman_ford(&0, &ring, weight).unwrap();
+        assert_eq!(paths.distance(&4), Some(&Integer::from(7)));
+
+        let all = g.floyd_warshall(&ring, weight).unwrap();
+        assert_eq!(all[0][4], Some(Integer::from(7)));
+        assert_eq!(all[2][4], Some(Integer::from(6)));
+        assert_eq!(all[4][0], None);
+
+        //negative weights
+        let negative = |u: &usize, v: &usize| weight(u, v) - Integer::from(3);
+        assert!(g.dijkstra(&0, &ring, negative).is_err());
+        let paths = g.bellman_ford(&0, &ring, negative).unwrap();
+        assert_eq!(paths.distance(&4), Some(&Integer::from(-5)));
+        assert_eq!(paths.path(&4), Some(vec![0, 2, 1, 3, 4]));
+
+        //a negative cycle
+        let mut h = g.clone();
+        h.add_edge(3, 0).unwrap();
+        let cyclic = |u: &usize, v: &usize| {
+            if (*u, *v) == (3, 0) {
+                Integer::from(-5)
+            } else {
+                weight(u, v)
+            }
+        };
+        assert!(h.bellman_ford(&0, &ring, cyclic).is_err());
+        assert!(h.bellman_ford(&4, &ring, cyclic).is_ok());
+        assert!(h.floyd_warshall(&ring, cyclic).is_err());
+    }
+}
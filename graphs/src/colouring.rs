@@ -0,0 +1,503 @@
+use crate::algorithms::Vertex;
+use crate::structure::{FiniteGraphSignature, UndirectedGraphSignature};
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature};
+
+/// Edges listed by their endpoints, each with a colour.
+pub type EdgeColouring<G> = Vec<((Vertex<G>, Vertex<G>), usize)>;
+
+//whether u and v are joined by an edge in either direction, ignoring loops
+fn simple_adjacency(multiplicities: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    let n = multiplicities.len();
+    (0..n)
+        .map(|u| {
+            (0..n)
+                .map(|v| u != v && (multiplicities[u][v] != 0 || multiplicities[v][u] != 0))
+                .collect()
+        })
+        .collect()
+}
+
+fn complement(adjacency: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let n = adjacency.len();
+    (0..n)
+        .map(|u| (0..n).map(|v| u != v && !adjacency[u][v]).collect())
+        .collect()
+}
+
+//Bron-Kerbosch with pivoting, keeping only cliques larger than the best found so far when `maximum` is set
+fn bron_kerbosch(
+    adjacency: &[Vec<bool>],
+    r: &mut Vec<usize>,
+    p: Vec<usize>,
+    x: Vec<usize>,
+    maximum: bool,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if p.is_empty() && x.is_empty() {
+        if !maximum || cliques.first().is_none_or(|c| r.len() > c.len()) {
+            if maximum {
+                cliques.clear();
+            }
+            cliques.push(r.clone());
+        }
+        return;
+    }
+    if maximum
+        && cliques
+            .first()
+            .is_some_and(|c| r.len() + p.len() <= c.len())
+    {
+        return;
+    }
+    //the pivot with the most neighbours in p leaves the fewest branches
+    let pivot = *p
+        .iter()
+        .chain(x.iter())
+        .max_by_key(|u| p.iter().filter(|v| adjacency[**u][**v]).count())
+        .unwrap();
+    let mut p = p;
+    let mut x = x;
+    let branches = p
+        .iter()
+        .copied()
+        .filter(|v| !adjacency[pivot][*v])
+        .collect::<Vec<_>>();
+    for v in branches {
+        r.push(v);
+        bron_kerbosch(
+            adjacency,
+            r,
+            p.iter().copied().filter(|u| adjacency[v][*u]).collect(),
+            x.iter().copied().filter(|u| adjacency[v][*u]).collect(),
+            maximum,
+            cliques,
+        );
+        r.pop();
+        p.retain(|u| *u != v);
+        x.push(v);
+    }
+}
+
+fn maximum_clique(adjacency: &[Vec<bool>]) -> Vec<usize> {
+    let mut cliques = vec![];
+    bron_kerbosch(
+        adjacency,
+        &mut vec![],
+        (0..adjacency.len()).collect(),
+        vec![],
+        true,
+        &mut cliques,
+    );
+    cliques.pop().unwrap_or_default()
+}
+
+struct Dsatur<'a> {
+    adjacency: &'a [Vec<bool>],
+    colours: Vec<Option<usize>>,
+    best: Option<Vec<usize>>,
+    lower_bound: usize,
+}
+
+impl Dsatur<'_> {
+    fn best_count(&self) -> usize {
+        self.best
+            .as_ref()
+            .map_or(usize::MAX, |b| b.iter().max().map_or(0, |c| c + 1))
+    }
+
+    fn search(&mut self, used: usize) {
+        if used >= self.best_count() || self.best_count() == self.lower_bound {
+            return;
+        }
+        let n = self.adjacency.len();
+        //the uncoloured vertex whose neighbours use the most colours, breaking ties by uncoloured degree
+        let saturation = |v: usize| {
+            let mut seen = (0..n)
+                .filter(|u| self.adjacency[v][*u])
+                .filter_map(|u| self.colours[u])
+                .collect::<Vec<_>>();
+            seen.sort_unstable();
+            seen.dedup();
+            seen.len()
+        };
+        let next = (0..n)
+            .filter(|v| self.colours[*v].is_none())
+            .max_by_key(|v| {
+                (
+                    saturation(*v),
+                    (0..n)
+                        .filter(|u| self.adjacency[*v][*u] && self.colours[*u].is_none())
+                        .count(),
+                )
+            });
+        let Some(v) = next else {
+            self.best = Some(self.colours.iter().map(|c| c.unwrap()).collect());
+            return;
+        };
+        //any of the used colours, or a single new one
+        for c in 0..=used {
+            if c + 1 >= self.best_count() {
+                break;
+            }
+            if (0..n).any(|u| self.adjacency[v][u] && self.colours[u] == Some(c)) {
+                continue;
+            }
+            self.colours[v] = Some(c);
+            self.search(used.max(c + 1));
+            self.colours[v] = None;
+        }
+    }
+}
+
+//an optimal colouring by DSATUR branch and bound
+//the vertices of a maximum clique are coloured first since they need distinct colours anyway
+fn optimal_colouring(adjacency: &[Vec<bool>]) -> Vec<usize> {
+    let clique = maximum_clique(adjacency);
+    let mut search = Dsatur {
+        adjacency,
+        colours: vec![None; adjacency.len()],
+        best: None,
+        lower_bound: clique.len(),
+    };
+    for (c, v) in clique.iter().enumerate() {
+        search.colours[*v] = Some(c);
+    }
+    search.search(clique.len());
+    search.best.unwrap()
+}
+
+/// Colourings, cliques and independent sets of finite graphs.
+///
+/// The direction of edges is ignored, and loops are ignored except that a graph with a loop has no proper vertex colouring.
+/// Colours are `0, 1, ..., k-1` and vertices are listed in the order given by `list_all_elements`.
+pub trait GraphColouring: FiniteGraphSignature {
+    /// Whether every vertex has a colour and adjacent vertices have different colours.
+    fn is_proper_vertex_colouring(&self, colouring: &[(Vertex<Self>, usize)]) -> bool {
+        let multiplicities = self.edge_multiplicities();
+        let elements = self.vertices().list_all_elements();
+        let colour_of = |v: &Vertex<Self>| {
+            colouring
+                .iter()
+                .find(|(u, _)| self.vertices().equal(u, v))
+                .map(|(_, c)| *c)
+        };
+        let Some(colours) = elements.iter().map(colour_of).collect::<Option<Vec<_>>>() else {
+            return false;
+        };
+        let n = elements.len();
+        (0..n).all(|u| (0..n).all(|v| multiplicities[u][v] == 0 || colours[u] != colours[v]))
+    }
+
+    /// A proper colouring of the vertices with as few colours as possible, or `None` if there is a loop.
+    fn optimal_vertex_colouring(&self) -> Option<Vec<(Vertex<Self>, usize)>> {
+        let multiplicities = self.edge_multiplicities();
+        if (0..multiplicities.len()).any(|i| multiplicities[i][i] != 0) {
+            return None;
+        }
+        let colours = optimal_colouring(&simple_adjacency(&multiplicities));
+        Some(
+            self.vertices()
+                .list_all_elements()
+                .into_iter()
+                .zip(colours)
+                .collect(),
+        )
+    }
+
+    /// The least number of colours in a proper colouring of the vertices, or `None` if there is a loop.
+    fn chromatic_number(&self) -> Option<usize> {
+        Some(
+            self.optimal_vertex_colouring()?
+                .into_iter()
+                .map(|(_, c)| c + 1)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    /// All maximal cliques, found using the Bron-Kerbosch algorithm with pivoting.
+    fn maximal_cliques(&self) -> Vec<Vec<Vertex<Self>>> {
+        let elements = self.vertices().list_all_elements();
+        let adjacency = simple_adjacency(&self.edge_multiplicities());
+        let mut cliques = vec![];
+        bron_kerbosch(
+            &adjacency,
+            &mut vec![],
+            (0..elements.len()).collect(),
+            vec![],
+            false,
+            &mut cliques,
+        );
+        cliques
+            .into_iter()
+            .map(|c| c.into_iter().map(|v| elements[v].clone()).collect())
+            .collect()
+    }
+
+    /// A largest set of pairwise adjacent vertices.
+    fn maximum_clique(&self) -> Vec<Vertex<Self>> {
+        let elements = self.vertices().list_all_elements();
+        maximum_clique(&simple_adjacency(&self.edge_multiplicities()))
+            .into_iter()
+            .map(|v| elements[v].clone())
+            .collect()
+    }
+
+    fn clique_number(&self) -> usize {
+        self.maximum_clique().len()
+    }
+
+    /// A largest set of pairwise non-adjacent vertices.
+    fn maximum_independent_set(&self) -> Vec<Vertex<Self>> {
+        let elements = self.vertices().list_all_elements();
+        maximum_clique(&complement(&simple_adjacency(&self.edge_multiplicities())))
+            .into_iter()
+            .map(|v| elements[v].clone())
+            .collect()
+    }
+
+    fn independence_number(&self) -> usize {
+        self.maximum_independent_set().len()
+    }
+
+    /// A partition of the vertices into as few cliques as possible.
+    fn minimum_clique_cover(&self) -> Vec<Vec<Vertex<Self>>> {
+        let elements = self.vertices().list_all_elements();
+        let colours =
+            optimal_colouring(&complement(&simple_adjacency(&self.edge_multiplicities())));
+        let mut cover = vec![vec![]; colours.iter().max().map_or(0, |c| c + 1)];
+        for (v, c) in colours.into_iter().enumerate() {
+            cover[c].push(elements[v].clone());
+        }
+        cover
+    }
+
+    fn clique_cover_number(&self) -> usize {
+        self.minimum_clique_cover().len()
+    }
+
+    /// A colouring of the edges with as few colours as possible such that edges sharing an endpoint have different colours.
+    /// Each edge is listed by its endpoints as many times as its multiplicity.
+    /// Returns `None` if there is a loop.
+    fn optimal_edge_colouring(&self) -> Option<EdgeColouring<Self>>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let multiplicities = self.edge_multiplicities();
+        let n = multiplicities.len();
+        if (0..n).any(|i| multiplicities[i][i] != 0) {
+            return None;
+        }
+        let edges = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .flat_map(|(u, v)| std::iter::repeat_n((u, v), multiplicities[u][v]))
+            .collect::<Vec<_>>();
+        //colour the vertices of the line graph
+        let line_graph = edges
+            .iter()
+            .enumerate()
+            .map(|(i, (a, b))| {
+                edges
+                    .iter()
+                    .enumerate()
+                    .map(|(j, (c, d))| i != j && (a == c || a == d || b == c || b == d))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let colours = optimal_colouring(&line_graph);
+        let elements = self.vertices().list_all_elements();
+        Some(
+            edges
+                .into_iter()
+                .zip(colours)
+                .map(|((u, v), c)| ((elements[u].clone(), elements[v].clone()), c))
+                .collect(),
+        )
+    }
+
+    /// The least number of colours in a proper colouring of the edges, or `None` if there is a loop.
+    fn chromatic_index(&self) -> Option<usize>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        Some(
+            self.optimal_edge_colouring()?
+                .into_iter()
+                .map(|(_, c)| c + 1)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+}
+impl<G: FiniteGraphSignature> GraphColouring for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{CompleteUndirectedGraph, UndirectedCycleGraph, WheelGraph};
+    use crate::graph::{DirectedGraph, UndirectedGraph};
+    use crate::invariants::AlgebraicGraphInvariants;
+    use crate::random::gnm_random_graph;
+    use algebraeon_nzq::{Integer, Rng};
+    use algebraeon_rings::polynomial::Polynomial;
+    use algebraeon_sets::structure::{EnumeratedFiniteSetStructure, MetaType};
+
+    //the Mycielskian of C_5, which is triangle free with chromatic number 4
+    fn grotzsch() -> UndirectedGraph {
+        let mut edges = vec![];
+        for i in 0..5 {
+            edges.push((i, (i + 1) % 5));
+            edges.push((i + 5, (i + 1) % 5));
+            edges.push(((i + 1) % 5 + 5, i));
+            edges.push((i + 5, 10));
+        }
+        UndirectedGraph::from_edges(11, edges).unwrap()
+    }
+
+    fn check_clique(g: &UndirectedGraph, clique: &[usize], independent: bool) {
+        for u in clique {
+            for v in clique {
+                if u != v {
+                    assert_eq!(g.has_edge(*u, *v), !independent);
+                }
+            }
+        }
+    }
+
+    fn check_edge_colouring(g: &UndirectedGraph, colouring: &[((usize, usize), usize)]) {
+        assert_eq!(colouring.len(), g.edge_count());
+        for (i, ((a, b), c)) in colouring.iter().enumerate() {
+            assert!(g.has_edge(*a, *b));
+            for ((x, y), d) in &colouring[i + 1..] {
+                if a == x || a == y || b == x || b == y {
+                    assert_ne!(c, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertex_colouring_closed_forms() {
+        for n in 3..10 {
+            let c = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(
+                GraphColouring::chromatic_number(&c),
+                Some(c.chromatic_number())
+            );
+            let colouring = c.optimal_vertex_colouring().unwrap();
+            assert!(c.is_proper_vertex_colouring(&colouring));
+        }
+        for n in 4..10 {
+            let w = WheelGraph::new(n).unwrap();
+            assert_eq!(
+                GraphColouring::chromatic_number(&w),
+                Some(w.chromatic_number())
+            );
+            assert!(w.is_proper_vertex_colouring(&w.optimal_vertex_colouring().unwrap()));
+        }
+        for n in 0..7 {
+            let k = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            assert_eq!(k.chromatic_number(), Some(n));
+            assert_eq!(k.clique_number(), n);
+            assert_eq!(k.independence_number(), n.min(1));
+        }
+        assert_eq!(petersen().chromatic_number(), Some(3));
+        let g = grotzsch();
+        assert_eq!(g.chromatic_number(), Some(4));
+        assert_eq!(g.clique_number(), 2);
+        assert!(g.is_proper_vertex_colouring(&g.optimal_vertex_colouring().unwrap()));
+
+        assert!(!g.is_proper_vertex_colouring(&[(0, 0)]));
+        assert!(!g.is_proper_vertex_colouring(&(0..11).map(|v| (v, 0)).collect::<Vec<_>>()));
+        let looped = UndirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        assert_eq!(looped.chromatic_number(), None);
+        let directed = DirectedGraph::from_edges(3, [(0, 1), (1, 2), (2, 0)]).unwrap();
+        assert_eq!(directed.chromatic_number(), Some(3));
+    }
+
+    #[test]
+    fn test_cliques_and_independent_sets() {
+        let p = petersen();
+        assert_eq!(p.clique_number(), 2);
+        assert_eq!(p.maximal_cliques().len(), 15);
+        let independent = p.maximum_independent_set();
+        assert_eq!(independent.len(), 4);
+        check_clique(&p, &independent, true);
+        let cover = p.minimum_clique_cover();
+        assert_eq!(cover.len(), 5);
+        for clique in &cover {
+            check_clique(&p, clique, false);
+        }
+        assert_eq!(cover.iter().map(|c| c.len()).sum::<usize>(), 10);
+
+        //two triangles sharing an edge and a pendant vertex
+        let g = UndirectedGraph::from_edges(5, [(0, 1), (1, 2), (2, 0), (1, 3), (2, 3), (3, 4)])
+            .unwrap();
+        let mut cliques = g
+            .maximal_cliques()
+            .into_iter()
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect::<Vec<_>>();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2], vec![1, 2, 3], vec![3, 4]]);
+        assert_eq!(g.clique_number(), 3);
+        assert_eq!(g.independence_number(), 2);
+        assert_eq!(g.clique_cover_number(), 2);
+
+        let c5 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(5), 5).unwrap();
+        assert_eq!(c5.clique_cover_number(), 3);
+        assert_eq!(c5.independence_number(), 2);
+    }
+
+    #[test]
+    fn test_edge_colouring() {
+        for n in 2..8 {
+            let k = UndirectedGraph::from_edges(
+                n,
+                (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))),
+            )
+            .unwrap();
+            let expected = if n % 2 == 0 { n - 1 } else { n };
+            assert_eq!(k.chromatic_index(), Some(expected));
+            check_edge_colouring(&k, &k.optimal_edge_colouring().unwrap());
+        }
+        //the Petersen graph is cubic but needs 4 colours
+        let p = petersen();
+        assert_eq!(p.chromatic_index(), Some(4));
+        check_edge_colouring(&p, &p.optimal_edge_colouring().unwrap());
+        //a triangle with a doubled edge needs 4 colours with maximum degree 3
+        let shannon = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (2, 0), (0, 1)]).unwrap();
+        assert_eq!(shannon.chromatic_index(), Some(4));
+        check_edge_colouring(&shannon, &shannon.optimal_edge_colouring().unwrap());
+        let looped = UndirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        assert_eq!(looped.chromatic_index(), None);
+    }
+
+    #[test]
+    fn test_against_chromatic_polynomial() {
+        let poly_ring = Polynomial::<Integer>::structure();
+        let mut rng = Rng::new(987654321);
+        for n in 1..=8 {
+            let pairs = n * (n - 1) / 2;
+            for i in 0..4 {
+                let g = gnm_random_graph(n, (i * pairs / 3).min(20), &mut rng).unwrap();
+                let p = g.chromatic_polynomial();
+                let k = g.chromatic_number().unwrap();
+                assert!(g.is_proper_vertex_colouring(&g.optimal_vertex_colouring().unwrap()));
+                assert_ne!(poly_ring.evaluate(&p, &Integer::from(k)), Integer::ZERO);
+                if k > 0 {
+                    assert_eq!(poly_ring.evaluate(&p, &Integer::from(k - 1)), Integer::ZERO);
+                }
+                let clique = g.maximum_clique();
+                check_clique(&g, &clique, false);
+                assert!(clique.len() <= k);
+                check_clique(&g, &g.maximum_independent_set(), true);
+                check_edge_colouring(&g, &g.optimal_edge_colouring().unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use algebraeon_groups::structure::GroupSignature;
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature, SetSignature};
+
+use crate::structure::{FiniteGraphSignature, GraphSignature};
+
+/// The Cayley graph of a group with respect to a list of generators, whose vertices are the elements of
+/// the group with an edge from `g` to `gs` for each generator `s`.
+///
+/// When the generators are closed under taking inverses the edges come in opposite pairs,
+/// so the graph may be regarded as undirected.
+#[derive(Debug, Clone)]
+pub struct CayleyGraph<Group: GroupSignature> {
+    group: Group,
+    generators: Vec<Group::Set>,
+}
+
+impl<Group: GroupSignature> CayleyGraph<Group> {
+    pub fn new(group: Group, generators: Vec<Group::Set>) -> Result<Self, String> {
+        for s in &generators {
+            group
+                .is_element(s)
+                .map_err(|e| format!("Generator is not an element of the group: {e}"))?;
+        }
+        Ok(Self { group, generators })
+    }
+
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    pub fn generators(&self) -> &Vec<Group::Set> {
+        &self.generators
+    }
+}
+
+impl<Group: GroupSignature + EqSignature> CayleyGraph<Group> {
+    /// Whether the inverse of every generator is also a generator.
+    pub fn is_undirected(&self) -> bool {
+        self.generators.iter().all(|s| {
+            let inverse = self.group.inverse(s);
+            self.generators
+                .iter()
+                .any(|t| self.group.equal(t, &inverse))
+        })
+    }
+}
+
+impl<Group: GroupSignature + EqSignature> GraphSignature for CayleyGraph<Group> {
+    type Vertices = Group;
+
+    fn has_directed_edge(
+        &self,
+        source: &<Self::Vertices as SetSignature>::Set,
+        target: &<Self::Vertices as SetSignature>::Set,
+    ) -> Result<(), String> {
+        if let Err(e) = self.group.is_element(source) {
+            return Err(format!("Source is not an element of Vertices: {e}"));
+        }
+        if let Err(e) = self.group.is_element(target) {
+            return Err(format!("Target is not an element of Vertices: {e}"));
+        }
+        if self
+            .generators
+            .iter()
+            .any(|s| self.group.equal(&self.group.compose(source, s), target))
+        {
+            Ok(())
+        } else {
+            Err("The target is not the source times a generator".to_string())
+        }
+    }
+}
+
+impl<Group: GroupSignature + FiniteSetSignature + EqSignature> FiniteGraphSignature
+    for CayleyGraph<Group>
+{
+    fn vertices(&self) -> &Self::Vertices {
+        &self.group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::FiniteGraphAlgorithms;
+    use crate::examples::UndirectedCycleGraph;
+    use crate::isomorphism::FiniteGraphIsomorphism;
+    use algebraeon_groups::permutation::{Permutation, SymmetricGroupStructure};
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    #[test]
+    fn test_cayley_graph() {
+        let s3 = SymmetricGroupStructure::new(3);
+        let transpositions = vec![
+            Permutation::new(vec![1, 0, 2]).unwrap(),
+            Permutation::new(vec![0, 2, 1]).unwrap(),
+        ];
+        let g = CayleyGraph::new(s3, transpositions).unwrap();
+        assert!(g.is_undirected());
+        let c6 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(6), 6).unwrap();
+        assert!(g.is_isomorphic_to(&c6));
+
+        //the permutohedron of order 4
+        let s4 = SymmetricGroupStructure::new(4);
+        let adjacent_transpositions = (0..3)
+            .map(|i| {
+                let mut images = vec![0, 1, 2, 3];
+                images.swap(i, i + 1);
+                Permutation::new(images).unwrap()
+            })
+            .collect();
+        let g = CayleyGraph::new(s4, adjacent_transpositions).unwrap();
+        assert!(g.is_bipartite());
+        assert_eq!(g.diameter(), Some(6));
+
+        let rotation = Permutation::new(vec![1, 2, 3, 0]).unwrap();
+        let g = CayleyGraph::new(SymmetricGroupStructure::new(4), vec![rotation]).unwrap();
+        assert!(!g.is_undirected());
+        assert_eq!(g.strongly_connected_components().len(), 6);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::structure::{GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature};
+use crate::structure::{
+    FiniteGraphSignature, GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature,
+};
 use algebraeon_sets::structure::{
-    EqSignature, PairsStructure, SetSignature, UnorderedPair, UnorderedPairs,
+    EqSignature, FiniteSetSignature, PairsStructure, SetSignature, UnorderedPair, UnorderedPairs,
 };
 
 pub struct CompleteDirectedGraph<Vertices: SetSignature> {
@@ -39,6 +41,14 @@ impl<Vertices: SetSignature + EqSignature> GraphSignature for CompleteDirectedGr
     }
 }
 
+impl<Vertices: FiniteSetSignature + EqSignature> FiniteGraphSignature
+    for CompleteDirectedGraph<Vertices>
+{
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
 impl<Vertices: SetSignature + EqSignature> LooplessGraphSignature
     for CompleteDirectedGraph<Vertices>
 {
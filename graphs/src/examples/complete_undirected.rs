@@ -1,7 +1,10 @@
-use algebraeon_sets::structure::{EqSignature, SetSignature, UnorderedPair, UnorderedPairs};
+use algebraeon_sets::structure::{
+    EqSignature, FiniteSetSignature, SetSignature, UnorderedPair, UnorderedPairs,
+};
 
 use crate::structure::{
-    GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature, UndirectedGraphSignature,
+    FiniteGraphSignature, GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature,
+    UndirectedGraphSignature,
 };
 
 #[allow(dead_code)]
@@ -43,6 +46,14 @@ impl<Vertices: SetSignature + EqSignature> GraphSignature for CompleteUndirected
     }
 }
 
+impl<Vertices: FiniteSetSignature + EqSignature> FiniteGraphSignature
+    for CompleteUndirectedGraph<Vertices>
+{
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
 impl<Vertices: SetSignature + EqSignature> LooplessGraphSignature
     for CompleteUndirectedGraph<Vertices>
 {
@@ -1,7 +1,8 @@
 use algebraeon_sets::structure::{SetSignature, UnorderedPair, UnorderedPairs};
 
 use crate::structure::{
-    GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature, UndirectedGraphSignature,
+    FiniteGraphSignature, GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature,
+    UndirectedGraphSignature,
 };
 
 /// An undirected cycle graph with n vertices arranged in a cycle.
@@ -80,6 +81,12 @@ impl GraphSignature for UndirectedCycleGraph<EnumeratedFiniteSetStructure> {
     }
 }
 
+impl FiniteGraphSignature for UndirectedCycleGraph<EnumeratedFiniteSetStructure> {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
 impl LooplessGraphSignature for UndirectedCycleGraph<EnumeratedFiniteSetStructure> {}
 
 impl UndirectedGraphSignature for UndirectedCycleGraph<EnumeratedFiniteSetStructure> {}
@@ -164,6 +171,12 @@ impl GraphSignature for DirectedCycleGraph<EnumeratedFiniteSetStructure> {
     }
 }
 
+impl FiniteGraphSignature for DirectedCycleGraph<EnumeratedFiniteSetStructure> {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
 impl LooplessGraphSignature for DirectedCycleGraph<EnumeratedFiniteSetStructure> {}
 
 #[cfg(test)]
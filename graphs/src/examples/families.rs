@@ -0,0 +1,123 @@
+use crate::graph::UndirectedGraph;
+
+/// The Petersen graph, with the outer cycle `0, 1, 2, 3, 4`, the spokes `i -- i+5`
+/// and the inner pentagram `5, 7, 9, 6, 8`.
+pub fn petersen_graph() -> UndirectedGraph {
+    UndirectedGraph::from_edges(
+        10,
+        (0..5).flat_map(|i| [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)]),
+    )
+    .unwrap()
+}
+
+/// The path `P_n` on the vertices `0, 1, ..., n-1` in order.
+pub fn path_graph(n: usize) -> UndirectedGraph {
+    UndirectedGraph::from_edges(n, (1..n).map(|i| (i - 1, i))).unwrap()
+}
+
+/// The star `K_{1,n}` with center `0` and leaves `1, 2, ..., n`.
+pub fn star_graph(n: usize) -> UndirectedGraph {
+    UndirectedGraph::from_edges(n + 1, (1..=n).map(|i| (0, i))).unwrap()
+}
+
+/// The hypercube `Q_d` whose vertices `0, 1, ..., 2^d-1` are adjacent when their binary expansions differ in one bit.
+pub fn hypercube_graph(d: usize) -> UndirectedGraph {
+    UndirectedGraph::from_edges(
+        1 << d,
+        (0..1usize << d).flat_map(|u| {
+            (0..d)
+                .map(move |i| (u, u ^ (1 << i)))
+                .filter(|(u, v)| u < v)
+        }),
+    )
+    .unwrap()
+}
+
+/// The complete bipartite graph `K_{m,n}` with parts `0, ..., m-1` and `m, ..., m+n-1`.
+pub fn complete_bipartite_graph(m: usize, n: usize) -> UndirectedGraph {
+    UndirectedGraph::from_edges(m + n, (0..m).flat_map(|i| (m..m + n).map(move |j| (i, j))))
+        .unwrap()
+}
+
+/// The `m` by `n` grid graph, where the vertex `i * n + j` in row `i` and column `j` is adjacent to its
+/// horizontal and vertical neighbours.
+pub fn grid_graph(m: usize, n: usize) -> UndirectedGraph {
+    let mut edges = vec![];
+    for i in 0..m {
+        for j in 0..n {
+            if j + 1 < n {
+                edges.push((i * n + j, i * n + j + 1));
+            }
+            if i + 1 < m {
+                edges.push((i * n + j, (i + 1) * n + j));
+            }
+        }
+    }
+    UndirectedGraph::from_edges(m * n, edges).unwrap()
+}
+
+/// The `m` by `n` torus graph, which is the grid graph with the rows and columns wrapping around.
+/// Requires `m >= 3` and `n >= 3` so that the graph is simple.
+pub fn torus_graph(m: usize, n: usize) -> Result<UndirectedGraph, String> {
+    if m < 3 || n < 3 {
+        return Err("Torus graphs require at least 3 rows and 3 columns".to_string());
+    }
+    let mut edges = vec![];
+    for i in 0..m {
+        for j in 0..n {
+            edges.push((i * n + j, i * n + (j + 1) % n));
+            edges.push((i * n + j, ((i + 1) % m) * n + j));
+        }
+    }
+    UndirectedGraph::from_edges(m * n, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::FiniteGraphAlgorithms;
+    use crate::colouring::GraphColouring;
+    use crate::isomorphism::FiniteGraphIsomorphism;
+    use algebraeon_nzq::Natural;
+
+    #[test]
+    fn test_families() {
+        let p = petersen_graph();
+        assert_eq!(p.edge_count(), 15);
+        assert_eq!(FiniteGraphAlgorithms::girth(&p), Some(5));
+        assert_eq!(p.automorphism_group_order(), Natural::from(120u32));
+
+        assert_eq!(path_graph(0).vertex_count(), 0);
+        assert_eq!(path_graph(5).edge_count(), 4);
+        assert_eq!(FiniteGraphAlgorithms::diameter(&path_graph(5)), Some(4));
+
+        let star = star_graph(4);
+        assert_eq!(star.degree(0), 4);
+        assert!(star.is_isomorphic_to(&complete_bipartite_graph(4, 1)));
+
+        let q3 = hypercube_graph(3);
+        assert_eq!((q3.vertex_count(), q3.edge_count()), (8, 12));
+        assert!(FiniteGraphAlgorithms::is_bipartite(&q3));
+        assert_eq!(q3.automorphism_group_order(), Natural::from(48u32));
+
+        let k33 = complete_bipartite_graph(3, 3);
+        assert_eq!(k33.edge_count(), 9);
+        assert_eq!(k33.automorphism_group_order(), Natural::from(72u32));
+
+        let grid = grid_graph(3, 4);
+        assert_eq!(grid.edge_count(), 17);
+        assert_eq!(FiniteGraphAlgorithms::diameter(&grid), Some(5));
+        assert!(grid_graph(2, 2).is_isomorphic_to(&hypercube_graph(2)));
+
+        let torus = torus_graph(3, 4).unwrap();
+        assert_eq!(torus.edge_count(), 24);
+        assert!((0..12).all(|v| torus.degree(v) == 4));
+        assert_eq!(torus.chromatic_number(), Some(3));
+        assert!(
+            torus_graph(4, 4)
+                .unwrap()
+                .is_isomorphic_to(&hypercube_graph(4))
+        );
+        assert!(torus_graph(2, 4).is_err());
+    }
+}
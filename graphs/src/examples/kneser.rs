@@ -0,0 +1,96 @@
+use algebraeon_sets::combinatorics::subsets;
+use algebraeon_sets::structure::{EnumeratedFiniteSetStructure, SetSignature};
+
+use crate::structure::{
+    FiniteGraphSignature, GraphSignature, LooplessGraphSignature, UndirectedGraphSignature,
+};
+
+/// The Kneser graph `KG(n, k)` whose vertices are the `k` element subsets of `{0, ..., n-1}`,
+/// with two adjacent when they are disjoint.
+/// The vertices are numbered in the order the subsets are listed by `subsets(n, k)`.
+#[derive(Debug, Clone)]
+pub struct KneserGraph {
+    vertices: EnumeratedFiniteSetStructure,
+    subsets: Vec<Vec<usize>>,
+}
+
+impl KneserGraph {
+    pub fn new(n: usize, k: usize) -> Result<Self, String> {
+        if k > n {
+            return Err("Kneser graphs require k <= n".to_string());
+        }
+        let subsets = subsets(n, k).collect::<Vec<_>>();
+        Ok(Self {
+            vertices: EnumeratedFiniteSetStructure::new(subsets.len()),
+            subsets,
+        })
+    }
+
+    /// The subset corresponding to a vertex.
+    pub fn subset(&self, v: usize) -> &Vec<usize> {
+        &self.subsets[v]
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.subsets.len()
+    }
+}
+
+impl GraphSignature for KneserGraph {
+    type Vertices = EnumeratedFiniteSetStructure;
+
+    fn has_directed_edge(
+        &self,
+        source: &<Self::Vertices as SetSignature>::Set,
+        target: &<Self::Vertices as SetSignature>::Set,
+    ) -> Result<(), String> {
+        if let Err(e) = self.vertices.is_element(source) {
+            return Err(format!("Source is not an element of Vertices: {e}"));
+        }
+        if let Err(e) = self.vertices.is_element(target) {
+            return Err(format!("Target is not an element of Vertices: {e}"));
+        }
+        if source == target {
+            return Err("Kneser graphs do not contain self-loops".to_string());
+        }
+        let (a, b) = (&self.subsets[*source], &self.subsets[*target]);
+        if a.iter().any(|x| b.contains(x)) {
+            Err(format!("The subsets {a:?} and {b:?} are not disjoint"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl FiniteGraphSignature for KneserGraph {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
+impl LooplessGraphSignature for KneserGraph {}
+
+impl UndirectedGraphSignature for KneserGraph {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colouring::GraphColouring;
+    use crate::examples::CompleteUndirectedGraph;
+    use crate::examples::families::petersen_graph;
+    use crate::isomorphism::FiniteGraphIsomorphism;
+
+    #[test]
+    fn test_kneser_graph() {
+        let kg = KneserGraph::new(5, 2).unwrap();
+        assert_eq!(kg.vertex_count(), 10);
+        assert!(kg.is_isomorphic_to(&petersen_graph()));
+        assert_eq!(kg.subset(0).len(), 2);
+
+        //KG(n, 1) is the complete graph and Lovász's theorem gives chromatic number n - 2k + 2
+        let k5 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(5));
+        assert!(KneserGraph::new(5, 1).unwrap().is_isomorphic_to(&k5));
+        assert_eq!(KneserGraph::new(6, 2).unwrap().chromatic_number(), Some(4));
+        assert!(KneserGraph::new(2, 3).is_err());
+    }
+}
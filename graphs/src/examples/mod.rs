@@ -1,9 +1,20 @@
+pub mod cayley;
 pub mod complete_directed;
 pub mod complete_undirected;
 pub mod cycle;
+pub mod families;
+pub mod kneser;
+pub mod paley;
 pub mod wheel;
 
+pub use cayley::CayleyGraph;
 pub use complete_directed::CompleteDirectedGraph;
 pub use complete_undirected::CompleteUndirectedGraph;
 pub use cycle::{DirectedCycleGraph, UndirectedCycleGraph};
+pub use families::{
+    complete_bipartite_graph, grid_graph, hypercube_graph, path_graph, petersen_graph, star_graph,
+    torus_graph,
+};
+pub use kneser::KneserGraph;
+pub use paley::PaleyGraph;
 pub use wheel::WheelGraph;
@@ -0,0 +1,112 @@
+use algebraeon_nzq::Natural;
+use algebraeon_rings::structure::{AdditiveMonoidEqSignature, FiniteFieldSignature};
+use algebraeon_sets::structure::{EqSignature, SetSignature};
+
+use crate::structure::{
+    FiniteGraphSignature, GraphSignature, LooplessGraphSignature, UndirectedGraphSignature,
+};
+
+/// The Paley graph of a finite field of order `q = 1 mod 4`, whose vertices are the elements of the field
+/// with two adjacent when their difference is a non-zero square.
+#[derive(Debug, Clone)]
+pub struct PaleyGraph<Field: FiniteFieldSignature> {
+    field: Field,
+    //a non-zero element is a square exactly when its power (q - 1) / 2 is one
+    half_order: Natural,
+}
+
+impl<Field: FiniteFieldSignature> PaleyGraph<Field> {
+    pub fn new(field: Field) -> Result<Self, String> {
+        let q = field.size();
+        if q % 4 != 1 {
+            return Err("Paley graphs require a field of order 1 mod 4".to_string());
+        }
+        Ok(Self {
+            field,
+            half_order: Natural::from((q - 1) / 2),
+        })
+    }
+
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.field.size()
+    }
+
+    /// Paley graphs are `(q-1)/2` regular.
+    pub fn degree(&self) -> usize {
+        (self.field.size() - 1) / 2
+    }
+}
+
+impl<Field: FiniteFieldSignature + EqSignature> GraphSignature for PaleyGraph<Field> {
+    type Vertices = Field;
+
+    fn has_directed_edge(
+        &self,
+        source: &<Self::Vertices as SetSignature>::Set,
+        target: &<Self::Vertices as SetSignature>::Set,
+    ) -> Result<(), String> {
+        if let Err(e) = self.field.is_element(source) {
+            return Err(format!("Source is not an element of Vertices: {e}"));
+        }
+        if let Err(e) = self.field.is_element(target) {
+            return Err(format!("Target is not an element of Vertices: {e}"));
+        }
+        let difference = self.field.sub(source, target);
+        if self.field.is_zero(&difference) {
+            return Err("Paley graphs do not contain self-loops".to_string());
+        }
+        if self.field.equal(
+            &self.field.nat_pow(&difference, &self.half_order),
+            &self.field.one(),
+        ) {
+            Ok(())
+        } else {
+            Err("The difference of the vertices is not a square".to_string())
+        }
+    }
+}
+
+impl<Field: FiniteFieldSignature + EqSignature> FiniteGraphSignature for PaleyGraph<Field> {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.field
+    }
+}
+
+impl<Field: FiniteFieldSignature + EqSignature> LooplessGraphSignature for PaleyGraph<Field> {}
+
+impl<Field: FiniteFieldSignature + EqSignature> UndirectedGraphSignature for PaleyGraph<Field> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::UndirectedCycleGraph;
+    use crate::graph::UndirectedGraph;
+    use crate::isomorphism::FiniteGraphIsomorphism;
+    use algebraeon_rings::finite_fields::conway_finite_fields::ConwayFiniteFieldStructure;
+    use algebraeon_rings::finite_fields::modulo::Modulo;
+    use algebraeon_sets::structure::{EnumeratedFiniteSetStructure, MetaType};
+
+    #[test]
+    fn test_paley_graph() {
+        let p5 = PaleyGraph::new(Modulo::<5>::structure()).unwrap();
+        let c5 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(5), 5).unwrap();
+        assert!(p5.is_isomorphic_to(&c5));
+        assert!(PaleyGraph::new(Modulo::<7>::structure()).is_err());
+
+        //Paley graphs are self-complementary
+        let p13 = PaleyGraph::new(Modulo::<13>::structure()).unwrap();
+        let g = UndirectedGraph::from_finite_graph(&p13).unwrap();
+        assert_eq!(g.edge_count(), 13 * p13.degree() / 2);
+        assert!(g.is_isomorphic_to(&g.complement()));
+
+        //the Paley graph of order 9 is the 3 by 3 rook's graph
+        let p9 = PaleyGraph::new(ConwayFiniteFieldStructure::new(3, 2).unwrap()).unwrap();
+        let g = UndirectedGraph::from_finite_graph(&p9).unwrap();
+        let k3 = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (0, 2)]).unwrap();
+        assert!(g.is_isomorphic_to(&k3.cartesian_product(&k3)));
+    }
+}
@@ -3,7 +3,8 @@ use algebraeon_sets::structure::{
 };
 
 use crate::structure::{
-    GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature, UndirectedGraphSignature,
+    FiniteGraphSignature, GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature,
+    UndirectedGraphSignature,
 };
 
 /// A wheel graph `W_n` with vertices `{0, …, n-1}` where vertex `0` is the center
@@ -104,6 +105,12 @@ impl GraphSignature for WheelGraph {
     }
 }
 
+impl FiniteGraphSignature for WheelGraph {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+}
+
 impl LooplessGraphSignature for WheelGraph {}
 
 impl UndirectedGraphSignature for WheelGraph {}
@@ -0,0 +1,394 @@
+use crate::algorithms::Vertex;
+use crate::structure::FiniteGraphSignature;
+use algebraeon_rings::structure::OrderedRingSignature;
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature, SetSignature};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// A maximum flow from a source to a sink together with a minimum cut.
+#[derive(Debug, Clone)]
+pub struct MaximumFlow<S: SetSignature, W> {
+    value: W,
+    edge_flows: Vec<(S::Set, S::Set, W)>,
+    source_side: Vec<S::Set>,
+    sink_side: Vec<S::Set>,
+    cut_edges: Vec<(S::Set, S::Set)>,
+}
+
+impl<S: SetSignature, W> MaximumFlow<S, W> {
+    /// The total flow leaving the source.
+    pub fn value(&self) -> &W {
+        &self.value
+    }
+
+    /// The positive flow along each edge `(u, v)`.
+    pub fn edge_flows(&self) -> &Vec<(S::Set, S::Set, W)> {
+        &self.edge_flows
+    }
+
+    /// A minimum cut as the vertices on the side of the source and the vertices on the side of the sink.
+    /// The source side is the set of vertices reachable from the source in the residual graph.
+    pub fn minimum_cut(&self) -> (&Vec<S::Set>, &Vec<S::Set>) {
+        (&self.source_side, &self.sink_side)
+    }
+
+    /// The edges from the source side to the sink side of the minimum cut.
+    /// Their total capacity equals the value of the flow.
+    pub fn cut_edges(&self) -> &Vec<(S::Set, S::Set)> {
+        &self.cut_edges
+    }
+}
+
+struct Residual<'a, R: OrderedRingSignature> {
+    ring: &'a R,
+    //for each arc its head, its remaining capacity and its original capacity; arc a ^ 1 is the reverse of arc a
+    head: Vec<usize>,
+    remaining: Vec<R::Set>,
+    capacity: Vec<R::Set>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl<R: OrderedRingSignature> Residual<'_, R> {
+    fn is_positive(&self, a: &R::Set) -> bool {
+        self.ring.ring_cmp(a, &self.ring.zero()) == Ordering::Greater
+    }
+
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.arcs.len()];
+        level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for a in &self.arcs[u] {
+                let v = self.head[*a];
+                if level[v].is_none() && self.is_positive(&self.remaining[*a]) {
+                    level[v] = Some(level[u].unwrap() + 1);
+                    queue.push_back(v);
+                }
+            }
+        }
+        level
+    }
+
+    //push flow from u towards the sink along arcs increasing the level, at most limit
+    fn push(
+        &mut self,
+        u: usize,
+        sink: usize,
+        limit: R::Set,
+        level: &[Option<usize>],
+        next_arc: &mut [usize],
+    ) -> R::Set {
+        if u == sink {
+            return limit;
+        }
+        while next_arc[u] < self.arcs[u].len() {
+            let a = self.arcs[u][next_arc[u]];
+            let v = self.head[a];
+            if level[v] == level[u].map(|l| l + 1) && self.is_positive(&self.remaining[a]) {
+                let bound = if self.ring.ring_cmp(&self.remaining[a], &limit) == Ordering::Less {
+                    self.remaining[a].clone()
+                } else {
+                    limit.clone()
+                };
+                let pushed = self.push(v, sink, bound, level, next_arc);
+                if self.is_positive(&pushed) {
+                    self.remaining[a] = self.ring.sub(&self.remaining[a], &pushed);
+                    self.remaining[a ^ 1] = self.ring.add(&self.remaining[a ^ 1], &pushed);
+                    return pushed;
+                }
+            }
+            next_arc[u] += 1;
+        }
+        self.ring.zero()
+    }
+}
+
+/// Network flows on finite graphs.
+pub trait GraphFlow: FiniteGraphSignature {
+    /// A flow of largest value from `source` to `sink`, computed by Dinic's algorithm.
+    ///
+    /// Each edge `u -> v` has capacity `capacity(u, v)`, which must not be negative.
+    /// An undirected edge may carry flow in either direction, up to its capacity in each.
+    /// Multiple edges between the same vertices count as one edge and loops are ignored.
+    ///
+    /// Returns `Err` if `source` or `sink` is not a vertex, if they are equal, or if a capacity is negative.
+    fn maximum_flow<R: OrderedRingSignature>(
+        &self,
+        source: &Vertex<Self>,
+        sink: &Vertex<Self>,
+        ring: &R,
+        capacity: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Result<MaximumFlow<Self::Vertices, R::Set>, String> {
+        let elements = self.vertices().list_all_elements();
+        let position = |v: &Vertex<Self>| elements.iter().position(|u| self.vertices().equal(u, v));
+        let s = position(source).ok_or_else(|| "The source is not a vertex".to_string())?;
+        let t = position(sink).ok_or_else(|| "The sink is not a vertex".to_string())?;
+        if s == t {
+            return Err("The source and the sink must be different".to_string());
+        }
+
+        let mut residual = Residual {
+            ring,
+            head: vec![],
+            remaining: vec![],
+            capacity: vec![],
+            arcs: vec![vec![]; elements.len()],
+        };
+        for (u, targets) in self.out_adjacency().into_iter().enumerate() {
+            for v in targets {
+                if u == v {
+                    continue;
+                }
+                let c = capacity(&elements[u], &elements[v]);
+                if ring.ring_cmp(&c, &ring.zero()) == Ordering::Less {
+                    return Err("Capacities must not be negative".to_string());
+                }
+                residual.arcs[u].push(residual.head.len());
+                residual.head.push(v);
+                residual.remaining.push(c.clone());
+                residual.capacity.push(c);
+                residual.arcs[v].push(residual.head.len());
+                residual.head.push(u);
+                residual.remaining.push(ring.zero());
+                residual.capacity.push(ring.zero());
+            }
+        }
+
+        let mut value = ring.zero();
+        loop {
+            let level = residual.levels(s);
+            if level[t].is_none() {
+                break;
+            }
+            //the total capacity out of the source bounds every augmenting path
+            let limit = residual.arcs[s]
+                .iter()
+                .map(|a| residual.remaining[*a].clone())
+                .fold(ring.zero(), |x, y| ring.add(&x, &y));
+            let mut next_arc = vec![0; elements.len()];
+            loop {
+                let pushed = residual.push(s, t, limit.clone(), &level, &mut next_arc);
+                if !residual.is_positive(&pushed) {
+                    break;
+                }
+                value = ring.add(&value, &pushed);
+            }
+        }
+
+        //the net flow from u to v, combining the arcs u -> v and v -> u
+        let mut net = vec![vec![ring.zero(); elements.len()]; elements.len()];
+        for a in (0..residual.head.len()).step_by(2) {
+            let (u, v) = (residual.head[a + 1], residual.head[a]);
+            let flow = ring.sub(&residual.capacity[a], &residual.remaining[a]);
+            net[u][v] = ring.add(&net[u][v], &flow);
+            net[v][u] = ring.sub(&net[v][u], &flow);
+        }
+        let mut edge_flows = vec![];
+        for (u, row) in net.into_iter().enumerate() {
+            for (v, flow) in row.into_iter().enumerate() {
+                if residual.is_positive(&flow) {
+                    edge_flows.push((elements[u].clone(), elements[v].clone(), flow));
+                }
+            }
+        }
+
+        let reachable = residual
+            .levels(s)
+            .into_iter()
+            .map(|l| l.is_some())
+            .collect::<Vec<_>>();
+        let mut cut_edges = vec![];
+        for (u, arcs) in residual.arcs.iter().enumerate() {
+            for a in arcs {
+                let v = residual.head[*a];
+                if a % 2 == 0 && reachable[u] && !reachable[v] {
+                    cut_edges.push((elements[u].clone(), elements[v].clone()));
+                }
+            }
+        }
+        let (source_side, sink_side) =
+            (0..elements.len()).partition::<Vec<_>, _>(|u| reachable[*u]);
+        Ok(MaximumFlow {
+            value,
+            edge_flows,
+            source_side: source_side
+                .into_iter()
+                .map(|u| elements[u].clone())
+                .collect(),
+            sink_side: sink_side.into_iter().map(|u| elements[u].clone()).collect(),
+            cut_edges,
+        })
+    }
+
+    /// The value of a maximum flow, which equals the capacity of a minimum cut.
+    fn minimum_cut_capacity<R: OrderedRingSignature>(
+        &self,
+        source: &Vertex<Self>,
+        sink: &Vertex<Self>,
+        ring: &R,
+        capacity: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Result<R::Set, String> {
+        Ok(self
+            .maximum_flow(source, sink, ring, capacity)?
+            .value()
+            .clone())
+    }
+}
+impl<G: FiniteGraphSignature> GraphFlow for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DirectedGraph, UndirectedGraph};
+    use crate::random::gnp_random_graph;
+    use algebraeon_nzq::{Integer, Rational, Rng};
+    use algebraeon_sets::structure::MetaType;
+
+    //the example network from Cormen, Leiserson, Rivest and Stein
+    fn clrs() -> (DirectedGraph, Vec<Vec<i64>>) {
+        let arcs = [
+            (0, 1, 16),
+            (0, 2, 13),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ];
+        let mut capacity = vec![vec![0; 6]; 6];
+        for (u, v, c) in arcs {
+            capacity[u][v] = c;
+        }
+        let graph = DirectedGraph::from_edges(6, arcs.iter().map(|(u, v, _)| (*u, *v))).unwrap();
+        (graph, capacity)
+    }
+
+    #[test]
+    fn test_maximum_flow() {
+        let (g, capacity) = clrs();
+        let ring = Integer::structure();
+        let flow = g
+            .maximum_flow(&0, &5, &ring, |u, v| Integer::from(capacity[*u][*v]))
+            .unwrap();
+        assert_eq!(flow.value(), &Integer::from(23));
+
+        //conservation at internal vertices and capacity constraints
+        for w in 1..5 {
+            let net = flow
+                .edge_flows()
+                .iter()
+                .map(|(u, v, f)| {
+                    if *u == w {
+                        f.clone()
+                    } else if *v == w {
+                        -f.clone()
+                    } else {
+                        Integer::from(0)
+                    }
+                })
+                .fold(Integer::from(0), |a, b| a + b);
+            assert_eq!(net, Integer::from(0));
+        }
+        for (u, v, f) in flow.edge_flows() {
+            assert!(f <= &Integer::from(capacity[*u][*v]));
+        }
+
+        let (source_side, sink_side) = flow.minimum_cut();
+        assert!(source_side.contains(&0) && sink_side.contains(&5));
+        let cut = flow
+            .cut_edges()
+            .iter()
+            .map(|(u, v)| capacity[*u][*v])
+            .sum::<i64>();
+        assert_eq!(cut, 23);
+
+        assert!(
+            g.maximum_flow(&0, &0, &ring, |_, _| Integer::from(1))
+                .is_err()
+        );
+        assert!(
+            g.maximum_flow(&0, &6, &ring, |_, _| Integer::from(1))
+                .is_err()
+        );
+        assert!(
+            g.maximum_flow(&0, &5, &ring, |_, _| Integer::from(-1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_undirected_flow() {
+        //edge disjoint paths between opposite corners of a cube
+        let cube = UndirectedGraph::from_edges(
+            8,
+            (0..8usize).flat_map(|v| {
+                (0..3)
+                    .map(move |i| (v, v ^ (1 << i)))
+                    .filter(|(u, w)| u < w)
+            }),
+        )
+        .unwrap();
+        let ring = Rational::structure();
+        let flow = cube
+            .maximum_flow(&0, &7, &ring, |_, _| Rational::from_integers(1, 2))
+            .unwrap();
+        assert_eq!(flow.value(), &Rational::from_integers(3, 2));
+        assert_eq!(flow.cut_edges().len(), 3);
+    }
+
+    #[test]
+    fn test_minimum_cut_brute_force() {
+        let mut rng = Rng::new(4242);
+        let ring = Integer::structure();
+        for (n, k) in (2..=7).flat_map(|n| (0..=8).map(move |k| (n, k))) {
+            //orient the edges of one random graph forwards and the edges of another backwards
+            let p = Rational::from_integers(k, 8);
+            let forwards = gnp_random_graph(n, &p, &mut rng).unwrap();
+            let backwards = gnp_random_graph(n, &p, &mut rng).unwrap();
+            let edges = forwards
+                .edges()
+                .chain(backwards.edges().map(|(u, v)| (v, u)))
+                .collect::<Vec<_>>();
+            let capacity = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| {
+                            rng.uniform_random_integer_from_inclusive_range(
+                                Integer::ZERO,
+                                Integer::from(9),
+                            )
+                            .try_into()
+                            .unwrap()
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<i64>>>();
+            let g = DirectedGraph::from_edges(n, edges).unwrap();
+            let flow = g
+                .maximum_flow(&0, &(n - 1), &ring, |u, v| Integer::from(capacity[*u][*v]))
+                .unwrap();
+            //every cut separating 0 from n - 1
+            let least = (0..1usize << n)
+                .filter(|side| side & 1 == 1 && side >> (n - 1) & 1 == 0)
+                .map(|side| {
+                    g.edges()
+                        .filter(|(u, v)| side >> u & 1 == 1 && side >> v & 1 == 0)
+                        .collect::<std::collections::HashSet<_>>()
+                        .into_iter()
+                        .map(|(u, v)| capacity[u][v])
+                        .sum::<i64>()
+                })
+                .min()
+                .unwrap();
+            assert_eq!(flow.value(), &Integer::from(least));
+            let cut = flow
+                .cut_edges()
+                .iter()
+                .map(|(u, v)| capacity[*u][*v])
+                .sum::<i64>();
+            assert_eq!(cut, least);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use crate::graph::{DirectedGraph, Graph, UndirectedGraph};
+use crate::structure::FiniteGraphSignature;
+
+//the number n encoded as in the graph6 family of formats
+fn encode_size(n: usize) -> Result<Vec<u8>, String> {
+    let digits = |n: usize, count: usize| {
+        (0..count)
+            .rev()
+            .map(move |i| ((n >> (6 * i)) & 63) as u8 + 63)
+    };
+    if n <= 62 {
+        Ok(vec![n as u8 + 63])
+    } else if n <= 258_047 {
+        Ok(std::iter::once(126).chain(digits(n, 3)).collect())
+    } else if n <= 68_719_476_735 {
+        Ok([126, 126].into_iter().chain(digits(n, 6)).collect())
+    } else {
+        Err("Too many vertices to encode".to_string())
+    }
+}
+
+//the number of vertices and the remaining bytes
+fn decode_size(bytes: &[u8]) -> Result<(usize, &[u8]), String> {
+    let number = |digits: &[u8]| {
+        digits
+            .iter()
+            .fold(0, |n, d| (n << 6) | usize::from(*d - 63))
+    };
+    match bytes {
+        [126, 126, rest @ ..] if rest.len() >= 6 => Ok((number(&rest[..6]), &rest[6..])),
+        [126, rest @ ..] if rest.len() >= 3 => Ok((number(&rest[..3]), &rest[3..])),
+        [126, ..] => Err("Truncated number of vertices".to_string()),
+        [first, rest @ ..] => Ok((usize::from(*first - 63), rest)),
+        [] => Err("Missing number of vertices".to_string()),
+    }
+}
+
+//pad the bits with `pad` to a multiple of six and pack them six to a byte
+fn encode_bits(mut bits: Vec<bool>, pad: bool) -> Vec<u8> {
+    while !bits.len().is_multiple_of(6) {
+        bits.push(pad);
+    }
+    bits.chunks(6)
+        .map(|chunk| chunk.iter().fold(0, |byte, b| (byte << 1) | u8::from(*b)) + 63)
+        .collect()
+}
+
+fn decode_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..6).rev().map(move |i| ((byte - 63) >> i) & 1 == 1))
+        .collect()
+}
+
+//strip an optional header, surrounding whitespace and the marker of the format, and check the remaining characters
+fn strip<'a>(text: &'a str, header: &str, marker: &str) -> Result<&'a [u8], String> {
+    let text = text.trim();
+    let text = text.strip_prefix(header).unwrap_or(text);
+    let text = text
+        .strip_prefix(marker)
+        .ok_or_else(|| format!("Expected the data to start with {marker:?}"))?;
+    if let Some(c) = text.bytes().find(|c| !(63..=126).contains(c)) {
+        return Err(format!("Invalid character {:?}", char::from(c)));
+    }
+    Ok(text.as_bytes())
+}
+
+fn check_symmetric(multiplicities: &[Vec<usize>]) -> Result<(), String> {
+    for (u, row) in multiplicities.iter().enumerate() {
+        for (v, m) in row.iter().enumerate() {
+            if *m != multiplicities[v][u] {
+                return Err("The graph is not undirected".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encoding finite graphs in the graph6, sparse6 and digraph6 formats used by nauty and networkx.
+///
+/// Vertices are numbered by their position in `list_all_elements`.
+pub trait GraphFormats: FiniteGraphSignature {
+    /// The graph6 encoding of a simple undirected graph.
+    fn to_graph6(&self) -> Result<String, String> {
+        let multiplicities = self.edge_multiplicities();
+        check_symmetric(&multiplicities)?;
+        let n = multiplicities.len();
+        if (0..n).any(|v| multiplicities[v][v] != 0) {
+            return Err("graph6 can not encode loops".to_string());
+        }
+        if multiplicities.iter().flatten().any(|m| *m > 1) {
+            return Err("graph6 can not encode multiple edges".to_string());
+        }
+        let bits = (1..n)
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .map(|(i, j)| multiplicities[i][j] != 0)
+            .collect();
+        let mut bytes = encode_size(n)?;
+        bytes.extend(encode_bits(bits, false));
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    /// The sparse6 encoding of an undirected graph, which may have loops and multiple edges.
+    fn to_sparse6(&self) -> Result<String, String> {
+        let multiplicities = self.edge_multiplicities();
+        check_symmetric(&multiplicities)?;
+        let n = multiplicities.len();
+        let mut k = 1;
+        while 1 << k < n {
+            k += 1;
+        }
+        let encode = |x: usize| (0..k).rev().map(move |i| (x >> i) & 1 == 1);
+        let mut bits = vec![];
+        let mut current = 0;
+        for v in 0..n {
+            for (u, row) in multiplicities.iter().enumerate().take(v + 1) {
+                for _ in 0..row[v] {
+                    if v == current {
+                        bits.push(false);
+                    } else if v == current + 1 {
+                        current = v;
+                        bits.push(true);
+                    } else {
+                        current = v;
+                        bits.push(true);
+                        bits.extend(encode(v));
+                        bits.push(false);
+                    }
+                    bits.extend(encode(u));
+                }
+            }
+        }
+        //padding with ones must not be read as an edge to the last vertex
+        if k < 6 && n == 1 << k && (6 - bits.len() % 6) % 6 >= k && current + 1 < n {
+            bits.push(false);
+        }
+        let mut bytes = vec![b':'];
+        bytes.extend(encode_size(n)?);
+        bytes.extend(encode_bits(bits, true));
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    /// The digraph6 encoding of a directed graph, which may have loops but not multiple edges.
+    /// An undirected edge is encoded as a pair of opposite directed edges.
+    fn to_digraph6(&self) -> Result<String, String> {
+        let multiplicities = self.edge_multiplicities();
+        if multiplicities.iter().flatten().any(|m| *m > 1) {
+            return Err("digraph6 can not encode multiple edges".to_string());
+        }
+        let bits = multiplicities.iter().flatten().map(|m| *m != 0).collect();
+        let mut bytes = vec![b'&'];
+        bytes.extend(encode_size(multiplicities.len())?);
+        bytes.extend(encode_bits(bits, false));
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+}
+impl<G: FiniteGraphSignature> GraphFormats for G {}
+
+impl UndirectedGraph {
+    /// Decode a graph in the graph6 format, with or without the `>>graph6<<` header.
+    pub fn from_graph6(text: &str) -> Result<Self, String> {
+        let (n, data) = decode_size(strip(text, ">>graph6<<", "")?)?;
+        let pairs = n * n.saturating_sub(1) / 2;
+        if data.len() != pairs.div_ceil(6) {
+            return Err("The length of the data does not match the number of vertices".to_string());
+        }
+        let bits = decode_bits(data);
+        let edges = (1..n)
+            .flat_map(|j| (0..j).map(move |i| (i, j)))
+            .zip(bits)
+            .filter_map(|(e, b)| if b { Some(e) } else { None });
+        Self::from_edges(n, edges)
+    }
+
+    /// Decode a graph in the sparse6 format, with or without the `>>sparse6<<` header.
+    pub fn from_sparse6(text: &str) -> Result<Self, String> {
+        let (n, data) = decode_size(strip(text, ">>sparse6<<", ":")?)?;
+        let mut k = 1;
+        while 1 << k < n {
+            k += 1;
+        }
+        let bits = decode_bits(data);
+        let mut graph = Self::new(n);
+        let mut v = 0;
+        let mut i = 0;
+        while i + 1 + k <= bits.len() {
+            if bits[i] {
+                v += 1;
+            }
+            let x = bits[i + 1..i + 1 + k]
+                .iter()
+                .fold(0, |x, b| (x << 1) | usize::from(*b));
+            i += 1 + k;
+            //the padding may be read as an overlarge vertex
+            if x >= n || v >= n {
+                break;
+            } else if x > v {
+                v = x;
+            } else {
+                graph.add_edge(x, v)?;
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl DirectedGraph {
+    /// Decode a graph in the digraph6 format, with or without the `>>digraph6<<` header.
+    pub fn from_digraph6(text: &str) -> Result<Self, String> {
+        let (n, data) = decode_size(strip(text, ">>digraph6<<", "&")?)?;
+        if data.len() != (n * n).div_ceil(6) {
+            return Err("The length of the data does not match the number of vertices".to_string());
+        }
+        let bits = decode_bits(data);
+        let edges = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .zip(bits)
+            .filter_map(|(e, b)| if b { Some(e) } else { None });
+        Self::from_edges(n, edges)
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<const DIRECTED: bool> Graph<DIRECTED> {
+    /// The graph in the DOT language of Graphviz.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|_| None, |_| None)
+    }
+
+    /// The graph in the DOT language of Graphviz, with an optional label and fill colour for each vertex.
+    /// Colours may be any colour understood by Graphviz, such as `red` or `#ff0000`.
+    pub fn to_dot_with(
+        &self,
+        label: impl Fn(usize) -> Option<String>,
+        colour: impl Fn(usize) -> Option<String>,
+    ) -> String {
+        let (keyword, arrow) = if DIRECTED {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{keyword} {{\n");
+        for v in 0..self.vertex_count() {
+            let mut attributes = vec![];
+            if let Some(label) = label(v) {
+                attributes.push(format!("label=\"{}\"", dot_escape(&label)));
+            }
+            if let Some(colour) = colour(v) {
+                attributes.push("style=filled".to_string());
+                attributes.push(format!("fillcolor=\"{}\"", dot_escape(&colour)));
+            }
+            if attributes.is_empty() {
+                dot.push_str(&format!("  {v};\n"));
+            } else {
+                dot.push_str(&format!("  {v} [{}];\n", attributes.join(", ")));
+            }
+        }
+        for (u, v) in self.edges() {
+            dot.push_str(&format!("  {u} {arrow} {v};\n"));
+        }
+        dot.push('}');
+        dot.push('\n');
+        dot
+    }
+
+    /// The edges as lines `u v` in the order of their labels.
+    pub fn to_edge_list(&self) -> String {
+        self.edges().map(|(u, v)| format!("{u} {v}\n")).collect()
+    }
+
+    /// Read edges given as lines `u v` of vertex numbers separated by whitespace.
+    /// Blank lines and lines starting with `#` are ignored.
+    /// The number of vertices is `n` if given and otherwise one more than the largest vertex.
+    pub fn from_edge_list(text: &str, n: Option<usize>) -> Result<Self, String> {
+        let mut edges = vec![];
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let vertices = line
+                .split_whitespace()
+                .map(|word| {
+                    word.parse::<usize>()
+                        .map_err(|_| format!("Line {}: {word:?} is not a vertex", number + 1))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            match vertices.as_slice() {
+                [u, v] => edges.push((*u, *v)),
+                _ => return Err(format!("Line {}: expected two vertices", number + 1)),
+            }
+        }
+        let n = n.unwrap_or_else(|| edges.iter().map(|(u, v)| u.max(v) + 1).max().unwrap_or(0));
+        Self::from_edges(n, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{
+        CompleteDirectedGraph, CompleteUndirectedGraph, DirectedCycleGraph, UndirectedCycleGraph,
+        WheelGraph,
+    };
+    use crate::isomorphism::FiniteGraphIsomorphism;
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    fn same_edges<const DIRECTED: bool>(g: &Graph<DIRECTED>, h: &Graph<DIRECTED>) -> bool {
+        g.vertex_count() == h.vertex_count() && g.edge_multiplicities() == h.edge_multiplicities()
+    }
+
+    #[test]
+    fn test_graph6() {
+        //examples from the description of the formats by McKay
+        let g = UndirectedGraph::from_edges(5, [(0, 2), (0, 4), (1, 3), (3, 4)]).unwrap();
+        assert_eq!(g.to_graph6().unwrap(), "DQc");
+        assert!(same_edges(
+            &UndirectedGraph::from_graph6("DQc").unwrap(),
+            &g
+        ));
+        assert!(same_edges(
+            &UndirectedGraph::from_graph6(">>graph6<<DQc\n").unwrap(),
+            &g
+        ));
+        assert_eq!(petersen().to_graph6().unwrap(), "IheA@GUAo");
+        assert_eq!(encode_size(30).unwrap(), vec![93]);
+        assert_eq!(encode_size(12345).unwrap(), vec![126, 66, 63, 120]);
+        assert_eq!(
+            encode_size(460_175_067).unwrap(),
+            vec![126, 126, 63, 90, 90, 90, 90, 90]
+        );
+        assert_eq!(decode_size(&[126, 66, 63, 120]).unwrap().0, 12345);
+
+        assert!(UndirectedGraph::from_graph6("DQ").is_err());
+        assert!(UndirectedGraph::from_graph6("DQc ").is_ok());
+        assert!(UndirectedGraph::from_graph6("D Qc").is_err());
+        let with_loop = UndirectedGraph::from_edges(2, [(0, 0)]).unwrap();
+        assert!(with_loop.to_graph6().is_err());
+        assert!(
+            DirectedGraph::from_edges(2, [(0, 1)])
+                .unwrap()
+                .to_graph6()
+                .is_err()
+        );
+
+        for n in 4..12 {
+            let wheel = WheelGraph::new(n).unwrap();
+            let g = UndirectedGraph::from_graph6(&wheel.to_graph6().unwrap()).unwrap();
+            assert!(g.is_isomorphic_to(&wheel));
+            assert_eq!(g.to_graph6().unwrap(), wheel.to_graph6().unwrap());
+            let cycle = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            let g = UndirectedGraph::from_graph6(&cycle.to_graph6().unwrap()).unwrap();
+            assert!(g.is_isomorphic_to(&cycle));
+            let complete = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            let g = UndirectedGraph::from_graph6(&complete.to_graph6().unwrap()).unwrap();
+            assert_eq!(g.edge_count(), n * (n - 1) / 2);
+        }
+        let big = UndirectedGraph::from_edges(100, (0..99).map(|i| (i, i + 1))).unwrap();
+        assert!(same_edges(
+            &UndirectedGraph::from_graph6(&big.to_graph6().unwrap()).unwrap(),
+            &big
+        ));
+    }
+
+    #[test]
+    fn test_sparse6() {
+        let g = UndirectedGraph::from_edges(7, [(0, 1), (0, 2), (1, 2), (5, 6)]).unwrap();
+        assert_eq!(g.to_sparse6().unwrap(), ":Fa@x^");
+        assert!(same_edges(
+            &UndirectedGraph::from_sparse6(":Fa@x^").unwrap(),
+            &g
+        ));
+        assert!(same_edges(
+            &UndirectedGraph::from_sparse6(">>sparse6<<:Fa@x^\n").unwrap(),
+            &g
+        ));
+        assert!(UndirectedGraph::from_sparse6("Fa@x^").is_err());
+
+        //loops and multiple edges
+        let multigraph =
+            UndirectedGraph::from_edges(5, [(0, 0), (0, 1), (1, 0), (3, 4), (4, 4), (2, 4)])
+                .unwrap();
+        assert!(same_edges(
+            &UndirectedGraph::from_sparse6(&multigraph.to_sparse6().unwrap()).unwrap(),
+            &multigraph
+        ));
+
+        for n in 1..40 {
+            for g in [
+                UndirectedGraph::from_edges(n, (0..n - 1).map(|i| (i, i + 1))).unwrap(),
+                UndirectedGraph::from_edges(n, (1..n).map(|i| (0, i))).unwrap(),
+                UndirectedGraph::from_edges(n, [(0, n - 1)]).unwrap(),
+                UndirectedGraph::from_edges(n, [(0, 0)]).unwrap(),
+                UndirectedGraph::new(n),
+            ] {
+                let h = UndirectedGraph::from_sparse6(&g.to_sparse6().unwrap()).unwrap();
+                assert!(same_edges(&g, &h));
+            }
+        }
+        for n in 4..12 {
+            let wheel = WheelGraph::new(n).unwrap();
+            let g = UndirectedGraph::from_sparse6(&wheel.to_sparse6().unwrap()).unwrap();
+            assert!(g.is_isomorphic_to(&wheel));
+        }
+    }
+
+    #[test]
+    fn test_digraph6() {
+        let g = DirectedGraph::from_edges(5, [(0, 2), (0, 4), (3, 1), (3, 4)]).unwrap();
+        assert_eq!(g.to_digraph6().unwrap(), "&DI?AO?");
+        assert!(same_edges(
+            &DirectedGraph::from_digraph6("&DI?AO?").unwrap(),
+            &g
+        ));
+        assert!(DirectedGraph::from_digraph6("DI?AO?").is_err());
+        let with_loops = DirectedGraph::from_edges(3, [(0, 0), (0, 1), (1, 0), (2, 2)]).unwrap();
+        assert!(same_edges(
+            &DirectedGraph::from_digraph6(&with_loops.to_digraph6().unwrap()).unwrap(),
+            &with_loops
+        ));
+        let multiple = DirectedGraph::from_edges(2, [(0, 1), (0, 1)]).unwrap();
+        assert!(multiple.to_digraph6().is_err());
+
+        for n in 3..10 {
+            let cycle = DirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            let g = DirectedGraph::from_digraph6(&cycle.to_digraph6().unwrap()).unwrap();
+            assert!(g.is_isomorphic_to(&cycle));
+            let complete = CompleteDirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            let g = DirectedGraph::from_digraph6(&complete.to_digraph6().unwrap()).unwrap();
+            assert!(g.is_isomorphic_to(&complete));
+        }
+    }
+
+    #[test]
+    fn test_dot() {
+        let g = UndirectedGraph::from_edges(3, [(0, 1), (1, 2)]).unwrap();
+        assert_eq!(
+            g.to_dot(),
+            "graph {\n  0;\n  1;\n  2;\n  0 -- 1;\n  1 -- 2;\n}\n"
+        );
+        let d = DirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        let dot = d.to_dot_with(
+            |v| {
+                if v == 0 {
+                    Some("a \"b\"".to_string())
+                } else {
+                    None
+                }
+            },
+            |v| Some(["red", "blue"][v].to_string()),
+        );
+        assert_eq!(
+            dot,
+            "digraph {\n  0 [label=\"a \\\"b\\\"\", style=filled, fillcolor=\"red\"];\n  1 [style=filled, fillcolor=\"blue\"];\n  0 -> 1;\n  1 -> 1;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_edge_list() {
+        let g = petersen();
+        let h = UndirectedGraph::from_edge_list(&g.to_edge_list(), None).unwrap();
+        assert_eq!(h.edges().collect::<Vec<_>>(), g.edges().collect::<Vec<_>>());
+
+        let d = DirectedGraph::from_edge_list("# a comment\n0 1\n\n  2\t0 \n", Some(4)).unwrap();
+        assert_eq!(d.vertex_count(), 4);
+        assert_eq!(d.edges().collect::<Vec<_>>(), vec![(0, 1), (2, 0)]);
+        assert_eq!(d.to_edge_list(), "0 1\n2 0\n");
+        assert!(DirectedGraph::from_edge_list("0 1 2", None).is_err());
+        assert!(DirectedGraph::from_edge_list("0 x", None).is_err());
+        assert!(DirectedGraph::from_edge_list("0 5", Some(3)).is_err());
+        assert_eq!(
+            DirectedGraph::from_edge_list("", None)
+                .unwrap()
+                .vertex_count(),
+            0
+        );
+    }
+}
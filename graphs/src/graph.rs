@@ -0,0 +1,599 @@
+use crate::structure::{
+    FiniteGraphSignature, GraphSignature, GraphWithEdgesSignature, LooplessGraphSignature,
+    UndirectedGraphSignature,
+};
+use algebraeon_sets::structure::{
+    EnumeratedFiniteSetStructure, SetSignature, UnorderedPair, UnorderedPairs,
+};
+
+/// A mutable finite graph on the vertices `0, 1, ..., n-1` stored as adjacency lists.
+///
+/// Loops and multiple edges are permitted. Edges are labelled `0, 1, ..., m-1` and each edge
+/// remembers its `(source, target)`, which for an undirected graph is just the order in which the
+/// endpoints were given.
+///
+/// Removing a vertex or an edge relabels the last vertex or edge with the label of the removed one,
+/// so that the labels always stay contiguous.
+#[derive(Debug, Clone)]
+pub struct Graph<const DIRECTED: bool> {
+    vertices: EnumeratedFiniteSetStructure,
+    n: usize,
+    edges: Vec<(usize, usize)>,
+    //the edges leaving each vertex, or every edge incident to the vertex if undirected
+    //a loop appears only once in the list of its vertex
+    out_edges: Vec<Vec<usize>>,
+    //the edges entering each vertex, always empty if undirected
+    in_edges: Vec<Vec<usize>>,
+}
+
+pub type DirectedGraph = Graph<true>;
+pub type UndirectedGraph = Graph<false>;
+
+impl<const DIRECTED: bool> Graph<DIRECTED> {
+    /// A graph with `n` vertices and no edges.
+    pub fn new(n: usize) -> Self {
+        Self {
+            vertices: EnumeratedFiniteSetStructure::new(n),
+            n,
+            edges: vec![],
+            out_edges: vec![vec![]; n],
+            in_edges: vec![vec![]; n],
+        }
+    }
+
+    pub fn from_edges(
+        n: usize,
+        edges: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, String> {
+        let mut graph = Self::new(n);
+        for (u, v) in edges {
+            graph.add_edge(u, v)?;
+        }
+        Ok(graph)
+    }
+
+    pub fn is_directed(&self) -> bool {
+        DIRECTED
+    }
+
+    pub fn vertices(&self) -> &EnumeratedFiniteSetStructure {
+        &self.vertices
+    }
+
+    /// The set of edge labels `0, 1, ..., m-1`.
+    pub fn edge_set(&self) -> EnumeratedFiniteSetStructure {
+        EnumeratedFiniteSetStructure::new(self.edges.len())
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.n
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn check_vertex(&self, v: usize) -> Result<(), String> {
+        self.vertices
+            .is_element(&v)
+            .map_err(|e| format!("{v} is not an element of Vertices: {e}"))
+    }
+
+    fn check_edge(&self, e: usize) -> Result<(), String> {
+        if e < self.edges.len() {
+            Ok(())
+        } else {
+            Err(format!("{e} is not an edge of the graph"))
+        }
+    }
+
+    fn attach(&mut self, e: usize) {
+        let (s, t) = self.edges[e];
+        self.out_edges[s].push(e);
+        if DIRECTED {
+            self.in_edges[t].push(e);
+        } else if s != t {
+            self.out_edges[t].push(e);
+        }
+    }
+
+    fn detach(&mut self, e: usize) {
+        fn remove_from(list: &mut Vec<usize>, e: usize) {
+            let i = list.iter().position(|f| *f == e).unwrap();
+            list.swap_remove(i);
+        }
+        let (s, t) = self.edges[e];
+        remove_from(&mut self.out_edges[s], e);
+        if DIRECTED {
+            remove_from(&mut self.in_edges[t], e);
+        } else if s != t {
+            remove_from(&mut self.out_edges[t], e);
+        }
+    }
+
+    /// Add a new isolated vertex and return its label.
+    pub fn add_vertex(&mut self) -> usize {
+        let v = self.n;
+        self.n += 1;
+        self.vertices = EnumeratedFiniteSetStructure::new(self.n);
+        self.out_edges.push(vec![]);
+        self.in_edges.push(vec![]);
+        v
+    }
+
+    /// Remove a vertex together with every edge incident to it.
+    /// The last vertex `n-1` takes over the label `v`.
+    pub fn remove_vertex(&mut self, v: usize) -> Result<(), String> {
+        self.check_vertex(v)?;
+        let mut incident = self.incident_edges(v);
+        incident.sort_unstable();
+        incident.dedup();
+        //removing in decreasing order means the edge moved into each gap is never one still to be removed
+        for e in incident.into_iter().rev() {
+            self.remove_edge(e).unwrap();
+        }
+
+        let last = self.n - 1;
+        if v != last {
+            for e in self.incident_edges(last) {
+                let (s, t) = &mut self.edges[e];
+                if *s == last {
+                    *s = v;
+                }
+                if *t == last {
+                    *t = v;
+                }
+            }
+            self.out_edges.swap(v, last);
+            self.in_edges.swap(v, last);
+        }
+        self.out_edges.pop();
+        self.in_edges.pop();
+        self.n -= 1;
+        self.vertices = EnumeratedFiniteSetStructure::new(self.n);
+        Ok(())
+    }
+
+    /// Add an edge from `source` to `target` and return its label.
+    pub fn add_edge(&mut self, source: usize, target: usize) -> Result<usize, String> {
+        self.check_vertex(source)?;
+        self.check_vertex(target)?;
+        let e = self.edges.len();
+        self.edges.push((source, target));
+        self.attach(e);
+        Ok(e)
+    }
+
+    /// Remove an edge and return its `(source, target)`.
+    /// The last edge `m-1` takes over the label `e`.
+    pub fn remove_edge(&mut self, e: usize) -> Result<(usize, usize), String> {
+        self.check_edge(e)?;
+        let last = self.edges.len() - 1;
+        self.detach(e);
+        if e != last {
+            self.detach(last);
+        }
+        let endpoints = self.edges.swap_remove(e);
+        if e != last {
+            self.attach(e);
+        }
+        Ok(endpoints)
+    }
+
+    /// Remove every edge from `source` to `target`, or between them if undirected, and return how many were removed.
+    pub fn remove_edges_between(&mut self, source: usize, target: usize) -> Result<usize, String> {
+        let mut between = self.edges_between(source, target)?;
+        between.sort_unstable();
+        for e in between.iter().rev() {
+            self.remove_edge(*e).unwrap();
+        }
+        Ok(between.len())
+    }
+
+    /// The `(source, target)` of an edge, which is `(v, v)` for a loop at `v`.
+    ///
+    /// `GraphWithEdgesSignature` gives the endpoints of an edge as an `UnorderedPair`, which cannot
+    /// represent a loop, so it is implemented for [`LooplessGraph`] rather than for `Graph`.
+    pub fn edge(&self, e: usize) -> (usize, usize) {
+        self.edges[e]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.edges.iter().copied()
+    }
+
+    fn other_endpoint(&self, e: usize, v: usize) -> usize {
+        let (s, t) = self.edges[e];
+        if s == v { t } else { s }
+    }
+
+    /// The edges leaving `v`, or incident to `v` if undirected.
+    pub fn out_edges(&self, v: usize) -> &Vec<usize> {
+        &self.out_edges[v]
+    }
+
+    /// The edges entering `v`, or incident to `v` if undirected.
+    pub fn in_edges(&self, v: usize) -> &Vec<usize> {
+        if DIRECTED {
+            &self.in_edges[v]
+        } else {
+            &self.out_edges[v]
+        }
+    }
+
+    /// Every edge with `v` as an endpoint. A loop at `v` appears once if undirected and twice if directed.
+    pub fn incident_edges(&self, v: usize) -> Vec<usize> {
+        if DIRECTED {
+            self.out_edges[v]
+                .iter()
+                .chain(self.in_edges[v].iter())
+                .copied()
+                .collect()
+        } else {
+            self.out_edges[v].clone()
+        }
+    }
+
+    /// The targets of the edges leaving `v`, with repetition for multiple edges.
+    pub fn out_neighbours(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.out_edges[v]
+            .iter()
+            .map(move |e| self.other_endpoint(*e, v))
+    }
+
+    /// The sources of the edges entering `v`, with repetition for multiple edges.
+    pub fn in_neighbours(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.in_edges(v)
+            .iter()
+            .map(move |e| self.other_endpoint(*e, v))
+    }
+
+    /// The vertices joined to `v` by an edge in either direction, without repetition.
+    pub fn neighbours(&self, v: usize) -> Vec<usize> {
+        let mut neighbours = self
+            .out_neighbours(v)
+            .chain(self.in_neighbours(v))
+            .collect::<Vec<_>>();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.out_edges(v).len()
+    }
+
+    pub fn in_degree(&self, v: usize) -> usize {
+        self.in_edges(v).len()
+    }
+
+    /// The number of edge ends at `v`, so a loop contributes two.
+    pub fn degree(&self, v: usize) -> usize {
+        if DIRECTED {
+            self.out_degree(v) + self.in_degree(v)
+        } else {
+            let loops = self.out_edges[v]
+                .iter()
+                .filter(|e| self.edges[**e] == (v, v))
+                .count();
+            self.out_degree(v) + loops
+        }
+    }
+
+    /// The edges from `source` to `target`, or between them if undirected.
+    pub fn edges_between(&self, source: usize, target: usize) -> Result<Vec<usize>, String> {
+        self.check_vertex(source)?;
+        self.check_vertex(target)?;
+        Ok(self.out_edges[source]
+            .iter()
+            .copied()
+            .filter(|e| self.other_endpoint(*e, source) == target)
+            .collect())
+    }
+
+    pub fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.edges_between(source, target)
+            .is_ok_and(|edges| !edges.is_empty())
+    }
+
+    pub fn has_loops(&self) -> bool {
+        self.edges.iter().any(|(s, t)| s == t)
+    }
+
+    pub fn has_multiple_edges(&self) -> bool {
+        (0..self.n).any(|v| {
+            let mut targets = self.out_neighbours(v).collect::<Vec<_>>();
+            let count = targets.len();
+            targets.sort_unstable();
+            targets.dedup();
+            targets.len() != count
+        })
+    }
+
+    /// No loops and no multiple edges.
+    pub fn is_simple(&self) -> bool {
+        !self.has_loops() && !self.has_multiple_edges()
+    }
+}
+
+impl<const DIRECTED: bool> GraphSignature for Graph<DIRECTED> {
+    type Vertices = EnumeratedFiniteSetStructure;
+
+    fn has_directed_edge(
+        &self,
+        source: &<Self::Vertices as SetSignature>::Set,
+        target: &<Self::Vertices as SetSignature>::Set,
+    ) -> Result<(), String> {
+        if let Err(e) = self.vertices.is_element(source) {
+            return Err(format!("Source is not an element of Vertices: {e}"));
+        }
+        if let Err(e) = self.vertices.is_element(target) {
+            return Err(format!("Target is not an element of Vertices: {e}"));
+        }
+        if self.has_edge(*source, *target) {
+            Ok(())
+        } else {
+            Err(format!("No edge from vertex {source} to vertex {target}"))
+        }
+    }
+}
+
+impl<const DIRECTED: bool> FiniteGraphSignature for Graph<DIRECTED> {
+    fn vertices(&self) -> &Self::Vertices {
+        &self.vertices
+    }
+
+    fn out_adjacency(&self) -> Vec<Vec<usize>> {
+        (0..self.n)
+            .map(|v| {
+                let mut targets = self.out_neighbours(v).collect::<Vec<_>>();
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect()
+    }
+
+    fn edge_multiplicities(&self) -> Vec<Vec<usize>> {
+        let mut multiplicities = vec![vec![0; self.n]; self.n];
+        for (s, t) in &self.edges {
+            multiplicities[*s][*t] += 1;
+            if !DIRECTED && s != t {
+                multiplicities[*t][*s] += 1;
+            }
+        }
+        multiplicities
+    }
+}
+
+impl UndirectedGraphSignature for UndirectedGraph {}
+
+impl UndirectedGraph {
+    /// A copy of a finite undirected graph, numbering the vertices in the order given by `list_all_elements`.
+    pub fn from_finite_graph<G: FiniteGraphSignature>(graph: &G) -> Result<Self, String> {
+        let multiplicities = graph.edge_multiplicities();
+        let n = multiplicities.len();
+        let mut edges = vec![];
+        for (u, row) in multiplicities.iter().enumerate() {
+            for (v, m) in row.iter().enumerate().skip(u) {
+                if *m != multiplicities[v][u] {
+                    return Err("The graph is not undirected".to_string());
+                }
+                edges.extend(std::iter::repeat_n((u, v), *m));
+            }
+        }
+        Self::from_edges(n, edges)
+    }
+}
+
+impl DirectedGraph {
+    /// A copy of a finite graph, numbering the vertices in the order given by `list_all_elements`.
+    /// An undirected edge becomes a pair of opposite directed edges.
+    pub fn from_finite_graph<G: FiniteGraphSignature>(graph: &G) -> Self {
+        let multiplicities = graph.edge_multiplicities();
+        let n = multiplicities.len();
+        let edges = (0..n)
+            .flat_map(|u| (0..n).map(move |v| (u, v)))
+            .flat_map(|(u, v)| std::iter::repeat_n((u, v), multiplicities[u][v]))
+            .collect::<Vec<_>>();
+        Self::from_edges(n, edges).unwrap()
+    }
+}
+
+/// A [`Graph`] with no loops, so that the endpoints of each edge are an `UnorderedPair`.
+#[derive(Debug, Clone)]
+pub struct LooplessGraph<const DIRECTED: bool> {
+    graph: Graph<DIRECTED>,
+}
+
+pub type LooplessDirectedGraph = LooplessGraph<true>;
+pub type LooplessUndirectedGraph = LooplessGraph<false>;
+
+impl<const DIRECTED: bool> LooplessGraph<DIRECTED> {
+    pub fn new(graph: Graph<DIRECTED>) -> Result<Self, String> {
+        if graph.has_loops() {
+            return Err("The graph has a loop".to_string());
+        }
+        Ok(Self { graph })
+    }
+
+    pub fn graph(&self) -> &Graph<DIRECTED> {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph<DIRECTED> {
+        self.graph
+    }
+}
+
+impl<const DIRECTED: bool> Graph<DIRECTED> {
+    /// This graph as a [`LooplessGraph`], or an error if it has a loop.
+    pub fn into_loopless(self) -> Result<LooplessGraph<DIRECTED>, String> {
+        LooplessGraph::new(self)
+    }
+}
+
+impl<const DIRECTED: bool> GraphSignature for LooplessGraph<DIRECTED> {
+    type Vertices = EnumeratedFiniteSetStructure;
+
+    fn has_directed_edge(
+        &self,
+        source: &<Self::Vertices as SetSignature>::Set,
+        target: &<Self::Vertices as SetSignature>::Set,
+    ) -> Result<(), String> {
+        self.graph.has_directed_edge(source, target)
+    }
+}
+
+impl<const DIRECTED: bool> FiniteGraphSignature for LooplessGraph<DIRECTED> {
+    fn vertices(&self) -> &Self::Vertices {
+        self.graph.vertices()
+    }
+
+    fn out_adjacency(&self) -> Vec<Vec<usize>> {
+        self.graph.out_adjacency()
+    }
+
+    fn edge_multiplicities(&self) -> Vec<Vec<usize>> {
+        self.graph.edge_multiplicities()
+    }
+}
+
+impl<const DIRECTED: bool> LooplessGraphSignature for LooplessGraph<DIRECTED> {}
+
+impl UndirectedGraphSignature for LooplessUndirectedGraph {}
+
+impl<const DIRECTED: bool> GraphWithEdgesSignature for LooplessGraph<DIRECTED> {
+    type Edges = EnumeratedFiniteSetStructure;
+
+    fn endpoints(
+        &self,
+        edge: &<Self::Edges as SetSignature>::Set,
+    ) -> UnorderedPair<<Self::Vertices as SetSignature>::Set> {
+        let (s, t) = self.graph.edge(*edge);
+        UnorderedPairs::new(self.graph.vertices().clone())
+            .new_pair(&s, &t)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algebraeon_sets::structure::{EqSignature, FiniteSetSignature};
+
+    #[test]
+    fn test_undirected_graph() {
+        let mut g = UndirectedGraph::from_edges(4, [(0, 1), (1, 2), (2, 0), (2, 3)]).unwrap();
+        assert!(!g.is_directed());
+        assert_eq!(g.vertex_count(), 4);
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.degree(2), 3);
+        assert_eq!(g.neighbours(2), vec![0, 1, 3]);
+        assert!(g.has_directed_edge(&3, &2).is_ok());
+        assert!(g.has_directed_edge(&2, &3).is_ok());
+        assert!(g.has_directed_edge(&0, &3).is_err());
+        assert!(g.has_directed_edge(&0, &4).is_err());
+        assert!(g.is_simple());
+
+        assert!(g.add_edge(0, 4).is_err());
+        let v = g.add_vertex();
+        assert_eq!(v, 4);
+        g.add_edge(0, 4).unwrap();
+        assert_eq!(g.degree(0), 3);
+        assert_eq!(g.edge_set().size(), 5);
+    }
+
+    #[test]
+    fn test_directed_graph() {
+        let g = DirectedGraph::from_edges(3, [(0, 1), (1, 2), (0, 2)]).unwrap();
+        assert!(g.is_directed());
+        assert!(g.has_directed_edge(&0, &1).is_ok());
+        assert!(g.has_directed_edge(&1, &0).is_err());
+        assert_eq!(g.out_degree(0), 2);
+        assert_eq!(g.in_degree(0), 0);
+        assert_eq!(g.in_degree(2), 2);
+        assert_eq!(g.degree(1), 2);
+        assert_eq!(g.out_neighbours(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.in_neighbours(2).collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(g.neighbours(1), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_loops_and_multiple_edges() {
+        let mut g = UndirectedGraph::from_edges(2, [(0, 0), (0, 1), (1, 0)]).unwrap();
+        assert!(g.has_loops());
+        assert!(g.has_multiple_edges());
+        assert!(!g.is_simple());
+        assert_eq!(g.degree(0), 4);
+        assert_eq!(g.degree(1), 2);
+        assert_eq!(g.edges_between(0, 1).unwrap().len(), 2);
+        assert!(g.has_directed_edge(&0, &0).is_ok());
+        assert_eq!(g.neighbours(0), vec![0, 1]);
+
+        assert_eq!(g.remove_edges_between(1, 0), Ok(2));
+        assert_eq!(g.edge_count(), 1);
+        assert!(!g.has_multiple_edges());
+        assert_eq!(g.degree(1), 0);
+
+        let mut d = DirectedGraph::from_edges(2, [(0, 0), (0, 1), (0, 1)]).unwrap();
+        assert_eq!(d.degree(0), 4);
+        assert!(d.has_multiple_edges());
+        assert_eq!(d.remove_edges_between(1, 0), Ok(0));
+        assert_eq!(d.remove_edges_between(0, 1), Ok(2));
+        assert!(!d.has_multiple_edges());
+    }
+
+    #[test]
+    fn test_edge_removal_relabels() {
+        let mut g = UndirectedGraph::from_edges(4, [(0, 1), (1, 2), (2, 3)]).unwrap();
+        assert_eq!(g.remove_edge(0), Ok((0, 1)));
+        //the last edge (2, 3) now has label 0
+        assert_eq!(g.edge(0), (2, 3));
+        assert_eq!(g.edge(1), (1, 2));
+        assert_eq!(g.out_edges(3), &vec![0]);
+        assert!(g.remove_edge(2).is_err());
+        assert!(g.has_directed_edge(&0, &1).is_err());
+        assert_eq!(g.degree(2), 2);
+    }
+
+    #[test]
+    fn test_vertex_removal_relabels() {
+        //a star with centre 1 and a loop at 3
+        let mut g =
+            DirectedGraph::from_edges(5, [(1, 0), (1, 2), (3, 1), (4, 1), (3, 3), (4, 2)]).unwrap();
+        g.remove_vertex(1).unwrap();
+        assert_eq!(g.vertex_count(), 4);
+        assert_eq!(g.edge_count(), 2);
+        //vertex 4 is now vertex 1
+        assert!(g.has_directed_edge(&1, &2).is_ok());
+        assert!(g.has_directed_edge(&3, &3).is_ok());
+        assert_eq!(g.out_degree(1), 1);
+        assert_eq!(g.in_degree(2), 1);
+        assert!(g.remove_vertex(4).is_err());
+
+        let mut g = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (2, 2)]).unwrap();
+        g.remove_vertex(2).unwrap();
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.edges().collect::<Vec<_>>(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let g = UndirectedGraph::from_edges(3, [(0, 1), (2, 1), (2, 2)]).unwrap();
+        assert_eq!(g.edge(1), (2, 1));
+        assert_eq!(g.edge(2), (2, 2));
+        assert!(g.has_loops());
+        assert_eq!(g.incident_edges(2), vec![1, 2]);
+        assert!(g.into_loopless().is_err());
+
+        let g = UndirectedGraph::from_edges(3, [(0, 1), (2, 1)])
+            .unwrap()
+            .into_loopless()
+            .unwrap();
+        let pairs = UnorderedPairs::new(g.vertices().clone());
+        assert!(pairs.equal(&g.endpoints(&1), &pairs.new_pair(&1, &2).unwrap()));
+        assert!(g.has_directed_edge(&1, &2).is_ok());
+        assert_eq!(g.into_graph().edge_count(), 2);
+    }
+}
@@ -0,0 +1,545 @@
+use crate::isomorphism::canonical_matrix;
+use crate::structure::{FiniteGraphSignature, UndirectedGraphSignature};
+use algebraeon_nzq::traits::Abs;
+use algebraeon_nzq::{Integer, Natural};
+use algebraeon_rings::isolated_algebraic::{ComplexAlgebraic, RealAlgebraic};
+use algebraeon_rings::matrix::Matrix;
+use algebraeon_rings::polynomial::{MultiPolynomial, Polynomial, Variable};
+use algebraeon_rings::structure::{
+    AdditiveGroupSignature, AdditiveMonoidSignature, SemiRingSignature,
+};
+use algebraeon_sets::structure::MetaType;
+use std::collections::{HashMap, VecDeque};
+
+//det(xI - A) by the Faddeev-LeVerrier algorithm, in which every division is exact
+//this is much faster than the Smith normal form over Q[x] used by `Matrix::characteristic_polynomial`
+fn integer_characteristic_polynomial(matrix: &Matrix<Integer>) -> Polynomial<Integer> {
+    let n = matrix.rows();
+    let a = |i: usize, j: usize| matrix.at(i, j).unwrap();
+    //coeffs[k] is the coefficient of x^k
+    let mut coeffs = vec![Integer::ZERO; n + 1];
+    coeffs[n] = Integer::from(1);
+    let mut m = vec![vec![Integer::ZERO; n]; n];
+    for k in 1..=n {
+        //M_k = A M_{k-1} + c_{n-k+1} I
+        let mut next = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| (0..n).map(|l| a(i, l) * &m[l][j]).sum::<Integer>())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (i, row) in next.iter_mut().enumerate() {
+            row[i] += &coeffs[n - k + 1];
+        }
+        m = next;
+        //c_{n-k} = -tr(A M_k) / k
+        let trace = (0..n)
+            .map(|i| (0..n).map(|l| a(i, l) * &m[l][i]).sum::<Integer>())
+            .sum::<Integer>();
+        coeffs[n - k] = -(trace / Integer::from(k));
+    }
+    Polynomial::from_coeffs(coeffs)
+}
+
+//the matrix with vertex v merged into vertex u
+fn contract(matrix: &[Vec<usize>], u: usize, v: usize) -> Vec<Vec<usize>> {
+    let mut contracted = matrix.to_vec();
+    for w in 0..matrix.len() {
+        if w != u && w != v {
+            contracted[u][w] += matrix[v][w];
+            contracted[w][u] += matrix[w][v];
+        }
+    }
+    contracted[u][u] += matrix[v][v];
+    contracted[u][v] = 0;
+    contracted[v][u] = 0;
+    contracted.remove(v);
+    for row in &mut contracted {
+        row.remove(v);
+    }
+    contracted
+}
+
+fn is_connected_between(matrix: &[Vec<usize>], u: usize, v: usize) -> bool {
+    let mut seen = vec![false; matrix.len()];
+    seen[u] = true;
+    let mut queue = VecDeque::from([u]);
+    while let Some(a) = queue.pop_front() {
+        for b in 0..matrix.len() {
+            if matrix[a][b] != 0 && !seen[b] {
+                seen[b] = true;
+                queue.push_back(b);
+            }
+        }
+    }
+    seen[v]
+}
+
+//chromatic polynomial of a simple graph with adjacency matrix of 0s and 1s
+//deletion-contraction is used for sparse graphs and addition-contraction for dense graphs
+fn chromatic(
+    matrix: Vec<Vec<usize>>,
+    memo: &mut HashMap<Vec<Vec<usize>>, Polynomial<Integer>>,
+) -> Polynomial<Integer> {
+    let poly_ring = Polynomial::<Integer>::structure();
+    let n = matrix.len();
+    let pairs = n * n.saturating_sub(1) / 2;
+    let edges = (0..n)
+        .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+        .filter(|(u, v)| matrix[*u][*v] != 0)
+        .count();
+    if edges == 0 {
+        return poly_ring.nat_pow(&poly_ring.var(), &Natural::from(n));
+    }
+    if edges == pairs {
+        return poly_ring.product(
+            (0..n)
+                .map(|i| Polynomial::from_coeffs(vec![-Integer::from(i), Integer::from(1)]))
+                .collect(),
+        );
+    }
+    let key = canonical_matrix(&matrix);
+    if let Some(p) = memo.get(&key) {
+        return p.clone();
+    }
+    let dense = 2 * edges > pairs;
+    let (u, v) = (0..n)
+        .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+        .find(|(u, v)| (matrix[*u][*v] != 0) != dense)
+        .unwrap();
+    let mut changed = matrix.clone();
+    changed[u][v] = 1 - changed[u][v];
+    changed[v][u] = 1 - changed[v][u];
+    let mut contracted = contract(&matrix, u, v);
+    for row in &mut contracted {
+        for x in row.iter_mut() {
+            *x = (*x).min(1);
+        }
+    }
+    let p = if dense {
+        //P(G) = P(G + e) + P(G / e)
+        poly_ring.add(&chromatic(changed, memo), &chromatic(contracted, memo))
+    } else {
+        //P(G) = P(G - e) - P(G / e)
+        poly_ring.sub(&chromatic(changed, memo), &chromatic(contracted, memo))
+    };
+    memo.insert(key, p.clone());
+    p
+}
+
+//Tutte polynomial of an undirected multigraph with loops on the diagonal
+fn tutte(
+    matrix: Vec<Vec<usize>>,
+    x: &MultiPolynomial<Integer>,
+    y: &MultiPolynomial<Integer>,
+    memo: &mut HashMap<Vec<Vec<usize>>, MultiPolynomial<Integer>>,
+) -> MultiPolynomial<Integer> {
+    let poly_ring = MultiPolynomial::<Integer>::structure();
+    let mut matrix = matrix;
+    //each loop contributes a factor of y
+    let loops = (0..matrix.len()).map(|i| matrix[i][i]).sum::<usize>();
+    if loops != 0 {
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 0;
+        }
+        return poly_ring.mul(
+            &poly_ring.nat_pow(y, &Natural::from(loops)),
+            &tutte(matrix, x, y, memo),
+        );
+    }
+    //isolated vertices do not contribute
+    let isolated = (0..matrix.len())
+        .filter(|i| matrix[*i].iter().all(|m| *m == 0))
+        .collect::<Vec<_>>();
+    if !isolated.is_empty() {
+        let keep = (0..matrix.len())
+            .filter(|i| !isolated.contains(i))
+            .collect::<Vec<_>>();
+        let matrix = keep
+            .iter()
+            .map(|i| keep.iter().map(|j| matrix[*i][*j]).collect())
+            .collect();
+        return tutte(matrix, x, y, memo);
+    }
+    if matrix.is_empty() {
+        return poly_ring.one();
+    }
+    let key = canonical_matrix(&matrix);
+    if let Some(t) = memo.get(&key) {
+        return t.clone();
+    }
+    //remove or contract the whole class of k parallel edges between u and v
+    let v = (1..matrix.len()).find(|v| matrix[0][*v] != 0).unwrap();
+    let k = matrix[0][v];
+    //y + y^2 + ... + y^(k-1)
+    let parallel = poly_ring.sum(
+        (1..k)
+            .map(|i| poly_ring.nat_pow(y, &Natural::from(i)))
+            .collect(),
+    );
+    let contracted = tutte(contract(&matrix, 0, v), x, y, memo);
+    let mut deleted = matrix.clone();
+    deleted[0][v] = 0;
+    deleted[v][0] = 0;
+    let t = if is_connected_between(&deleted, 0, v) {
+        //T(G) = T(G - class) + (1 + y + ... + y^(k-1)) T(G / class)
+        poly_ring.add(
+            &tutte(deleted, x, y, memo),
+            &poly_ring.mul(&poly_ring.add(&poly_ring.one(), &parallel), &contracted),
+        )
+    } else {
+        //T(G) = (x + y + ... + y^(k-1)) T(G / class)
+        poly_ring.mul(&poly_ring.add(x, &parallel), &contracted)
+    };
+    memo.insert(key, t.clone());
+    t
+}
+
+/// Invariants of finite graphs computed using exact linear algebra and polynomials.
+///
+/// Rows and columns of matrices are indexed in the order given by `list_all_elements`.
+pub trait AlgebraicGraphInvariants: FiniteGraphSignature {
+    /// The matrix whose `(i, j)` entry is the number of edges from vertex `i` to vertex `j`.
+    /// An undirected edge counts in both directions and an undirected loop counts once.
+    fn adjacency_matrix(&self) -> Matrix<Integer> {
+        let multiplicities = self.edge_multiplicities();
+        let n = multiplicities.len();
+        Matrix::construct(n, n, |i, j| Integer::from(multiplicities[i][j]))
+    }
+
+    /// `D - A` where `A` is the adjacency matrix and `D` is the diagonal matrix of its row sums.
+    /// Loops do not contribute.
+    fn laplacian_matrix(&self) -> Matrix<Integer> {
+        let a = self.adjacency_matrix();
+        let n = a.rows();
+        Matrix::construct(n, n, |i, j| {
+            let degree = (0..n).map(|k| a.at(i, k).unwrap().clone()).sum::<Integer>();
+            if i == j {
+                degree - a.at(i, j).unwrap()
+            } else {
+                -a.at(i, j).unwrap()
+            }
+        })
+    }
+
+    /// `D + A` where `A` is the adjacency matrix and `D` is the diagonal matrix of its row sums.
+    fn signless_laplacian_matrix(&self) -> Matrix<Integer> {
+        let a = self.adjacency_matrix();
+        let n = a.rows();
+        Matrix::construct(n, n, |i, j| {
+            let degree = (0..n).map(|k| a.at(i, k).unwrap().clone()).sum::<Integer>();
+            if i == j {
+                degree + a.at(i, j).unwrap()
+            } else {
+                a.at(i, j).unwrap().clone()
+            }
+        })
+    }
+
+    /// The characteristic polynomial `det(xI - A)` of the adjacency matrix.
+    fn characteristic_polynomial(&self) -> Polynomial<Integer> {
+        integer_characteristic_polynomial(&self.adjacency_matrix())
+    }
+
+    /// The eigenvalues of the adjacency matrix with multiplicity.
+    fn complex_spectrum(&self) -> Vec<ComplexAlgebraic> {
+        self.characteristic_polynomial().all_complex_roots()
+    }
+
+    /// The eigenvalues of the adjacency matrix with multiplicity, in increasing order.
+    fn spectrum(&self) -> Vec<RealAlgebraic>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let mut spectrum = self.characteristic_polynomial().all_real_roots();
+        spectrum.sort();
+        spectrum
+    }
+
+    /// The eigenvalues of the Laplacian matrix with multiplicity, in increasing order.
+    fn laplacian_spectrum(&self) -> Vec<RealAlgebraic>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let mut spectrum =
+            integer_characteristic_polynomial(&self.laplacian_matrix()).all_real_roots();
+        spectrum.sort();
+        spectrum
+    }
+
+    /// The eigenvalues of the signless Laplacian matrix with multiplicity, in increasing order.
+    fn signless_laplacian_spectrum(&self) -> Vec<RealAlgebraic>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let mut spectrum =
+            integer_characteristic_polynomial(&self.signless_laplacian_matrix()).all_real_roots();
+        spectrum.sort();
+        spectrum
+    }
+
+    /// The number of spanning trees, computed by Kirchhoff's matrix-tree theorem.
+    /// Parallel edges give distinct spanning trees.
+    fn spanning_tree_count(&self) -> Natural
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let laplacian = self.laplacian_matrix();
+        let n = laplacian.rows();
+        if n == 0 {
+            return Natural::ZERO;
+        }
+        let minor = Matrix::construct(n - 1, n - 1, |i, j| {
+            laplacian.at(i + 1, j + 1).unwrap().clone()
+        });
+        minor.det().unwrap().abs()
+    }
+
+    /// The polynomial whose value at a natural number `k` is the number of proper colourings of the vertices with `k` colours.
+    /// It is computed by deletion-contraction, remembering the results for isomorphic minors.
+    fn chromatic_polynomial(&self) -> Polynomial<Integer>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        let multiplicities = self.edge_multiplicities();
+        if (0..multiplicities.len()).any(|i| multiplicities[i][i] != 0) {
+            return Polynomial::<Integer>::structure().zero();
+        }
+        let simple = multiplicities
+            .into_iter()
+            .map(|row| row.into_iter().map(|m| m.min(1)).collect())
+            .collect();
+        chromatic(simple, &mut HashMap::new())
+    }
+
+    /// The Tutte polynomial `T(x, y)`, computed by deletion-contraction, remembering the results for isomorphic minors.
+    /// Parallel edges and loops are taken into account.
+    fn tutte_polynomial(&self, x: &Variable, y: &Variable) -> MultiPolynomial<Integer>
+    where
+        Self: UndirectedGraphSignature,
+    {
+        tutte(
+            self.edge_multiplicities(),
+            &MultiPolynomial::var(x.clone()),
+            &MultiPolynomial::var(y.clone()),
+            &mut HashMap::new(),
+        )
+    }
+}
+impl<G: FiniteGraphSignature> AlgebraicGraphInvariants for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::FiniteGraphAlgorithms;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{
+        CompleteUndirectedGraph, DirectedCycleGraph, UndirectedCycleGraph, WheelGraph,
+    };
+    use crate::graph::UndirectedGraph;
+    use algebraeon_nzq::Rational;
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    fn int_poly(coeffs: Vec<i64>) -> Polynomial<Integer> {
+        Polynomial::from_coeffs(coeffs.into_iter().map(Integer::from).collect())
+    }
+
+    fn evaluate_tutte(
+        t: &MultiPolynomial<Integer>,
+        x: &Variable,
+        y: &Variable,
+        a: i64,
+        b: i64,
+    ) -> Integer {
+        t.evaluate(HashMap::from([
+            (x.clone(), Integer::from(a)),
+            (y.clone(), Integer::from(b)),
+        ]))
+    }
+
+    #[test]
+    fn test_matrices() {
+        let g = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (1, 2), (2, 2)]).unwrap();
+        assert_eq!(
+            g.adjacency_matrix(),
+            Matrix::from_rows(vec![vec![0, 1, 0], vec![1, 0, 2], vec![0, 2, 1]])
+        );
+        assert_eq!(
+            g.laplacian_matrix(),
+            Matrix::from_rows(vec![vec![1, -1, 0], vec![-1, 3, -2], vec![0, -2, 2]])
+        );
+        assert_eq!(
+            g.signless_laplacian_matrix(),
+            Matrix::from_rows(vec![vec![1, 1, 0], vec![1, 3, 2], vec![0, 2, 4]])
+        );
+        assert_eq!(g.spanning_tree_count(), Natural::from(2u32));
+
+        let d = DirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(3), 3).unwrap();
+        assert_eq!(
+            d.adjacency_matrix(),
+            Matrix::from_rows(vec![vec![0, 1, 0], vec![0, 0, 1], vec![1, 0, 0]])
+        );
+        assert_eq!(d.characteristic_polynomial(), int_poly(vec![-1, 0, 0, 1]));
+        assert_eq!(d.complex_spectrum().len(), 3);
+    }
+
+    #[test]
+    fn test_spectra() {
+        //K_4 has characteristic polynomial (x - 3)(x + 1)^3
+        let k4 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(4));
+        assert_eq!(
+            k4.characteristic_polynomial(),
+            int_poly(vec![-3, -8, -6, 0, 1])
+        );
+
+        let spectrum = petersen().spectrum();
+        let expected = [-2, -2, -2, -2, 1, 1, 1, 1, 1, 3]
+            .into_iter()
+            .map(|e| RealAlgebraic::Rational(Rational::from(e)))
+            .collect::<Vec<_>>();
+        assert_eq!(spectrum, expected);
+        let laplacian = petersen().laplacian_spectrum();
+        let expected = [0, 2, 2, 2, 2, 2, 5, 5, 5, 5]
+            .into_iter()
+            .map(|e| RealAlgebraic::Rational(Rational::from(e)))
+            .collect::<Vec<_>>();
+        assert_eq!(laplacian, expected);
+
+        //the eigenvalues of C_5 are 2 and the irrational (-1 ± sqrt 5) / 2 twice each
+        let c5 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(5), 5).unwrap();
+        let spectrum = c5.spectrum();
+        assert_eq!(spectrum.len(), 5);
+        assert_eq!(spectrum[0], spectrum[1]);
+        assert_eq!(spectrum[2], spectrum[3]);
+        assert_eq!(spectrum[4], RealAlgebraic::Rational(Rational::from(2)));
+        assert!(matches!(spectrum[0], RealAlgebraic::Real(_)));
+        //the signless Laplacian of a bipartite graph has the same spectrum as the Laplacian
+        let c6 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(6), 6).unwrap();
+        assert_eq!(c6.laplacian_spectrum(), c6.signless_laplacian_spectrum());
+        assert_ne!(c5.laplacian_spectrum(), c5.signless_laplacian_spectrum());
+    }
+
+    #[test]
+    fn test_spanning_tree_counts() {
+        for n in 1..7usize {
+            let k = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            let expected = if n == 1 { 1 } else { n.pow(n as u32 - 2) };
+            assert_eq!(k.spanning_tree_count(), Natural::from(expected));
+        }
+        for n in 3..9 {
+            let c = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(c.spanning_tree_count(), Natural::from(n));
+        }
+        //the wheel with r rim vertices has L(2r) - 2 spanning trees where L are the Lucas numbers
+        let mut lucas = vec![2usize, 1];
+        for i in 2..20 {
+            lucas.push(lucas[i - 1] + lucas[i - 2]);
+        }
+        for n in 4..10 {
+            let w = WheelGraph::new(n).unwrap();
+            assert_eq!(
+                w.spanning_tree_count(),
+                Natural::from(lucas[2 * (n - 1)] - 2)
+            );
+        }
+        assert_eq!(petersen().spanning_tree_count(), Natural::from(2000u32));
+        let disconnected = UndirectedGraph::from_edges(3, [(0, 1)]).unwrap();
+        assert_eq!(disconnected.spanning_tree_count(), Natural::ZERO);
+    }
+
+    #[test]
+    fn test_chromatic_polynomials() {
+        let poly_ring = Polynomial::<Integer>::structure();
+        for n in 3..9 {
+            //(x - 1)^n + (-1)^n (x - 1)
+            let c = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            let x_minus_one = int_poly(vec![-1, 1]);
+            let sign = if n % 2 == 0 { 1 } else { -1 };
+            let expected = poly_ring.add(
+                &poly_ring.nat_pow(&x_minus_one, &Natural::from(n)),
+                &poly_ring.mul(&int_poly(vec![sign]), &x_minus_one),
+            );
+            assert_eq!(c.chromatic_polynomial(), expected);
+            let colourings =
+                |k: i64| poly_ring.evaluate(&c.chromatic_polynomial(), &Integer::from(k));
+            assert_eq!(colourings(2) != Integer::ZERO, c.chromatic_number() == 2);
+        }
+        for n in 1..7 {
+            let k = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            let p = k.chromatic_polynomial();
+            assert_eq!(poly_ring.evaluate(&p, &Integer::from(n - 1)), Integer::ZERO);
+            assert_eq!(
+                poly_ring.evaluate(&p, &Integer::from(n)),
+                Integer::from((1..=n).product::<usize>())
+            );
+        }
+        for n in 4..9 {
+            let w = WheelGraph::new(n).unwrap();
+            let p = w.chromatic_polynomial();
+            let k = w.chromatic_number();
+            assert_eq!(poly_ring.evaluate(&p, &Integer::from(k - 1)), Integer::ZERO);
+            assert_ne!(poly_ring.evaluate(&p, &Integer::from(k)), Integer::ZERO);
+        }
+        let looped = UndirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        assert_eq!(looped.chromatic_polynomial(), poly_ring.zero());
+        let double = UndirectedGraph::from_edges(2, [(0, 1), (0, 1)]).unwrap();
+        assert_eq!(double.chromatic_polynomial(), int_poly(vec![0, -1, 1]));
+    }
+
+    #[test]
+    fn test_tutte_polynomials() {
+        let x = Variable::new("x");
+        let y = Variable::new("y");
+        let xp = MultiPolynomial::<Integer>::var(x.clone());
+        let yp = MultiPolynomial::<Integer>::var(y.clone());
+        let ring = MultiPolynomial::<Integer>::structure();
+
+        let k3 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(3));
+        assert_eq!(
+            k3.tutte_polynomial(&x, &y),
+            ring.sum(vec![ring.mul(&xp, &xp), xp.clone(), yp.clone()])
+        );
+        let double = UndirectedGraph::from_edges(2, [(0, 1), (0, 1)]).unwrap();
+        assert_eq!(double.tutte_polynomial(&x, &y), ring.add(&xp, &yp));
+        let looped = UndirectedGraph::from_edges(2, [(0, 1), (1, 1)]).unwrap();
+        assert_eq!(looped.tutte_polynomial(&x, &y), ring.mul(&xp, &yp));
+
+        let poly_ring = Polynomial::<Integer>::structure();
+        let graphs = vec![
+            petersen(),
+            UndirectedGraph::from_edges(5, [(0, 1), (1, 2), (2, 0), (2, 3), (3, 3), (1, 2)])
+                .unwrap(),
+            UndirectedGraph::from_edges(
+                6,
+                [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (4, 5), (1, 3)],
+            )
+            .unwrap(),
+        ];
+        for g in graphs {
+            let t = g.tutte_polynomial(&x, &y);
+            //T(1, 1) counts spanning trees of connected graphs, and T(2, 2) = 2^|E|
+            if g.is_connected() {
+                assert_eq!(
+                    evaluate_tutte(&t, &x, &y, 1, 1),
+                    Integer::from(g.spanning_tree_count())
+                );
+            }
+            assert_eq!(
+                evaluate_tutte(&t, &x, &y, 2, 2),
+                Integer::from(2i64.pow(g.edge_count() as u32))
+            );
+            //P(k) = (-1)^(n - c) k^c T(1 - k, 0)
+            let n = g.vertex_count();
+            let c = g.connected_components().len();
+            let p = g.chromatic_polynomial();
+            for k in 0..5i64 {
+                let sign = if (n - c) % 2 == 0 { 1 } else { -1 };
+                assert_eq!(
+                    poly_ring.evaluate(&p, &Integer::from(k)),
+                    Integer::from(sign * k.pow(c as u32)) * evaluate_tutte(&t, &x, &y, 1 - k, 0)
+                );
+            }
+        }
+    }
+}
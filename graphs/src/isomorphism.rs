@@ -0,0 +1,470 @@
+use crate::algorithms::Vertex;
+use crate::structure::FiniteGraphSignature;
+use algebraeon_groups::permutation::Permutation;
+use algebraeon_groups::stabilizer_chain::StabilizerChain;
+use algebraeon_nzq::Natural;
+use algebraeon_sets::structure::FiniteSetSignature;
+use std::cmp::Ordering;
+
+//refine the colouring until vertices of the same colour cannot be told apart by the colours of their neighbours
+//colours are 0, 1, ..., k-1 and each round splits cells while keeping their relative order
+fn refine(matrix: &[Vec<usize>], mut colours: Vec<usize>) -> Vec<usize> {
+    let n = matrix.len();
+    let mut cell_count = colours.iter().max().map_or(0, |c| c + 1);
+    loop {
+        let signatures = (0..n)
+            .map(|v| {
+                let mut out_edges = (0..n)
+                    .filter(|u| *u != v && matrix[v][*u] != 0)
+                    .map(|u| (colours[u], matrix[v][u]))
+                    .collect::<Vec<_>>();
+                out_edges.sort_unstable();
+                let mut in_edges = (0..n)
+                    .filter(|u| *u != v && matrix[*u][v] != 0)
+                    .map(|u| (colours[u], matrix[u][v]))
+                    .collect::<Vec<_>>();
+                in_edges.sort_unstable();
+                (colours[v], matrix[v][v], out_edges, in_edges)
+            })
+            .collect::<Vec<_>>();
+        let mut distinct = signatures.iter().collect::<Vec<_>>();
+        distinct.sort_unstable();
+        distinct.dedup();
+        colours = signatures
+            .iter()
+            .map(|s| distinct.binary_search(&s).unwrap())
+            .collect();
+        if distinct.len() == cell_count {
+            return colours;
+        }
+        cell_count = distinct.len();
+    }
+}
+
+//give v a colour of its own, placed just before the rest of its cell
+fn individualize(colours: &[usize], v: usize) -> Vec<usize> {
+    colours
+        .iter()
+        .enumerate()
+        .map(|(u, c)| {
+            if *c > colours[v] || (*c == colours[v] && u != v) {
+                c + 1
+            } else {
+                *c
+            }
+        })
+        .collect()
+}
+
+//the matrix with rows and columns permuted so that vertex v is moved to position labelling[v]
+fn relabel(matrix: &[Vec<usize>], labelling: &[usize]) -> Vec<Vec<usize>> {
+    let n = matrix.len();
+    let mut relabelled = vec![vec![0; n]; n];
+    for i in 0..n {
+        for j in 0..n {
+            relabelled[labelling[i]][labelling[j]] = matrix[i][j];
+        }
+    }
+    relabelled
+}
+
+fn union_find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+#[derive(Debug, Clone)]
+struct Leaf {
+    labelling: Vec<usize>,
+    certificate: Vec<Vec<usize>>,
+}
+
+impl Leaf {
+    //the automorphism g with g(v) = self^-1(other(v))
+    fn automorphism_to(&self, other: &Leaf) -> Vec<usize> {
+        let mut inverse = vec![0; self.labelling.len()];
+        for (v, l) in self.labelling.iter().enumerate() {
+            inverse[*l] = v;
+        }
+        other.labelling.iter().map(|l| inverse[*l]).collect()
+    }
+}
+
+/// Search the tree of refined partitions obtained by individualizing vertices.
+///
+/// The canonical labelling is the leaf with the greatest relabelled matrix.
+/// Leaves with equal relabelled matrices differ by an automorphism, and subtrees are pruned
+/// using the automorphisms found so far, as in McKay's algorithm.
+struct CanonicalSearch<'a> {
+    matrix: &'a [Vec<usize>],
+    first_path: Vec<usize>,
+    first: Option<Leaf>,
+    best: Option<Leaf>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<'a> CanonicalSearch<'a> {
+    fn run(matrix: &'a [Vec<usize>]) -> Self {
+        let mut search = Self {
+            matrix,
+            first_path: vec![],
+            first: None,
+            best: None,
+            automorphisms: vec![],
+        };
+        search.search(vec![0; matrix.len()], &mut vec![]);
+        search
+    }
+
+    //returns true when a leaf equivalent to the first leaf was found
+    //in that case the rest of the subtree below the last node on the first path can be skipped
+    fn search(&mut self, colours: Vec<usize>, path: &mut Vec<usize>) -> bool {
+        let n = self.matrix.len();
+        let colours = refine(self.matrix, colours);
+        //the first cell with more than one vertex
+        let target = (0..n).find(|c| colours.iter().filter(|d| *d == c).count() > 1);
+        let Some(target) = target else {
+            return self.leaf(colours, path);
+        };
+        let cell = (0..n).filter(|v| colours[*v] == target).collect::<Vec<_>>();
+        let mut explored: Vec<usize> = vec![];
+        for v in cell {
+            //skip v if an automorphism fixing the path maps an explored vertex to v
+            let mut parent = (0..n).collect::<Vec<_>>();
+            for g in &self.automorphisms {
+                if path.iter().all(|p| g[*p] == *p) {
+                    for (x, gx) in g.iter().enumerate() {
+                        let (a, b) = (
+                            union_find_root(&mut parent, x),
+                            union_find_root(&mut parent, *gx),
+                        );
+                        parent[a] = b;
+                    }
+                }
+            }
+            let root = union_find_root(&mut parent, v);
+            if explored
+                .iter()
+                .any(|u| union_find_root(&mut parent, *u) == root)
+            {
+                continue;
+            }
+            explored.push(v);
+
+            path.push(v);
+            let found_first = self.search(individualize(&colours, v), path);
+            path.pop();
+            if found_first && !self.first_path.starts_with(path) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn leaf(&mut self, labelling: Vec<usize>, path: &[usize]) -> bool {
+        let leaf = Leaf {
+            certificate: relabel(self.matrix, &labelling),
+            labelling,
+        };
+        let Some(first) = &self.first else {
+            self.first_path = path.to_vec();
+            self.first = Some(leaf.clone());
+            self.best = Some(leaf);
+            return false;
+        };
+        if leaf.certificate == first.certificate {
+            self.automorphisms.push(first.automorphism_to(&leaf));
+            return true;
+        }
+        let best = self.best.as_ref().unwrap();
+        match leaf.certificate.cmp(&best.certificate) {
+            Ordering::Less => {}
+            Ordering::Equal => {
+                self.automorphisms.push(best.automorphism_to(&leaf));
+            }
+            Ordering::Greater => {
+                self.best = Some(leaf);
+            }
+        }
+        false
+    }
+
+    fn best(&self) -> Leaf {
+        self.best.clone().unwrap_or(Leaf {
+            labelling: vec![],
+            certificate: vec![],
+        })
+    }
+}
+
+//the edge multiplicities relabelled into canonical order
+pub(crate) fn canonical_matrix(matrix: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    CanonicalSearch::run(matrix).best().certificate
+}
+
+/// Isomorphisms and automorphisms of finite graphs.
+///
+/// Graphs are compared as directed graphs with multiple edges, where an undirected edge is a pair of opposite directed edges.
+pub trait FiniteGraphIsomorphism: FiniteGraphSignature {
+    /// The vertices in canonical order.
+    /// Two graphs are isomorphic if and only if listing their vertices in canonical order is an isomorphism.
+    fn canonical_labelling(&self) -> Vec<Vertex<Self>> {
+        let elements = self.vertices().list_all_elements();
+        let labelling = CanonicalSearch::run(&self.edge_multiplicities())
+            .best()
+            .labelling;
+        let mut order = elements.clone();
+        for (v, l) in labelling.into_iter().enumerate() {
+            order[l] = elements[v].clone();
+        }
+        order
+    }
+
+    /// The edge multiplicities with vertices in canonical order.
+    /// Two graphs are isomorphic if and only if they have the same canonical form.
+    fn canonical_form(&self) -> Vec<Vec<usize>> {
+        canonical_matrix(&self.edge_multiplicities())
+    }
+
+    fn is_isomorphic_to<G: FiniteGraphSignature>(&self, other: &G) -> bool {
+        self.canonical_form() == other.canonical_form()
+    }
+
+    /// A bijection between the vertices of `self` and `other` which maps edges to edges, if there is one.
+    fn find_isomorphism<G: FiniteGraphSignature>(
+        &self,
+        other: &G,
+    ) -> Option<Vec<(Vertex<Self>, Vertex<G>)>> {
+        let self_leaf = CanonicalSearch::run(&self.edge_multiplicities()).best();
+        let other_leaf = CanonicalSearch::run(&other.edge_multiplicities()).best();
+        if self_leaf.certificate != other_leaf.certificate {
+            return None;
+        }
+        let self_elements = self.vertices().list_all_elements();
+        let other_elements = other.vertices().list_all_elements();
+        Some(
+            other_leaf
+                .automorphism_to(&self_leaf)
+                .into_iter()
+                .enumerate()
+                .map(|(v, w)| (self_elements[v].clone(), other_elements[w].clone()))
+                .collect(),
+        )
+    }
+
+    /// Generators of the automorphism group, as permutations of the positions of vertices in `list_all_elements`.
+    fn automorphism_group_generators(&self) -> Vec<Permutation> {
+        CanonicalSearch::run(&self.edge_multiplicities())
+            .automorphisms
+            .into_iter()
+            .map(|g| Permutation::new(g).unwrap())
+            .filter(|g| g.n() != 0)
+            .collect()
+    }
+
+    /// The automorphism group as a stabilizer chain, acting on the positions of vertices in `list_all_elements`.
+    fn automorphism_group(&self) -> StabilizerChain {
+        StabilizerChain::new(self.automorphism_group_generators())
+    }
+
+    fn automorphism_group_order(&self) -> Natural {
+        self.automorphism_group().order()
+    }
+}
+impl<G: FiniteGraphSignature> FiniteGraphIsomorphism for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{
+        CompleteUndirectedGraph, DirectedCycleGraph, UndirectedCycleGraph, WheelGraph,
+    };
+    use crate::graph::{DirectedGraph, UndirectedGraph};
+    use crate::random::gnm_random_graph;
+    use algebraeon_nzq::Rng;
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    fn prism() -> UndirectedGraph {
+        UndirectedGraph::from_edges(
+            10,
+            (0..5).flat_map(|i| [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 1) % 5 + 5)]),
+        )
+        .unwrap()
+    }
+
+    fn relabelled(graph: &UndirectedGraph, perm: &[usize]) -> UndirectedGraph {
+        UndirectedGraph::from_edges(
+            graph.vertex_count(),
+            graph.edges().map(|(u, v)| (perm[u], perm[v])),
+        )
+        .unwrap()
+    }
+
+    fn check_generators<G: FiniteGraphSignature>(graph: &G) {
+        let matrix = graph.edge_multiplicities();
+        for g in graph.automorphism_group_generators() {
+            assert_ne!(g, Permutation::new(vec![]).unwrap());
+            for i in 0..matrix.len() {
+                for j in 0..matrix.len() {
+                    assert_eq!(matrix[i][j], matrix[g.call(i)][g.call(j)]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_automorphism_group_orders() {
+        for n in 3..9 {
+            let c = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(c.automorphism_group_order(), Natural::from(2 * n));
+            check_generators(&c);
+
+            let d = DirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap();
+            assert_eq!(d.automorphism_group_order(), Natural::from(n));
+            check_generators(&d);
+        }
+        for n in 0..7 {
+            let k = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(n));
+            assert_eq!(
+                k.automorphism_group_order(),
+                Natural::from((1..=n).product::<usize>())
+            );
+            check_generators(&k);
+        }
+        assert_eq!(
+            WheelGraph::new(4).unwrap().automorphism_group_order(),
+            Natural::from(24u32)
+        );
+        for n in 5..10 {
+            let w = WheelGraph::new(n).unwrap();
+            assert_eq!(w.automorphism_group_order(), Natural::from(2 * (n - 1)));
+            check_generators(&w);
+        }
+        assert_eq!(petersen().automorphism_group_order(), Natural::from(120u32));
+        check_generators(&petersen());
+        assert_eq!(prism().automorphism_group_order(), Natural::from(20u32));
+
+        let cube = UndirectedGraph::from_edges(
+            8,
+            (0..8usize).flat_map(|v| {
+                (0..3)
+                    .map(move |b| (v, v ^ (1 << b)))
+                    .filter(|(u, v)| u < v)
+            }),
+        )
+        .unwrap();
+        assert_eq!(cube.automorphism_group_order(), Natural::from(48u32));
+        check_generators(&cube);
+    }
+
+    #[test]
+    fn test_isomorphism() {
+        let p = petersen();
+        let perm = [3, 7, 0, 9, 5, 1, 8, 2, 6, 4];
+        let q = relabelled(&p, &perm);
+        assert!(p.is_isomorphic_to(&q));
+        assert_eq!(p.canonical_form(), q.canonical_form());
+        let bijection = p.find_isomorphism(&q).unwrap();
+        let f = |v: usize| bijection.iter().find(|(a, _)| *a == v).unwrap().1;
+        for u in 0..10 {
+            for v in 0..10 {
+                assert_eq!(p.has_edge(u, v), q.has_edge(f(u), f(v)));
+            }
+        }
+        let canonical = relabelled(&p, &{
+            let order = p.canonical_labelling();
+            let mut perm = vec![0; 10];
+            for (i, v) in order.into_iter().enumerate() {
+                perm[v] = i;
+            }
+            perm
+        });
+        assert_eq!(canonical.edge_multiplicities(), p.canonical_form());
+
+        //both are 3-regular on 10 vertices
+        assert!(!p.is_isomorphic_to(&prism()));
+        assert!(p.find_isomorphism(&prism()).is_none());
+
+        let c6 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(6), 6).unwrap();
+        let two_triangles =
+            UndirectedGraph::from_edges(6, [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)])
+                .unwrap();
+        assert!(!c6.is_isomorphic_to(&two_triangles));
+        let hexagon =
+            UndirectedGraph::from_edges(6, [(0, 2), (2, 4), (4, 1), (1, 3), (3, 5), (5, 0)])
+                .unwrap();
+        assert!(c6.is_isomorphic_to(&hexagon));
+    }
+
+    #[test]
+    fn test_directed_and_multigraph_isomorphism() {
+        let path = DirectedGraph::from_edges(3, [(0, 1), (1, 2)]).unwrap();
+        let reversed = DirectedGraph::from_edges(3, [(2, 1), (1, 0)]).unwrap();
+        let fork = DirectedGraph::from_edges(3, [(0, 1), (0, 2)]).unwrap();
+        assert!(path.is_isomorphic_to(&reversed));
+        assert!(!path.is_isomorphic_to(&fork));
+        assert_eq!(fork.automorphism_group_order(), Natural::from(2u32));
+
+        let single = UndirectedGraph::from_edges(3, [(0, 1), (1, 2)]).unwrap();
+        let double = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (1, 2)]).unwrap();
+        let looped = UndirectedGraph::from_edges(3, [(0, 1), (1, 2), (2, 2)]).unwrap();
+        assert!(!single.is_isomorphic_to(&double));
+        assert!(!single.is_isomorphic_to(&looped));
+        assert_eq!(single.automorphism_group_order(), Natural::from(2u32));
+        assert_eq!(double.automorphism_group_order(), Natural::ONE);
+        assert!(
+            double.is_isomorphic_to(
+                &UndirectedGraph::from_edges(3, [(2, 0), (0, 1), (2, 0)]).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng::new(12345);
+        for n in 1..=6 {
+            let pairs = n * (n - 1) / 2;
+            let factorial = (1..=n).product::<usize>();
+            for i in 0..10 {
+                //orient the edges of one random graph forwards and the edges of another backwards
+                let forwards = gnm_random_graph(n, i * pairs / 9, &mut rng).unwrap();
+                let backwards = gnm_random_graph(n, (9 - i) * pairs / 18, &mut rng).unwrap();
+                let edges = forwards
+                    .edges()
+                    .chain(backwards.edges().map(|(u, v)| (v, u)))
+                    .collect::<Vec<_>>();
+                let g = DirectedGraph::from_edges(n, edges.clone()).unwrap();
+                let matrix = g.edge_multiplicities();
+                let automorphisms = Permutation::all_permutations(n)
+                    .filter(|p| {
+                        (0..n).all(|i| (0..n).all(|j| matrix[i][j] == matrix[p.call(i)][p.call(j)]))
+                    })
+                    .count();
+                assert_eq!(g.automorphism_group_order(), Natural::from(automorphisms));
+                check_generators(&g);
+
+                let index = rng.uniform_random_natural_less_than(Natural::from(factorial - 1));
+                let perm = Permutation::all_permutations(n)
+                    .nth(index.try_into().unwrap())
+                    .unwrap();
+                let h = DirectedGraph::from_edges(
+                    n,
+                    edges.iter().map(|(u, v)| (perm.call(*u), perm.call(*v))),
+                )
+                .unwrap();
+                assert_eq!(g.canonical_form(), h.canonical_form());
+                let bijection = g.find_isomorphism(&h).unwrap();
+                let h_matrix = h.edge_multiplicities();
+                for (u, fu) in &bijection {
+                    for (v, fv) in &bijection {
+                        assert_eq!(matrix[*u][*v], h_matrix[*fu][*fv]);
+                    }
+                }
+            }
+        }
+    }
+}
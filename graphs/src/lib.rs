@@ -5,5 +5,16 @@
     clippy::missing_errors_doc
 )]
 
+pub mod algorithms;
+pub mod colouring;
 pub mod examples;
+pub mod flow;
+pub mod formats;
+pub mod graph;
+pub mod invariants;
+pub mod isomorphism;
+pub mod matching;
+pub mod operations;
+pub mod planarity;
+pub mod random;
 pub mod structure;
@@ -0,0 +1,1075 @@
+use crate::algorithms::{FiniteGraphAlgorithms, Vertex};
+use crate::structure::FiniteGraphSignature;
+use algebraeon_rings::structure::{FieldSignature, OrderedRingSignature};
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+/// Pairs of matched vertices.
+pub type Matching<G> = Vec<(Vertex<G>, Vertex<G>)>;
+
+//neighbours ignoring the direction of edges and loops
+fn simple_neighbours(multiplicities: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = multiplicities.len();
+    (0..n)
+        .map(|u| {
+            (0..n)
+                .filter(|v| u != *v && (multiplicities[u][*v] != 0 || multiplicities[*v][u] != 0))
+                .collect()
+        })
+        .collect()
+}
+
+fn matching_from_mates<G: FiniteGraphSignature + ?Sized>(
+    graph: &G,
+    mate: &[Option<usize>],
+) -> Matching<G> {
+    let elements = graph.vertices().list_all_elements();
+    (0..mate.len())
+        .filter_map(|u| match mate[u] {
+            Some(v) if u < v => Some((elements[u].clone(), elements[v].clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+//Hopcroft-Karp on a bipartite graph with the given sides
+fn hopcroft_karp(neighbours: &[Vec<usize>], left: &[usize]) -> Vec<Option<usize>> {
+    let n = neighbours.len();
+    let mut mate: Vec<Option<usize>> = vec![None; n];
+    loop {
+        //layer the graph by breadth first search from the free left vertices
+        let mut layer: Vec<Option<usize>> = vec![None; n];
+        let mut queue = VecDeque::new();
+        for u in left {
+            if mate[*u].is_none() {
+                layer[*u] = Some(0);
+                queue.push_back(*u);
+            }
+        }
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            for v in &neighbours[u] {
+                match mate[*v] {
+                    None => found = true,
+                    Some(w) => {
+                        if layer[w].is_none() {
+                            layer[w] = Some(layer[u].unwrap() + 1);
+                            queue.push_back(w);
+                        }
+                    }
+                }
+            }
+        }
+        if !found {
+            return mate;
+        }
+        //find a maximal set of vertex disjoint shortest augmenting paths by depth first search
+        fn augment(
+            u: usize,
+            neighbours: &[Vec<usize>],
+            mate: &mut [Option<usize>],
+            layer: &mut [Option<usize>],
+        ) -> bool {
+            for v in &neighbours[u] {
+                let next = match mate[*v] {
+                    None => true,
+                    Some(w) => {
+                        layer[w] == layer[u].map(|l| l + 1) && augment(w, neighbours, mate, layer)
+                    }
+                };
+                if next {
+                    mate[u] = Some(*v);
+                    mate[*v] = Some(u);
+                    return true;
+                }
+            }
+            layer[u] = None;
+            false
+        }
+        for u in left {
+            if mate[*u].is_none() {
+                augment(*u, neighbours, &mut mate, &mut layer);
+            }
+        }
+    }
+}
+
+//Edmonds' blossom algorithm, growing an alternating tree from each free vertex in turn
+fn edmonds(neighbours: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let n = neighbours.len();
+    let mut mate: Vec<Option<usize>> = vec![None; n];
+
+    //the base of the blossom containing the lowest common ancestor of a and b in the alternating tree
+    let lowest_common_ancestor = |mate: &[Option<usize>],
+                                  base: &[usize],
+                                  parent: &[Option<usize>],
+                                  mut a: usize,
+                                  mut b: usize| {
+        let mut seen = vec![false; n];
+        loop {
+            a = base[a];
+            seen[a] = true;
+            match mate[a] {
+                None => break,
+                Some(m) => a = parent[m].unwrap(),
+            }
+        }
+        loop {
+            b = base[b];
+            if seen[b] {
+                return b;
+            }
+            b = parent[mate[b].unwrap()].unwrap();
+        }
+    };
+
+    for root in 0..n {
+        if mate[root].is_some() {
+            continue;
+        }
+        let mut in_tree = vec![false; n];
+        let mut parent: Vec<Option<usize>> = vec![None; n];
+        let mut base = (0..n).collect::<Vec<_>>();
+        in_tree[root] = true;
+        let mut queue = VecDeque::from([root]);
+        let mut free_end = None;
+        'search: while let Some(v) = queue.pop_front() {
+            for to in &neighbours[v] {
+                let to = *to;
+                if base[v] == base[to] || mate[v] == Some(to) {
+                    continue;
+                }
+                if to == root || mate[to].is_some_and(|m| parent[m].is_some()) {
+                    //an odd cycle, so contract the blossom
+                    let blossom_base = lowest_common_ancestor(&mate, &base, &parent, v, to);
+                    let mut in_blossom = vec![false; n];
+                    for (mut x, mut child) in [(v, to), (to, v)] {
+                        while base[x] != blossom_base {
+                            let m = mate[x].unwrap();
+                            in_blossom[base[x]] = true;
+                            in_blossom[base[m]] = true;
+                            parent[x] = Some(child);
+                            child = m;
+                            x = parent[m].unwrap();
+                        }
+                    }
+                    for i in 0..n {
+                        if in_blossom[base[i]] {
+                            base[i] = blossom_base;
+                            if !in_tree[i] {
+                                in_tree[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if parent[to].is_none() {
+                    parent[to] = Some(v);
+                    match mate[to] {
+                        None => {
+                            free_end = Some(to);
+                            break 'search;
+                        }
+                        Some(m) => {
+                            in_tree[m] = true;
+                            queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+        //flip the edges along the augmenting path
+        let mut v = free_end;
+        while let Some(x) = v {
+            let p = parent[x].unwrap();
+            let next = mate[p];
+            mate[x] = Some(p);
+            mate[p] = Some(x);
+            v = next;
+        }
+    }
+    mate
+}
+
+const NONE: usize = usize::MAX;
+
+/// The primal-dual weighted blossom algorithm of Edmonds, following Galil's presentation.
+///
+/// Dual variables are stored doubled so that the slack of an edge `ij` is `u_i + u_j - 2w_ij`.
+/// Vertices are `0..n` and blossoms are `n..2n`.
+/// Edge `k` has endpoints `2k` and `2k + 1`, so that `p ^ 1` is the other end of the edge with endpoint `p`.
+struct WeightedBlossom<'a, R: OrderedRingSignature + FieldSignature> {
+    ring: &'a R,
+    n: usize,
+    edges: Vec<(usize, usize, R::Set)>,
+    max_cardinality: bool,
+    endpoint: Vec<usize>,
+    neighbour_ends: Vec<Vec<usize>>,
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    label_end: Vec<usize>,
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<usize>,
+    blossom_children: Vec<Vec<usize>>,
+    blossom_base: Vec<usize>,
+    blossom_ends: Vec<Vec<usize>>,
+    best_edge: Vec<usize>,
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused_blossoms: Vec<usize>,
+    dual: Vec<R::Set>,
+    allow_edge: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<'a, R: OrderedRingSignature + FieldSignature> WeightedBlossom<'a, R> {
+    fn new(
+        ring: &'a R,
+        n: usize,
+        edges: Vec<(usize, usize, R::Set)>,
+        max_cardinality: bool,
+    ) -> Self {
+        let mut max_weight = ring.zero();
+        for (_, _, w) in &edges {
+            if ring.ring_cmp(w, &max_weight) == Ordering::Greater {
+                max_weight = w.clone();
+            }
+        }
+        let mut neighbour_ends = vec![vec![]; n];
+        let mut endpoint = vec![];
+        for (k, (i, j, _)) in edges.iter().enumerate() {
+            endpoint.push(*i);
+            endpoint.push(*j);
+            neighbour_ends[*i].push(2 * k + 1);
+            neighbour_ends[*j].push(2 * k);
+        }
+        let edge_count = edges.len();
+        Self {
+            ring,
+            n,
+            edges,
+            max_cardinality,
+            endpoint,
+            neighbour_ends,
+            mate: vec![NONE; n],
+            label: vec![0; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            in_blossom: (0..n).collect(),
+            blossom_parent: vec![NONE; 2 * n],
+            blossom_children: vec![vec![]; 2 * n],
+            blossom_base: (0..n).chain(std::iter::repeat_n(NONE, n)).collect(),
+            blossom_ends: vec![vec![]; 2 * n],
+            best_edge: vec![NONE; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused_blossoms: (n..2 * n).collect(),
+            dual: std::iter::repeat_n(max_weight, n)
+                .chain(std::iter::repeat_n(ring.zero(), n))
+                .collect(),
+            allow_edge: vec![false; edge_count],
+            queue: vec![],
+        }
+    }
+
+    fn less(&self, a: &R::Set, b: &R::Set) -> bool {
+        self.ring.ring_cmp(a, b) == Ordering::Less
+    }
+
+    fn is_zero(&self, a: &R::Set) -> bool {
+        self.ring.ring_cmp(a, &self.ring.zero()) == Ordering::Equal
+    }
+
+    fn slack(&self, k: usize) -> R::Set {
+        let (i, j, w) = &self.edges[k];
+        self.ring.sub(
+            &self.ring.add(&self.dual[*i], &self.dual[*j]),
+            &self.ring.add(w, w),
+        )
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.n {
+            vec![b]
+        } else {
+            self.blossom_children[b]
+                .iter()
+                .flat_map(|t| self.leaves(*t))
+                .collect()
+        }
+    }
+
+    //label the top level blossom containing w with t, reached through the edge with endpoint p
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let base = self.blossom_base[b];
+            let m = self.mate[base];
+            self.assign_label(self.endpoint[m], 1, m ^ 1);
+        }
+    }
+
+    //trace back from v and w to find the base of a new blossom, or NONE if there is an augmenting path
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = vec![];
+        let mut base = NONE;
+        while v != NONE || w != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            if self.label_end[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k].clone();
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused_blossoms.pop().unwrap();
+        self.blossom_base[b] = base;
+        self.blossom_parent[b] = NONE;
+        self.blossom_parent[bb] = b;
+        let mut path = vec![];
+        let mut ends = vec![];
+        while bv != bb {
+            self.blossom_parent[bv] = b;
+            path.push(bv);
+            ends.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.blossom_parent[bw] = b;
+            path.push(bw);
+            ends.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = self.ring.zero();
+        for leaf in self.leaves_of_children(&path) {
+            if self.label[self.in_blossom[leaf]] == 2 {
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = b;
+        }
+        //the least slack edge from the new blossom to each other S-blossom
+        let mut best_edge_to = vec![NONE; 2 * self.n];
+        for bv in &path {
+            let lists = match self.blossom_best_edges[*bv].take() {
+                None => self
+                    .leaves(*bv)
+                    .into_iter()
+                    .map(|leaf| self.neighbour_ends[leaf].iter().map(|p| p / 2).collect())
+                    .collect::<Vec<Vec<_>>>(),
+                Some(list) => vec![list],
+            };
+            for list in lists {
+                for k in list {
+                    let (mut i, mut j, _) = self.edges[k].clone();
+                    if self.in_blossom[j] == b {
+                        std::mem::swap(&mut i, &mut j);
+                    }
+                    let bj = self.in_blossom[j];
+                    if bj != b
+                        && self.label[bj] == 1
+                        && (best_edge_to[bj] == NONE
+                            || self.less(&self.slack(k), &self.slack(best_edge_to[bj])))
+                    {
+                        best_edge_to[bj] = k;
+                    }
+                }
+            }
+            self.best_edge[*bv] = NONE;
+        }
+        let best_edges = best_edge_to
+            .into_iter()
+            .filter(|k| *k != NONE)
+            .collect::<Vec<_>>();
+        self.best_edge[b] = NONE;
+        for k in &best_edges {
+            if self.best_edge[b] == NONE
+                || self.less(&self.slack(*k), &self.slack(self.best_edge[b]))
+            {
+                self.best_edge[b] = *k;
+            }
+        }
+        self.blossom_best_edges[b] = Some(best_edges);
+        self.blossom_children[b] = path;
+        self.blossom_ends[b] = ends;
+    }
+
+    fn leaves_of_children(&self, children: &[usize]) -> Vec<usize> {
+        children.iter().flat_map(|c| self.leaves(*c)).collect()
+    }
+
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for s in self.blossom_children[b].clone() {
+            self.blossom_parent[s] = NONE;
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.is_zero(&self.dual[s]) {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for leaf in self.leaves(s) {
+                    self.in_blossom[leaf] = s;
+                }
+            }
+        }
+        if !end_stage && self.label[b] == 2 {
+            //relabel the children on the even length path from the entry child to the base
+            let children = self.blossom_children[b].clone();
+            let ends = self.blossom_ends[b].clone();
+            let len = children.len() as isize;
+            let at = |j: isize| j.rem_euclid(len) as usize;
+            let entry_child = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let mut j = children.iter().position(|c| *c == entry_child).unwrap() as isize;
+            let (step, trick) = if j & 1 == 1 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.label_end[b];
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[ends[at(j - trick)] ^ (trick as usize) ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p);
+                self.allow_edge[ends[at(j - trick)] / 2] = true;
+                j += step;
+                p = ends[at(j - trick)] ^ (trick as usize);
+                self.allow_edge[p / 2] = true;
+                j += step;
+            }
+            let bv = children[at(j)];
+            self.label[self.endpoint[p ^ 1]] = 2;
+            self.label[bv] = 2;
+            self.label_end[self.endpoint[p ^ 1]] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += step;
+            while children[at(j)] != entry_child {
+                let bv = children[at(j)];
+                if self.label[bv] == 1 {
+                    j += step;
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|v| self.label[*v] != 0) {
+                    self.label[v] = 0;
+                    self.label[self.endpoint[self.mate[self.blossom_base[bv]]]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+                j += step;
+            }
+        }
+        self.label[b] = 0;
+        self.label_end[b] = NONE;
+        self.blossom_children[b] = vec![];
+        self.blossom_ends[b] = vec![];
+        self.blossom_base[b] = NONE;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused_blossoms.push(b);
+    }
+
+    //swap matched and unmatched edges on the path through blossom b from vertex v to its base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossom_parent[t] != b {
+            t = self.blossom_parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+        let len = self.blossom_children[b].len() as isize;
+        let at = |j: isize| j.rem_euclid(len) as usize;
+        let i = self.blossom_children[b]
+            .iter()
+            .position(|c| *c == t)
+            .unwrap();
+        let mut j = i as isize;
+        let (step, trick) = if j & 1 == 1 {
+            j -= len;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += step;
+            let t = self.blossom_children[b][at(j)];
+            let p = self.blossom_ends[b][at(j - trick)] ^ (trick as usize);
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += step;
+            let t = self.blossom_children[b][at(j)];
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.blossom_children[b].rotate_left(i);
+        self.blossom_ends[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_children[b][0]];
+    }
+
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k].clone();
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    fn run(mut self) -> Vec<Option<usize>> {
+        let n = self.n;
+        let two = self.ring.add(&self.ring.one(), &self.ring.one());
+        for _ in 0..n {
+            self.label = vec![0; 2 * n];
+            self.best_edge = vec![NONE; 2 * n];
+            for b in n..2 * n {
+                self.blossom_best_edges[b] = None;
+            }
+            self.allow_edge = vec![false; self.edges.len()];
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.in_blossom[v]] == 0 {
+                    self.assign_label(v, 1, NONE);
+                }
+            }
+            let mut augmented = false;
+            loop {
+                while !augmented && let Some(v) = self.queue.pop() {
+                    for p in self.neighbour_ends[v].clone() {
+                        let k = p / 2;
+                        let w = self.endpoint[p];
+                        if self.in_blossom[v] == self.in_blossom[w] {
+                            continue;
+                        }
+                        let mut k_slack = None;
+                        if !self.allow_edge[k] {
+                            let s = self.slack(k);
+                            if !self.less(&self.ring.zero(), &s) {
+                                self.allow_edge[k] = true;
+                            }
+                            k_slack = Some(s);
+                        }
+                        if self.allow_edge[k] {
+                            if self.label[self.in_blossom[w]] == 0 {
+                                self.assign_label(w, 2, p ^ 1);
+                            } else if self.label[self.in_blossom[w]] == 1 {
+                                let base = self.scan_blossom(v, w);
+                                if base != NONE {
+                                    self.add_blossom(base, k);
+                                } else {
+                                    self.augment_matching(k);
+                                    augmented = true;
+                                    break;
+                                }
+                            } else if self.label[w] == 0 {
+                                self.label[w] = 2;
+                                self.label_end[w] = p ^ 1;
+                            }
+                        } else if self.label[self.in_blossom[w]] == 1 {
+                            let b = self.in_blossom[v];
+                            if self.best_edge[b] == NONE
+                                || self
+                                    .less(k_slack.as_ref().unwrap(), &self.slack(self.best_edge[b]))
+                            {
+                                self.best_edge[b] = k;
+                            }
+                        } else if self.label[w] == 0
+                            && (self.best_edge[w] == NONE
+                                || self.less(
+                                    k_slack.as_ref().unwrap(),
+                                    &self.slack(self.best_edge[w]),
+                                ))
+                        {
+                            self.best_edge[w] = k;
+                        }
+                    }
+                }
+                if augmented {
+                    break;
+                }
+
+                //the largest change of the dual variables keeping every slack non-negative
+                let mut delta_type = 0;
+                let mut delta: Option<R::Set> = None;
+                let mut delta_edge = NONE;
+                let mut delta_blossom = NONE;
+                let smaller = |delta: &Option<R::Set>, d: &R::Set| {
+                    delta.as_ref().is_none_or(|delta| self.less(d, delta))
+                };
+                if !self.max_cardinality {
+                    delta_type = 1;
+                    delta = self.dual[..n]
+                        .iter()
+                        .min_by(|a, b| self.ring.ring_cmp(a, b))
+                        .cloned();
+                }
+                for v in 0..n {
+                    if self.label[self.in_blossom[v]] == 0 && self.best_edge[v] != NONE {
+                        let d = self.slack(self.best_edge[v]);
+                        if smaller(&delta, &d) {
+                            delta = Some(d);
+                            delta_type = 2;
+                            delta_edge = self.best_edge[v];
+                        }
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.blossom_parent[b] == NONE
+                        && self.label[b] == 1
+                        && self.best_edge[b] != NONE
+                    {
+                        let d = self.ring.div(&self.slack(self.best_edge[b]), &two).unwrap();
+                        if smaller(&delta, &d) {
+                            delta = Some(d);
+                            delta_type = 3;
+                            delta_edge = self.best_edge[b];
+                        }
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE
+                        && self.blossom_parent[b] == NONE
+                        && self.label[b] == 2
+                        && smaller(&delta, &self.dual[b])
+                    {
+                        delta = Some(self.dual[b].clone());
+                        delta_type = 4;
+                        delta_blossom = b;
+                    }
+                }
+                if delta_type == 0 {
+                    //no further progress is possible with maximum cardinality
+                    delta_type = 1;
+                    let least = self.dual[..n]
+                        .iter()
+                        .min_by(|a, b| self.ring.ring_cmp(a, b))
+                        .unwrap()
+                        .clone();
+                    delta = Some(if self.less(&least, &self.ring.zero()) {
+                        self.ring.zero()
+                    } else {
+                        least
+                    });
+                }
+                let delta = delta.unwrap();
+
+                for v in 0..n {
+                    match self.label[self.in_blossom[v]] {
+                        1 => self.dual[v] = self.ring.sub(&self.dual[v], &delta),
+                        2 => self.dual[v] = self.ring.add(&self.dual[v], &delta),
+                        _ => {}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE {
+                        match self.label[b] {
+                            1 => self.dual[b] = self.ring.add(&self.dual[b], &delta),
+                            2 => self.dual[b] = self.ring.sub(&self.dual[b], &delta),
+                            _ => {}
+                        }
+                    }
+                }
+
+                match delta_type {
+                    1 => break,
+                    2 => {
+                        self.allow_edge[delta_edge] = true;
+                        let (i, j, _) = self.edges[delta_edge].clone();
+                        let i = if self.label[self.in_blossom[i]] == 0 {
+                            j
+                        } else {
+                            i
+                        };
+                        self.queue.push(i);
+                    }
+                    3 => {
+                        self.allow_edge[delta_edge] = true;
+                        let (i, _, _) = self.edges[delta_edge].clone();
+                        self.queue.push(i);
+                    }
+                    _ => self.expand_blossom(delta_blossom, false),
+                }
+            }
+            if !augmented {
+                break;
+            }
+            //blossoms with zero dual which are not needed any more
+            for b in n..2 * n {
+                if self.blossom_parent[b] == NONE
+                    && self.blossom_base[b] != NONE
+                    && self.label[b] == 1
+                    && self.is_zero(&self.dual[b])
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+        self.mate
+            .iter()
+            .map(|p| {
+                if *p == NONE {
+                    None
+                } else {
+                    Some(self.endpoint[*p])
+                }
+            })
+            .collect()
+    }
+}
+
+/// Matchings of finite graphs, where the direction of edges and loops are ignored.
+///
+/// A matching is returned as pairs of vertices, each listed in the order given by `list_all_elements`.
+pub trait GraphMatching: FiniteGraphSignature {
+    /// Whether the pairs are edges of the graph and no vertex occurs twice.
+    fn is_matching(&self, matching: &[(Vertex<Self>, Vertex<Self>)]) -> bool {
+        let mut used = vec![];
+        for (u, v) in matching {
+            if self.vertices().equal(u, v)
+                || (self.has_directed_edge(u, v).is_err() && self.has_directed_edge(v, u).is_err())
+            {
+                return false;
+            }
+            for w in [u, v] {
+                if used.iter().any(|x| self.vertices().equal(x, w)) {
+                    return false;
+                }
+                used.push(w.clone());
+            }
+        }
+        true
+    }
+
+    /// A matching of largest size in a bipartite graph, computed by the Hopcroft-Karp algorithm.
+    /// Each pair lists the vertex from the first part of `bipartition` first.
+    /// Returns `Err` if the graph is not bipartite.
+    fn maximum_bipartite_matching(&self) -> Result<Matching<Self>, String>
+    where
+        Self: Sized,
+    {
+        let elements = self.vertices().list_all_elements();
+        let neighbours = simple_neighbours(&self.edge_multiplicities());
+        let (left, _) = self
+            .bipartition()
+            .ok_or_else(|| "The graph is not bipartite".to_string())?;
+        let left = left
+            .iter()
+            .map(|u| {
+                elements
+                    .iter()
+                    .position(|v| self.vertices().equal(u, v))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let mate = hopcroft_karp(&neighbours, &left);
+        Ok(left
+            .into_iter()
+            .filter_map(|u| Some((elements[u].clone(), elements[mate[u]?].clone())))
+            .collect())
+    }
+
+    /// A matching of largest size, computed by Edmonds' blossom algorithm.
+    fn maximum_matching(&self) -> Matching<Self> {
+        let mate = edmonds(&simple_neighbours(&self.edge_multiplicities()));
+        matching_from_mates(self, &mate)
+    }
+
+    /// A matching covering every vertex, if there is one.
+    fn perfect_matching(&self) -> Option<Matching<Self>> {
+        let matching = self.maximum_matching();
+        if 2 * matching.len() == self.vertices().size() {
+            Some(matching)
+        } else {
+            None
+        }
+    }
+
+    /// A matching of greatest total weight, computed by the weighted blossom algorithm.
+    /// Edges of negative weight are never used.
+    fn maximum_weight_matching<R: OrderedRingSignature + FieldSignature>(
+        &self,
+        ring: &R,
+        weight: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Matching<Self> {
+        let elements = self.vertices().list_all_elements();
+        let neighbours = simple_neighbours(&self.edge_multiplicities());
+        let edges = (0..elements.len())
+            .flat_map(|u| {
+                neighbours[u]
+                    .iter()
+                    .filter(move |v| u < **v)
+                    .map(move |v| (u, *v))
+            })
+            .map(|(u, v)| (u, v, weight(&elements[u], &elements[v])))
+            .collect();
+        let mate = WeightedBlossom::new(ring, elements.len(), edges, false).run();
+        matching_from_mates(self, &mate)
+    }
+
+    /// A perfect matching of least total weight, or `None` if there is no perfect matching.
+    fn minimum_weight_perfect_matching<R: OrderedRingSignature + FieldSignature>(
+        &self,
+        ring: &R,
+        weight: impl Fn(&Vertex<Self>, &Vertex<Self>) -> R::Set,
+    ) -> Option<Matching<Self>> {
+        let elements = self.vertices().list_all_elements();
+        let neighbours = simple_neighbours(&self.edge_multiplicities());
+        let edges = (0..elements.len())
+            .flat_map(|u| {
+                neighbours[u]
+                    .iter()
+                    .filter(move |v| u < **v)
+                    .map(move |v| (u, *v))
+            })
+            .map(|(u, v)| (u, v, weight(&elements[u], &elements[v])))
+            .collect::<Vec<_>>();
+        //among matchings of largest size, maximize the total of c - w where c exceeds every weight
+        let mut c = ring.one();
+        for (_, _, w) in &edges {
+            let bound = ring.add(w, &ring.one());
+            if ring.ring_cmp(&bound, &c) == Ordering::Greater {
+                c = bound;
+            }
+        }
+        let edges = edges
+            .into_iter()
+            .map(|(u, v, w)| (u, v, ring.sub(&c, &w)))
+            .collect();
+        let mate = WeightedBlossom::new(ring, elements.len(), edges, true).run();
+        if mate.iter().any(|m| m.is_none()) {
+            return None;
+        }
+        Some(matching_from_mates(self, &mate))
+    }
+}
+impl<G: FiniteGraphSignature> GraphMatching for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{CompleteUndirectedGraph, UndirectedCycleGraph};
+    use crate::graph::UndirectedGraph;
+    use crate::random::gnp_random_graph;
+    use algebraeon_nzq::{Integer, Rational, Rng};
+    use algebraeon_sets::structure::{EnumeratedFiniteSetStructure, MetaType};
+
+    //all matchings of the graph, by brute force
+    fn all_matchings(g: &UndirectedGraph) -> Vec<Vec<(usize, usize)>> {
+        fn extend(
+            g: &UndirectedGraph,
+            u: usize,
+            used: &mut Vec<bool>,
+            current: &mut Vec<(usize, usize)>,
+            all: &mut Vec<Vec<(usize, usize)>>,
+        ) {
+            if u == used.len() {
+                all.push(current.clone());
+                return;
+            }
+            extend(g, u + 1, used, current, all);
+            if used[u] {
+                return;
+            }
+            for v in u + 1..used.len() {
+                if !used[v] && g.has_edge(u, v) {
+                    used[u] = true;
+                    used[v] = true;
+                    current.push((u, v));
+                    extend(g, u + 1, used, current, all);
+                    current.pop();
+                    used[u] = false;
+                    used[v] = false;
+                }
+            }
+        }
+        let mut all = vec![];
+        extend(
+            g,
+            0,
+            &mut vec![false; g.vertex_count()],
+            &mut vec![],
+            &mut all,
+        );
+        all
+    }
+
+    //random graphs on up to max_n vertices with edge probabilities 0, 1/4, 1/2, 3/4 and 1
+    fn random_graphs(rng: &mut Rng, max_n: usize) -> Vec<UndirectedGraph> {
+        let mut graphs = vec![];
+        for n in 1..=max_n {
+            for k in 0..=4 {
+                graphs.push(gnp_random_graph(n, &Rational::from_integers(k, 4), rng).unwrap());
+            }
+        }
+        graphs
+    }
+
+    #[test]
+    fn test_bipartite_matching() {
+        //a 3x3 grid is bipartite with parts of sizes 5 and 4
+        let grid = UndirectedGraph::from_edges(
+            9,
+            (0..9).flat_map(|v| {
+                let mut e = vec![];
+                if v % 3 != 2 {
+                    e.push((v, v + 1));
+                }
+                if v < 6 {
+                    e.push((v, v + 3));
+                }
+                e
+            }),
+        )
+        .unwrap();
+        let matching = grid.maximum_bipartite_matching().unwrap();
+        assert_eq!(matching.len(), 4);
+        assert!(grid.is_matching(&matching));
+        assert!(petersen().maximum_bipartite_matching().is_err());
+
+        let c6 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(6), 6).unwrap();
+        let matching = c6.maximum_bipartite_matching().unwrap();
+        assert_eq!(matching.len(), 3);
+        assert!(c6.is_matching(&matching));
+        for (u, _) in &matching {
+            assert_eq!(u % 2, 0);
+        }
+    }
+
+    #[test]
+    fn test_maximum_matching() {
+        let p = petersen();
+        let matching = p.perfect_matching().unwrap();
+        assert_eq!(matching.len(), 5);
+        assert!(p.is_matching(&matching));
+        let c7 = UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(7), 7).unwrap();
+        assert_eq!(c7.maximum_matching().len(), 3);
+        assert!(c7.perfect_matching().is_none());
+        assert!(!p.is_matching(&[(0, 1), (1, 2)]));
+        assert!(!p.is_matching(&[(0, 2)]));
+
+        for g in random_graphs(&mut Rng::new(31415), 9) {
+            let largest = all_matchings(&g)
+                .into_iter()
+                .map(|m| m.len())
+                .max()
+                .unwrap();
+            let matching = g.maximum_matching();
+            assert!(g.is_matching(&matching));
+            assert_eq!(matching.len(), largest);
+            if let Ok(matching) = g.maximum_bipartite_matching() {
+                assert!(g.is_matching(&matching));
+                assert_eq!(matching.len(), largest);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_matching() {
+        let ring = Rational::structure();
+        let k4 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(4));
+        let weight = |u: &usize, v: &usize| Rational::from((u * v + u + v) as i64);
+        //the perfect matchings {01,23}, {02,13} and {03,12} have weights 12, 9 and 8
+        let matching = k4.minimum_weight_perfect_matching(&ring, weight).unwrap();
+        assert_eq!(matching, vec![(0, 3), (1, 2)]);
+        let matching = k4.maximum_weight_matching(&ring, weight);
+        assert_eq!(matching, vec![(0, 1), (2, 3)]);
+
+        let mut rng = Rng::new(1618);
+        for g in random_graphs(&mut rng, 8) {
+            let n = g.vertex_count();
+            let weights = (0..n * n)
+                .map(|_| {
+                    Rational::from_integers(
+                        rng.uniform_random_integer_from_inclusive_range(
+                            Integer::from(-10),
+                            Integer::from(10),
+                        ),
+                        rng.uniform_random_integer_from_inclusive_range(
+                            Integer::from(1),
+                            Integer::from(4),
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let weight = |u: &usize, v: &usize| weights[u.min(v) * n + u.max(v)].clone();
+            let total = |m: &[(usize, usize)]| {
+                m.iter()
+                    .map(|(u, v)| weight(u, v))
+                    .fold(Rational::from(0), |a, b| a + b)
+            };
+            let matchings = all_matchings(&g);
+
+            let best = matchings.iter().map(|m| total(m)).max().unwrap();
+            let matching = g.maximum_weight_matching(&ring, weight);
+            assert!(g.is_matching(&matching));
+            assert_eq!(total(&matching), best);
+
+            let least_perfect = matchings
+                .iter()
+                .filter(|m| 2 * m.len() == n)
+                .map(|m| total(m))
+                .min();
+            match g.minimum_weight_perfect_matching(&ring, weight) {
+                Some(matching) => {
+                    assert!(g.is_matching(&matching));
+                    assert_eq!(2 * matching.len(), n);
+                    assert_eq!(Some(total(&matching)), least_perfect);
+                }
+                None => assert_eq!(least_perfect, None),
+            }
+        }
+    }
+}
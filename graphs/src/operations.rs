@@ -0,0 +1,246 @@
+use crate::graph::Graph;
+
+/// Operations building new graphs out of old ones.
+///
+/// The complement and the products only look at which pairs of vertices are joined,
+/// so multiple edges are treated as a single edge.
+impl<const DIRECTED: bool> Graph<DIRECTED> {
+    //the ordered pairs of vertices of a graph on n vertices which may be joined by an edge
+    fn vertex_pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
+        (0..n).flat_map(move |a| ((if DIRECTED { 0 } else { a })..n).map(move |b| (a, b)))
+    }
+
+    /// The simple graph on the same vertices whose edges join the distinct vertices not joined in `self`.
+    pub fn complement(&self) -> Self {
+        Self::from_edges(
+            self.vertex_count(),
+            Self::vertex_pairs(self.vertex_count())
+                .filter(|(u, v)| u != v && !self.has_edge(*u, *v)),
+        )
+        .unwrap()
+    }
+
+    /// The line graph whose vertices are the edge labels of `self`.
+    ///
+    /// If undirected, two distinct edges are adjacent when they share an endpoint.
+    /// If directed, there is an edge from `e` to `f` when `e` ends where `f` starts.
+    pub fn line_graph(&self) -> Self {
+        let m = self.edge_count();
+        Self::from_edges(
+            m,
+            Self::vertex_pairs(m).filter(|(e, f)| {
+                let (s, t) = self.edge(*e);
+                let (u, v) = self.edge(*f);
+                if DIRECTED {
+                    t == u
+                } else {
+                    e != f && (s == u || s == v || t == u || t == v)
+                }
+            }),
+        )
+        .unwrap()
+    }
+
+    fn product(&self, other: &Self, adjacent: impl Fn(bool, bool, bool, bool) -> bool) -> Self {
+        let n = other.vertex_count();
+        Self::from_edges(
+            self.vertex_count() * n,
+            Self::vertex_pairs(self.vertex_count() * n).filter(|(a, b)| {
+                let (u1, v1) = (a / n, a % n);
+                let (u2, v2) = (b / n, b % n);
+                adjacent(
+                    u1 == u2,
+                    self.has_edge(u1, u2),
+                    v1 == v2,
+                    other.has_edge(v1, v2),
+                )
+            }),
+        )
+        .unwrap()
+    }
+
+    /// The Cartesian product, where `(u1, v1)` and `(u2, v2)` are adjacent when one coordinate
+    /// is equal and the other coordinates are adjacent.
+    ///
+    /// The vertex `(u, v)` is labelled `u * m + v` where `m` is the number of vertices of `other`.
+    pub fn cartesian_product(&self, other: &Self) -> Self {
+        self.product(other, |u_equal, u_adjacent, v_equal, v_adjacent| {
+            (u_equal && v_adjacent) || (u_adjacent && v_equal)
+        })
+    }
+
+    /// The tensor product, where `(u1, v1)` and `(u2, v2)` are adjacent when both coordinates are adjacent.
+    ///
+    /// The vertex `(u, v)` is labelled `u * m + v` where `m` is the number of vertices of `other`.
+    pub fn tensor_product(&self, other: &Self) -> Self {
+        self.product(other, |_, u_adjacent, _, v_adjacent| {
+            u_adjacent && v_adjacent
+        })
+    }
+
+    /// The strong product, whose edges are those of the Cartesian product together with those of the tensor product.
+    ///
+    /// The vertex `(u, v)` is labelled `u * m + v` where `m` is the number of vertices of `other`.
+    pub fn strong_product(&self, other: &Self) -> Self {
+        self.product(other, |u_equal, u_adjacent, v_equal, v_adjacent| {
+            (u_equal || u_adjacent) && (v_equal || v_adjacent) && !(u_equal && v_equal)
+        })
+    }
+
+    /// The disjoint union, where the vertices of `other` are shifted up by the number of vertices of `self`.
+    pub fn disjoint_union(&self, other: &Self) -> Self {
+        let n = self.vertex_count();
+        Self::from_edges(
+            n + other.vertex_count(),
+            self.edges()
+                .chain(other.edges().map(|(s, t)| (s + n, t + n))),
+        )
+        .unwrap()
+    }
+
+    /// The disjoint union together with an edge from every vertex of `self` to every vertex of `other`,
+    /// and back again if directed.
+    pub fn join(&self, other: &Self) -> Self {
+        let n = self.vertex_count();
+        let m = other.vertex_count();
+        let mut graph = self.disjoint_union(other);
+        for u in 0..n {
+            for v in n..n + m {
+                graph.add_edge(u, v).unwrap();
+                if DIRECTED {
+                    graph.add_edge(v, u).unwrap();
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{
+        CompleteUndirectedGraph, UndirectedCycleGraph, WheelGraph, complete_bipartite_graph,
+        hypercube_graph, path_graph, torus_graph,
+    };
+    use crate::graph::{DirectedGraph, UndirectedGraph};
+    use crate::isomorphism::FiniteGraphIsomorphism;
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    fn complete(n: usize) -> UndirectedGraph {
+        UndirectedGraph::from_finite_graph(&CompleteUndirectedGraph::new(
+            EnumeratedFiniteSetStructure::new(n),
+        ))
+        .unwrap()
+    }
+
+    fn cycle(n: usize) -> UndirectedGraph {
+        UndirectedGraph::from_finite_graph(
+            &UndirectedCycleGraph::new(EnumeratedFiniteSetStructure::new(n), n).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_complement() {
+        let c5 = cycle(5);
+        assert!(c5.complement().is_isomorphic_to(&c5));
+        assert_eq!(complete(4).complement().edge_count(), 0);
+        assert!(
+            complete_bipartite_graph(3, 3)
+                .complement()
+                .is_isomorphic_to(&complete(3).disjoint_union(&complete(3)))
+        );
+
+        let mut g = DirectedGraph::from_edges(3, [(0, 1), (1, 2), (1, 1)]).unwrap();
+        g = g.complement();
+        assert_eq!(g.edge_count(), 4);
+        assert!(!g.has_loops());
+        assert!(g.has_edge(1, 0) && !g.has_edge(0, 1));
+    }
+
+    #[test]
+    fn test_line_graph() {
+        //the line graph of K4 is the octahedron
+        let k2 = UndirectedGraph::from_edges(2, [(0, 1)]).unwrap();
+        let matching = k2.disjoint_union(&k2).disjoint_union(&k2);
+        assert!(
+            complete(4)
+                .line_graph()
+                .is_isomorphic_to(&matching.complement())
+        );
+
+        //the line graph of K_{3,3} is the 3 by 3 rook's graph
+        assert!(
+            complete_bipartite_graph(3, 3)
+                .line_graph()
+                .is_isomorphic_to(&complete(3).cartesian_product(&complete(3)))
+        );
+        assert!(cycle(6).line_graph().is_isomorphic_to(&cycle(6)));
+        assert!(path_graph(5).line_graph().is_isomorphic_to(&path_graph(4)));
+
+        //the line digraph of a directed cycle is a directed cycle
+        let g = DirectedGraph::from_edges(4, [(0, 1), (1, 2), (2, 3), (3, 0)]).unwrap();
+        assert!(g.line_graph().is_isomorphic_to(&g));
+        let g = DirectedGraph::from_edges(2, [(0, 1), (1, 0), (0, 0)]).unwrap();
+        assert_eq!(g.line_graph().edge_count(), 5);
+    }
+
+    #[test]
+    fn test_products() {
+        let k2 = complete(2);
+        let mut q = UndirectedGraph::new(1);
+        for d in 1..5 {
+            q = q.cartesian_product(&k2);
+            assert!(q.is_isomorphic_to(&hypercube_graph(d)));
+        }
+        assert!(
+            cycle(3)
+                .cartesian_product(&cycle(4))
+                .is_isomorphic_to(&torus_graph(3, 4).unwrap())
+        );
+
+        //|E(G x H)| = 2 |E(G)| |E(H)| and K2 x G is the bipartite double cover
+        let t = cycle(3).tensor_product(&cycle(5));
+        assert_eq!(t.edge_count(), 2 * 3 * 5);
+        assert!(k2.tensor_product(&cycle(5)).is_isomorphic_to(&cycle(10)));
+
+        //|E(G ⊠ H)| = |E(G □ H)| + |E(G x H)|
+        let s = cycle(4).strong_product(&path_graph(3));
+        assert_eq!(
+            s.edge_count(),
+            cycle(4).cartesian_product(&path_graph(3)).edge_count()
+                + cycle(4).tensor_product(&path_graph(3)).edge_count()
+        );
+        assert!(
+            complete(2)
+                .strong_product(&complete(3))
+                .is_isomorphic_to(&complete(6))
+        );
+
+        let a = DirectedGraph::from_edges(2, [(0, 1)]).unwrap();
+        let b = DirectedGraph::from_edges(3, [(0, 1), (1, 2)]).unwrap();
+        assert_eq!(a.cartesian_product(&b).edge_count(), 2 * 2 + 3);
+        assert_eq!(a.tensor_product(&b).edge_count(), 2);
+        assert_eq!(a.strong_product(&b).edge_count(), 9);
+    }
+
+    #[test]
+    fn test_union_and_join() {
+        let g = cycle(3).disjoint_union(&path_graph(2));
+        assert_eq!((g.vertex_count(), g.edge_count()), (5, 4));
+        assert!(g.has_edge(3, 4));
+
+        for n in 3..8 {
+            let wheel = UndirectedGraph::new(1).join(&cycle(n));
+            assert!(wheel.is_isomorphic_to(&WheelGraph::new(n + 1).unwrap()));
+        }
+        assert!(
+            UndirectedGraph::new(2)
+                .join(&UndirectedGraph::new(3))
+                .is_isomorphic_to(&complete_bipartite_graph(2, 3))
+        );
+
+        let d = DirectedGraph::new(1).join(&DirectedGraph::new(2));
+        assert_eq!(d.edge_count(), 4);
+    }
+}
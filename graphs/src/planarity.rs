@@ -0,0 +1,1795 @@
+use crate::structure::FiniteGraphSignature;
+use algebraeon_geometry::ambient_space::AffineSpace;
+use algebraeon_geometry::simplex_collection::LabelledSimplexCollection;
+use algebraeon_geometry::simplicial_complex::SimplicialComplex;
+use algebraeon_nzq::{Rational, RationalCanonicalStructure};
+use algebraeon_sets::structure::{FiniteSetSignature, SetSignature};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+//neighbours ignoring the direction of edges, loops and multiple edges
+fn simple_adjacency(multiplicities: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = multiplicities.len();
+    (0..n)
+        .map(|u| {
+            (0..n)
+                .filter(|v| u != *v && (multiplicities[u][*v] != 0 || multiplicities[*v][u] != 0))
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ConflictPair {
+    id: usize,
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+//a rotation system stored as a doubly linked list of neighbours around each vertex
+struct Rotation {
+    first: Vec<Option<usize>>,
+    cw: HashMap<(usize, usize), usize>,
+    ccw: HashMap<(usize, usize), usize>,
+}
+
+impl Rotation {
+    fn new(n: usize) -> Self {
+        Self {
+            first: vec![None; n],
+            cw: HashMap::new(),
+            ccw: HashMap::new(),
+        }
+    }
+
+    //insert w immediately clockwise of reference around v, or as the only neighbour of v
+    fn add_cw(&mut self, v: usize, w: usize, reference: Option<usize>) {
+        match reference {
+            None => {
+                self.first[v] = Some(w);
+                self.cw.insert((v, w), w);
+                self.ccw.insert((v, w), w);
+            }
+            Some(r) => {
+                let next = self.cw[&(v, r)];
+                self.cw.insert((v, r), w);
+                self.cw.insert((v, w), next);
+                self.ccw.insert((v, next), w);
+                self.ccw.insert((v, w), r);
+            }
+        }
+    }
+
+    //insert w immediately counterclockwise of reference around v
+    fn add_ccw(&mut self, v: usize, w: usize, reference: Option<usize>) {
+        match reference {
+            None => self.add_cw(v, w, None),
+            Some(r) => {
+                self.add_cw(v, w, Some(self.ccw[&(v, r)]));
+                if self.first[v] == Some(r) {
+                    self.first[v] = Some(w);
+                }
+            }
+        }
+    }
+
+    fn add_first(&mut self, v: usize, w: usize) {
+        let first = self.first[v];
+        self.add_ccw(v, w, first);
+    }
+
+    fn into_lists(self) -> Vec<Vec<usize>> {
+        (0..self.first.len())
+            .map(|v| {
+                let mut list = vec![];
+                if let Some(first) = self.first[v] {
+                    let mut w = first;
+                    loop {
+                        list.push(w);
+                        w = self.cw[&(v, w)];
+                        if w == first {
+                            break;
+                        }
+                    }
+                }
+                list
+            })
+            .collect()
+    }
+}
+
+/// The left-right planarity test of de Fraysseix and Rosenstiehl as presented by Brandes.
+struct LeftRight<'a> {
+    adjacency: &'a [Vec<usize>],
+    //the edges oriented by the depth first search
+    tail: Vec<usize>,
+    head: Vec<usize>,
+    oriented: HashSet<(usize, usize)>,
+    out_edges: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<isize>,
+    //the out edges of each vertex ordered by nesting depth
+    ordered: Vec<Vec<usize>>,
+    reference: Vec<Option<usize>>,
+    side: Vec<isize>,
+    stack: Vec<ConflictPair>,
+    next_pair_id: usize,
+    stack_bottom: Vec<Option<usize>>,
+    lowpt_edge: Vec<Option<usize>>,
+    left_ref: Vec<usize>,
+    right_ref: Vec<usize>,
+}
+
+impl<'a> LeftRight<'a> {
+    fn new(adjacency: &'a [Vec<usize>]) -> Self {
+        let n = adjacency.len();
+        Self {
+            adjacency,
+            tail: vec![],
+            head: vec![],
+            oriented: HashSet::new(),
+            out_edges: vec![vec![]; n],
+            roots: vec![],
+            height: vec![None; n],
+            parent_edge: vec![None; n],
+            lowpt: vec![],
+            lowpt2: vec![],
+            nesting_depth: vec![],
+            ordered: vec![],
+            reference: vec![],
+            side: vec![],
+            stack: vec![],
+            next_pair_id: 0,
+            stack_bottom: vec![],
+            lowpt_edge: vec![],
+            left_ref: vec![0; n],
+            right_ref: vec![0; n],
+        }
+    }
+
+    fn new_pair(&mut self, left: Interval, right: Interval) -> ConflictPair {
+        self.next_pair_id += 1;
+        ConflictPair {
+            id: self.next_pair_id,
+            left,
+            right,
+        }
+    }
+
+    fn top_id(&self) -> Option<usize> {
+        self.stack.last().map(|p| p.id)
+    }
+
+    fn conflicting(&self, interval: &Interval, e: usize) -> bool {
+        !interval.is_empty() && self.lowpt[interval.high.unwrap()] > self.lowpt[e]
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(r)) => self.lowpt[r],
+            (Some(l), None) => self.lowpt[l],
+            (Some(l), Some(r)) => self.lowpt[l].min(self.lowpt[r]),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    //orient the edges by a depth first search and compute lowpoints and nesting depths
+    fn orient(&mut self, v: usize) {
+        let e = self.parent_edge[v];
+        let adjacency = self.adjacency;
+        for w in adjacency[v].iter().copied() {
+            if self.oriented.contains(&(v.min(w), v.max(w))) {
+                continue;
+            }
+            self.oriented.insert((v.min(w), v.max(w)));
+            let vw = self.tail.len();
+            self.tail.push(v);
+            self.head.push(w);
+            self.out_edges[v].push(vw);
+            let hv = self.height[v].unwrap();
+            self.lowpt.push(hv);
+            self.lowpt2.push(hv);
+            self.nesting_depth.push(0);
+            match self.height[w] {
+                None => {
+                    self.parent_edge[w] = Some(vw);
+                    self.height[w] = Some(hv + 1);
+                    self.orient(w);
+                }
+                Some(hw) => self.lowpt[vw] = hw,
+            }
+            self.nesting_depth[vw] = 2 * self.lowpt[vw] as isize;
+            if self.lowpt2[vw] < hv {
+                //chordal
+                self.nesting_depth[vw] += 1;
+            }
+            if let Some(e) = e {
+                if self.lowpt[vw] < self.lowpt[e] {
+                    self.lowpt2[e] = self.lowpt[e].min(self.lowpt2[vw]);
+                    self.lowpt[e] = self.lowpt[vw];
+                } else if self.lowpt[vw] > self.lowpt[e] {
+                    self.lowpt2[e] = self.lowpt2[e].min(self.lowpt[vw]);
+                } else {
+                    self.lowpt2[e] = self.lowpt2[e].min(self.lowpt2[vw]);
+                }
+            }
+        }
+    }
+
+    fn test(&mut self, v: usize) -> bool {
+        let e = self.parent_edge[v];
+        let hv = self.height[v].unwrap();
+        for (i, ei) in self.ordered[v].clone().into_iter().enumerate() {
+            let w = self.head[ei];
+            self.stack_bottom[ei] = self.top_id();
+            if Some(ei) == self.parent_edge[w] {
+                if !self.test(w) {
+                    return false;
+                }
+            } else {
+                self.lowpt_edge[ei] = Some(ei);
+                let pair = self.new_pair(
+                    Interval::default(),
+                    Interval {
+                        low: Some(ei),
+                        high: Some(ei),
+                    },
+                );
+                self.stack.push(pair);
+            }
+            //integrate the return edges of ei
+            if self.lowpt[ei] < hv {
+                let e = e.unwrap();
+                if i == 0 {
+                    self.lowpt_edge[e] = self.lowpt_edge[ei];
+                } else if !self.add_constraints(ei, e) {
+                    return false;
+                }
+            }
+        }
+        if let Some(e) = e {
+            self.remove_back_edges(e);
+        }
+        true
+    }
+
+    fn add_constraints(&mut self, ei: usize, e: usize) -> bool {
+        let mut p = self.new_pair(Interval::default(), Interval::default());
+        //merge the return edges of ei into the right interval of p
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+            if self.lowpt[q.right.low.unwrap()] > self.lowpt[e] {
+                if p.right.is_empty() {
+                    p.right = q.right;
+                } else {
+                    self.reference[p.right.low.unwrap()] = q.right.high;
+                }
+                p.right.low = q.right.low;
+            } else {
+                self.reference[q.right.low.unwrap()] = self.lowpt_edge[e];
+            }
+            if self.top_id() == self.stack_bottom[ei] {
+                break;
+            }
+        }
+        //merge the conflicting return edges of the earlier siblings into the left interval of p
+        while let Some(top) = self.stack.last()
+            && (self.conflicting(&top.left, ei) || self.conflicting(&top.right, ei))
+        {
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(&q.right, ei) {
+                q.swap();
+            }
+            if self.conflicting(&q.right, ei) {
+                return false;
+            }
+            self.reference[p.right.low.unwrap()] = q.right.high;
+            if q.right.low.is_some() {
+                p.right.low = q.right.low;
+            }
+            if p.left.is_empty() {
+                p.left = q.left;
+            } else {
+                self.reference[p.left.low.unwrap()] = q.left.high;
+            }
+            p.left.low = q.left.low;
+        }
+        if !(p.left.is_empty() && p.right.is_empty()) {
+            self.stack.push(p);
+        }
+        true
+    }
+
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.tail[e];
+        let hu = self.height[u].unwrap();
+        //drop entire conflict pairs
+        while let Some(top) = self.stack.last()
+            && self.lowest(top) == hu
+        {
+            let p = self.stack.pop().unwrap();
+            if let Some(low) = p.left.low {
+                self.side[low] = -1;
+            }
+        }
+        //one more conflict pair to trim
+        if let Some(mut p) = self.stack.pop() {
+            while let Some(high) = p.left.high
+                && self.head[high] == u
+            {
+                p.left.high = self.reference[high];
+            }
+            if p.left.high.is_none()
+                && let Some(low) = p.left.low
+            {
+                self.reference[low] = p.right.low;
+                self.side[low] = -1;
+                p.left.low = None;
+            }
+            while let Some(high) = p.right.high
+                && self.head[high] == u
+            {
+                p.right.high = self.reference[high];
+            }
+            if p.right.high.is_none()
+                && let Some(low) = p.right.low
+            {
+                self.reference[low] = p.left.low;
+                self.side[low] = -1;
+                p.right.low = None;
+            }
+            self.stack.push(p);
+        }
+        //the side of e is the side of a highest return edge
+        if self.lowpt[e] < hu {
+            let top = self.stack.last().unwrap();
+            let (hl, hr) = (top.left.high, top.right.high);
+            self.reference[e] = match (hl, hr) {
+                (Some(l), None) => Some(l),
+                (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => Some(l),
+                _ => hr,
+            };
+        }
+    }
+
+    fn sign(&mut self, e: usize) -> isize {
+        if let Some(r) = self.reference[e] {
+            self.side[e] *= self.sign(r);
+            self.reference[e] = None;
+        }
+        self.side[e]
+    }
+
+    fn embed(&mut self, v: usize, rotation: &mut Rotation) {
+        for ei in self.ordered[v].clone() {
+            let w = self.head[ei];
+            if Some(ei) == self.parent_edge[w] {
+                rotation.add_first(w, v);
+                self.left_ref[v] = w;
+                self.right_ref[v] = w;
+                self.embed(w, rotation);
+            } else if self.side[ei] == 1 {
+                rotation.add_cw(w, v, Some(self.right_ref[w]));
+            } else {
+                rotation.add_ccw(w, v, Some(self.left_ref[w]));
+                self.left_ref[w] = v;
+            }
+        }
+    }
+
+    fn order_by_nesting_depth(&mut self) {
+        self.ordered = self
+            .out_edges
+            .iter()
+            .map(|edges| {
+                let mut edges = edges.clone();
+                edges.sort_by_key(|e| self.nesting_depth[*e]);
+                edges
+            })
+            .collect();
+    }
+
+    //the neighbours of each vertex in clockwise order, or None if the graph is not planar
+    fn run(mut self) -> Option<Vec<Vec<usize>>> {
+        let n = self.adjacency.len();
+        let m = self.adjacency.iter().map(|a| a.len()).sum::<usize>() / 2;
+        if n > 2 && m > 3 * n - 6 {
+            return None;
+        }
+        for v in 0..n {
+            if self.height[v].is_none() {
+                self.height[v] = Some(0);
+                self.roots.push(v);
+                self.orient(v);
+            }
+        }
+
+        self.order_by_nesting_depth();
+        self.reference = vec![None; m];
+        self.side = vec![1; m];
+        self.stack_bottom = vec![None; m];
+        self.lowpt_edge = vec![None; m];
+        for v in self.roots.clone() {
+            if !self.test(v) {
+                return None;
+            }
+        }
+
+        for e in 0..m {
+            self.nesting_depth[e] *= self.sign(e);
+        }
+        self.order_by_nesting_depth();
+        let mut rotation = Rotation::new(n);
+        for v in 0..n {
+            let mut previous = None;
+            for e in &self.ordered[v] {
+                rotation.add_cw(v, self.head[*e], previous);
+                previous = Some(self.head[*e]);
+            }
+        }
+        for v in self.roots.clone() {
+            self.embed(v, &mut rotation);
+        }
+        Some(rotation.into_lists())
+    }
+}
+
+fn planar_rotation(adjacency: &[Vec<usize>]) -> Option<Vec<Vec<usize>>> {
+    LeftRight::new(adjacency).run()
+}
+
+/// The edge addition planarity test of Boyer and Myrvold, run on a graph the left-right test has rejected to
+/// isolate a Kuratowski subgraph in linear time.
+///
+/// Vertices are numbered by depth first index. Until it is merged into its parent, the biconnected component
+/// containing the tree edge to `c` is rooted at the virtual vertex `n + c`, a copy of the parent of `c`.
+struct EdgeAddition {
+    n: usize,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    neighbours: Vec<Vec<usize>>,
+    least_ancestor: Vec<usize>,
+    lowpoint: Vec<usize>,
+    //the children of each vertex ordered by lowpoint, those before the start having been merged
+    separated: Vec<Vec<usize>>,
+    separated_start: Vec<usize>,
+    merged: Vec<bool>,
+    //arcs 2i and 2i + 1 are the two halves of an edge, kept in a doubly linked list around their tail
+    //whose two ends are the edges on the external face
+    target: Vec<usize>,
+    link: Vec<[Option<usize>; 2]>,
+    ends: Vec<[Option<usize>; 2]>,
+    //the next vertex along the external face from each side of a vertex and the side it is entered from,
+    //skipping vertices which can no longer take part in an edge
+    external: Vec<[(usize, usize); 2]>,
+    //the vertex an unembedded back edge goes up to
+    back_edge: Vec<Option<usize>>,
+    visited: Vec<Option<usize>>,
+    //the roots of the separated components below each vertex with back edges up to the current vertex,
+    //those which also reach higher last
+    pertinent_roots: Vec<VecDeque<usize>>,
+}
+
+impl EdgeAddition {
+    //the test state with each tree edge in a component of its own, and the vertex numbered by each index
+    fn new(adjacency: &[Vec<usize>]) -> (Self, Vec<usize>) {
+        let n = adjacency.len();
+        let mut order = vec![];
+        let mut index = vec![None; n];
+        let mut parent = vec![];
+        for s in 0..n {
+            if index[s].is_some() {
+                continue;
+            }
+            index[s] = Some(order.len());
+            order.push(s);
+            parent.push(None);
+            let mut stack = vec![(s, 0)];
+            while let Some((u, i)) = stack.last_mut() {
+                if let Some(w) = adjacency[*u].get(*i).copied() {
+                    *i += 1;
+                    if index[w].is_none() {
+                        index[w] = Some(order.len());
+                        parent.push(index[*u]);
+                        order.push(w);
+                        stack.push((w, 0));
+                    }
+                } else {
+                    stack.pop();
+                }
+            }
+        }
+        let neighbours = order
+            .iter()
+            .map(|u| adjacency[*u].iter().map(|w| index[*w].unwrap()).collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut children = vec![vec![]; n];
+        for (c, p) in parent.iter().enumerate() {
+            if let Some(p) = p {
+                children[*p].push(c);
+            }
+        }
+        let least_ancestor = (0..n)
+            .map(|u| {
+                neighbours[u]
+                    .iter()
+                    .copied()
+                    .filter(|w| Some(*w) != parent[u])
+                    .fold(u, usize::min)
+            })
+            .collect::<Vec<_>>();
+        let mut lowpoint = least_ancestor.clone();
+        for c in (0..n).rev() {
+            if let Some(p) = parent[c] {
+                lowpoint[p] = lowpoint[p].min(lowpoint[c]);
+            }
+        }
+        let mut by_lowpoint = vec![vec![]; n];
+        for c in 0..n {
+            if parent[c].is_some() {
+                by_lowpoint[lowpoint[c]].push(c);
+            }
+        }
+        let mut separated = vec![vec![]; n];
+        for c in by_lowpoint.into_iter().flatten() {
+            separated[parent[c].unwrap()].push(c);
+        }
+
+        let mut test = Self {
+            n,
+            parent,
+            children,
+            neighbours,
+            least_ancestor,
+            lowpoint,
+            separated,
+            separated_start: vec![0; n],
+            merged: vec![false; n],
+            target: vec![],
+            link: vec![],
+            ends: vec![[None; 2]; 2 * n],
+            external: vec![[(0, 0); 2]; 2 * n],
+            back_edge: vec![None; n],
+            visited: vec![None; 2 * n],
+            pertinent_roots: vec![VecDeque::new(); n],
+        };
+        for c in 0..n {
+            if test.parent[c].is_some() {
+                test.add_edge(n + c, 0, c, 1);
+                test.external[n + c][1] = (c, 0);
+                test.external[c][0] = (n + c, 1);
+            }
+        }
+        (test, order)
+    }
+
+    //put an arc at one end of the list around u
+    fn attach(&mut self, u: usize, side: usize, arc: usize) {
+        let end = self.ends[u][side];
+        self.link[arc] = [None; 2];
+        self.link[arc][1 - side] = end;
+        match end {
+            Some(end) => self.link[end][side] = Some(arc),
+            None => self.ends[u][1 - side] = Some(arc),
+        }
+        self.ends[u][side] = Some(arc);
+    }
+
+    //embed an edge on the external face between the given sides of u and w
+    fn add_edge(&mut self, u: usize, u_side: usize, w: usize, w_side: usize) {
+        let arc = self.target.len();
+        self.target.extend([w, u]);
+        self.link.extend([[None; 2]; 2]);
+        self.attach(u, u_side, arc);
+        self.attach(w, w_side, arc + 1);
+        self.external[u][u_side] = (w, w_side);
+        self.external[w][w_side] = (u, u_side);
+    }
+
+    fn arcs(&self, u: usize) -> Vec<usize> {
+        let mut arcs = vec![];
+        let mut arc = self.ends[u][0];
+        while let Some(a) = arc {
+            arcs.push(a);
+            arc = self.link[a][1];
+        }
+        arcs
+    }
+
+    fn pertinent(&self, u: usize, v: usize) -> bool {
+        self.back_edge[u] == Some(v) || !self.pertinent_roots[u].is_empty()
+    }
+
+    fn externally_active(&mut self, u: usize, v: usize) -> bool {
+        if self.least_ancestor[u] < v {
+            return true;
+        }
+        let separated = &self.separated[u];
+        let mut start = self.separated_start[u];
+        while start < separated.len() && self.merged[separated[start]] {
+            start += 1;
+        }
+        self.separated_start[u] = start;
+        start < separated.len() && self.lowpoint[separated[start]] < v
+    }
+
+    fn active(&mut self, u: usize, v: usize) -> bool {
+        self.pertinent(u, v) || self.externally_active(u, v)
+    }
+
+    fn internally_active(&mut self, u: usize, v: usize) -> bool {
+        self.pertinent(u, v) && !self.externally_active(u, v)
+    }
+
+    //record the back edge from w up to v, and the roots of the components it passes through on the way
+    fn walkup(&mut self, v: usize, w: usize) {
+        let n = self.n;
+        self.back_edge[w] = Some(v);
+        let ((mut x, mut x_side), (mut y, mut y_side)) = ((w, 1), (w, 0));
+        while self.visited[x] != Some(v) && self.visited[y] != Some(v) {
+            self.visited[x] = Some(v);
+            self.visited[y] = Some(v);
+            let root = if x >= n {
+                x
+            } else if y >= n {
+                y
+            } else {
+                (x, x_side) = self.external[x][1 - x_side];
+                (y, y_side) = self.external[y][1 - y_side];
+                continue;
+            };
+            let c = root - n;
+            let p = self.parent[c].unwrap();
+            if p == v {
+                break;
+            }
+            if self.lowpoint[c] < v {
+                self.pertinent_roots[p].push_back(root);
+            } else {
+                self.pertinent_roots[p].push_front(root);
+            }
+            ((x, x_side), (y, y_side)) = ((p, 1), (p, 0));
+        }
+    }
+
+    //the first vertex along the external face from a side of root which can still take part in an edge
+    fn first_active(&mut self, root: usize, side: usize, v: usize) -> (usize, usize) {
+        let (mut w, mut w_side) = self.external[root][side];
+        while w != root && !self.active(w, v) {
+            (w, w_side) = self.external[w][1 - w_side];
+        }
+        if w != root {
+            self.external[root][side] = (w, w_side);
+            self.external[w][w_side] = (root, side);
+        }
+        (w, w_side)
+    }
+
+    //merge the component at root into w, which the walk entered from w_side and leaves root from root_side,
+    //flipping the component if those are the same side
+    fn merge(&mut self, w: usize, w_side: usize, root: usize, root_side: usize) {
+        let (q, q_side) = self.external[root][1 - root_side];
+        let arcs = self.arcs(root);
+        if root_side == w_side {
+            for arc in &arcs {
+                self.link[*arc].swap(0, 1);
+            }
+            self.ends[root].swap(0, 1);
+        }
+        for arc in &arcs {
+            self.target[arc ^ 1] = w;
+        }
+        let inner = self.ends[root][1 - w_side].unwrap();
+        let outer = self.ends[root][w_side].unwrap();
+        let end = self.ends[w][w_side].unwrap();
+        self.link[inner][1 - w_side] = Some(end);
+        self.link[end][w_side] = Some(inner);
+        self.ends[w][w_side] = Some(outer);
+        self.ends[root] = [None; 2];
+        self.external[w][w_side] = (q, q_side);
+        self.external[q][q_side] = (w, w_side);
+        self.merged[root - self.n] = true;
+        let front = self.pertinent_roots[w].pop_front();
+        debug_assert_eq!(front, Some(root));
+    }
+
+    //embed the back edges from v into the component at root, returning the root of a component the walk is
+    //blocked in
+    fn walkdown(&mut self, v: usize, root: usize) -> Option<usize> {
+        let mut merges = vec![];
+        for root_side in 0..2 {
+            let (mut w, mut w_side) = self.external[root][root_side];
+            while w != root {
+                if self.back_edge[w] == Some(v) {
+                    while let Some((r, r_side)) = merges.pop() {
+                        let (q, q_side) = merges.pop().unwrap();
+                        self.merge(q, q_side, r, r_side);
+                    }
+                    self.add_edge(root, root_side, w, w_side);
+                    self.back_edge[w] = None;
+                }
+                if let Some(r) = self.pertinent_roots[w].front().copied() {
+                    merges.push((w, w_side));
+                    let (x, x_side) = self.first_active(r, 0, v);
+                    let (y, y_side) = self.first_active(r, 1, v);
+                    let (next, next_side, r_side) = if self.internally_active(x, v) {
+                        (x, x_side, 0)
+                    } else if self.internally_active(y, v) || !self.pertinent(x, v) {
+                        (y, y_side, 1)
+                    } else {
+                        (x, x_side, 0)
+                    };
+                    merges.push((r, r_side));
+                    (w, w_side) = (next, next_side);
+                } else if !self.active(w, v) {
+                    (w, w_side) = self.external[w][1 - w_side];
+                } else {
+                    if merges.is_empty() {
+                        self.external[root][root_side] = (w, w_side);
+                        self.external[w][w_side] = (root, root_side);
+                    }
+                    break;
+                }
+            }
+            if let Some((r, _)) = merges.last() {
+                return Some(*r);
+            }
+        }
+        None
+    }
+
+    //add the vertices in decreasing order of index with their back edges from below, returning the vertex and
+    //the root of the component at which this fails
+    fn run(&mut self) -> Option<(usize, usize)> {
+        let n = self.n;
+        for v in (0..n).rev() {
+            let below = self.neighbours[v]
+                .iter()
+                .copied()
+                .filter(|w| *w > v && self.parent[*w] != Some(v))
+                .collect::<Vec<_>>();
+            for w in below.iter().copied() {
+                self.walkup(v, w);
+            }
+            for c in self.children[v].clone() {
+                if self.visited[n + c] == Some(v)
+                    && let Some(root) = self.walkdown(v, n + c)
+                {
+                    return Some((v, root));
+                }
+            }
+            if let Some(mut c) = below.into_iter().find(|w| self.back_edge[*w] == Some(v)) {
+                while self.parent[c] != Some(v) {
+                    c = self.parent[c].unwrap();
+                }
+                return Some((v, n + c));
+            }
+        }
+        None
+    }
+
+    //the tree edges from u up to its ancestor a
+    fn tree_path(&self, mut u: usize, a: usize) -> Vec<(usize, usize)> {
+        let mut path = vec![];
+        while u != a {
+            let p = self.parent[u].unwrap();
+            path.push((u, p));
+            u = p;
+        }
+        path
+    }
+
+    //a vertex in the subtree of c with a neighbour satisfying the predicate, and that neighbour
+    fn descendant_adjacent(&self, c: usize, predicate: impl Fn(usize) -> bool) -> (usize, usize) {
+        let mut stack = vec![c];
+        while let Some(d) = stack.pop() {
+            if let Some(t) = self.neighbours[d].iter().copied().find(|t| predicate(*t)) {
+                return (d, t);
+            }
+            stack.extend(self.children[d].iter().copied());
+        }
+        unreachable!()
+    }
+
+    //a path from an externally active vertex u up to a proper ancestor of v, and that ancestor
+    fn external_path(&mut self, u: usize, v: usize) -> (Vec<(usize, usize)>, usize) {
+        if self.least_ancestor[u] < v {
+            let t = self.neighbours[u].iter().copied().find(|t| *t < v).unwrap();
+            return (vec![(u, t)], t);
+        }
+        self.externally_active(u, v);
+        let c = self.separated[u][self.separated_start[u]];
+        let (d, t) = self.descendant_adjacent(c, |t| t < v);
+        let mut path = self.tree_path(d, u);
+        path.push((d, t));
+        (path, t)
+    }
+
+    //a path from a pertinent vertex u to v
+    fn pertinent_path(&self, u: usize, v: usize) -> Vec<(usize, usize)> {
+        if self.back_edge[u] == Some(v) {
+            return vec![(u, v)];
+        }
+        let c = self.pertinent_roots[u][0] - self.n;
+        let (d, _) = self.descendant_adjacent(c, |t| t == v);
+        let mut path = self.tree_path(d, u);
+        path.push((d, v));
+        path
+    }
+
+    //paths through a bridge of the external face of the component which conflicts both with a bridge outside
+    //attached at the root, x and y, or at those and an externally active vertex the bridge spans, and with
+    //one attached at the root and w, together with the position of that externally active vertex
+    fn conflicting_bridge(
+        &self,
+        cycle: &[usize],
+        position: &[Option<usize>],
+        external: &[bool],
+        (ix, iy, iw): (usize, usize, usize),
+    ) -> (Vec<(usize, usize)>, Option<usize>) {
+        let length = cycle.len();
+        let mut bridge = vec![None; 2 * self.n];
+        let mut tree_parent = vec![None; 2 * self.n];
+        let mut attachments = vec![];
+        for (i, a) in cycle.iter().copied().enumerate() {
+            for arc in self.arcs(a) {
+                let t = self.target[arc];
+                match position[t] {
+                    Some(j) => {
+                        if i < j && j != i + 1 && !(i == 0 && j == length - 1) {
+                            attachments.push(vec![(i, (a, t)), (j, (t, a))]);
+                        }
+                    }
+                    None => {
+                        if bridge[t].is_none() {
+                            bridge[t] = Some(attachments.len());
+                            attachments.push(vec![]);
+                            let mut queue = VecDeque::from([t]);
+                            while let Some(u) = queue.pop_front() {
+                                for arc in self.arcs(u) {
+                                    let s = self.target[arc];
+                                    if position[s].is_none() && bridge[s].is_none() {
+                                        bridge[s] = bridge[t];
+                                        tree_parent[s] = Some(u);
+                                        queue.push_back(s);
+                                    }
+                                }
+                            }
+                        }
+                        attachments[bridge[t].unwrap()].push((i, (a, t)));
+                    }
+                }
+            }
+        }
+        let mut next_external = vec![length; length + 1];
+        for i in (0..length).rev() {
+            next_external[i] = if external[i] { i } else { next_external[i + 1] };
+        }
+        for attached in &attachments {
+            let Some(b) = conflict(&[0, iw], attached) else {
+                continue;
+            };
+            let (a, iz) = match conflict(&[0, ix, iy], attached) {
+                Some(a) => (a, None),
+                None => {
+                    let low = attached.iter().map(|(i, _)| *i).min().unwrap();
+                    let high = attached.iter().map(|(i, _)| *i).max().unwrap();
+                    let iz = next_external[low + 1];
+                    if iz >= high {
+                        continue;
+                    }
+                    let mut fixed = [0, ix, iy, iz];
+                    fixed.sort_unstable();
+                    (conflict(&fixed, attached).unwrap(), Some(iz))
+                }
+            };
+            let mut paths = vec![];
+            for (s, mut t) in a.into_iter().chain(b) {
+                paths.push((s, t));
+                while let Some(p) = tree_parent[t] {
+                    paths.push((t, p));
+                    t = p;
+                }
+            }
+            return (paths, iz);
+        }
+        unreachable!()
+    }
+
+    //the edges of a non-planar subgraph made of boundedly many paths, around the component at root where
+    //adding the back edges up to v failed
+    fn obstruction(&mut self, v: usize, root: usize) -> Vec<(usize, usize)> {
+        let n = self.n;
+        let r = self.parent[root - n].unwrap();
+        let mut cycle = vec![root];
+        let mut arc = self.ends[root][0].unwrap();
+        while self.target[arc] != root {
+            let u = self.target[arc];
+            cycle.push(u);
+            let side = if self.ends[u][0] == Some(arc ^ 1) {
+                0
+            } else {
+                1
+            };
+            arc = self.ends[u][1 - side].unwrap();
+        }
+        let mut position = vec![None; 2 * n];
+        for (i, u) in cycle.iter().enumerate() {
+            position[*u] = Some(i);
+        }
+
+        //the first externally active vertices x and y along either side from the root and a pertinent vertex
+        //w between them, preferring one with a child reaching both v and above
+        let mut ix = 1;
+        while !self.externally_active(cycle[ix], v) {
+            ix += 1;
+        }
+        let mut iy = cycle.len() - 1;
+        while !self.externally_active(cycle[iy], v) {
+            iy -= 1;
+        }
+        let pertinent = (ix + 1..iy)
+            .filter(|i| self.pertinent(cycle[*i], v))
+            .collect::<Vec<_>>();
+        let reaching_both = pertinent.iter().copied().find(|i| {
+            self.pertinent_roots[cycle[*i]]
+                .back()
+                .is_some_and(|q| self.lowpoint[q - n] < v)
+        });
+        let iw = reaching_both.unwrap_or(pertinent[0]);
+        let (x, y, w) = (cycle[ix], cycle[iy], cycle[iw]);
+
+        let mut edges = (0..cycle.len())
+            .map(|i| (cycle[i], cycle[(i + 1) % cycle.len()]))
+            .collect::<Vec<_>>();
+        let (x_path, x_top) = self.external_path(x, v);
+        let (y_path, y_top) = self.external_path(y, v);
+        edges.extend(x_path);
+        edges.extend(y_path);
+        let mut top = x_top.min(y_top);
+        if r != v {
+            //the component hangs below v
+            edges.extend(self.pertinent_path(w, v));
+            edges.extend(self.tree_path(r, v));
+        } else if reaching_both.is_some() {
+            let c = self.pertinent_roots[w].back().unwrap() - n;
+            let (d, _) = self.descendant_adjacent(c, |t| t == v);
+            edges.extend(self.tree_path(d, w));
+            edges.push((d, v));
+            let (d, t) = self.descendant_adjacent(c, |t| t < v);
+            edges.extend(self.tree_path(d, w));
+            edges.push((d, t));
+            top = top.min(t);
+        } else {
+            edges.extend(self.pertinent_path(w, v));
+            let external = cycle
+                .iter()
+                .map(|u| *u != root && self.externally_active(*u, v))
+                .collect::<Vec<_>>();
+            let (paths, iz) = self.conflicting_bridge(&cycle, &position, &external, (ix, iy, iw));
+            edges.extend(paths);
+            if let Some(iz) = iz {
+                let (z_path, z_top) = self.external_path(cycle[iz], v);
+                edges.extend(z_path);
+                top = top.min(z_top);
+            }
+        }
+        edges.extend(self.tree_path(v, top));
+        let real = |u: usize| if u == root { r } else { u };
+        edges.into_iter().map(|(a, b)| (real(a), real(b))).collect()
+    }
+}
+
+//the attachments of a bridge, at positions around a cycle, which do not all lie on one segment between
+//consecutive positions of fixed, if there are any
+fn conflict<T: Copy>(fixed: &[usize], attachments: &[(usize, T)]) -> Option<Vec<T>> {
+    let k = fixed.len();
+    let segments = |p: usize| match fixed.iter().position(|f| *f == p) {
+        Some(i) => [(i + k - 1) % k, i],
+        None => {
+            let s = (fixed.iter().filter(|f| **f < p).count() + k - 1) % k;
+            [s, s]
+        }
+    };
+    let mut possible = (0..k).collect::<Vec<_>>();
+    let mut witnesses = vec![];
+    for (p, t) in attachments {
+        let next = possible
+            .iter()
+            .copied()
+            .filter(|s| segments(*p).contains(s))
+            .collect::<Vec<_>>();
+        if next.len() < possible.len() {
+            witnesses.push(*t);
+            possible = next;
+            if possible.is_empty() {
+                return Some(witnesses);
+            }
+        }
+    }
+    None
+}
+
+//delete paths between vertices of degree at least three from a non-planar graph with few such vertices until
+//what remains is a subdivision of K5 or K3,3
+fn kuratowski_subdivision(edges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let mut neighbours = BTreeMap::<usize, Vec<usize>>::new();
+    for (u, v) in edges
+        .into_iter()
+        .map(|(u, v)| (u.min(v), u.max(v)))
+        .collect::<BTreeSet<_>>()
+    {
+        neighbours.entry(u).or_default().push(v);
+        neighbours.entry(v).or_default().push(u);
+    }
+    //drop dangling paths
+    let mut leaves = neighbours
+        .iter()
+        .filter(|(_, around)| around.len() == 1)
+        .map(|(u, _)| *u)
+        .collect::<Vec<_>>();
+    while let Some(u) = leaves.pop() {
+        for w in neighbours.remove(&u).unwrap_or_default() {
+            let around = neighbours.get_mut(&w).unwrap();
+            around.retain(|x| *x != u);
+            if around.len() <= 1 {
+                leaves.push(w);
+            }
+        }
+    }
+
+    let branch = neighbours
+        .iter()
+        .filter(|(_, around)| around.len() >= 3)
+        .map(|(u, _)| *u)
+        .collect::<Vec<_>>();
+    let mut paths = vec![];
+    let mut followed = HashSet::new();
+    for b in branch.iter().copied() {
+        for start in neighbours[&b].iter().copied() {
+            if followed.contains(&(b, start)) {
+                continue;
+            }
+            let mut path = vec![b, start];
+            while neighbours[&path[path.len() - 1]].len() == 2 {
+                let (prev, at) = (path[path.len() - 2], path[path.len() - 1]);
+                path.push(*neighbours[&at].iter().find(|x| **x != prev).unwrap());
+            }
+            followed.insert((path[path.len() - 1], path[path.len() - 2]));
+            paths.push(path);
+        }
+    }
+    let index = branch
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (*b, i))
+        .collect::<HashMap<_, _>>();
+    let non_planar = |keep: &[bool]| {
+        let mut adjacency = vec![vec![]; branch.len()];
+        for (path, _) in paths.iter().zip(keep).filter(|(_, keep)| **keep) {
+            let (a, b) = (index[&path[0]], index[&path[path.len() - 1]]);
+            if a != b && !adjacency[a].contains(&b) {
+                adjacency[a].push(b);
+                adjacency[b].push(a);
+            }
+        }
+        planar_rotation(&adjacency).is_none()
+    };
+    let mut keep = vec![true; paths.len()];
+    debug_assert!(non_planar(&keep));
+    for i in 0..paths.len() {
+        keep[i] = false;
+        keep[i] = !non_planar(&keep);
+    }
+    paths
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .flat_map(|(path, _)| path.windows(2).map(|e| (e[0].min(e[1]), e[0].max(e[1]))))
+        .collect()
+}
+
+//the edges of a Kuratowski subgraph of a graph which is not planar
+fn kuratowski_edges(adjacency: &[Vec<usize>]) -> Vec<(usize, usize)> {
+    let (mut test, order) = EdgeAddition::new(adjacency);
+    let (v, root) = test.run().unwrap();
+    let edges = test
+        .obstruction(v, root)
+        .into_iter()
+        .map(|(a, b)| (order[a], order[b]))
+        .collect();
+    kuratowski_subdivision(edges)
+}
+
+//the boundary walks of the faces of a rotation system
+fn rotation_faces(rotation: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut visited = HashSet::new();
+    let mut faces = vec![];
+    for (v, neighbours) in rotation.iter().enumerate() {
+        for w in neighbours {
+            if visited.contains(&(v, *w)) {
+                continue;
+            }
+            let mut face = vec![];
+            let (mut a, mut b) = (v, *w);
+            while visited.insert((a, b)) {
+                face.push(a);
+                //the next half edge leaves b counterclockwise of a
+                let around = &rotation[b];
+                let i = around.iter().position(|x| *x == a).unwrap();
+                let c = around[(i + around.len() - 1) % around.len()];
+                (a, b) = (b, c);
+            }
+            faces.push(face);
+        }
+    }
+    faces
+}
+
+/// A planar embedding of a graph, given by the clockwise order of the neighbours around each vertex.
+#[derive(Debug, Clone)]
+pub struct PlanarEmbedding<S: SetSignature> {
+    vertices: Vec<S::Set>,
+    rotation: Vec<Vec<usize>>,
+}
+
+impl<S: SetSignature> PlanarEmbedding<S> {
+    /// Each vertex together with its neighbours in clockwise order.
+    pub fn rotation_system(&self) -> Vec<(S::Set, Vec<S::Set>)> {
+        self.vertices
+            .iter()
+            .zip(&self.rotation)
+            .map(|(v, around)| {
+                (
+                    v.clone(),
+                    around.iter().map(|w| self.vertices[*w].clone()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The faces of the embedding, each given by the vertices of its boundary walk.
+    /// A vertex with no neighbours lies on no face.
+    pub fn faces(&self) -> Vec<Vec<S::Set>> {
+        rotation_faces(&self.rotation)
+            .into_iter()
+            .map(|face| face.into_iter().map(|v| self.vertices[v].clone()).collect())
+            .collect()
+    }
+}
+
+/// Which of the two Kuratowski graphs a subgraph is a subdivision of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KuratowskiGraph {
+    K5,
+    K33,
+}
+
+/// A subgraph which is a subdivision of `K_5` or `K_{3,3}`, witnessing that a graph is not planar.
+#[derive(Debug, Clone)]
+pub struct KuratowskiSubgraph<S: SetSignature> {
+    kind: KuratowskiGraph,
+    branch_vertices: Vec<S::Set>,
+    edges: Vec<(S::Set, S::Set)>,
+}
+
+impl<S: SetSignature> KuratowskiSubgraph<S> {
+    pub fn kind(&self) -> KuratowskiGraph {
+        self.kind
+    }
+
+    /// The vertices corresponding to the vertices of `K_5` or `K_{3,3}`.
+    pub fn branch_vertices(&self) -> &Vec<S::Set> {
+        &self.branch_vertices
+    }
+
+    /// The edges of the subdivision.
+    pub fn edges(&self) -> &Vec<(S::Set, S::Set)> {
+        &self.edges
+    }
+}
+
+/// The outcome of a planarity test.
+#[derive(Debug, Clone)]
+pub enum Planarity<S: SetSignature> {
+    Planar(PlanarEmbedding<S>),
+    NonPlanar(KuratowskiSubgraph<S>),
+}
+
+/// A planar drawing of a graph with straight edges and rational coordinates.
+#[derive(Debug, Clone)]
+pub struct PlanarDrawing<S: SetSignature> {
+    vertices: Vec<S::Set>,
+    positions: Vec<(Rational, Rational)>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl<S: SetSignature> PlanarDrawing<S> {
+    /// The position of each vertex.
+    pub fn positions(&self) -> Vec<(S::Set, (Rational, Rational))> {
+        self.vertices
+            .iter()
+            .cloned()
+            .zip(self.positions.iter().cloned())
+            .collect()
+    }
+
+    /// The edges of the drawing as straight line segments between the positions of their endpoints.
+    pub fn segments(&self) -> Vec<((Rational, Rational), (Rational, Rational))> {
+        self.edges
+            .iter()
+            .map(|(u, v)| (self.positions[*u].clone(), self.positions[*v].clone()))
+            .collect()
+    }
+
+    /// The drawing as a simplicial complex in the plane, with a point for each vertex and a line segment for each edge.
+    pub fn simplicial_complex(&self) -> SimplicialComplex<'static, RationalCanonicalStructure> {
+        let space = AffineSpace::new_linear(Rational::structure_ref(), 2);
+        let point = |v: usize| {
+            let (x, y) = self.positions[v].clone();
+            space.vector([x, y])
+        };
+        let mut simplexes = HashSet::new();
+        for v in 0..self.vertices.len() {
+            simplexes.insert(space.simplex(vec![point(v)]).unwrap());
+        }
+        for (u, v) in &self.edges {
+            simplexes.insert(space.simplex(vec![point(*u), point(*v)]).unwrap());
+        }
+        SimplicialComplex::try_new(space, simplexes).unwrap()
+    }
+}
+
+//add edges to a planar rotation system until every face is a triangle, keeping the graph simple
+fn triangulate(rotation: &mut [Vec<usize>]) {
+    let n = rotation.len();
+    let mut adjacent = HashSet::new();
+    for (v, around) in rotation.iter().enumerate() {
+        for w in around {
+            adjacent.insert((v, *w));
+        }
+    }
+    //join the components in a chain
+    let mut component = vec![None; n];
+    let mut representatives = vec![];
+    for s in 0..n {
+        if component[s].is_some() {
+            continue;
+        }
+        component[s] = Some(representatives.len());
+        let mut stack = vec![s];
+        while let Some(v) = stack.pop() {
+            for w in &rotation[v] {
+                if component[*w].is_none() {
+                    component[*w] = Some(representatives.len());
+                    stack.push(*w);
+                }
+            }
+        }
+        representatives.push(s);
+    }
+    for pair in representatives.windows(2) {
+        let (u, v) = (pair[0], pair[1]);
+        rotation[u].push(v);
+        rotation[v].push(u);
+        adjacent.insert((u, v));
+        adjacent.insert((v, u));
+    }
+
+    for mut face in rotation_faces(rotation) {
+        //cut off a triangle w_i, w_{i+1}, w_{i+2} at a time with a new edge from w_i to w_{i+2}
+        while face.len() > 3 {
+            let k = face.len();
+            let i = (0..k)
+                .find(|i| {
+                    let (a, c) = (face[*i], face[(i + 2) % k]);
+                    a != c && !adjacent.contains(&(a, c))
+                })
+                .expect("a face of a simple plane graph has a chord");
+            let (a, b, c) = (face[i], face[(i + 1) % k], face[(i + 2) % k]);
+            let j = rotation[a].iter().position(|x| *x == b).unwrap();
+            rotation[a].insert(j + 1, c);
+            let j = rotation[c].iter().position(|x| *x == b).unwrap();
+            rotation[c].insert(j, a);
+            adjacent.insert((a, c));
+            adjacent.insert((c, a));
+            face.remove((i + 1) % k);
+        }
+    }
+}
+
+//solve the square system m x = b over the rationals, where m is invertible
+fn solve(m: Vec<Vec<Rational>>, b: Vec<Vec<Rational>>) -> Vec<Vec<Rational>> {
+    let n = m.len();
+    //rows of the augmented matrix (m | b)
+    let mut rows = m
+        .into_iter()
+        .zip(b)
+        .map(|(mut row, b_row)| {
+            row.extend(b_row);
+            row
+        })
+        .collect::<Vec<_>>();
+    for col in 0..n {
+        let pivot = (col..n).find(|r| rows[*r][col] != Rational::ZERO).unwrap();
+        rows.swap(col, pivot);
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != col && row[col] != Rational::ZERO {
+                let factor = &row[col] / &pivot_row[col];
+                for (x, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *x = &*x - &(&factor * p);
+                }
+            }
+        }
+    }
+    rows.into_iter()
+        .enumerate()
+        .map(|(r, row)| row[n..].iter().map(|x| x / &row[r]).collect())
+        .collect()
+}
+
+//positions for a straight line planar drawing of a planar rotation system
+fn tutte_drawing(rotation: &[Vec<usize>]) -> Vec<(Rational, Rational)> {
+    let n = rotation.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![(Rational::ZERO, Rational::ZERO)],
+        2 => {
+            return vec![
+                (Rational::ZERO, Rational::ZERO),
+                (Rational::ONE, Rational::ZERO),
+            ];
+        }
+        _ => {}
+    }
+    let mut triangulation = rotation.to_vec();
+    triangulate(&mut triangulation);
+
+    //fix an outer triangle and place every other vertex at the average of its neighbours
+    let outer = rotation_faces(&triangulation)[0].clone();
+    let mut positions = vec![(Rational::ZERO, Rational::ZERO); n];
+    positions[outer[1]] = (Rational::ONE, Rational::ZERO);
+    positions[outer[2]] = (Rational::ZERO, Rational::ONE);
+    let inner = (0..n).filter(|v| !outer.contains(v)).collect::<Vec<_>>();
+    let index = inner
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (*v, i))
+        .collect::<HashMap<_, _>>();
+    let mut m = vec![vec![Rational::ZERO; inner.len()]; inner.len()];
+    let mut b = vec![vec![Rational::ZERO; 2]; inner.len()];
+    for (i, v) in inner.iter().enumerate() {
+        m[i][i] = Rational::from(triangulation[*v].len());
+        for w in &triangulation[*v] {
+            match index.get(w) {
+                Some(j) => m[i][*j] = &m[i][*j] - &Rational::ONE,
+                None => {
+                    b[i][0] = &b[i][0] + &positions[*w].0;
+                    b[i][1] = &b[i][1] + &positions[*w].1;
+                }
+            }
+        }
+    }
+    for (v, mut xy) in inner.iter().zip(solve(m, b)) {
+        let y = xy.pop().unwrap();
+        let x = xy.pop().unwrap();
+        positions[*v] = (x, y);
+    }
+    positions
+}
+
+/// Planarity testing, planar embeddings and planar drawings of finite graphs.
+/// The direction of edges, loops and multiple edges are ignored.
+pub trait GraphPlanarity: FiniteGraphSignature {
+    /// Whether the graph can be drawn in the plane without crossing edges, by the left-right planarity test in linear time.
+    fn is_planar(&self) -> bool {
+        planar_rotation(&simple_adjacency(&self.edge_multiplicities())).is_some()
+    }
+
+    /// A planar embedding if the graph is planar and a Kuratowski subgraph otherwise.
+    ///
+    /// When the left-right test fails the edge addition test of Boyer and Myrvold is run until it gets stuck,
+    /// and the Kuratowski subgraph is isolated around the component it got stuck in, all in linear time.
+    fn planarity(&self) -> Planarity<Self::Vertices> {
+        let vertices = self.vertices().list_all_elements();
+        let adjacency = simple_adjacency(&self.edge_multiplicities());
+        if let Some(rotation) = planar_rotation(&adjacency) {
+            return Planarity::Planar(PlanarEmbedding { vertices, rotation });
+        }
+
+        let edges = kuratowski_edges(&adjacency);
+        let mut degree = HashMap::<usize, usize>::new();
+        for (u, v) in &edges {
+            *degree.entry(*u).or_default() += 1;
+            *degree.entry(*v).or_default() += 1;
+        }
+        let mut branch = degree
+            .into_iter()
+            .filter(|(_, d)| *d >= 3)
+            .map(|(v, _)| v)
+            .collect::<Vec<_>>();
+        branch.sort_unstable();
+        Planarity::NonPlanar(KuratowskiSubgraph {
+            kind: if branch.len() == 5 {
+                KuratowskiGraph::K5
+            } else {
+                KuratowskiGraph::K33
+            },
+            branch_vertices: branch.iter().map(|v| vertices[*v].clone()).collect(),
+            edges: edges
+                .into_iter()
+                .map(|(u, v)| (vertices[u].clone(), vertices[v].clone()))
+                .collect(),
+        })
+    }
+
+    fn planar_embedding(&self) -> Option<PlanarEmbedding<Self::Vertices>> {
+        let vertices = self.vertices().list_all_elements();
+        let rotation = planar_rotation(&simple_adjacency(&self.edge_multiplicities()))?;
+        Some(PlanarEmbedding { vertices, rotation })
+    }
+
+    fn kuratowski_subgraph(&self) -> Option<KuratowskiSubgraph<Self::Vertices>> {
+        match self.planarity() {
+            Planarity::Planar(_) => None,
+            Planarity::NonPlanar(subgraph) => Some(subgraph),
+        }
+    }
+
+    /// A drawing with straight edges and rational coordinates in which no two edges cross, if the graph is planar.
+    ///
+    /// The embedding is extended to a triangulation whose Tutte embedding, with an outer triangle fixed at
+    /// `(0, 0)`, `(1, 0)` and `(0, 1)`, places each inner vertex at the average of its neighbours.
+    fn planar_drawing(&self) -> Option<PlanarDrawing<Self::Vertices>> {
+        let vertices = self.vertices().list_all_elements();
+        let adjacency = simple_adjacency(&self.edge_multiplicities());
+        let rotation = planar_rotation(&adjacency)?;
+        let positions = tutte_drawing(&rotation);
+        let edges = (0..vertices.len())
+            .flat_map(|u| {
+                adjacency[u]
+                    .iter()
+                    .filter(move |v| u < **v)
+                    .map(move |v| (u, *v))
+            })
+            .collect();
+        Some(PlanarDrawing {
+            vertices,
+            positions,
+            edges,
+        })
+    }
+}
+impl<G: FiniteGraphSignature> GraphPlanarity for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::families::petersen_graph as petersen;
+    use crate::examples::{CompleteUndirectedGraph, WheelGraph};
+    use crate::graph::UndirectedGraph;
+    use crate::random::{gnm_random_graph, gnp_random_graph};
+    use algebraeon_nzq::{Rational, Rng};
+    use algebraeon_sets::structure::EnumeratedFiniteSetStructure;
+
+    fn cube() -> UndirectedGraph {
+        UndirectedGraph::from_edges(
+            8,
+            (0..8usize).flat_map(|v| {
+                (0..3)
+                    .map(move |i| (v, v ^ (1 << i)))
+                    .filter(|(u, w)| u < w)
+            }),
+        )
+        .unwrap()
+    }
+
+    //random graphs on up to 11 vertices with up to 3n edges, together with a loop and some repeated edges
+    fn random_graphs(rng: &mut Rng) -> Vec<UndirectedGraph> {
+        let mut graphs = vec![];
+        for n in 1..=11 {
+            let pairs = n * (n - 1) / 2;
+            for m in 0..=pairs.min(3 * n) {
+                let g = gnm_random_graph(n, m, rng).unwrap();
+                let repeated = gnm_random_graph(n, m / 4, rng).unwrap();
+                let edges = g
+                    .edges()
+                    .chain(repeated.edges().map(|(u, v)| (v, u)))
+                    .chain([(m % n, m % n)])
+                    .collect::<Vec<_>>();
+                graphs.push(UndirectedGraph::from_edges(n, edges).unwrap());
+            }
+        }
+        graphs
+    }
+
+    fn simple_edges(g: &UndirectedGraph) -> HashSet<(usize, usize)> {
+        g.edges()
+            .filter(|(u, v)| u != v)
+            .map(|(u, v)| (u.min(v), u.max(v)))
+            .collect()
+    }
+
+    //check the embedding has genus zero using Euler's formula on each component
+    fn check_embedding(
+        g: &UndirectedGraph,
+        embedding: &PlanarEmbedding<EnumeratedFiniteSetStructure>,
+    ) {
+        let edges = simple_edges(g);
+        for (v, around) in embedding.rotation_system() {
+            let mut expected = edges
+                .iter()
+                .filter_map(|(a, b)| {
+                    if *a == v {
+                        Some(*b)
+                    } else if *b == v {
+                        Some(*a)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            let mut around = around;
+            expected.sort_unstable();
+            around.sort_unstable();
+            assert_eq!(around, expected);
+        }
+        let touched = (0..g.vertex_count())
+            .filter(|v| edges.iter().any(|(a, b)| a == v || b == v))
+            .collect::<Vec<_>>();
+        let components = UndirectedGraph::from_edges(g.vertex_count(), edges.iter().copied())
+            .unwrap()
+            .vertices()
+            .list_all_elements()
+            .into_iter()
+            .filter(|v| touched.contains(v))
+            .fold(vec![], |mut roots: Vec<usize>, v| {
+                let mut seen = vec![v];
+                let mut i = 0;
+                while i < seen.len() {
+                    for (a, b) in &edges {
+                        for (x, y) in [(a, b), (b, a)] {
+                            if *x == seen[i] && !seen.contains(y) {
+                                seen.push(*y);
+                            }
+                        }
+                    }
+                    i += 1;
+                }
+                let root = *seen.iter().min().unwrap();
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+                roots
+            })
+            .len();
+        assert_eq!(
+            embedding.faces().len() + touched.len(),
+            edges.len() + 2 * components
+        );
+    }
+
+    //check the subgraph is a subdivision of K5 or K3,3 inside g
+    fn check_kuratowski(
+        g: &UndirectedGraph,
+        subgraph: &KuratowskiSubgraph<EnumeratedFiniteSetStructure>,
+    ) {
+        let edges = simple_edges(g);
+        let mut neighbours = HashMap::<usize, Vec<usize>>::new();
+        for (u, v) in subgraph.edges() {
+            assert!(edges.contains(&(*u.min(v), *u.max(v))));
+            neighbours.entry(*u).or_default().push(*v);
+            neighbours.entry(*v).or_default().push(*u);
+        }
+        let branch = subgraph.branch_vertices();
+        let (degree, branch_count) = match subgraph.kind() {
+            KuratowskiGraph::K5 => (4, 5),
+            KuratowskiGraph::K33 => (3, 6),
+        };
+        assert_eq!(branch.len(), branch_count);
+        for (v, around) in &neighbours {
+            if branch.contains(v) {
+                assert_eq!(around.len(), degree);
+            } else {
+                assert_eq!(around.len(), 2);
+            }
+        }
+        //follow the subdivided paths between branch vertices
+        let mut contracted = HashSet::new();
+        for b in branch {
+            for start in &neighbours[b] {
+                let (mut prev, mut at) = (*b, *start);
+                while !branch.contains(&at) {
+                    let next = *neighbours[&at].iter().find(|x| **x != prev).unwrap();
+                    (prev, at) = (at, next);
+                }
+                assert_ne!(at, *b);
+                contracted.insert((*b.min(&at), *b.max(&at)));
+            }
+        }
+        assert_eq!(contracted.len(), if degree == 4 { 10 } else { 9 });
+        if degree == 3 {
+            //K3,3 rather than another cubic graph on six vertices: no triangles
+            for (a, b) in &contracted {
+                for c in branch {
+                    assert!(
+                        !(contracted.contains(&(*a.min(c), *a.max(c)))
+                            && contracted.contains(&(*b.min(c), *b.max(c))))
+                    );
+                }
+            }
+        }
+    }
+
+    //check no two edges of the drawing meet except at a shared endpoint
+    fn check_drawing(g: &UndirectedGraph, drawing: &PlanarDrawing<EnumeratedFiniteSetStructure>) {
+        let positions = drawing
+            .positions()
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>();
+        let orientation =
+            |p: &(Rational, Rational), q: &(Rational, Rational), r: &(Rational, Rational)| {
+                let d = (&q.0 - &p.0) * (&r.1 - &p.1) - (&q.1 - &p.1) * (&r.0 - &p.0);
+                d.cmp(&Rational::ZERO)
+            };
+        let on_segment =
+            |p: &(Rational, Rational), a: &(Rational, Rational), b: &(Rational, Rational)| {
+                orientation(a, b, p).is_eq()
+                    && p.0 >= a.0.clone().min(b.0.clone())
+                    && p.0 <= a.0.clone().max(b.0.clone())
+                    && p.1 >= a.1.clone().min(b.1.clone())
+                    && p.1 <= a.1.clone().max(b.1.clone())
+            };
+        for u in 0..positions.len() {
+            for v in 0..u {
+                assert_ne!(positions[u], positions[v]);
+            }
+        }
+        let edges = simple_edges(g).into_iter().collect::<Vec<_>>();
+        for (a, b) in &edges {
+            for v in 0..positions.len() {
+                if v != *a && v != *b {
+                    assert!(!on_segment(&positions[v], &positions[*a], &positions[*b]));
+                }
+            }
+        }
+        for (i, (a, b)) in edges.iter().enumerate() {
+            for (c, d) in &edges[..i] {
+                if a == c || a == d || b == c || b == d {
+                    continue;
+                }
+                let (pa, pb, pc, pd) = (
+                    &positions[*a],
+                    &positions[*b],
+                    &positions[*c],
+                    &positions[*d],
+                );
+                let crossing = orientation(pa, pb, pc) != orientation(pa, pb, pd)
+                    && orientation(pc, pd, pa) != orientation(pc, pd, pb);
+                assert!(!crossing);
+            }
+        }
+        assert_eq!(
+            drawing.simplicial_complex().simplexes().len(),
+            positions.len() + edges.len()
+        );
+    }
+
+    #[test]
+    fn test_planarity() {
+        let k4 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(4));
+        assert!(k4.is_planar());
+        let k5 = CompleteUndirectedGraph::new(EnumeratedFiniteSetStructure::new(5));
+        assert!(!k5.is_planar());
+        assert_eq!(
+            k5.kuratowski_subgraph().unwrap().kind(),
+            KuratowskiGraph::K5
+        );
+        let wheel = WheelGraph::new(8).unwrap();
+        assert!(wheel.is_planar());
+        assert!(cube().is_planar());
+        assert_eq!(cube().planar_embedding().unwrap().faces().len(), 6);
+
+        let p = petersen();
+        assert!(!p.is_planar());
+        let subgraph = p.kuratowski_subgraph().unwrap();
+        assert_eq!(subgraph.kind(), KuratowskiGraph::K33);
+        check_kuratowski(&p, &subgraph);
+
+        let k33 = UndirectedGraph::from_edges(6, (0..3).flat_map(|i| (3..6).map(move |j| (i, j))))
+            .unwrap();
+        assert!(!k33.is_planar());
+        let mut k33_minus_edge = k33.clone();
+        k33_minus_edge.remove_edges_between(0, 3).unwrap();
+        assert!(k33_minus_edge.is_planar());
+    }
+
+    #[test]
+    fn test_random_planarity() {
+        let mut planar = 0;
+        let mut rng = Rng::new(2024);
+        for g in random_graphs(&mut rng) {
+            match g.planarity() {
+                Planarity::Planar(embedding) => {
+                    planar += 1;
+                    check_embedding(&g, &embedding);
+                    check_drawing(&g, &g.planar_drawing().unwrap());
+                }
+                Planarity::NonPlanar(subgraph) => {
+                    assert!(g.planar_drawing().is_none());
+                    check_kuratowski(&g, &subgraph);
+                }
+            }
+        }
+        //make sure both cases are well represented
+        assert!(planar > 50 && planar < 130, "{planar}");
+
+        //random subgraphs of a triangulated 5x5 grid are planar
+        let grid = (0..25)
+            .flat_map(|v| {
+                let mut e = vec![];
+                if v % 5 != 4 {
+                    e.push((v, v + 1));
+                }
+                if v < 20 {
+                    e.push((v, v + 5));
+                }
+                if v % 5 != 4 && v < 20 {
+                    e.push((v, v + 6));
+                }
+                e
+            })
+            .collect::<HashSet<_>>();
+        for keep in (1..=10).flat_map(|keep| [keep; 3]) {
+            let random =
+                gnp_random_graph(25, &Rational::from_integers(keep, 10), &mut rng).unwrap();
+            let edges = random
+                .edges()
+                .filter(|e| grid.contains(e))
+                .collect::<Vec<_>>();
+            let g = UndirectedGraph::from_edges(25, edges).unwrap();
+            check_embedding(&g, &g.planar_embedding().unwrap());
+            check_drawing(&g, &g.planar_drawing().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_planar_drawing() {
+        for g in [cube(), UndirectedGraph::new(0), UndirectedGraph::new(3)] {
+            check_drawing(&g, &g.planar_drawing().unwrap());
+        }
+        let wheel = WheelGraph::new(6).unwrap();
+        let drawing = wheel.planar_drawing().unwrap();
+        assert_eq!(drawing.segments().len(), 10);
+        assert!(petersen().planar_drawing().is_none());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::graph::UndirectedGraph;
+use algebraeon_nzq::traits::Fraction;
+use algebraeon_nzq::{Natural, Rational, Rng};
+
+/// A uniformly random element of `0, 1, ..., n-1`. Requires `n >= 1`.
+fn random_below(rng: &mut Rng, n: usize) -> usize {
+    debug_assert!(n >= 1);
+    rng.uniform_random_natural_less_than(Natural::from(n - 1))
+        .try_into()
+        .unwrap()
+}
+
+fn check_probability(p: &Rational) -> Result<(), String> {
+    if p < &Rational::ZERO || p > &Rational::ONE {
+        return Err(format!("{p} is not a probability"));
+    }
+    Ok(())
+}
+
+/// Return `true` with probability `p`, exactly.
+fn random_bool(rng: &mut Rng, p: &Rational) -> bool {
+    let (numerator, denominator) = p.numerator_and_denominator();
+    let x = rng.uniform_random_natural_less_than(denominator - Natural::ONE);
+    numerator > x
+}
+
+/// Move a uniformly random choice of `k` of the elements of `items` to the front, in a uniformly random order.
+fn partial_shuffle<T>(rng: &mut Rng, items: &mut [T], k: usize) {
+    for i in 0..k {
+        let j = i + random_below(rng, items.len() - i);
+        items.swap(i, j);
+    }
+}
+
+/// The Erdős–Rényi graph `G(n, p)` on `n` vertices where each of the possible edges is present
+/// independently with probability `p`.
+pub fn gnp_random_graph(n: usize, p: &Rational, rng: &mut Rng) -> Result<UndirectedGraph, String> {
+    check_probability(p)?;
+    let mut edges = vec![];
+    for u in 0..n {
+        for v in (u + 1)..n {
+            if random_bool(rng, p) {
+                edges.push((u, v));
+            }
+        }
+    }
+    UndirectedGraph::from_edges(n, edges)
+}
+
+/// The Erdős–Rényi graph `G(n, m)` chosen uniformly from the simple graphs on `n` vertices with `m` edges.
+pub fn gnm_random_graph(n: usize, m: usize, rng: &mut Rng) -> Result<UndirectedGraph, String> {
+    let mut pairs = (0..n)
+        .flat_map(|u| ((u + 1)..n).map(move |v| (u, v)))
+        .collect::<Vec<_>>();
+    if m > pairs.len() {
+        return Err(format!(
+            "A simple graph on {n} vertices has at most {} edges",
+            pairs.len()
+        ));
+    }
+    partial_shuffle(rng, &mut pairs, m);
+    pairs.truncate(m);
+    pairs.sort_unstable();
+    UndirectedGraph::from_edges(n, pairs)
+}
+
+/// A random simple `d`-regular graph on `n` vertices.
+///
+/// Edges are added one at a time between random pairs of vertices which still need more edges,
+/// starting again from scratch whenever no such pair can be joined.
+/// The result is close to uniform for small `d` but is not exactly uniform.
+pub fn random_regular_graph(n: usize, d: usize, rng: &mut Rng) -> Result<UndirectedGraph, String> {
+    if d >= n && n > 0 {
+        return Err("A simple d-regular graph on n vertices requires d < n".to_string());
+    }
+    if !(n * d).is_multiple_of(2) {
+        return Err("A d-regular graph on n vertices requires n * d to be even".to_string());
+    }
+    'attempt: loop {
+        let mut adjacent = vec![vec![false; n]; n];
+        //one entry per missing edge end
+        let mut stubs = (0..n)
+            .flat_map(|v| std::iter::repeat_n(v, d))
+            .collect::<Vec<_>>();
+        let mut edges = vec![];
+        while !stubs.is_empty() {
+            let i = random_below(rng, stubs.len());
+            let j = random_below(rng, stubs.len());
+            let (u, v) = (stubs[i], stubs[j]);
+            if u == v || adjacent[u][v] {
+                let mut remaining = stubs.clone();
+                remaining.sort_unstable();
+                remaining.dedup();
+                if remaining
+                    .iter()
+                    .enumerate()
+                    .any(|(k, a)| remaining[(k + 1)..].iter().any(|b| !adjacent[*a][*b]))
+                {
+                    continue;
+                }
+                continue 'attempt;
+            }
+            adjacent[u][v] = true;
+            adjacent[v][u] = true;
+            edges.push((u.min(v), u.max(v)));
+            stubs.swap_remove(i.max(j));
+            stubs.swap_remove(i.min(j));
+        }
+        return UndirectedGraph::from_edges(n, edges);
+    }
+}
+
+/// A random graph on `n` vertices grown by Barabási–Albert preferential attachment.
+///
+/// The vertices `0, 1, ..., m-1` start with no edges. Each later vertex is joined to `m` distinct
+/// earlier vertices, the first to all of `0, 1, ..., m-1` and the rest to vertices chosen with
+/// probability proportional to their degree. Requires `1 <= m < n`.
+pub fn barabasi_albert_graph(n: usize, m: usize, rng: &mut Rng) -> Result<UndirectedGraph, String> {
+    if m == 0 || m >= n {
+        return Err("Barabási–Albert graphs require 1 <= m < n".to_string());
+    }
+    let mut edges = vec![];
+    //each vertex appears once for each edge end at it
+    let mut ends: Vec<usize> = vec![];
+    let mut targets = (0..m).collect::<Vec<_>>();
+    for v in m..n {
+        for t in &targets {
+            edges.push((*t, v));
+        }
+        ends.extend(targets.iter().copied());
+        ends.extend(std::iter::repeat_n(v, m));
+        targets.clear();
+        while targets.len() < m {
+            let t = ends[random_below(rng, ends.len())];
+            if !targets.contains(&t) {
+                targets.push(t);
+            }
+        }
+    }
+    UndirectedGraph::from_edges(n, edges)
+}
+
+/// The labelled tree on `n + 2` vertices with the given Prüfer sequence of length `n`.
+pub fn tree_from_prufer_sequence(sequence: &[usize]) -> Result<UndirectedGraph, String> {
+    let n = sequence.len() + 2;
+    let mut degree = vec![1; n];
+    for v in sequence {
+        if *v >= n {
+            return Err(format!("{v} is not a vertex of a tree with {n} vertices"));
+        }
+        degree[*v] += 1;
+    }
+    let mut edges = vec![];
+    for v in sequence {
+        //join v to the smallest remaining leaf
+        let leaf = (0..n).find(|u| degree[*u] == 1).unwrap();
+        edges.push((leaf, *v));
+        degree[leaf] -= 1;
+        degree[*v] -= 1;
+    }
+    let last = (0..n).filter(|u| degree[*u] == 1).collect::<Vec<_>>();
+    edges.push((last[0], last[1]));
+    UndirectedGraph::from_edges(n, edges)
+}
+
+/// A uniformly random labelled tree on `n` vertices, from a random Prüfer sequence.
+pub fn random_tree(n: usize, rng: &mut Rng) -> UndirectedGraph {
+    if n <= 1 {
+        return UndirectedGraph::new(n);
+    }
+    let sequence = (0..n - 2).map(|_| random_below(rng, n)).collect::<Vec<_>>();
+    tree_from_prufer_sequence(&sequence).unwrap()
+}
+
+/// A random bipartite graph with parts `0, ..., m-1` and `m, ..., m+n-1` where each of the possible
+/// edges between the parts is present independently with probability `p`.
+pub fn random_bipartite_graph(
+    m: usize,
+    n: usize,
+    p: &Rational,
+    rng: &mut Rng,
+) -> Result<UndirectedGraph, String> {
+    check_probability(p)?;
+    let mut edges = vec![];
+    for u in 0..m {
+        for v in m..(m + n) {
+            if random_bool(rng, p) {
+                edges.push((u, v));
+            }
+        }
+    }
+    UndirectedGraph::from_edges(m + n, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::FiniteGraphAlgorithms;
+    use crate::isomorphism::FiniteGraphIsomorphism;
+
+    #[test]
+    fn test_seeds_are_reproducible() {
+        let p = Rational::from_integers(1, 3);
+        for seed in 0..5 {
+            let a = gnp_random_graph(12, &p, &mut Rng::new(seed)).unwrap();
+            let b = gnp_random_graph(12, &p, &mut Rng::new(seed)).unwrap();
+            assert_eq!(a.edges().collect::<Vec<_>>(), b.edges().collect::<Vec<_>>());
+
+            let a = random_regular_graph(10, 3, &mut Rng::new(seed)).unwrap();
+            let b = random_regular_graph(10, 3, &mut Rng::new(seed)).unwrap();
+            assert_eq!(a.edges().collect::<Vec<_>>(), b.edges().collect::<Vec<_>>());
+        }
+        let a = gnm_random_graph(20, 30, &mut Rng::new(1)).unwrap();
+        let b = gnm_random_graph(20, 30, &mut Rng::new(2)).unwrap();
+        assert_ne!(a.edges().collect::<Vec<_>>(), b.edges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_erdos_renyi() {
+        let mut rng = Rng::new(0);
+        assert_eq!(
+            gnp_random_graph(8, &Rational::ZERO, &mut rng)
+                .unwrap()
+                .edge_count(),
+            0
+        );
+        assert_eq!(
+            gnp_random_graph(8, &Rational::ONE, &mut rng)
+                .unwrap()
+                .edge_count(),
+            28
+        );
+        assert!(gnp_random_graph(8, &Rational::from(2), &mut rng).is_err());
+
+        //about half of the 1225 possible edges
+        let g = gnp_random_graph(50, &Rational::from_integers(1, 2), &mut rng).unwrap();
+        assert!(g.is_simple());
+        assert!((500..725).contains(&g.edge_count()));
+
+        for m in [0, 7, 45] {
+            let g = gnm_random_graph(10, m, &mut rng).unwrap();
+            assert!(g.is_simple());
+            assert_eq!(g.edge_count(), m);
+        }
+        assert!(gnm_random_graph(10, 46, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_random_regular_graph() {
+        let mut rng = Rng::new(0);
+        for (n, d) in [(0, 0), (1, 0), (10, 3), (12, 4), (7, 6), (20, 5), (9, 8)] {
+            let g = random_regular_graph(n, d, &mut rng).unwrap();
+            assert!(g.is_simple());
+            assert!((0..n).all(|v| g.degree(v) == d));
+        }
+        assert!(random_regular_graph(7, 3, &mut rng).is_err());
+        assert!(random_regular_graph(4, 4, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_barabasi_albert_graph() {
+        let mut rng = Rng::new(0);
+        for m in 1..4 {
+            let g = barabasi_albert_graph(30, m, &mut rng).unwrap();
+            assert!(g.is_simple());
+            assert!(g.is_connected());
+            assert_eq!(g.edge_count(), (30 - m) * m);
+            assert!((m..30).all(|v| g.degree(v) >= m));
+        }
+        assert!(barabasi_albert_graph(3, 3, &mut rng).is_err());
+        assert!(barabasi_albert_graph(3, 0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_random_trees() {
+        let path = tree_from_prufer_sequence(&[1, 2, 3]).unwrap();
+        assert_eq!(
+            path.edges().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 3), (3, 4)]
+        );
+        let star = tree_from_prufer_sequence(&[0, 0, 0]).unwrap();
+        assert_eq!(star.degree(0), 4);
+        assert!(tree_from_prufer_sequence(&[5]).is_err());
+
+        let mut rng = Rng::new(0);
+        for n in 0..20 {
+            let t = random_tree(n, &mut rng);
+            assert_eq!(t.vertex_count(), n);
+            assert_eq!(t.edge_count(), n.saturating_sub(1));
+            assert!(t.is_connected());
+        }
+
+        //every one of the 16 labelled trees on 4 vertices shows up, in 3 + 1 isomorphism classes
+        let mut seen = vec![];
+        for _ in 0..400 {
+            let edges = random_tree(4, &mut rng).edges().collect::<Vec<_>>();
+            if !seen.contains(&edges) {
+                seen.push(edges);
+            }
+        }
+        assert_eq!(seen.len(), 16);
+        let stars = seen
+            .iter()
+            .filter(|edges| {
+                UndirectedGraph::from_edges(4, edges.iter().copied())
+                    .unwrap()
+                    .is_isomorphic_to(&tree_from_prufer_sequence(&[0, 0]).unwrap())
+            })
+            .count();
+        assert_eq!(stars, 4);
+    }
+
+    #[test]
+    fn test_random_bipartite_graph() {
+        let mut rng = Rng::new(0);
+        let g = random_bipartite_graph(5, 7, &Rational::from_integers(2, 3), &mut rng).unwrap();
+        assert_eq!(g.vertex_count(), 12);
+        assert!(g.edges().all(|(u, v)| u < 5 && v >= 5));
+        assert!(FiniteGraphAlgorithms::is_bipartite(&g));
+        assert_eq!(
+            random_bipartite_graph(3, 4, &Rational::ONE, &mut rng)
+                .unwrap()
+                .edge_count(),
+            12
+        );
+        assert!(random_bipartite_graph(3, 4, &Rational::from(-1), &mut rng).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use algebraeon_sets::structure::{SetSignature, UnorderedPair};
+use algebraeon_sets::structure::{EqSignature, FiniteSetSignature, SetSignature, UnorderedPair};
 
 /// A directed graph permitting loops.
 pub trait GraphSignature {
@@ -26,3 +26,38 @@ pub trait GraphWithEdgesSignature: GraphSignature {
         edge: &<Self::Edges as SetSignature>::Set,
     ) -> UnorderedPair<<Self::Vertices as SetSignature>::Set>;
 }
+
+/// A graph whose vertices can be listed.
+pub trait FiniteGraphSignature: GraphSignature<Vertices: FiniteSetSignature + EqSignature> {
+    fn vertices(&self) -> &Self::Vertices;
+
+    /// For each vertex, in the order given by `list_all_elements`, the positions of the targets of the edges leaving it.
+    /// Each target is listed once even if there are multiple edges to it.
+    fn out_adjacency(&self) -> Vec<Vec<usize>> {
+        let vertices = self.vertices().list_all_elements();
+        vertices
+            .iter()
+            .map(|u| {
+                (0..vertices.len())
+                    .filter(|j| self.has_directed_edge(u, &vertices[*j]).is_ok())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The number of edges from each vertex to each other vertex, indexed in the order given by `list_all_elements`.
+    /// An undirected edge counts in both directions and an undirected loop counts once.
+    fn edge_multiplicities(&self) -> Vec<Vec<usize>> {
+        let adjacency = self.out_adjacency();
+        adjacency
+            .iter()
+            .map(|targets| {
+                let mut row = vec![0; adjacency.len()];
+                for t in targets {
+                    row[*t] = 1;
+                }
+                row
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,539 @@
+use crate::composition_table::group::FiniteGroupMultiplicationTable;
+use crate::permutation::Permutation;
+use crate::structure::Group;
+use algebraeon_sets::structure::{
+    EnumeratedFiniteSetStructure, EqSignature, FiniteSetSignature, SetSignature,
+};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A partition of the points of `X` into blocks.
+pub type BlockSystem<X> = Vec<Vec<<X as SetSignature>::Set>>;
+
+/// An action of the group generated by `generators()` on a finite set.
+///
+/// Implementors provide the set, a generating set for the acting group and the action of a single
+/// group element. The remaining methods compute orbits, stabilizers and block systems by working
+/// with the permutations the generators induce on the points of the set. Points are hashed to look up
+/// their indices, so hashing must agree with `equal`.
+pub trait GroupAction<G: Group + Hash> {
+    type X: FiniteSetSignature<Set: Hash + Eq> + EqSignature;
+
+    fn set(&self) -> &Self::X;
+
+    fn generators(&self) -> Vec<G>;
+
+    /// The image of `x` under `g`. Must satisfy `act(gh, x) = act(g, act(h, x))`.
+    fn act(&self, g: &G, x: &<Self::X as SetSignature>::Set) -> <Self::X as SetSignature>::Set;
+
+    /// All points of the set in the order they are labelled by the other methods.
+    fn points(&self) -> Vec<<Self::X as SetSignature>::Set> {
+        self.set().list_all_elements()
+    }
+
+    /// The index of each point in `points()`. Prefer this to repeated calls of `point_index`, each of
+    /// which lists all the points.
+    fn point_indices(&self) -> HashMap<<Self::X as SetSignature>::Set, usize> {
+        self.points()
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x, i))
+            .collect()
+    }
+
+    fn point_index(&self, x: &<Self::X as SetSignature>::Set) -> usize {
+        self.point_indices()[x]
+    }
+
+    /// The permutation of point indices induced by `g`.
+    fn point_permutation(&self, g: &G) -> Permutation {
+        self.generator_permutations_of(std::slice::from_ref(g))
+            .pop()
+            .unwrap()
+    }
+
+    /// The permutations of point indices induced by each of `gs`, looking up the index of every point
+    /// in a single index map.
+    fn generator_permutations_of(&self, gs: &[G]) -> Vec<Permutation> {
+        let points = self.points();
+        let indices = self.point_indices();
+        gs.iter()
+            .map(|g| {
+                Permutation::new_unchecked(
+                    points.iter().map(|x| indices[&self.act(g, x)]).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// The permutations of point indices induced by the generators.
+    fn generator_permutations(&self) -> Vec<Permutation> {
+        self.generator_permutations_of(&self.generators())
+    }
+
+    /// Every element of the acting group.
+    fn group_elements(&self) -> Vec<G> {
+        let gens = self.generators();
+        let mut elems = vec![G::identity()];
+        let mut found = HashSet::from([G::identity()]);
+        let mut i = 0;
+        while i < elems.len() {
+            for g in &gens {
+                let h = G::compose_refs(g, &elems[i]);
+                if !found.contains(&h) {
+                    found.insert(h.clone());
+                    elems.push(h);
+                }
+            }
+            i += 1;
+        }
+        elems
+    }
+
+    /// The orbits of the action as lists of point indices.
+    fn orbit_indices(&self) -> Vec<Vec<usize>> {
+        let n = self.set().size();
+        let gen_perms = self.generator_permutations();
+        let mut seen = vec![false; n];
+        let mut orbits = vec![];
+        for x in 0..n {
+            if seen[x] {
+                continue;
+            }
+            seen[x] = true;
+            let mut orbit = vec![x];
+            let mut i = 0;
+            while i < orbit.len() {
+                for perm in &gen_perms {
+                    let y = perm.call(orbit[i]);
+                    if !seen[y] {
+                        seen[y] = true;
+                        orbit.push(y);
+                    }
+                }
+                i += 1;
+            }
+            orbits.push(orbit);
+        }
+        orbits
+    }
+
+    fn orbits(&self) -> Vec<Vec<<Self::X as SetSignature>::Set>> {
+        let points = self.points();
+        self.orbit_indices()
+            .into_iter()
+            .map(|orbit| orbit.into_iter().map(|i| points[i].clone()).collect())
+            .collect()
+    }
+
+    /// The orbit of `x` together with, for each point `y` in the orbit, a group element mapping `x` to `y`.
+    fn orbit_transversal(
+        &self,
+        x: &<Self::X as SetSignature>::Set,
+    ) -> Vec<(<Self::X as SetSignature>::Set, G)> {
+        let gens = self.generators();
+        let mut orbit = vec![(x.clone(), G::identity())];
+        let mut i = 0;
+        while i < orbit.len() {
+            for g in &gens {
+                let y = self.act(g, &orbit[i].0);
+                if !orbit.iter().any(|(z, _)| self.set().equal(z, &y)) {
+                    let gh = G::compose_refs(g, &orbit[i].1);
+                    orbit.push((y, gh));
+                }
+            }
+            i += 1;
+        }
+        orbit
+    }
+
+    fn orbit(&self, x: &<Self::X as SetSignature>::Set) -> Vec<<Self::X as SetSignature>::Set> {
+        self.orbit_transversal(x)
+            .into_iter()
+            .map(|(y, _)| y)
+            .collect()
+    }
+
+    /// Generators for the stabilizer of `x` obtained from Schreier's lemma.
+    fn stabilizer_generators(&self, x: &<Self::X as SetSignature>::Set) -> Vec<G> {
+        let transversal = self.orbit_transversal(x);
+        let mut stab_gens = HashSet::new();
+        for (y, t) in &transversal {
+            for g in self.generators() {
+                let gy = self.act(&g, y);
+                let (_, t_gy) = transversal
+                    .iter()
+                    .find(|(z, _)| self.set().equal(z, &gy))
+                    .unwrap();
+                let s = G::compose_refs(&t_gy.inverse_ref(), &G::compose_refs(&g, t));
+                if s != G::identity() {
+                    stab_gens.insert(s);
+                }
+            }
+        }
+        stab_gens.into_iter().collect()
+    }
+
+    /// Every element of the acting group which fixes `x`.
+    fn stabilizer(&self, x: &<Self::X as SetSignature>::Set) -> Vec<G> {
+        self.group_elements()
+            .into_iter()
+            .filter(|g| self.set().equal(&self.act(g, x), x))
+            .collect()
+    }
+
+    fn fixed_points(&self, g: &G) -> Vec<<Self::X as SetSignature>::Set> {
+        self.points()
+            .into_iter()
+            .filter(|x| self.set().equal(&self.act(g, x), x))
+            .collect()
+    }
+
+    /// Count the orbits using Burnside's lemma: the average number of fixed points of a group element.
+    fn count_orbits_burnside(&self) -> usize {
+        let elems = self.group_elements();
+        let total_fixed: usize = elems.iter().map(|g| self.fixed_points(g).len()).sum();
+        debug_assert_eq!(total_fixed % elems.len(), 0);
+        total_fixed / elems.len()
+    }
+
+    fn is_transitive(&self) -> bool {
+        self.orbit_indices().len() <= 1
+    }
+
+    /// The finest block system, as lists of point indices, in which the points with indices `x` and `y` lie in the same block.
+    fn minimal_block_system_indices(&self, x: usize, y: usize) -> Vec<Vec<usize>> {
+        minimal_block_system(self.set().size(), &self.generator_permutations(), x, y)
+    }
+
+    fn minimal_block_system(
+        &self,
+        x: &<Self::X as SetSignature>::Set,
+        y: &<Self::X as SetSignature>::Set,
+    ) -> BlockSystem<Self::X> {
+        let points = self.points();
+        let indices = self.point_indices();
+        self.minimal_block_system_indices(indices[x], indices[y])
+            .into_iter()
+            .map(|block| block.into_iter().map(|i| points[i].clone()).collect())
+            .collect()
+    }
+
+    /// Every block system of the action, as lists of point indices, including the trivial ones.
+    /// Returns `None` if the action is not transitive, since block systems are only defined for
+    /// transitive actions.
+    fn block_system_indices(&self) -> Option<Vec<Vec<Vec<usize>>>> {
+        if !self.is_transitive() {
+            return None;
+        }
+        let n = self.set().size();
+        let gen_perms = self.generator_permutations();
+
+        //every block system is a join of the minimal block systems containing {0, y}
+        let mut systems = vec![(0..n).map(|a| vec![a]).collect::<Vec<_>>()];
+        for y in 1..n {
+            let system = minimal_block_system(n, &gen_perms, 0, y);
+            if !systems.contains(&system) {
+                systems.push(system);
+            }
+        }
+        let mut i = 0;
+        while i < systems.len() {
+            for j in 0..i {
+                //the join of two block systems merges any blocks which overlap
+                let mut parent = (0..n).collect::<Vec<_>>();
+                for block in systems[i].iter().chain(systems[j].iter()) {
+                    for a in block {
+                        let ra = find(&mut parent, *a);
+                        let rb = find(&mut parent, block[0]);
+                        parent[ra] = rb;
+                    }
+                }
+                let join = partition_from_union_find(&mut parent);
+                if !systems.contains(&join) {
+                    systems.push(join);
+                }
+            }
+            i += 1;
+        }
+        Some(systems)
+    }
+
+    /// Every block system of the action, including the trivial ones, or `None` if the action is not
+    /// transitive.
+    fn block_systems(&self) -> Option<Vec<BlockSystem<Self::X>>> {
+        let points = self.points();
+        Some(
+            self.block_system_indices()?
+                .into_iter()
+                .map(|system| {
+                    system
+                        .into_iter()
+                        .map(|block| block.into_iter().map(|i| points[i].clone()).collect())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// A transitive action is primitive if its only block systems are the trivial ones.
+    fn is_primitive(&self) -> bool {
+        if !self.is_transitive() {
+            return false;
+        }
+        let n = self.set().size();
+        let gen_perms = self.generator_permutations();
+        (1..n).all(|y| minimal_block_system(n, &gen_perms, 0, y).len() == 1)
+    }
+}
+
+//the finest partition of {0, 1, ..., n-1} invariant under gen_perms in which x and y lie in the same class
+fn minimal_block_system(
+    n: usize,
+    gen_perms: &[Permutation],
+    x: usize,
+    y: usize,
+) -> Vec<Vec<usize>> {
+    let mut parent = (0..n).collect::<Vec<_>>();
+    //merge classes and propagate the merge along the generators until the partition is invariant
+    let mut to_merge = vec![(x, y)];
+    while let Some((a, b)) = to_merge.pop() {
+        let ra = find(&mut parent, a);
+        let rb = find(&mut parent, b);
+        if ra == rb {
+            continue;
+        }
+        parent[rb] = ra;
+        for perm in gen_perms {
+            to_merge.push((perm.call(a), perm.call(b)));
+        }
+    }
+
+    partition_from_union_find(&mut parent)
+}
+
+fn find(parent: &mut [usize], mut a: usize) -> usize {
+    while parent[a] != a {
+        parent[a] = parent[parent[a]];
+        a = parent[a];
+    }
+    a
+}
+
+//the classes of a union-find forest as sorted lists of sorted blocks
+fn partition_from_union_find(parent: &mut [usize]) -> Vec<Vec<usize>> {
+    let mut blocks: HashMap<usize, Vec<usize>> = HashMap::new();
+    for a in 0..parent.len() {
+        let r = find(parent, a);
+        blocks.entry(r).or_default().push(a);
+    }
+    let mut blocks = blocks.into_values().collect::<Vec<_>>();
+    blocks.sort();
+    blocks
+}
+
+/// The natural action of a group of permutations on `{0, 1, ..., n-1}`.
+#[derive(Debug, Clone)]
+pub struct PermutationAction {
+    set: EnumeratedFiniteSetStructure,
+    generators: Vec<Permutation>,
+}
+
+impl PermutationAction {
+    pub fn new(n: usize, generators: Vec<Permutation>) -> Result<Self, &'static str> {
+        for g in &generators {
+            if g.n() > n {
+                return Err("generator moves a point outside of the set");
+            }
+        }
+        Ok(Self {
+            set: EnumeratedFiniteSetStructure::new(n),
+            generators,
+        })
+    }
+}
+
+impl GroupAction<Permutation> for PermutationAction {
+    type X = EnumeratedFiniteSetStructure;
+
+    fn set(&self) -> &Self::X {
+        &self.set
+    }
+
+    fn generators(&self) -> Vec<Permutation> {
+        self.generators.clone()
+    }
+
+    fn act(&self, g: &Permutation, x: &usize) -> usize {
+        g.call(*x)
+    }
+
+    fn point_index(&self, x: &usize) -> usize {
+        *x
+    }
+
+    fn point_permutation(&self, g: &Permutation) -> Permutation {
+        g.clone()
+    }
+
+    fn generator_permutations_of(&self, gs: &[Permutation]) -> Vec<Permutation> {
+        gs.to_vec()
+    }
+}
+
+impl FiniteGroupMultiplicationTable {
+    /// The action of the group on itself by left multiplication as permutations of its elements.
+    pub fn left_regular_action(&self) -> PermutationAction {
+        PermutationAction::new(
+            self.size(),
+            self.generating_set()
+                .gens()
+                .iter()
+                .map(|g| {
+                    Permutation::new_unchecked(self.elems().map(|x| self.mul(*g, x)).collect())
+                })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    /// The action of the group on itself by conjugation as permutations of its elements.
+    pub fn conjugation_action(&self) -> PermutationAction {
+        PermutationAction::new(
+            self.size(),
+            self.generating_set()
+                .gens()
+                .iter()
+                .map(|g| self.conjugation_automorphism(*g))
+                .collect(),
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod action_tests {
+    use super::*;
+    use crate::composition_table::group::examples;
+
+    fn cycle(n: usize) -> Permutation {
+        Permutation::new((0..n).map(|i| (i + 1) % n).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_orbits_and_stabilizers() {
+        //<(0 1 2), (3 4)> acting on {0, ..., 5}
+        let action = PermutationAction::new(
+            6,
+            vec![
+                Permutation::new(vec![1, 2, 0]).unwrap(),
+                Permutation::new(vec![0, 1, 2, 4, 3]).unwrap(),
+            ],
+        )
+        .unwrap();
+        let mut orbits = action.orbits();
+        for orbit in &mut orbits {
+            orbit.sort_unstable();
+        }
+        orbits.sort();
+        assert_eq!(orbits, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(action.count_orbits_burnside(), 3);
+        assert!(!action.is_transitive());
+        assert_eq!(action.block_systems(), None);
+
+        assert_eq!(action.group_elements().len(), 6);
+        for x in 0..6 {
+            //orbit-stabilizer theorem
+            assert_eq!(action.orbit(&x).len() * action.stabilizer(&x).len(), 6);
+            for (y, g) in action.orbit_transversal(&x) {
+                assert_eq!(action.act(&g, &x), y);
+            }
+            for g in action.stabilizer_generators(&x) {
+                assert_eq!(action.act(&g, &x), x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_burnside_necklaces() {
+        //colourings of a necklace of 6 beads with 2 colours up to rotation
+        #[derive(Debug, Clone)]
+        struct NecklaceAction {
+            set: EnumeratedFiniteSetStructure,
+        }
+
+        impl GroupAction<Permutation> for NecklaceAction {
+            type X = EnumeratedFiniteSetStructure;
+
+            fn set(&self) -> &Self::X {
+                &self.set
+            }
+
+            fn generators(&self) -> Vec<Permutation> {
+                vec![cycle(6)]
+            }
+
+            fn act(&self, g: &Permutation, x: &usize) -> usize {
+                (0..6)
+                    .filter(|i| x & (1 << i) != 0)
+                    .map(|i| 1 << g.call(i))
+                    .sum()
+            }
+        }
+
+        let action = NecklaceAction {
+            set: EnumeratedFiniteSetStructure::new(64),
+        };
+        assert_eq!(action.count_orbits_burnside(), 14);
+        assert_eq!(action.orbits().len(), 14);
+    }
+
+    #[test]
+    fn test_primitivity_and_blocks() {
+        //a 6-cycle acting on {0, ..., 5} is transitive but imprimitive
+        let action = PermutationAction::new(6, vec![cycle(6)]).unwrap();
+        assert!(action.is_transitive());
+        assert!(!action.is_primitive());
+        assert_eq!(
+            action.minimal_block_system(&0, &3),
+            vec![vec![0, 3], vec![1, 4], vec![2, 5]]
+        );
+        //the block systems correspond to the subgroups of C6
+        assert_eq!(action.block_systems().unwrap().len(), 4);
+
+        //a 5-cycle acting on {0, ..., 4} is primitive
+        let action = PermutationAction::new(5, vec![cycle(5)]).unwrap();
+        assert!(action.is_primitive());
+        assert_eq!(action.block_systems().unwrap().len(), 2);
+
+        //the dihedral group acting on the vertices of a square preserves the diagonals
+        let action = PermutationAction::new(
+            4,
+            vec![cycle(4), Permutation::new(vec![0, 3, 2, 1]).unwrap()],
+        )
+        .unwrap();
+        assert!(!action.is_primitive());
+        assert_eq!(action.block_systems().unwrap().len(), 3);
+
+        //S4 acting naturally is primitive
+        let action =
+            PermutationAction::new(4, vec![cycle(4), Permutation::new(vec![1, 0]).unwrap()])
+                .unwrap();
+        assert!(action.is_primitive());
+        assert_eq!(action.group_elements().len(), 24);
+    }
+
+    #[test]
+    fn test_composition_table_actions() {
+        let grp = examples::symmetric_group_structure(4);
+        let regular = grp.left_regular_action();
+        assert!(regular.is_transitive());
+        assert_eq!(regular.group_elements().len(), 24);
+        assert!(regular.stabilizer(&0).len() == 1);
+
+        //orbits of the conjugation action are the conjugacy classes
+        let conj = grp.conjugation_action();
+        assert_eq!(conj.orbits().len(), grp.conjugacy_classes().size());
+        assert_eq!(conj.count_orbits_burnside(), 5);
+    }
+}
@@ -0,0 +1,191 @@
+use super::group::FiniteGroupMultiplicationTable;
+use super::normal_subgroup::NormalSubgroup;
+use super::subgroup::Subgroup;
+use super::subset::Subset;
+use crate::permutation::Permutation;
+use crate::structure::Group;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// The automorphism group of a finite group.
+///
+/// Each automorphism is stored as a permutation of the elements of the group and is labelled by
+/// its index in the multiplication table of the automorphism group. Composition in the table is
+/// composition of functions, so `table().mul(f, g)` is the automorphism `x -> f(g(x))`.
+pub struct AutomorphismGroup<'a> {
+    group: &'a FiniteGroupMultiplicationTable,
+    table: FiniteGroupMultiplicationTable,
+    automorphisms: Vec<Permutation>,
+    lookup: HashMap<Permutation, usize>,
+}
+
+impl<'a> AutomorphismGroup<'a> {
+    pub fn check_state(&self) -> Result<(), &'static str> {
+        self.table.check_state()?;
+
+        if self.automorphisms.len() != self.table.size() {
+            return Err("automorphisms len does not match table size");
+        }
+
+        for (i, f) in self.automorphisms.iter().enumerate() {
+            if self.lookup.get(f) != Some(&i) {
+                return Err("automorphism lookup is incorrect");
+            }
+            for x in self.group.elems() {
+                for y in self.group.elems() {
+                    if f.call(self.group.mul(x, y)) != self.group.mul(f.call(x), f.call(y)) {
+                        return Err("automorphism does not respect composition");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn group(&self) -> &'a FiniteGroupMultiplicationTable {
+        self.group
+    }
+
+    pub fn table(&self) -> &FiniteGroupMultiplicationTable {
+        &self.table
+    }
+
+    pub fn size(&self) -> usize {
+        self.table.size()
+    }
+
+    pub fn automorphism(&self, f: usize) -> &Permutation {
+        &self.automorphisms[f]
+    }
+
+    pub fn index_of(&self, f: &Permutation) -> Option<usize> {
+        self.lookup.get(f).copied()
+    }
+
+    //apply the automorphism labelled by f to the element x of the group
+    pub fn apply(&self, f: usize, x: usize) -> usize {
+        self.automorphisms[f].call(x)
+    }
+
+    //the label of the inner automorphism x -> gxg^{-1}
+    pub fn conjugation(&self, g: usize) -> usize {
+        self.lookup[&self.group.conjugation_automorphism(g)]
+    }
+
+    pub fn inner_automorphism_group(&'_ self) -> NormalSubgroup<'_> {
+        NormalSubgroup::new_unchecked(Subgroup {
+            subset: Subset::new_unchecked(
+                &self.table,
+                self.group.elems().map(|g| self.conjugation(g)).collect(),
+            ),
+        })
+    }
+
+    pub fn outer_automorphism_group(&self) -> FiniteGroupMultiplicationTable {
+        self.inner_automorphism_group().quotient_group()
+    }
+}
+
+impl FiniteGroupMultiplicationTable {
+    pub fn conjugation_automorphism(&self, g: usize) -> Permutation {
+        Permutation::new_unchecked(
+            self.elems()
+                .map(|x| self.mul(self.mul(g, x), self.inv(g)))
+                .collect(),
+        )
+    }
+
+    //every automorphism of the group as a permutation of its elements
+    pub fn automorphisms(&self) -> Vec<Permutation> {
+        if self.size() == 1 {
+            return vec![Permutation::identity()];
+        }
+        let gen_set = self.small_generating_set(None);
+
+        //an automorphism is determined by the images of the generators
+        //and each generator must map to an element of the same order
+        let image_options = gen_set
+            .gens()
+            .iter()
+            .map(|g| {
+                let g_order = self.order(*g).unwrap();
+                self.elems()
+                    .filter(|x| self.order(*x).unwrap() == g_order)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        image_options
+            .into_iter()
+            .multi_cartesian_product()
+            .filter_map(|images| {
+                let f = gen_set.generated_homomorphism(&images, self).unwrap()?;
+                let func = f.images().clone();
+                f.to_isomorphism()?;
+                Some(Permutation::new_unchecked(func))
+            })
+            .collect()
+    }
+
+    pub fn automorphism_group(&'_ self) -> AutomorphismGroup<'_> {
+        let (table, automorphisms, lookup) =
+            Permutation::generated_finite_subgroup_table(self.automorphisms());
+        AutomorphismGroup {
+            group: self,
+            table,
+            automorphisms,
+            lookup,
+        }
+    }
+}
+
+#[cfg(test)]
+mod automorphism_tests {
+    use crate::composition_table::group::examples;
+
+    #[test]
+    fn test_automorphism_group_sizes() {
+        for (grp, num_auts) in vec![
+            (examples::trivial_group_structure(), 1),
+            (examples::cyclic_group_structure(2), 1),
+            (examples::cyclic_group_structure(7), 6),
+            (examples::cyclic_group_structure(12), 4),
+            (examples::klein_four_structure(), 6),
+            (examples::dihedral_group_structure(4), 8),
+            (examples::quaternion_group_structure(), 24),
+            (examples::symmetric_group_structure(3), 6),
+            (examples::symmetric_group_structure(4), 24),
+        ] {
+            let aut = grp.automorphism_group();
+            aut.check_state().unwrap();
+            assert_eq!(aut.size(), num_auts);
+        }
+    }
+
+    #[test]
+    fn test_inner_and_outer_automorphisms() {
+        //Inn(Q8) = Q8 / Z(Q8) = V4 and Out(Q8) = S3
+        let q8 = examples::quaternion_group_structure();
+        let aut = q8.automorphism_group();
+        let inn = aut.inner_automorphism_group();
+        inn.check_state().unwrap();
+        assert_eq!(inn.size(), 4);
+        let out = aut.outer_automorphism_group();
+        out.check_state().unwrap();
+        assert_eq!(out.size(), 6);
+        assert!(!out.is_abelian());
+
+        //S3 is complete
+        let s3 = examples::symmetric_group_structure(3);
+        let aut = s3.automorphism_group();
+        assert_eq!(aut.inner_automorphism_group().size(), 6);
+        assert_eq!(aut.outer_automorphism_group().size(), 1);
+
+        //abelian groups have no non-trivial inner automorphisms
+        let c5 = examples::cyclic_group_structure(5);
+        let aut = c5.automorphism_group();
+        assert_eq!(aut.inner_automorphism_group().size(), 1);
+        assert_eq!(aut.outer_automorphism_group().size(), 4);
+    }
+}
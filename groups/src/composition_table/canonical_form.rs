@@ -0,0 +1,419 @@
+use super::group::FiniteGroupMultiplicationTable;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A complete isomorphism invariant of a finite group.
+///
+/// Two groups are isomorphic if and only if their canonical keys are equal. The key is the
+/// multiplication table of the group with respect to its canonical labelling, so groups of
+/// different orders are ordered by their orders.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalKey {
+    order: usize,
+    table: Vec<Vec<usize>>,
+}
+
+impl CanonicalKey {
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// The multiplication table of the group with respect to its canonical labelling.
+    /// The identity is labelled by `0`.
+    pub fn table(&self) -> &Vec<Vec<usize>> {
+        &self.table
+    }
+}
+
+//the elements of the subgroup generated by gens, labelled in the order they are found by
+//repeatedly multiplying the labelled elements on the right by each generator in turn
+#[derive(Debug, Clone)]
+struct Labelling {
+    elems: Vec<usize>,
+    labels: Vec<Option<usize>>,
+    gens: Vec<usize>,
+}
+
+impl Labelling {
+    fn new(group: &FiniteGroupMultiplicationTable) -> Self {
+        let mut labels = vec![None; group.size()];
+        labels[group.ident()] = Some(0);
+        Self {
+            elems: vec![group.ident()],
+            labels,
+            gens: vec![],
+        }
+    }
+
+    fn contains(&self, x: usize) -> bool {
+        self.labels[x].is_some()
+    }
+
+    fn extend(&self, group: &FiniteGroupMultiplicationTable, g: usize) -> Self {
+        let mut extended = self.clone();
+        extended.gens.push(g);
+        let mut i = 0;
+        while i < extended.elems.len() {
+            for j in 0..extended.gens.len() {
+                let y = group.mul(extended.elems[i], extended.gens[j]);
+                if extended.labels[y].is_none() {
+                    extended.labels[y] = Some(extended.elems.len());
+                    extended.elems.push(y);
+                }
+            }
+            i += 1;
+        }
+        extended
+    }
+
+    //the relabelled multiplication table of the labelled subgroup listed shell by shell,
+    //so that the entries for a subgroup form a prefix of the entries for any larger subgroup
+    fn shells(&self, group: &FiniteGroupMultiplicationTable) -> Vec<usize> {
+        let label = |x: usize, y: usize| self.labels[group.mul(x, y)].unwrap();
+        let m = self.elems.len();
+        let mut entries = Vec::with_capacity(m * m);
+        for k in 0..m {
+            for j in 0..=k {
+                entries.push(label(self.elems[k], self.elems[j]));
+            }
+            for i in 0..k {
+                entries.push(label(self.elems[i], self.elems[k]));
+            }
+        }
+        entries
+    }
+}
+
+//the order, the size of the conjugacy class and the numbers of square and cube roots of an element
+type Colour = (usize, usize, usize, usize);
+
+//a colouring of the elements which is preserved by every automorphism
+fn element_colours(group: &FiniteGroupMultiplicationTable) -> Vec<Colour> {
+    let ccls = group.conjugacy_classes();
+    let mut square_roots = vec![0; group.size()];
+    let mut cube_roots = vec![0; group.size()];
+    for y in group.elems() {
+        let y2 = group.mul(y, y);
+        square_roots[y2] += 1;
+        cube_roots[group.mul(y2, y)] += 1;
+    }
+    group
+        .elems()
+        .map(|x| {
+            (
+                group.order(x).unwrap(),
+                ccls.partition.class_containing(x).len(),
+                square_roots[x],
+                cube_roots[x],
+            )
+        })
+        .collect()
+}
+
+//whether x and y lie in the same orbit of the group generated by the given permutations of the elements
+fn same_orbit(automorphisms: &[&Vec<usize>], x: usize, y: usize) -> bool {
+    let mut orbit = vec![x];
+    let mut i = 0;
+    while i < orbit.len() {
+        for f in automorphisms {
+            let z = f[orbit[i]];
+            if !orbit.contains(&z) {
+                orbit.push(z);
+            }
+        }
+        i += 1;
+    }
+    orbit.contains(&y)
+}
+
+//search the generating sequences where each generator is taken from the rarest colour outside the
+//subgroup generated so far, keeping the labelling whose table comes first
+//whenever two complete labellings give the same table they differ by an automorphism, and candidates for
+//the next generator which are related by an automorphism fixing the generators so far are only tried once
+fn search(
+    group: &FiniteGroupMultiplicationTable,
+    rank: &[(usize, Colour)],
+    labelling: Labelling,
+    best: &mut Option<(Vec<usize>, Labelling)>,
+    automorphisms: &mut Vec<Vec<usize>>,
+) {
+    let shells = labelling.shells(group);
+    if let Some((best_shells, best_labelling)) = best {
+        match shells.as_slice().cmp(&best_shells[..shells.len()]) {
+            Ordering::Greater => {
+                return;
+            }
+            Ordering::Equal if labelling.elems.len() == group.size() => {
+                let mut f = vec![0; group.size()];
+                for (x, y) in best_labelling.elems.iter().zip(&labelling.elems) {
+                    f[*x] = *y;
+                }
+                automorphisms.push(f);
+                return;
+            }
+            _ => {}
+        }
+    }
+    if labelling.elems.len() == group.size() {
+        *best = Some((shells, labelling));
+        return;
+    }
+    let min_rank = group
+        .elems()
+        .filter(|x| !labelling.contains(*x))
+        .map(|x| rank[x])
+        .min()
+        .unwrap();
+    let mut tried: Vec<usize> = vec![];
+    for x in group.elems() {
+        if !labelling.contains(x) && rank[x] == min_rank {
+            let stabilizer = automorphisms
+                .iter()
+                .filter(|f| labelling.gens.iter().all(|g| f[*g] == *g))
+                .collect::<Vec<_>>();
+            if tried.iter().any(|y| same_orbit(&stabilizer, x, *y)) {
+                continue;
+            }
+            search(group, rank, labelling.extend(group, x), best, automorphisms);
+            tried.push(x);
+        }
+    }
+}
+
+fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut primes = vec![];
+    let mut p = 2;
+    while n > 1 {
+        if n.is_multiple_of(p) {
+            primes.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    primes
+}
+
+//an abelian group is determined by the orders of the elements of a basis, so labelling from any
+//basis whose orders are listed in a fixed way gives the same table
+fn abelian_labelling(group: &FiniteGroupMultiplicationTable) -> Labelling {
+    let mut labelling = Labelling::new(group);
+    for p in prime_factors(group.size()) {
+        let is_p_power = |mut k: usize| {
+            while k.is_multiple_of(p) {
+                k /= p;
+            }
+            k == 1
+        };
+        let sylow = group
+            .elems()
+            .filter(|x| is_p_power(group.order(*x).unwrap()))
+            .collect::<Vec<_>>();
+        let sylow_part = |labelling: &Labelling| {
+            sylow
+                .iter()
+                .copied()
+                .filter(|x| labelling.contains(*x))
+                .collect::<Vec<_>>()
+        };
+        //the smallest k > 0 such that x^k lies in the labelled subgroup
+        let relative_order = |labelling: &Labelling, x: usize| {
+            let mut y = x;
+            let mut k = 1;
+            while !labelling.contains(y) {
+                y = group.mul(y, x);
+                k += 1;
+            }
+            k
+        };
+        loop {
+            //take an element of largest order modulo the basis so far,
+            //and adjust it by the basis so that its order equals its order modulo the basis
+            let (x, r) = sylow
+                .iter()
+                .map(|x| (*x, relative_order(&labelling, *x)))
+                .max_by_key(|(_, r)| *r)
+                .unwrap();
+            if r == 1 {
+                break;
+            }
+            let g = sylow_part(&labelling)
+                .into_iter()
+                .map(|h| group.mul(x, h))
+                .find(|g| group.order(*g).unwrap() == r)
+                .unwrap();
+            labelling = labelling.extend(group, g);
+        }
+    }
+    debug_assert_eq!(labelling.elems.len(), group.size());
+    labelling
+}
+
+impl FiniteGroupMultiplicationTable {
+    /// The elements of the group listed in their canonical order.
+    ///
+    /// Relabelling isomorphic groups by their canonical orders gives equal multiplication tables.
+    /// Abelian groups are labelled from a basis found from their element orders. Otherwise the
+    /// elements are coloured by their order, the size of their conjugacy class and their numbers of
+    /// square and cube roots, and the labelling comes from a search over the generating sequences
+    /// built by repeatedly adding an element of the rarest colour not yet generated.
+    pub fn canonical_labelling(&self) -> Vec<usize> {
+        if self.is_abelian() {
+            return abelian_labelling(self).elems;
+        }
+        let colours = element_colours(self);
+        let mut colour_counts = HashMap::new();
+        for colour in &colours {
+            *colour_counts.entry(*colour).or_insert(0) += 1;
+        }
+        let rank = colours
+            .iter()
+            .map(|colour| (colour_counts[colour], *colour))
+            .collect::<Vec<_>>();
+        let mut best = None;
+        search(self, &rank, Labelling::new(self), &mut best, &mut vec![]);
+        best.unwrap().1.elems
+    }
+
+    /// The group relabelled by its canonical labelling.
+    pub fn canonical_form(&self) -> FiniteGroupMultiplicationTable {
+        let elems = self.canonical_labelling();
+        let mut labels = vec![0; self.size()];
+        for (i, x) in elems.iter().enumerate() {
+            labels[*x] = i;
+        }
+        FiniteGroupMultiplicationTable::new_unchecked(
+            self.size(),
+            0,
+            elems.iter().map(|x| labels[self.inv(*x)]).collect(),
+            elems
+                .iter()
+                .map(|x| elems.iter().map(|y| labels[self.mul(*x, *y)]).collect())
+                .collect(),
+            None,
+            None,
+        )
+    }
+
+    pub fn canonical_key(&self) -> CanonicalKey {
+        let canonical_form = self.canonical_form();
+        CanonicalKey {
+            order: self.size(),
+            table: canonical_form
+                .elems()
+                .map(|x| {
+                    canonical_form
+                        .elems()
+                        .map(|y| canonical_form.mul(x, y))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the groups are isomorphic, decided by comparing canonical keys.
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        self.size() == other.size()
+            && self.is_abelian() == other.is_abelian()
+            && self.canonical_key() == other.canonical_key()
+    }
+}
+
+#[cfg(test)]
+mod canonical_form_tests {
+    use super::*;
+    use crate::composition_table::group::{direct_product_structure, examples};
+    use crate::permutation::Permutation;
+    use crate::structure::Group;
+
+    //the same group with its elements relabelled by a shuffle
+    fn shuffled(
+        group: &FiniteGroupMultiplicationTable,
+        seed: usize,
+    ) -> FiniteGroupMultiplicationTable {
+        let n = group.size();
+        let mut perm = (0..n).collect::<Vec<_>>();
+        let mut state = seed;
+        for i in (1..n).rev() {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            perm.swap(i, (state >> 33) % (i + 1));
+        }
+        let perm = Permutation::new_unchecked(perm);
+        let inv = perm.inverse_ref();
+        FiniteGroupMultiplicationTable::new_unchecked(
+            n,
+            perm.call(group.ident()),
+            (0..n).map(|x| perm.call(group.inv(inv.call(x)))).collect(),
+            (0..n)
+                .map(|x| {
+                    (0..n)
+                        .map(|y| perm.call(group.mul(inv.call(x), inv.call(y))))
+                        .collect()
+                })
+                .collect(),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_canonical_form_of_relabelled_groups() {
+        for group in [
+            examples::cyclic_group_structure(12),
+            direct_product_structure(
+                &examples::cyclic_group_structure(4),
+                &direct_product_structure(
+                    &examples::cyclic_group_structure(2),
+                    &examples::cyclic_group_structure(6),
+                ),
+            ),
+            examples::dihedral_group_structure(6),
+            examples::quaternion_group_structure(),
+            examples::symmetric_group_structure(4),
+            direct_product_structure(
+                &examples::dihedral_group_structure(4),
+                &examples::cyclic_group_structure(2),
+            ),
+        ] {
+            let canonical_form = group.canonical_form();
+            canonical_form.check_state().unwrap();
+            assert_eq!(canonical_form.ident(), 0);
+            assert!(group.is_isomorphic(&canonical_form));
+            for seed in 0..3 {
+                let other = shuffled(&group, seed);
+                other.check_state().unwrap();
+                assert_eq!(other.canonical_key(), group.canonical_key());
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_key_separates_groups() {
+        let groups = [
+            examples::cyclic_group_structure(8),
+            direct_product_structure(
+                &examples::cyclic_group_structure(4),
+                &examples::cyclic_group_structure(2),
+            ),
+            examples::dihedral_group_structure(4),
+            examples::quaternion_group_structure(),
+            direct_product_structure(
+                &examples::klein_four_structure(),
+                &examples::cyclic_group_structure(2),
+            ),
+        ];
+        for (i, a) in groups.iter().enumerate() {
+            for (j, b) in groups.iter().enumerate() {
+                assert_eq!(a.canonical_key() == b.canonical_key(), i == j);
+                assert_eq!(a.is_isomorphic(b), i == j);
+            }
+        }
+        assert!(
+            examples::symmetric_group_structure(3)
+                .is_isomorphic(&examples::dihedral_group_structure(3))
+        );
+    }
+}
@@ -1,15 +1,23 @@
+use super::automorphism::AutomorphismGroup;
+use super::homomorphism::Homomorphism;
 use super::normal_subgroup::NormalSubgroup;
 use super::partition::GroupPartition;
 use super::subgroup::Subgroup;
 use super::subset::Subset;
+use crate::permutation::Permutation;
+use crate::structure::{Group, GroupSignature, MonoidSignature};
 use algebraeon_sets::combinatorics::Partition;
+use algebraeon_sets::structure::{
+    CountableSetSignature, EqSignature, FiniteSetSignature, SetSignature, Signature,
+};
 use rayon::prelude::*;
+use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FiniteGroupMultiplicationTable {
     n: usize,
     ident: usize,
@@ -20,6 +28,63 @@ pub struct FiniteGroupMultiplicationTable {
     is_simple: Option<bool>,
 }
 
+//two tables are equal when they define the same group structure on 0, 1, ..., n-1
+impl PartialEq for FiniteGroupMultiplicationTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.ident == other.ident && self.mul == other.mul
+    }
+}
+
+impl Eq for FiniteGroupMultiplicationTable {}
+
+impl Signature for FiniteGroupMultiplicationTable {}
+
+impl SetSignature for FiniteGroupMultiplicationTable {
+    type Set = usize;
+
+    fn is_element(&self, x: &Self::Set) -> Result<(), String> {
+        if *x < self.n {
+            Ok(())
+        } else {
+            Err("not an element of the group".to_string())
+        }
+    }
+}
+
+impl EqSignature for FiniteGroupMultiplicationTable {
+    fn equal(&self, a: &Self::Set, b: &Self::Set) -> bool {
+        a == b
+    }
+}
+
+impl CountableSetSignature for FiniteGroupMultiplicationTable {
+    fn generate_all_elements(&self) -> impl Iterator<Item = Self::Set> + Clone {
+        self.elems()
+    }
+}
+
+impl FiniteSetSignature for FiniteGroupMultiplicationTable {
+    fn size(&self) -> usize {
+        self.n
+    }
+}
+
+impl MonoidSignature for FiniteGroupMultiplicationTable {
+    fn identity(&self) -> Self::Set {
+        self.ident
+    }
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        self.mul[*a][*b]
+    }
+}
+
+impl GroupSignature for FiniteGroupMultiplicationTable {
+    fn inverse(&self, a: &Self::Set) -> Self::Set {
+        self.inv[*a]
+    }
+}
+
 impl FiniteGroupMultiplicationTable {
     pub fn check_state(&self) -> Result<(), &'static str> {
         //check ident
@@ -366,6 +431,33 @@ impl FiniteGroupMultiplicationTable {
             .map(|(subgroup, gens)| (NormalSubgroup::new_unchecked(subgroup), gens))
             .collect()
     }
+
+    //the elements which commute with every element of the group
+    pub fn center(&'_ self) -> NormalSubgroup<'_> {
+        let elems = self
+            .elems()
+            .filter(|x| self.elems().all(|y| self.mul[*x][y] == self.mul[y][*x]))
+            .collect();
+        NormalSubgroup::new_unchecked(Subgroup {
+            subset: Subset::new_unchecked(self, elems),
+        })
+    }
+
+    //the subgroup generated by all commutators xyx^{-1}y^{-1}
+    pub fn commutator_subgroup(&'_ self) -> NormalSubgroup<'_> {
+        let commutators = self
+            .elems()
+            .flat_map(|x| {
+                self.elems()
+                    .map(move |y| self.mul[self.mul[x][y]][self.mul[self.inv[x]][self.inv[y]]])
+            })
+            .collect();
+        NormalSubgroup::new_unchecked(
+            Subset::new_unchecked(self, commutators)
+                .generated_subgroup()
+                .unwrap(),
+        )
+    }
 }
 
 pub fn direct_product_structure(
@@ -404,6 +496,106 @@ pub fn direct_product_structure(
     }
 }
 
+//the semidirect product N : H where N is the normal subgroup whose automorphism group is automorphisms
+//and H is the domain of action, acting on N through the homomorphism action: H -> Aut(N)
+//the underlying set is pairs (n, h) with (n1, h1)(n2, h2) = (n1 action(h1)(n2), h1 h2)
+pub fn semidirect_product_structure<
+    DomainT: Borrow<FiniteGroupMultiplicationTable> + Clone,
+    RangeT: Borrow<FiniteGroupMultiplicationTable> + Clone,
+>(
+    automorphisms: &AutomorphismGroup,
+    action: &Homomorphism<DomainT, RangeT>,
+) -> Result<FiniteGroupMultiplicationTable, &'static str> {
+    if !std::ptr::eq(action.range(), automorphisms.table()) {
+        return Err("action should map into the automorphism group");
+    }
+    let normal = automorphisms.group();
+    let acting = action.domain();
+    let phi = |h: usize, n: usize| automorphisms.apply(action.apply(h), n);
+
+    Ok(FiniteGroupMultiplicationTable::from_raw_model_unchecked(
+        acting
+            .elems()
+            .flat_map(|h| normal.elems().map(move |n| (n, h)))
+            .collect(),
+        || (normal.ident(), acting.ident()),
+        |(n, h)| {
+            let h_inv = acting.inv(h);
+            (phi(h_inv, normal.inv(n)), h_inv)
+        },
+        |(n1, h1), (n2, h2)| (normal.mul(n1, phi(h1, n2)), acting.mul(h1, h2)),
+        None,
+        None,
+    ))
+}
+
+//the wreath product of base by top where top acts on {0, 1, ..., degree-1} via action[h]
+//the underlying set is pairs (f, h) where f is a function {0, 1, ..., degree-1} -> base
+//and (f1, h1)(f2, h2) = (x -> f1(x) f2(action[h1]^{-1}(x)), h1 h2)
+pub fn wreath_product_structure(
+    base: &FiniteGroupMultiplicationTable,
+    top: &FiniteGroupMultiplicationTable,
+    degree: usize,
+    action: &[Permutation],
+) -> Result<FiniteGroupMultiplicationTable, &'static str> {
+    if action.len() != top.size() {
+        return Err("action should contain one permutation for each element of top");
+    }
+    for perm in action {
+        if perm.n() > degree {
+            return Err("action permutation moves points outside of the degree");
+        }
+    }
+    for x in top.elems() {
+        for y in top.elems() {
+            if action[top.mul(x, y)] != Permutation::compose_refs(&action[x], &action[y]) {
+                return Err("action does not respect composition");
+            }
+        }
+    }
+
+    let mut functions: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 0..degree {
+        functions = functions
+            .into_iter()
+            .flat_map(|f| {
+                base.elems().map(move |b| {
+                    let mut f = f.clone();
+                    f.push(b);
+                    f
+                })
+            })
+            .collect();
+    }
+
+    Ok(FiniteGroupMultiplicationTable::from_raw_model_unchecked(
+        top.elems()
+            .flat_map(|h| functions.iter().map(move |f| (f.clone(), h)))
+            .collect(),
+        || (vec![base.ident(); degree], top.ident()),
+        |(f, h)| {
+            let h_inv = top.inv(h);
+            (
+                (0..degree)
+                    .map(|x| base.inv(f[action[h].call(x)]))
+                    .collect(),
+                h_inv,
+            )
+        },
+        |(f1, h1), (f2, h2)| {
+            let h1_inv = top.inv(h1);
+            (
+                (0..degree)
+                    .map(|x| base.mul(f1[x], f2[action[h1_inv].call(x)]))
+                    .collect(),
+                top.mul(h1, h2),
+            )
+        },
+        None,
+        None,
+    ))
+}
+
 pub mod examples {
     use crate::free_group::todd_coxeter::FinitelyGeneratedGroupPresentation;
 
@@ -445,6 +637,42 @@ pub mod examples {
         grp
     }
 
+    pub fn dicyclic_group_structure(n: usize) -> FiniteGroupMultiplicationTable {
+        // dicyclic group of order 4n using the presentation
+        // <a x : a^{2n} = e, x^2 = a^n, x^{-1}ax = a^{-1}>
+        assert!(1 <= n);
+
+        let mut grp = FinitelyGeneratedGroupPresentation::new();
+        let a = grp.add_generator();
+        let x = grp.add_generator();
+        grp.add_relation(a.pow(2 * n as isize));
+        grp.add_two_sided_relation(x.pow(2), a.pow(n as isize));
+        grp.add_two_sided_relation(&x.inv() * &a * &x, a.inv());
+        let mut grp = grp.into_finite_group();
+        grp.is_abelian = Some(n <= 1);
+        grp
+    }
+
+    pub fn generalized_dihedral_group_structure(
+        abelian: &FiniteGroupMultiplicationTable,
+    ) -> FiniteGroupMultiplicationTable {
+        // the semidirect product A : C2 where C2 acts on the abelian group A by inversion
+        assert!(abelian.is_abelian());
+
+        let flip = |a: usize, s: bool| if s { abelian.inv(a) } else { a };
+        FiniteGroupMultiplicationTable::from_raw_model_unchecked(
+            [false, true]
+                .into_iter()
+                .flat_map(|s| abelian.elems().map(move |a| (a, s)))
+                .collect(),
+            || (abelian.ident(), false),
+            |(a, s)| (flip(abelian.inv(a), s), s),
+            |(a1, s1), (a2, s2)| (abelian.mul(a1, flip(a2, s1)), s1 != s2),
+            None,
+            None,
+        )
+    }
+
     pub fn quaternion_group_structure() -> FiniteGroupMultiplicationTable {
         // quaternion group using the presentation
         // <-1 i j k : (-1)^2 = 1  i^2 = j^2 = k^2 = ijk = -1>
@@ -531,4 +759,117 @@ mod group_tests {
             assert_eq!(grp.conjugacy_classes().size(), num_ccls);
         }
     }
+
+    #[test]
+    fn test_dicyclic() {
+        for k in [1, 2, 3, 5] {
+            let grp = examples::dicyclic_group_structure(k);
+            grp.check_state().unwrap();
+            assert_eq!(grp.size(), 4 * k);
+        }
+        assert!(
+            super::super::homomorphism::find_isomorphism(
+                &examples::dicyclic_group_structure(2),
+                &examples::quaternion_group_structure()
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_generalized_dihedral() {
+        let grp =
+            examples::generalized_dihedral_group_structure(&examples::cyclic_group_structure(5));
+        grp.check_state().unwrap();
+        assert!(
+            super::super::homomorphism::find_isomorphism(
+                &grp,
+                &examples::dihedral_group_structure(5)
+            )
+            .is_some()
+        );
+
+        let grp = examples::generalized_dihedral_group_structure(&examples::klein_four_structure());
+        grp.check_state().unwrap();
+        assert_eq!(grp.size(), 8);
+        assert!(grp.is_abelian());
+    }
+
+    #[test]
+    fn test_semidirect_product() {
+        //C7 : C3 where C3 acts by x -> 2x
+        let c7 = examples::cyclic_group_structure(7);
+        let c3 = examples::cyclic_group_structure(3);
+        let aut = c7.automorphism_group();
+        let times_two = aut
+            .index_of(&Permutation::new_unchecked(
+                (0..7).map(|x| (2 * x) % 7).collect(),
+            ))
+            .unwrap();
+        let action = Homomorphism::new(
+            &c3,
+            aut.table(),
+            vec![
+                aut.table().ident(),
+                times_two,
+                aut.table().mul(times_two, times_two),
+            ],
+        )
+        .unwrap();
+        let grp = semidirect_product_structure(&aut, &action).unwrap();
+        grp.check_state().unwrap();
+        assert_eq!(grp.size(), 21);
+        assert!(!grp.is_abelian());
+        assert_eq!(
+            grp.small_group_id(),
+            Some(super::super::small_groups::SmallGroupId {
+                order: 21,
+                index: 1
+            })
+        );
+
+        //the trivial action gives the direct product
+        let action = Homomorphism::new(&c3, aut.table(), vec![aut.table().ident(); 3]).unwrap();
+        let grp = semidirect_product_structure(&aut, &action).unwrap();
+        grp.check_state().unwrap();
+        assert!(grp.is_abelian());
+
+        //the action must land in the automorphism group
+        let c6 = examples::cyclic_group_structure(6);
+        let action = Homomorphism::new(&c3, &c6, vec![0, 2, 4]).unwrap();
+        assert!(semidirect_product_structure(&aut, &action).is_err());
+    }
+
+    #[test]
+    fn test_wreath_product() {
+        //C2 wr C2 is the dihedral group of order 8
+        let c2 = examples::cyclic_group_structure(2);
+        let swap = vec![
+            Permutation::identity(),
+            Permutation::new(vec![1, 0]).unwrap(),
+        ];
+        let grp = wreath_product_structure(&c2, &c2, 2, &swap).unwrap();
+        grp.check_state().unwrap();
+        assert!(
+            super::super::homomorphism::find_isomorphism(
+                &grp,
+                &examples::dihedral_group_structure(4)
+            )
+            .is_some()
+        );
+
+        //C2 wr S3 is the hyperoctahedral group of order 48
+        let (s3, s3_perms, _) = Permutation::symmetric_composition_table(3);
+        let grp = wreath_product_structure(&c2, &s3, 3, &s3_perms).unwrap();
+        grp.check_state().unwrap();
+        assert_eq!(grp.size(), 48);
+        assert_eq!(grp.center().size(), 2);
+
+        //the action must be a homomorphism
+        let bad = vec![
+            Permutation::new(vec![1, 0]).unwrap(),
+            Permutation::new(vec![1, 0]).unwrap(),
+        ];
+        assert!(wreath_product_structure(&c2, &c2, 2, &bad).is_err());
+    }
 }
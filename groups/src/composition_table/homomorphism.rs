@@ -46,6 +46,14 @@ impl<
         Ok(())
     }
 
+    pub fn new(domain: DomainT, range: RangeT, func: Vec<usize>) -> Result<Self, &'static str> {
+        let hom = Self::new_unchecked(domain, range, func);
+        match hom.check_state() {
+            Ok(()) => Ok(hom),
+            Err(msg) => Err(msg),
+        }
+    }
+
     pub fn new_unchecked(domain: DomainT, range: RangeT, func: Vec<usize>) -> Self {
         Self {
             domain,
@@ -54,6 +62,22 @@ impl<
         }
     }
 
+    pub fn domain(&self) -> &FiniteGroupMultiplicationTable {
+        self.domain.borrow()
+    }
+
+    pub fn range(&self) -> &FiniteGroupMultiplicationTable {
+        self.range.borrow()
+    }
+
+    pub fn apply(&self, x: usize) -> usize {
+        self.func[x]
+    }
+
+    pub fn images(&self) -> &Vec<usize> {
+        &self.func
+    }
+
     pub fn to_isomorphism(self) -> Option<Isomorphism<DomainT, RangeT>> {
         let n = self.domain.borrow().size();
         if n != self.range.borrow().size() {
@@ -0,0 +1,116 @@
+use super::group::FiniteGroupMultiplicationTable;
+use std::collections::BTreeMap;
+
+/// A collection of isomorphism invariants of a finite group.
+///
+/// Isomorphic groups always have equal invariants, so comparing invariants is a cheap way to rule
+/// out an isomorphism before attempting to search for one. Non-isomorphic groups may still share
+/// the same invariants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GroupInvariants {
+    pub order: usize,
+    pub is_abelian: bool,
+    /// The number of elements of each order.
+    pub element_orders: BTreeMap<usize, usize>,
+    /// The number of conjugacy classes of each (class size, element order) pair.
+    pub conjugacy_classes: BTreeMap<(usize, usize), usize>,
+    pub center_size: usize,
+    pub commutator_subgroup_size: usize,
+    /// The number of subgroups of each size.
+    pub subgroup_sizes: BTreeMap<usize, usize>,
+    /// The number of normal subgroups of each size.
+    pub normal_subgroup_sizes: BTreeMap<usize, usize>,
+}
+
+impl GroupInvariants {
+    pub fn new(group: &FiniteGroupMultiplicationTable) -> Self {
+        let mut element_orders = BTreeMap::new();
+        for x in group.elems() {
+            *element_orders.entry(group.order(x).unwrap()).or_insert(0) += 1;
+        }
+
+        let mut conjugacy_classes = BTreeMap::new();
+        let ccls = group.conjugacy_classes();
+        for i in 0..ccls.size() {
+            let class = ccls.partition.get_class(i);
+            let x = *class.iter().next().unwrap();
+            *conjugacy_classes
+                .entry((class.len(), group.order(x).unwrap()))
+                .or_insert(0) += 1;
+        }
+
+        let mut subgroup_sizes = BTreeMap::new();
+        for (sg, _gens) in group.subgroups() {
+            *subgroup_sizes.entry(sg.size()).or_insert(0) += 1;
+        }
+
+        let mut normal_subgroup_sizes = BTreeMap::new();
+        for (nsg, _gens) in group.normal_subgroups() {
+            *normal_subgroup_sizes.entry(nsg.size()).or_insert(0) += 1;
+        }
+
+        Self {
+            order: group.size(),
+            is_abelian: group.is_abelian(),
+            element_orders,
+            conjugacy_classes,
+            center_size: group.center().size(),
+            commutator_subgroup_size: group.commutator_subgroup().size(),
+            subgroup_sizes,
+            normal_subgroup_sizes,
+        }
+    }
+}
+
+impl FiniteGroupMultiplicationTable {
+    pub fn invariants(&self) -> GroupInvariants {
+        GroupInvariants::new(self)
+    }
+}
+
+#[cfg(test)]
+mod invariants_tests {
+    use super::*;
+    use crate::composition_table::group::examples;
+
+    #[test]
+    fn test_invariants() {
+        let s3 = examples::symmetric_group_structure(3);
+        let inv = s3.invariants();
+        assert_eq!(inv.order, 6);
+        assert!(!inv.is_abelian);
+        assert_eq!(inv.element_orders, BTreeMap::from([(1, 1), (2, 3), (3, 2)]));
+        assert_eq!(
+            inv.conjugacy_classes,
+            BTreeMap::from([((1, 1), 1), ((3, 2), 1), ((2, 3), 1)])
+        );
+        assert_eq!(inv.center_size, 1);
+        assert_eq!(inv.commutator_subgroup_size, 3);
+        assert_eq!(
+            inv.subgroup_sizes,
+            BTreeMap::from([(1, 1), (2, 3), (3, 1), (6, 1)])
+        );
+        assert_eq!(
+            inv.normal_subgroup_sizes,
+            BTreeMap::from([(1, 1), (3, 1), (6, 1)])
+        );
+
+        assert_eq!(inv, examples::dihedral_group_structure(3).invariants());
+        assert_ne!(inv, examples::cyclic_group_structure(6).invariants());
+    }
+
+    #[test]
+    fn test_center_and_commutator_subgroup() {
+        let q8 = examples::quaternion_group_structure();
+        assert_eq!(q8.center().size(), 2);
+        assert_eq!(q8.commutator_subgroup().size(), 2);
+
+        let s4 = examples::symmetric_group_structure(4);
+        assert_eq!(s4.center().size(), 1);
+        assert_eq!(s4.commutator_subgroup().size(), 12);
+
+        let c12 = examples::cyclic_group_structure(12);
+        assert_eq!(c12.center().size(), 12);
+        assert_eq!(c12.commutator_subgroup().size(), 1);
+    }
+}
@@ -1,5 +1,6 @@
 use super::group::{FiniteGroupMultiplicationTable, direct_product_structure, examples};
 use super::homomorphism::find_isomorphism;
+use super::small_groups::{CatalogueGroupId, SmallGroupId, catalogue_group, small_group};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -13,6 +14,8 @@ pub enum IsomorphismClass {
     Alternating(usize),
     Symmetric(usize),
     DirectProduct(Box<BTreeMap<IsomorphismClass, usize>>), //count how many of each isomorphic factor
+    SmallGroup(SmallGroupId), //a group from the small groups catalogue not covered by the named classes
+    CatalogueGroup(CatalogueGroupId), //as above, for orders beyond those numbered as in GAP
     Unknown(usize),
 }
 
@@ -38,6 +41,16 @@ impl IsomorphismClass {
             Self::DirectProduct(_factors) => {
                 todo!();
             }
+            Self::SmallGroup(id) => {
+                if small_group(id.order, id.index).is_none() {
+                    return Err("SmallGroup not present in the catalogue");
+                }
+            }
+            Self::CatalogueGroup(id) => {
+                if catalogue_group(id.order, id.index).is_none() {
+                    return Err("CatalogueGroup not present in the catalogue");
+                }
+            }
             Self::Unknown(n) => {
                 if *n == 0 {
                     return Err("Unknown group with 0 elements is not valid");
@@ -121,6 +134,14 @@ impl IsomorphismClass {
             return Self::Dihedral(n / 2);
         }
 
+        //small groups catalogue
+        if let Some(id) = group.small_group_id() {
+            return Self::SmallGroup(id);
+        }
+        if let Some(id) = group.catalogue_group_id() {
+            return Self::CatalogueGroup(id);
+        }
+
         IsomorphismClass::Unknown(n)
     }
 
@@ -152,6 +173,8 @@ impl IsomorphismClass {
                 }
                 Ok(prod_group)
             }
+            Self::SmallGroup(id) => small_group(id.order, id.index).ok_or(()),
+            Self::CatalogueGroup(id) => catalogue_group(id.order, id.index).ok_or(()),
             Self::Unknown(_n) => Err(()),
         }
     }
@@ -179,6 +202,8 @@ impl IsomorphismClass {
                 }
                 ans
             }
+            Self::SmallGroup(id) => id.to_string(),
+            Self::CatalogueGroup(id) => id.to_string(),
             Self::Unknown(n) => "Unknown".to_owned() + &n.to_string(),
         }
     }
@@ -1,8 +1,12 @@
+pub mod automorphism;
+pub mod canonical_form;
 pub mod generating_set;
 pub mod group;
 pub mod homomorphism;
+pub mod invariants;
 pub mod iso_rep;
 pub mod normal_subgroup;
 pub mod partition;
+pub mod small_groups;
 pub mod subgroup;
 pub mod subset;
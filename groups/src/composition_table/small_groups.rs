@@ -0,0 +1,729 @@
+use super::canonical_form::CanonicalKey;
+use super::group::{FiniteGroupMultiplicationTable, direct_product_structure, examples};
+use crate::free_group::todd_coxeter::FinitelyGeneratedGroupPresentation;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// The largest order for which every group is present in the small groups catalogue.
+pub const SMALL_GROUPS_MAX_ORDER: usize = 63;
+
+/// The largest order for which the catalogue is numbered as in the SmallGroups library of GAP.
+pub const GAP_SMALL_GROUPS_MAX_ORDER: usize = 31;
+
+/// Identifies a finite group of order at most [`GAP_SMALL_GROUPS_MAX_ORDER`] up to isomorphism as
+/// `SmallGroup(order, index)` in GAP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SmallGroupId {
+    pub order: usize,
+    pub index: usize,
+}
+
+impl std::fmt::Display for SmallGroupId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SmallGroup({}, {})", self.order, self.index)
+    }
+}
+
+/// Identifies a finite group of order greater than [`GAP_SMALL_GROUPS_MAX_ORDER`] and at most
+/// [`SMALL_GROUPS_MAX_ORDER`] up to isomorphism as the `index`-th group of order `order` in the
+/// small groups catalogue.
+///
+/// These groups are numbered in increasing order of their [`CanonicalKey`]. This is not the numbering
+/// used by GAP, and it changes whenever the canonical form does, so these identifiers should not be
+/// stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CatalogueGroupId {
+    pub order: usize,
+    pub index: usize,
+}
+
+impl std::fmt::Display for CatalogueGroupId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CatalogueGroup({}, {})", self.order, self.index)
+    }
+}
+
+fn cyclic(n: usize) -> FiniteGroupMultiplicationTable {
+    examples::cyclic_group_structure(n)
+}
+
+fn product(factors: Vec<FiniteGroupMultiplicationTable>) -> FiniteGroupMultiplicationTable {
+    let mut prod = examples::trivial_group_structure();
+    for factor in factors {
+        prod = direct_product_structure(&prod, &factor);
+    }
+    prod
+}
+
+// <a b : a^m = b^n = e, b^{-1}ab = a^r>
+fn metacyclic(m: usize, n: usize, r: usize) -> FiniteGroupMultiplicationTable {
+    debug_assert_eq!(
+        (0..n).fold(1, |acc, _| (acc * r) % m),
+        1 % m,
+        "r^n must be 1 mod m"
+    );
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let a = grp.add_generator();
+    let b = grp.add_generator();
+    grp.add_relation(a.pow(m as isize));
+    grp.add_relation(b.pow(n as isize));
+    grp.add_two_sided_relation(&b.inv() * &a * &b, a.pow(r as isize));
+    grp.into_finite_group()
+}
+
+// (C4 x C2) : C2
+// <a b c : a^4 = b^2 = c^2 = e, ab = ba, bc = cb, cac = ab>
+fn small_group_16_3() -> FiniteGroupMultiplicationTable {
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let a = grp.add_generator();
+    let b = grp.add_generator();
+    let c = grp.add_generator();
+    grp.add_relation(a.pow(4));
+    grp.add_relation(b.pow(2));
+    grp.add_relation(c.pow(2));
+    grp.add_two_sided_relation(&a * &b, &b * &a);
+    grp.add_two_sided_relation(&b * &c, &c * &b);
+    grp.add_two_sided_relation(&c * &a * &c, &a * &b);
+    grp.into_finite_group()
+}
+
+// the Pauli group, a central product of C4 and D8
+// <a b c : a^4 = b^2 = c^2 = e, ab = ba, ac = ca, (bc)^2 = a^2>
+fn small_group_16_13() -> FiniteGroupMultiplicationTable {
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let a = grp.add_generator();
+    let b = grp.add_generator();
+    let c = grp.add_generator();
+    grp.add_relation(a.pow(4));
+    grp.add_relation(b.pow(2));
+    grp.add_relation(c.pow(2));
+    grp.add_two_sided_relation(&a * &b, &b * &a);
+    grp.add_two_sided_relation(&a * &c, &c * &a);
+    grp.add_two_sided_relation((&b * &c).pow(2), a.pow(2));
+    grp.into_finite_group()
+}
+
+// SL(2, 3), the binary tetrahedral group
+// <s t : (st)^2 = s^3 = t^3>
+fn small_group_24_3() -> FiniteGroupMultiplicationTable {
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let s = grp.add_generator();
+    let t = grp.add_generator();
+    grp.add_two_sided_relation((&s * &t).pow(2), s.pow(3));
+    grp.add_two_sided_relation(s.pow(3), t.pow(3));
+    grp.into_finite_group()
+}
+
+// C3 : D8 where the kernel of the action is a Klein four subgroup of D8
+// <a b c : a^3 = b^4 = c^2 = (bc)^2 = e, b^{-1}ab = a^{-1}, cac = a>
+fn small_group_24_8() -> FiniteGroupMultiplicationTable {
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let a = grp.add_generator();
+    let b = grp.add_generator();
+    let c = grp.add_generator();
+    grp.add_relation(a.pow(3));
+    grp.add_relation(b.pow(4));
+    grp.add_relation(c.pow(2));
+    grp.add_relation((&b * &c).pow(2));
+    grp.add_two_sided_relation(&b.inv() * &a * &b, a.inv());
+    grp.add_two_sided_relation(&c * &a * &c, a.clone());
+    grp.into_finite_group()
+}
+
+// the Heisenberg group modulo 3
+// <a b : a^3 = b^3 = [a, b]^3 = e, [a, b] is central>
+fn small_group_27_3() -> FiniteGroupMultiplicationTable {
+    let mut grp = FinitelyGeneratedGroupPresentation::new();
+    let a = grp.add_generator();
+    let b = grp.add_generator();
+    let c = &a.inv() * &b.inv() * &a * &b;
+    grp.add_relation(a.pow(3));
+    grp.add_relation(b.pow(3));
+    grp.add_relation(c.pow(3));
+    grp.add_two_sided_relation(&a * &c, &c * &a);
+    grp.add_two_sided_relation(&b * &c, &c * &b);
+    grp.into_finite_group()
+}
+
+//a polycyclic presentation of a solvable group on generators g_0, ..., g_{k-1} with prime relative orders p_i
+//every element is uniquely g_0^e_0 ... g_{k-1}^e_{k-1} with 0 <= e_i < p_i, and is numbered by the e_i in mixed radix
+//g_i^p_i and g_i^{-1} g_j g_i for i < j are elements of the subgroup generated by g_{i+1}, ..., g_{k-1}
+#[derive(Debug, Clone)]
+struct PcPresentation {
+    relative_orders: Vec<usize>,
+    powers: Vec<Vec<usize>>,
+    conjugates: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl PcPresentation {
+    //the number of the element g_from^e_0 g_{from+1}^e_1 ...
+    fn number(&self, from: usize, exponents: &[usize]) -> usize {
+        self.relative_orders[from..]
+            .iter()
+            .zip(exponents)
+            .fold(0, |x, (p, e)| x * p + e)
+    }
+
+    fn exponents(&self, from: usize, mut x: usize) -> Vec<usize> {
+        let mut exponents = vec![0; self.relative_orders.len() - from];
+        for (e, p) in exponents
+            .iter_mut()
+            .zip(&self.relative_orders[from..])
+            .rev()
+        {
+            *e = x % p;
+            x /= p;
+        }
+        exponents
+    }
+
+    //the multiplication table of the subgroup N generated by g_from, ..., g_{k-1}
+    //elements of N are t^i x for t = g_from and x in the subgroup M generated by g_{from+1}, ..., g_{k-1}
+    //so that (t^i x)(t^j y) = t^{i+j} alpha^j(x) y where alpha is conjugation by t on M and t^p is in M
+    fn multiplication_table(&self, from: usize) -> Vec<Vec<usize>> {
+        if from == self.relative_orders.len() {
+            return vec![vec![0]];
+        }
+        let sub_mul = self.multiplication_table(from + 1);
+        let m = sub_mul.len();
+        let p = self.relative_orders[from];
+        //the images under alpha of g_{from+1}, ..., g_{k-1}, which are fixed unless a conjugate is given
+        let generator_images = (from + 1..self.relative_orders.len())
+            .map(|j| match self.conjugates.get(&(from, j)) {
+                Some(w) => self.number(from + 1, w),
+                None => self.relative_orders[j + 1..].iter().product(),
+            })
+            .collect::<Vec<_>>();
+        let alpha = (0..m)
+            .map(|x| {
+                let mut y = 0;
+                for (g, e) in generator_images.iter().zip(self.exponents(from + 1, x)) {
+                    for _ in 0..e {
+                        y = sub_mul[y][*g];
+                    }
+                }
+                y
+            })
+            .collect::<Vec<_>>();
+        let t_pow_p = self.number(from + 1, &self.powers[from]);
+        let mut alpha_pows = vec![(0..m).collect::<Vec<_>>()];
+        for j in 1..p {
+            alpha_pows.push(alpha_pows[j - 1].iter().map(|x| alpha[*x]).collect());
+        }
+        let mut mul = vec![vec![0; p * m]; p * m];
+        for i in 0..p {
+            for x in 0..m {
+                for j in 0..p {
+                    for y in 0..m {
+                        let mut z = sub_mul[alpha_pows[j][x]][y];
+                        if i + j >= p {
+                            z = sub_mul[t_pow_p][z];
+                        }
+                        mul[i * m + x][j * m + y] = ((i + j) % p) * m + z;
+                    }
+                }
+            }
+        }
+        mul
+    }
+
+    fn group(&self) -> FiniteGroupMultiplicationTable {
+        let mul = self.multiplication_table(0);
+        let n = mul.len();
+        let inv = (0..n)
+            .map(|x| (0..n).find(|y| mul[x][*y] == 0).unwrap())
+            .collect();
+        FiniteGroupMultiplicationTable::new_unchecked(n, 0, inv, mul, None, None)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CatalogueEntry {
+    Solvable(PcPresentation),
+    Alternating(usize),
+}
+
+impl CatalogueEntry {
+    fn group(&self) -> FiniteGroupMultiplicationTable {
+        match self {
+            Self::Solvable(presentation) => presentation.group(),
+            Self::Alternating(n) => examples::alternating_group_structure(*n),
+        }
+    }
+}
+
+//parse a word such as "bc2e" in the generators a, b, c, ... into its exponents
+//the generators must appear in order, so that the word is in normal form
+fn parse_word(word: &str, relative_orders: &[usize]) -> Result<Vec<usize>, ()> {
+    let mut exponents = vec![0; relative_orders.len()];
+    let mut last = None;
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !c.is_ascii_lowercase() {
+            return Err(());
+        }
+        let i = (c as u8 - b'a') as usize;
+        if i >= relative_orders.len() || last.is_some_and(|last| i <= last) {
+            return Err(());
+        }
+        let mut digits = String::new();
+        while let Some(d) = chars.next_if(char::is_ascii_digit) {
+            digits.push(d);
+        }
+        let e = if digits.is_empty() {
+            1
+        } else {
+            digits.parse().map_err(|_| ())?
+        };
+        if e == 0 || e >= relative_orders[i] {
+            return Err(());
+        }
+        exponents[i] = e;
+        last = Some(i);
+    }
+    Ok(exponents)
+}
+
+fn parse_catalogue_entry(line: &str) -> Result<(usize, CatalogueEntry), ()> {
+    let (order, presentation) = line.split_once(": ").ok_or(())?;
+    let order = order.parse::<usize>().map_err(|_| ())?;
+    if let Some(n) = presentation.strip_prefix('A') {
+        let n = n.parse::<usize>().map_err(|_| ())?;
+        return Ok((order, CatalogueEntry::Alternating(n)));
+    }
+    let (relative_orders, relations) = presentation.split_once("; ").unwrap_or((presentation, ""));
+    let relative_orders = relative_orders
+        .split(' ')
+        .map(|p| p.parse::<usize>().map_err(|_| ()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let k = relative_orders.len();
+    let mut powers = vec![vec![0; k]; k];
+    let mut conjugates = HashMap::new();
+    for relation in relations.split(", ").filter(|r| !r.is_empty()) {
+        let (lhs, rhs) = relation.split_once('=').ok_or(())?;
+        let rhs = parse_word(rhs, &relative_orders)?;
+        let lhs = lhs.as_bytes();
+        let i = (lhs[0].wrapping_sub(b'a')) as usize;
+        if i >= k {
+            return Err(());
+        }
+        if lhs.len() == 2 && lhs[1].is_ascii_lowercase() {
+            //g_j g_i = g_i w, that is g_i^{-1} g_j g_i = w
+            let (j, i) = (i, (lhs[1] - b'a') as usize);
+            if !(i < j && j < k && rhs[i] == 1 && rhs[..i].iter().all(|e| *e == 0)) {
+                return Err(());
+            }
+            conjugates.insert((i, j), rhs[i + 1..].to_vec());
+        } else {
+            //g_i^p_i = w
+            let p = std::str::from_utf8(&lhs[1..])
+                .map_err(|_| ())?
+                .parse::<usize>()
+                .map_err(|_| ())?;
+            if p != relative_orders[i] || rhs[..=i].iter().any(|e| *e != 0) {
+                return Err(());
+            }
+            powers[i] = rhs;
+        }
+    }
+    for (i, power) in powers.iter_mut().enumerate() {
+        *power = power[i + 1..].to_vec();
+    }
+    Ok((
+        order,
+        CatalogueEntry::Solvable(PcPresentation {
+            relative_orders,
+            powers,
+            conjugates,
+        }),
+    ))
+}
+
+//the groups of orders 32 to 63, in catalogue order
+fn catalogue_entries(n: usize) -> &'static [CatalogueEntry] {
+    static ENTRIES: OnceLock<HashMap<usize, Vec<CatalogueEntry>>> = OnceLock::new();
+    ENTRIES
+        .get_or_init(|| {
+            let mut entries: HashMap<usize, Vec<CatalogueEntry>> = HashMap::new();
+            for line in include_str!("small_groups.txt").lines() {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let (order, entry) = parse_catalogue_entry(line).unwrap();
+                entries.entry(order).or_default().push(entry);
+            }
+            entries
+        })
+        .get(&n)
+        .map_or(&[], |entries| entries.as_slice())
+}
+
+/// The number of groups of order `n` up to isomorphism, if `n` is covered by the catalogue.
+pub fn small_group_count(n: usize) -> Option<usize> {
+    const COUNTS: [usize; SMALL_GROUPS_MAX_ORDER + 1] = [
+        0, 1, 1, 1, 2, 1, 2, 1, 5, 2, 2, 1, 5, 1, 2, 1, 14, 1, 5, 1, 5, 2, 2, 1, 15, 2, 2, 5, 4, 1,
+        4, 1, 51, 1, 2, 1, 14, 1, 2, 2, 14, 1, 6, 1, 4, 2, 2, 1, 52, 2, 5, 1, 5, 1, 15, 2, 13, 2,
+        2, 1, 13, 1, 2, 4,
+    ];
+    if (1..=SMALL_GROUPS_MAX_ORDER).contains(&n) {
+        Some(COUNTS[n])
+    } else {
+        None
+    }
+}
+
+/// All groups of order `n` up to isomorphism, ordered by their index in the catalogue.
+/// Returns `None` if `n` is not covered by the catalogue.
+#[allow(clippy::too_many_lines)]
+pub fn small_groups(n: usize) -> Option<Vec<FiniteGroupMultiplicationTable>> {
+    let d = examples::dihedral_group_structure;
+    let groups = match n {
+        1 | 2 | 3 | 5 | 7 | 11 | 13 | 15 | 17 | 19 | 23 | 29 | 31 => vec![cyclic(n)],
+        4 => vec![cyclic(4), product(vec![cyclic(2), cyclic(2)])],
+        6 | 10 | 14 | 22 | 26 => vec![d(n / 2), cyclic(n)],
+        8 => vec![
+            cyclic(8),
+            product(vec![cyclic(4), cyclic(2)]),
+            d(4),
+            examples::quaternion_group_structure(),
+            product(vec![cyclic(2), cyclic(2), cyclic(2)]),
+        ],
+        9 | 25 => {
+            let p = if n == 9 { 3 } else { 5 };
+            vec![cyclic(n), product(vec![cyclic(p), cyclic(p)])]
+        }
+        12 => vec![
+            examples::dicyclic_group_structure(3),
+            cyclic(12),
+            examples::alternating_group_structure(4),
+            d(6),
+            product(vec![cyclic(6), cyclic(2)]),
+        ],
+        16 => vec![
+            cyclic(16),
+            product(vec![cyclic(4), cyclic(4)]),
+            small_group_16_3(),
+            metacyclic(4, 4, 3),
+            product(vec![cyclic(8), cyclic(2)]),
+            metacyclic(8, 2, 5),
+            d(8),
+            metacyclic(8, 2, 3),
+            examples::dicyclic_group_structure(4),
+            product(vec![cyclic(4), cyclic(2), cyclic(2)]),
+            product(vec![cyclic(2), d(4)]),
+            product(vec![cyclic(2), examples::quaternion_group_structure()]),
+            small_group_16_13(),
+            product(vec![cyclic(2), cyclic(2), cyclic(2), cyclic(2)]),
+        ],
+        18 => vec![
+            d(9),
+            cyclic(18),
+            product(vec![cyclic(3), d(3)]),
+            examples::generalized_dihedral_group_structure(&product(vec![cyclic(3), cyclic(3)])),
+            product(vec![cyclic(6), cyclic(3)]),
+        ],
+        20 => vec![
+            examples::dicyclic_group_structure(5),
+            cyclic(20),
+            metacyclic(5, 4, 2),
+            d(10),
+            product(vec![cyclic(10), cyclic(2)]),
+        ],
+        21 => vec![metacyclic(7, 3, 2), cyclic(21)],
+        24 => vec![
+            metacyclic(3, 8, 2),
+            cyclic(24),
+            small_group_24_3(),
+            examples::dicyclic_group_structure(6),
+            product(vec![cyclic(4), d(3)]),
+            d(12),
+            product(vec![cyclic(2), examples::dicyclic_group_structure(3)]),
+            small_group_24_8(),
+            product(vec![cyclic(12), cyclic(2)]),
+            product(vec![cyclic(3), d(4)]),
+            product(vec![cyclic(3), examples::quaternion_group_structure()]),
+            examples::symmetric_group_structure(4),
+            product(vec![cyclic(2), examples::alternating_group_structure(4)]),
+            product(vec![cyclic(2), d(6)]),
+            product(vec![cyclic(6), cyclic(2), cyclic(2)]),
+        ],
+        27 => vec![
+            cyclic(27),
+            product(vec![cyclic(9), cyclic(3)]),
+            small_group_27_3(),
+            metacyclic(9, 3, 4),
+            product(vec![cyclic(3), cyclic(3), cyclic(3)]),
+        ],
+        28 => vec![
+            examples::dicyclic_group_structure(7),
+            cyclic(28),
+            d(14),
+            product(vec![cyclic(14), cyclic(2)]),
+        ],
+        30 => vec![
+            product(vec![cyclic(5), d(3)]),
+            product(vec![cyclic(3), d(5)]),
+            d(15),
+            cyclic(30),
+        ],
+        32..=SMALL_GROUPS_MAX_ORDER => catalogue_entries(n)
+            .iter()
+            .map(CatalogueEntry::group)
+            .collect(),
+        _ => {
+            return None;
+        }
+    };
+    debug_assert_eq!(Some(groups.len()), small_group_count(n));
+    Some(groups)
+}
+
+fn catalogue_group_by_index(n: usize, k: usize) -> Option<FiniteGroupMultiplicationTable> {
+    if k == 0 {
+        return None;
+    }
+    small_groups(n)?.into_iter().nth(k - 1)
+}
+
+/// The group `SmallGroup(n, k)` of GAP, if `n` is at most [`GAP_SMALL_GROUPS_MAX_ORDER`] and `k` is in
+/// range.
+pub fn small_group(n: usize, k: usize) -> Option<FiniteGroupMultiplicationTable> {
+    if n > GAP_SMALL_GROUPS_MAX_ORDER {
+        return None;
+    }
+    catalogue_group_by_index(n, k)
+}
+
+/// The group identified by `CatalogueGroup(n, k)`, if `n` is greater than
+/// [`GAP_SMALL_GROUPS_MAX_ORDER`] and at most [`SMALL_GROUPS_MAX_ORDER`] and `k` is in range.
+pub fn catalogue_group(n: usize, k: usize) -> Option<FiniteGroupMultiplicationTable> {
+    if n <= GAP_SMALL_GROUPS_MAX_ORDER {
+        return None;
+    }
+    catalogue_group_by_index(n, k)
+}
+
+//find the index of group among the catalogue groups of the same order by looking up its canonical key
+//the canonical keys of the catalogue groups of an order are computed the first time a group of that order is identified
+fn catalogue_index(group: &FiniteGroupMultiplicationTable) -> Option<usize> {
+    static INDICES: [OnceLock<HashMap<CanonicalKey, usize>>; SMALL_GROUPS_MAX_ORDER + 1] =
+        [const { OnceLock::new() }; SMALL_GROUPS_MAX_ORDER + 1];
+
+    let n = group.size();
+    if small_group_count(n)? == 1 {
+        return Some(1);
+    }
+    let indices = INDICES[n].get_or_init(|| {
+        small_groups(n)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(k, candidate)| (candidate.canonical_key(), k + 1))
+            .collect()
+    });
+    let index = *indices.get(&group.canonical_key()).unwrap_or_else(|| {
+        unreachable!("the small groups catalogue should contain every group of order {n}")
+    });
+    Some(index)
+}
+
+/// Find the GAP identifier of `group` by looking up its [`CanonicalKey`] in the small groups catalogue.
+/// Returns `None` if the order of `group` is greater than [`GAP_SMALL_GROUPS_MAX_ORDER`].
+pub fn identify_small_group(group: &FiniteGroupMultiplicationTable) -> Option<SmallGroupId> {
+    let order = group.size();
+    if order > GAP_SMALL_GROUPS_MAX_ORDER {
+        return None;
+    }
+    let index = catalogue_index(group)?;
+    Some(SmallGroupId { order, index })
+}
+
+/// Find the catalogue identifier of `group` by looking up its [`CanonicalKey`] in the small groups
+/// catalogue.
+/// Returns `None` unless the order of `group` is greater than [`GAP_SMALL_GROUPS_MAX_ORDER`] and at
+/// most [`SMALL_GROUPS_MAX_ORDER`].
+pub fn identify_catalogue_group(
+    group: &FiniteGroupMultiplicationTable,
+) -> Option<CatalogueGroupId> {
+    let order = group.size();
+    if order <= GAP_SMALL_GROUPS_MAX_ORDER {
+        return None;
+    }
+    let index = catalogue_index(group)?;
+    Some(CatalogueGroupId { order, index })
+}
+
+impl FiniteGroupMultiplicationTable {
+    pub fn small_group_id(&self) -> Option<SmallGroupId> {
+        identify_small_group(self)
+    }
+
+    pub fn catalogue_group_id(&self) -> Option<CatalogueGroupId> {
+        identify_catalogue_group(self)
+    }
+}
+
+#[cfg(test)]
+mod small_groups_tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogue_is_valid() {
+        for n in 1..=SMALL_GROUPS_MAX_ORDER {
+            let groups = small_groups(n).unwrap();
+            assert_eq!(groups.len(), small_group_count(n).unwrap());
+            for grp in &groups {
+                assert_eq!(grp.size(), n);
+                grp.check_state().unwrap();
+            }
+        }
+        assert!(small_groups(0).is_none());
+        assert!(small_groups(SMALL_GROUPS_MAX_ORDER + 1).is_none());
+        assert!(small_group(8, 0).is_none());
+        assert!(small_group(8, 6).is_none());
+        assert!(small_group(32, 1).is_none());
+        assert!(catalogue_group(8, 1).is_none());
+        assert!(catalogue_group(32, 52).is_none());
+        assert_eq!(catalogue_group(32, 51).unwrap().size(), 32);
+    }
+
+    #[test]
+    fn test_catalogue_is_irredundant() {
+        //each catalogue group should be identified as itself
+        for n in [8, 12, 16, 18, 20, 24, 27] {
+            for (k, grp) in small_groups(n).unwrap().iter().enumerate() {
+                assert_eq!(
+                    identify_small_group(grp),
+                    Some(SmallGroupId {
+                        order: n,
+                        index: k + 1
+                    })
+                );
+                assert_eq!(identify_catalogue_group(grp), None);
+            }
+        }
+        for n in [32, 36, 40, 48, 54, 56, 60] {
+            for (k, grp) in small_groups(n).unwrap().iter().enumerate() {
+                assert_eq!(
+                    identify_catalogue_group(grp),
+                    Some(CatalogueGroupId {
+                        order: n,
+                        index: k + 1
+                    })
+                );
+                assert_eq!(identify_small_group(grp), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_catalogue_is_ordered_by_canonical_key() {
+        for n in 32..=SMALL_GROUPS_MAX_ORDER {
+            let keys = small_groups(n)
+                .unwrap()
+                .iter()
+                .map(FiniteGroupMultiplicationTable::canonical_key)
+                .collect::<Vec<_>>();
+            assert!(keys.is_sorted_by(|a, b| a < b));
+        }
+    }
+
+    #[test]
+    fn test_parse_catalogue_entry() {
+        //the quaternion group <a b c : a^2 = c, b^2 = c, b^a = bc>
+        let (order, entry) = parse_catalogue_entry("8: 2 2 2; a2=c, b2=c, ba=abc").unwrap();
+        assert_eq!(order, 8);
+        let grp = entry.group();
+        grp.check_state().unwrap();
+        assert!(grp.is_isomorphic(&examples::quaternion_group_structure()));
+
+        let (_, entry) = parse_catalogue_entry("6: 2 3").unwrap();
+        assert!(entry.group().is_isomorphic(&cyclic(6)));
+        let (_, entry) = parse_catalogue_entry("60: A5").unwrap();
+        assert_eq!(entry.group().size(), 60);
+
+        for line in [
+            "8: 2 2 2; a3=c",
+            "8: 2 2 2; a2=a",
+            "8: 2 2 2; ab=abc",
+            "8: 2 2 2; ba=bc",
+            "8: 2 2 2; b2=cb",
+            "8: 2 2 2; ba=abd",
+            "8: 2 2 2; a2=c2",
+            "8 2 2 2",
+        ] {
+            assert!(parse_catalogue_entry(line).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_identify_small_group() {
+        let id = |n, k| Some(SmallGroupId { order: n, index: k });
+        assert_eq!(
+            examples::trivial_group_structure().small_group_id(),
+            id(1, 1)
+        );
+        assert_eq!(examples::klein_four_structure().small_group_id(), id(4, 2));
+        assert_eq!(
+            examples::symmetric_group_structure(3).small_group_id(),
+            id(6, 1)
+        );
+        assert_eq!(
+            examples::dihedral_group_structure(4).small_group_id(),
+            id(8, 3)
+        );
+        assert_eq!(
+            examples::quaternion_group_structure().small_group_id(),
+            id(8, 4)
+        );
+        assert_eq!(
+            examples::alternating_group_structure(4).small_group_id(),
+            id(12, 3)
+        );
+        assert_eq!(
+            examples::symmetric_group_structure(4).small_group_id(),
+            id(24, 12)
+        );
+        assert_eq!(
+            examples::cyclic_group_structure(24).small_group_id(),
+            id(24, 2)
+        );
+        assert_eq!(
+            direct_product_structure(
+                &examples::symmetric_group_structure(4),
+                &examples::cyclic_group_structure(2)
+            )
+            .catalogue_group_id()
+            .map(|id| id.order),
+            Some(48)
+        );
+        assert_eq!(
+            examples::alternating_group_structure(5).catalogue_group_id(),
+            small_groups(60)
+                .unwrap()
+                .iter()
+                .position(|grp| grp.invariants().commutator_subgroup_size == 60)
+                .map(|k| CatalogueGroupId {
+                    order: 60,
+                    index: k + 1
+                })
+        );
+        assert_eq!(examples::cyclic_group_structure(32).small_group_id(), None);
+        assert_eq!(examples::cyclic_group_structure(64).small_group_id(), None);
+        assert_eq!(
+            examples::cyclic_group_structure(64).catalogue_group_id(),
+            None
+        );
+        assert_eq!(format!("{}", id(24, 12).unwrap()), "SmallGroup(24, 12)");
+        assert_eq!(
+            format!(
+                "{}",
+                CatalogueGroupId {
+                    order: 60,
+                    index: 5
+                }
+            ),
+            "CatalogueGroup(60, 5)"
+        );
+    }
+}
@@ -1,6 +1,8 @@
-use crate::structure::Group;
+use crate::structure::{AbelianGroupSignature, Group, GroupSignature, MonoidSignature};
+use algebraeon_sets::structure::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CanonicalStructure)]
+#[canonical_structure(eq)]
 pub enum C2 {
     Identity,
     Flip,
@@ -28,6 +30,32 @@ impl Group for C2 {
     }
 }
 
+impl CountableSetSignature for C2CanonicalStructure {
+    fn generate_all_elements(&self) -> impl Iterator<Item = Self::Set> + Clone {
+        [C2::Identity, C2::Flip].into_iter()
+    }
+}
+
+impl FiniteSetSignature for C2CanonicalStructure {}
+
+impl MonoidSignature for C2CanonicalStructure {
+    fn identity(&self) -> Self::Set {
+        C2::Identity
+    }
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        C2::compose(*a, *b)
+    }
+}
+
+impl GroupSignature for C2CanonicalStructure {
+    fn inverse(&self, a: &Self::Set) -> Self::Set {
+        *a
+    }
+}
+
+impl AbelianGroupSignature for C2CanonicalStructure {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +70,18 @@ mod tests {
         debug_assert_eq!(C2::compose(C2::Identity, C2::Flip), C2::Flip);
         debug_assert_eq!(C2::compose(C2::Flip, C2::Flip), C2::Identity);
     }
+
+    #[test]
+    fn test_c2_structure() {
+        use crate::structure::{FiniteGroupSignature, MetaGroup};
+        let c2 = C2::structure();
+        assert_eq!(c2.identity(), C2::Identity);
+        assert_eq!(c2.compose(&C2::Flip, &C2::Flip), C2::Identity);
+        assert_eq!(MetaGroup::inverse(&C2::Flip), C2::Flip);
+        assert_eq!(c2.element_order(&C2::Flip), 2);
+        assert!(c2.is_abelian());
+        let table = c2.multiplication_table();
+        table.check_state().unwrap();
+        assert_eq!(table.size(), 2);
+    }
 }
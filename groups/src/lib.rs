@@ -14,8 +14,10 @@
     clippy::needless_range_loop
 )]
 
+pub mod action;
 pub mod composition_table;
 pub mod examples;
 pub mod free_group;
 pub mod permutation;
+pub mod stabilizer_chain;
 pub mod structure;
@@ -3,7 +3,13 @@ use std::hash::Hash;
 
 use itertools::Itertools;
 
-use super::{examples::c2::C2, structure::Group};
+use super::{
+    examples::c2::C2,
+    structure::{Group, GroupSignature, MonoidSignature},
+};
+use algebraeon_sets::structure::{
+    CountableSetSignature, EqSignature, FiniteSetSignature, SetSignature, Signature,
+};
 
 #[derive(Debug, Clone)]
 pub struct Cycle {
@@ -150,7 +156,7 @@ impl Permutation {
         shape
     }
 
-    pub fn all_permutations(n: usize) -> impl Iterator<Item = Self> {
+    pub fn all_permutations(n: usize) -> impl Iterator<Item = Self> + Clone {
         (0..n).permutations(n).map(Self::new_unchecked)
     }
 
@@ -252,6 +258,66 @@ impl std::fmt::Display for Permutation {
     }
 }
 
+/// The symmetric group of all permutations of `0, 1, ..., n-1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymmetricGroupStructure {
+    n: usize,
+}
+
+impl SymmetricGroupStructure {
+    pub fn new(n: usize) -> Self {
+        Self { n }
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+}
+
+impl Signature for SymmetricGroupStructure {}
+
+impl SetSignature for SymmetricGroupStructure {
+    type Set = Permutation;
+
+    fn is_element(&self, x: &Self::Set) -> Result<(), String> {
+        if x.n() <= self.n {
+            Ok(())
+        } else {
+            Err(format!("permutation moves points outside of 0..{}", self.n))
+        }
+    }
+}
+
+impl EqSignature for SymmetricGroupStructure {
+    fn equal(&self, a: &Self::Set, b: &Self::Set) -> bool {
+        a == b
+    }
+}
+
+impl CountableSetSignature for SymmetricGroupStructure {
+    fn generate_all_elements(&self) -> impl Iterator<Item = Self::Set> + Clone {
+        Permutation::all_permutations(self.n)
+    }
+}
+
+impl FiniteSetSignature for SymmetricGroupStructure {}
+
+impl MonoidSignature for SymmetricGroupStructure {
+    fn identity(&self) -> Self::Set {
+        Permutation::identity()
+    }
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        Permutation::compose_refs(a, b)
+    }
+}
+
+impl GroupSignature for SymmetricGroupStructure {
+    fn inverse(&self, a: &Self::Set) -> Self::Set {
+        a.inverse_ref()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
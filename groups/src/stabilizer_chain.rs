@@ -0,0 +1,224 @@
+use crate::permutation::Permutation;
+use crate::structure::Group;
+use algebraeon_nzq::Natural;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+struct StabilizerChainLevel {
+    base_point: usize,
+    generators: Vec<Permutation>,
+    //for each point p in the orbit of base_point, a permutation u with u(base_point) = p
+    transversal: HashMap<usize, Permutation>,
+}
+
+impl StabilizerChainLevel {
+    fn new(base_point: usize, generators: Vec<Permutation>) -> Self {
+        let mut level = Self {
+            base_point,
+            generators,
+            transversal: HashMap::new(),
+        };
+        level.compute_transversal();
+        level
+    }
+
+    fn compute_transversal(&mut self) {
+        self.transversal = HashMap::from([(self.base_point, Permutation::identity())]);
+        let mut boundary = vec![self.base_point];
+        while let Some(p) = boundary.pop() {
+            let u_p = self.transversal[&p].clone();
+            for g in &self.generators {
+                let q = g.call(p);
+                if let std::collections::hash_map::Entry::Vacant(e) = self.transversal.entry(q) {
+                    e.insert(Permutation::compose_refs(g, &u_p));
+                    boundary.push(q);
+                }
+            }
+        }
+    }
+}
+
+/// A base and strong generating set for a group of permutations, computed by the Schreier-Sims algorithm.
+///
+/// Level `i` of the chain stores a base point `b_i` and generators for the pointwise stabilizer of
+/// `b_0, ..., b_{i-1}`, together with a transversal for the orbit of `b_i` under that stabilizer.
+#[derive(Debug, Clone)]
+pub struct StabilizerChain {
+    levels: Vec<StabilizerChainLevel>,
+}
+
+impl StabilizerChain {
+    pub fn new(generators: Vec<Permutation>) -> Self {
+        let generators = generators
+            .into_iter()
+            .filter(|g| g != &Permutation::identity())
+            .collect::<Vec<_>>();
+        let mut chain = Self { levels: vec![] };
+        if generators.is_empty() {
+            return chain;
+        }
+        let base_point = first_moved_point(&generators[0]);
+        chain
+            .levels
+            .push(StabilizerChainLevel::new(base_point, generators));
+
+        //make each level generate the stabilizer of its base point in the level above, deepest level first
+        let mut i = 0;
+        loop {
+            if let Some((h, j)) = chain.find_missing_schreier_generator(i) {
+                chain.add_strong_generator(h, i + 1, j);
+                i = j;
+            } else if i == 0 {
+                break;
+            } else {
+                i -= 1;
+            }
+        }
+        chain
+    }
+
+    //find a Schreier generator of level i which does not sift through the levels below i
+    //return the residue of the sift and the level at which sifting stopped
+    fn find_missing_schreier_generator(&self, i: usize) -> Option<(Permutation, usize)> {
+        let level = &self.levels[i];
+        for (p, u_p) in &level.transversal {
+            for g in &level.generators {
+                let u_gp = &level.transversal[&g.call(*p)];
+                let s = Permutation::compose_refs(
+                    &u_gp.inverse_ref(),
+                    &Permutation::compose_refs(g, u_p),
+                );
+                let (h, j) = self.sift(s, i + 1);
+                if h != Permutation::identity() {
+                    return Some((h, j));
+                }
+            }
+        }
+        None
+    }
+
+    fn add_strong_generator(&mut self, h: Permutation, from: usize, to: usize) {
+        if to == self.levels.len() {
+            let base_point = first_moved_point(&h);
+            self.levels
+                .push(StabilizerChainLevel::new(base_point, vec![]));
+        }
+        for level in &mut self.levels[from..=to] {
+            level.generators.push(h.clone());
+            level.compute_transversal();
+        }
+    }
+
+    //sift g through the levels starting at from
+    //return the residue and the level at which it could not be sifted further
+    fn sift(&self, mut g: Permutation, from: usize) -> (Permutation, usize) {
+        for i in from..self.levels.len() {
+            let level = &self.levels[i];
+            match level.transversal.get(&g.call(level.base_point)) {
+                Some(u) => {
+                    g = Permutation::compose_refs(&u.inverse_ref(), &g);
+                }
+                None => {
+                    return (g, i);
+                }
+            }
+        }
+        (g, self.levels.len())
+    }
+
+    pub fn base(&self) -> Vec<usize> {
+        self.levels.iter().map(|level| level.base_point).collect()
+    }
+
+    pub fn strong_generators(&self) -> Vec<Permutation> {
+        match self.levels.first() {
+            Some(level) => level.generators.clone(),
+            None => vec![],
+        }
+    }
+
+    /// The sizes of the basic orbits, whose product is the order of the group.
+    pub fn orbit_sizes(&self) -> Vec<usize> {
+        self.levels
+            .iter()
+            .map(|level| level.transversal.len())
+            .collect()
+    }
+
+    pub fn order(&self) -> Natural {
+        let mut order = Natural::ONE;
+        for size in self.orbit_sizes() {
+            order *= Natural::from(size);
+        }
+        order
+    }
+
+    pub fn contains(&self, g: &Permutation) -> bool {
+        self.sift(g.clone(), 0).0 == Permutation::identity()
+    }
+}
+
+fn first_moved_point(g: &Permutation) -> usize {
+    (0..g.n()).find(|x| g.call(*x) != *x).unwrap()
+}
+
+#[cfg(test)]
+mod stabilizer_chain_tests {
+    use super::*;
+
+    #[test]
+    fn test_trivial() {
+        let chain = StabilizerChain::new(vec![]);
+        assert_eq!(chain.order(), Natural::ONE);
+        assert!(chain.contains(&Permutation::identity()));
+        assert!(!chain.contains(&Permutation::new(vec![1, 0]).unwrap()));
+    }
+
+    #[test]
+    fn test_symmetric_and_alternating() {
+        for n in 2..8usize {
+            let cycle = Permutation::new((0..n).map(|i| (i + 1) % n).collect()).unwrap();
+            let swap = Permutation::new(vec![1, 0]).unwrap();
+            let chain = StabilizerChain::new(vec![cycle, swap.clone()]);
+            let factorial = (1..=n).product::<usize>();
+            assert_eq!(chain.order(), Natural::from(factorial));
+            assert!(chain.contains(&swap));
+
+            if n >= 3 {
+                let three_cycles = (2..n)
+                    .map(|k| {
+                        Permutation::new(
+                            (0..=k)
+                                .map(|i| match i {
+                                    0 => 1,
+                                    1 => k,
+                                    i if i == k => 0,
+                                    i => i,
+                                })
+                                .collect(),
+                        )
+                        .unwrap()
+                    })
+                    .collect();
+                let chain = StabilizerChain::new(three_cycles);
+                assert_eq!(chain.order(), Natural::from(factorial / 2));
+                assert!(!chain.contains(&swap));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mathieu_11() {
+        //M11 is generated by (0 1 2 3 4 5 6 7 8 9 10) and (2 6 10 7)(3 9 4 5)
+        let a = Permutation::new((0..11).map(|i| (i + 1) % 11).collect()).unwrap();
+        let mut b = (0..11).collect::<Vec<usize>>();
+        for cycle in [[2, 6, 10, 7], [3, 9, 4, 5]] {
+            for k in 0..4 {
+                b[cycle[k]] = cycle[(k + 1) % 4];
+            }
+        }
+        let b = Permutation::new(b).unwrap();
+        let chain = StabilizerChain::new(vec![a, b]);
+        assert_eq!(chain.order(), Natural::from(7920u32));
+    }
+}
@@ -1,11 +1,16 @@
+use crate::composition_table::group::FiniteGroupMultiplicationTable;
 use algebraeon_nzq::traits::Abs;
 use algebraeon_nzq::{Integer, Natural};
+use algebraeon_sets::structure::{
+    EqSignature, FiniteSetSignature, MetaType, SetSignature, Signature,
+};
 use itertools::Itertools;
 use std::{
     borrow::Borrow,
     collections::{HashMap, HashSet},
     fmt::Debug,
     hash::Hash,
+    marker::PhantomData,
 };
 
 pub trait Group: Debug + Clone + PartialEq + Eq {
@@ -80,7 +85,7 @@ pub trait Group: Debug + Clone + PartialEq + Eq {
     fn generated_finite_subgroup_table(
         generators: Vec<Self>,
     ) -> (
-        crate::composition_table::group::FiniteGroupMultiplicationTable,
+        FiniteGroupMultiplicationTable,
         Vec<Self>,
         HashMap<Self, usize>,
     )
@@ -141,9 +146,7 @@ pub trait Group: Debug + Clone + PartialEq + Eq {
             .map(|elem| *elem_to_idx.get(&Self::inverse_ref(elem)).unwrap())
             .collect_vec();
 
-        let grp = crate::composition_table::group::FiniteGroupMultiplicationTable::new_unchecked(
-            n, 0, inv, mul, None, None,
-        );
+        let grp = FiniteGroupMultiplicationTable::new_unchecked(n, 0, inv, mul, None, None);
 
         #[cfg(debug_assertions)]
         grp.check_state().unwrap();
@@ -252,3 +255,284 @@ impl<G: Group> FiniteSubgroup<G> {
         self.elems.iter()
     }
 }
+
+pub trait MonoidSignature: SetSignature {
+    fn identity(&self) -> Self::Set;
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set;
+
+    fn compose_mut(&self, a: &mut Self::Set, b: &Self::Set) {
+        *a = self.compose(a, b);
+    }
+
+    fn compose_list(&self, elems: Vec<impl Borrow<Self::Set>>) -> Self::Set {
+        let mut ans = self.identity();
+        for elem in elems {
+            self.compose_mut(&mut ans, elem.borrow());
+        }
+        ans
+    }
+
+    fn nat_pow(&self, a: &Self::Set, n: &Natural) -> Self::Set {
+        if *n == Natural::ZERO {
+            self.identity()
+        } else if *n == Natural::ONE {
+            a.clone()
+        } else {
+            debug_assert!(*n >= Natural::TWO);
+            let bits: Vec<_> = n.bits().collect();
+            let mut pows = vec![a.clone()];
+            while pows.len() < bits.len() {
+                pows.push(self.compose(pows.last().unwrap(), pows.last().unwrap()));
+            }
+            let count = bits.len();
+            debug_assert_eq!(count, pows.len());
+            let mut ans = self.identity();
+            for i in 0..count {
+                if bits[i] {
+                    self.compose_mut(&mut ans, &pows[i]);
+                }
+            }
+            ans
+        }
+    }
+}
+
+pub trait MetaMonoid: MetaType
+where
+    Self::Signature: MonoidSignature,
+{
+    fn identity() -> Self {
+        Self::structure().identity()
+    }
+
+    fn compose(a: &Self, b: &Self) -> Self {
+        Self::structure().compose(a, b)
+    }
+
+    fn compose_list(elems: Vec<impl Borrow<Self>>) -> Self {
+        Self::structure().compose_list(elems)
+    }
+
+    fn nat_pow(&self, n: &Natural) -> Self {
+        Self::structure().nat_pow(self, n)
+    }
+}
+impl<G: MetaType> MetaMonoid for G where Self::Signature: MonoidSignature {}
+
+pub trait GroupSignature: MonoidSignature {
+    fn inverse(&self, a: &Self::Set) -> Self::Set;
+
+    fn int_pow(&self, a: &Self::Set, n: &Integer) -> Self::Set {
+        if *n < Integer::ZERO {
+            self.inverse(&self.nat_pow(a, &n.abs()))
+        } else {
+            self.nat_pow(a, &n.abs())
+        }
+    }
+
+    /// `gxg^{-1}`
+    fn conjugate(&self, g: &Self::Set, x: &Self::Set) -> Self::Set {
+        self.compose(&self.compose(g, x), &self.inverse(g))
+    }
+
+    /// `aba^{-1}b^{-1}`
+    fn commutator(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        self.compose(
+            &self.compose(a, b),
+            &self.compose(&self.inverse(a), &self.inverse(b)),
+        )
+    }
+}
+
+pub trait MetaGroup: MetaType
+where
+    Self::Signature: GroupSignature,
+{
+    fn inverse(&self) -> Self {
+        Self::structure().inverse(self)
+    }
+
+    fn int_pow(&self, n: &Integer) -> Self {
+        Self::structure().int_pow(self, n)
+    }
+
+    fn conjugate(g: &Self, x: &Self) -> Self {
+        Self::structure().conjugate(g, x)
+    }
+
+    fn commutator(a: &Self, b: &Self) -> Self {
+        Self::structure().commutator(a, b)
+    }
+}
+impl<G: MetaType> MetaGroup for G where Self::Signature: GroupSignature {}
+
+/// A group in which `compose(a, b) = compose(b, a)` for all `a` and `b`.
+pub trait AbelianGroupSignature: GroupSignature {}
+
+pub trait FiniteGroupSignature: GroupSignature + FiniteSetSignature + EqSignature {
+    fn is_identity(&self, a: &Self::Set) -> bool {
+        self.equal(a, &self.identity())
+    }
+
+    /// The smallest `n >= 1` such that `a^n` is the identity.
+    fn element_order(&self, a: &Self::Set) -> usize {
+        let mut n = 1;
+        let mut x = a.clone();
+        while !self.is_identity(&x) {
+            self.compose_mut(&mut x, a);
+            n += 1;
+        }
+        n
+    }
+
+    fn is_abelian(&self) -> bool {
+        let elems = self.list_all_elements();
+        elems.iter().all(|a| {
+            elems
+                .iter()
+                .all(|b| self.equal(&self.compose(a, b), &self.compose(b, a)))
+        })
+    }
+
+    /// The multiplication table of the group, labelling elements by their position in `list_all_elements()`.
+    /// Elements are located using `equal` so this is only suitable for small groups.
+    fn multiplication_table(&self) -> FiniteGroupMultiplicationTable {
+        let elems = self.list_all_elements();
+        let index_of = |x: &Self::Set| elems.iter().position(|y| self.equal(x, y)).unwrap();
+        let n = elems.len();
+        let ident = index_of(&self.identity());
+        let inv = elems.iter().map(|a| index_of(&self.inverse(a))).collect();
+        let mul = elems
+            .iter()
+            .map(|a| {
+                elems
+                    .iter()
+                    .map(|b| index_of(&self.compose(a, b)))
+                    .collect()
+            })
+            .collect();
+        let table = FiniteGroupMultiplicationTable::new_unchecked(n, ident, inv, mul, None, None);
+        #[cfg(debug_assertions)]
+        table.check_state().unwrap();
+        table
+    }
+}
+impl<G: GroupSignature + FiniteSetSignature + EqSignature> FiniteGroupSignature for G {}
+
+/// The structure of all values of a type implementing [`Group`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupElementsStructure<G: Group> {
+    _group: PhantomData<G>,
+}
+
+impl<G: Group> GroupElementsStructure<G> {
+    pub fn new() -> Self {
+        Self {
+            _group: PhantomData,
+        }
+    }
+}
+
+impl<G: Group> Default for GroupElementsStructure<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Group + Send + Sync> Signature for GroupElementsStructure<G> {}
+
+impl<G: Group + Send + Sync> SetSignature for GroupElementsStructure<G> {
+    type Set = G;
+
+    fn is_element(&self, _x: &Self::Set) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<G: Group + Send + Sync> EqSignature for GroupElementsStructure<G> {
+    fn equal(&self, a: &Self::Set, b: &Self::Set) -> bool {
+        a == b
+    }
+}
+
+impl<G: Group + Send + Sync> MonoidSignature for GroupElementsStructure<G> {
+    fn identity(&self) -> Self::Set {
+        G::identity()
+    }
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        G::compose_refs(a, b)
+    }
+
+    fn nat_pow(&self, a: &Self::Set, n: &Natural) -> Self::Set {
+        a.nat_pow(n)
+    }
+}
+
+impl<G: Group + Send + Sync> GroupSignature for GroupElementsStructure<G> {
+    fn inverse(&self, a: &Self::Set) -> Self::Set {
+        a.inverse_ref()
+    }
+}
+
+#[cfg(test)]
+mod structure_tests {
+    use super::*;
+    use crate::composition_table::group::examples;
+    use crate::composition_table::small_groups::SmallGroupId;
+    use crate::permutation::{Permutation, SymmetricGroupStructure};
+
+    //written once and used with several representations of groups below
+    fn count_involutions<G: FiniteGroupSignature>(group: &G) -> usize {
+        group
+            .list_all_elements()
+            .iter()
+            .filter(|x| group.element_order(x) == 2)
+            .count()
+    }
+
+    #[test]
+    fn test_generic_algorithms() {
+        let s4 = SymmetricGroupStructure::new(4);
+        assert_eq!(s4.size(), 24);
+        assert_eq!(count_involutions(&s4), 9);
+        assert!(!FiniteGroupSignature::is_abelian(&s4));
+        assert!(
+            s4.is_element(&Permutation::new(vec![1, 0]).unwrap())
+                .is_ok()
+        );
+        assert!(
+            s4.is_element(&Permutation::new(vec![4, 1, 2, 3, 0]).unwrap())
+                .is_err()
+        );
+
+        let table = examples::symmetric_group_structure(4);
+        assert_eq!(count_involutions(&table), 9);
+        assert_eq!(
+            count_involutions(&examples::quaternion_group_structure()),
+            1
+        );
+        assert_eq!(count_involutions(&examples::klein_four_structure()), 3);
+
+        assert_eq!(
+            s4.multiplication_table().small_group_id(),
+            Some(SmallGroupId {
+                order: 24,
+                index: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_group_elements_structure() {
+        let perms = GroupElementsStructure::<Permutation>::new();
+        let a = Permutation::new(vec![1, 2, 0]).unwrap();
+        let b = Permutation::new(vec![1, 0]).unwrap();
+        assert_eq!(perms.int_pow(&a, &Integer::from(-1)), a.inverse_ref());
+        assert_eq!(perms.nat_pow(&a, &Natural::from(3u32)), perms.identity());
+        assert_eq!(perms.conjugate(&a, &b).cycle_shape(), vec![2]);
+        assert_eq!(perms.commutator(&a, &b).cycle_shape(), vec![3]);
+        assert_eq!(perms.commutator(&a, &a), perms.identity());
+    }
+}
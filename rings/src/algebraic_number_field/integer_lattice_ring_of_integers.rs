@@ -276,6 +276,8 @@ impl SemiRingUnitsSignature for RingOfIntegersWithIntegralBasisStructure {
     }
 }
 
+impl CommutativeRingSignature for RingOfIntegersWithIntegralBasisStructure {}
+
 impl IntegralDomainSignature for RingOfIntegersWithIntegralBasisStructure {
     fn div(
         &self,
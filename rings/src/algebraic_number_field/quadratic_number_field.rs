@@ -9,8 +9,8 @@ use crate::{
     algebraic_number_field::structure::AlgebraicNumberFieldSignature,
     structure::{
         AdditiveGroupSignature, AdditiveMonoidSignature, CharZeroFieldSignature,
-        CharZeroRingSignature, CharacteristicSignature, FieldSignature, IntegralDomainSignature,
-        RingSignature, SemiRingSignature, SemiRingUnitsSignature,
+        CharZeroRingSignature, CharacteristicSignature, CommutativeRingSignature, FieldSignature,
+        IntegralDomainSignature, RingSignature, SemiRingSignature, SemiRingUnitsSignature,
     },
 };
 use algebraeon_nzq::{Integer, Natural, Rational, RationalCanonicalStructure};
@@ -173,6 +173,8 @@ impl<D: BorrowedStructure<Integer>> SemiRingUnitsSignature for QuadraticNumberFi
     }
 }
 
+impl<D: BorrowedStructure<Integer>> CommutativeRingSignature for QuadraticNumberFieldStructure<D> {}
+
 impl<D: BorrowedStructure<Integer>> IntegralDomainSignature for QuadraticNumberFieldStructure<D> {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         Ok(self.mul(a, &self.inv(b)?))
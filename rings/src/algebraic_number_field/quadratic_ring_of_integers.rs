@@ -5,9 +5,9 @@ use crate::{
     },
     structure::{
         AdditiveGroupSignature, AdditiveMonoidSignature, CharZeroRingSignature,
-        CharacteristicSignature, DedekindDomainSignature, IntegralDomainSignature,
-        MetaFactorableSignature, RingDivisionError, RingSignature, SemiRingSignature,
-        SemiRingUnitsSignature,
+        CharacteristicSignature, CommutativeRingSignature, DedekindDomainSignature,
+        IntegralDomainSignature, MetaFactorableSignature, RingDivisionError, RingSignature,
+        SemiRingSignature, SemiRingUnitsSignature,
     },
 };
 use algebraeon_nzq::{Integer, Natural};
@@ -110,6 +110,11 @@ impl<D: BorrowedStructure<Integer>> SemiRingUnitsSignature for QuadraticRingOfIn
     }
 }
 
+impl<D: BorrowedStructure<Integer>> CommutativeRingSignature
+    for QuadraticRingOfIntegersStructure<D>
+{
+}
+
 impl<D: BorrowedStructure<Integer>> IntegralDomainSignature
     for QuadraticRingOfIntegersStructure<D>
 {
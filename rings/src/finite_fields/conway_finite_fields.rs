@@ -148,6 +148,8 @@ impl SemiRingUnitsSignature for ConwayFiniteFieldStructure {
     }
 }
 
+impl CommutativeRingSignature for ConwayFiniteFieldStructure {}
+
 impl IntegralDomainSignature for ConwayFiniteFieldStructure {
     fn div(
         &self,
@@ -222,6 +222,17 @@ impl<const N: usize> FiniteSetSignature for ModuloCanonicalStructure<N> {
     }
 }
 
+impl<const N: usize> FiniteUnitsSignature for ModuloCanonicalStructure<N> {
+    fn all_units(&self) -> Vec<Modulo<N>> {
+        (0..N)
+            .filter(|x| xgcd(*x, N).0 == 1)
+            .map(Modulo::new)
+            .collect()
+    }
+}
+
+impl<const N: usize> CommutativeRingSignature for ModuloCanonicalStructure<N> {}
+
 macro_rules! impl_field {
     ($N: literal) => {
         impl IntegralDomainSignature for ModuloCanonicalStructure<$N> {
@@ -237,15 +248,6 @@ macro_rules! impl_field {
             }
         }
         impl FieldSignature for ModuloCanonicalStructure<$N> {}
-        impl FiniteUnitsSignature for ModuloCanonicalStructure<$N> {
-            fn all_units(&self) -> Vec<Modulo<$N>> {
-                let mut units = vec![];
-                for x in 1..$N {
-                    units.push(Modulo { x });
-                }
-                units
-            }
-        }
         impl FiniteFieldSignature for ModuloCanonicalStructure<$N> {
             fn characteristic_and_power(&self) -> (Natural, Natural) {
                 (Natural::from($N as usize), Natural::from(1u8))
@@ -97,6 +97,8 @@ impl SemiRingUnitsSignature for QuaternaryFieldCanonicalStructure {
     }
 }
 
+impl CommutativeRingSignature for QuaternaryFieldCanonicalStructure {}
+
 impl IntegralDomainSignature for QuaternaryFieldCanonicalStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         #[allow(clippy::match_same_arms)]
@@ -61,6 +61,8 @@ impl SemiRingUnitsSignature for IntegerCanonicalStructure {
     }
 }
 
+impl CommutativeRingSignature for IntegerCanonicalStructure {}
+
 impl IntegralDomainSignature for IntegerCanonicalStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         match self.quorem(a, b) {
@@ -963,6 +963,8 @@ impl SemiRingUnitsSignature for ComplexAlgebraicCanonicalStructure {
     }
 }
 
+impl CommutativeRingSignature for ComplexAlgebraicCanonicalStructure {}
+
 impl IntegralDomainSignature for ComplexAlgebraicCanonicalStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         Ok(self.mul(a, &self.inv(b)?))
@@ -838,6 +838,8 @@ impl SemiRingUnitsSignature for PAdicAlgebraicStructure {
     }
 }
 
+impl CommutativeRingSignature for PAdicAlgebraicStructure {}
+
 impl IntegralDomainSignature for PAdicAlgebraicStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         debug_assert!(self.is_element(a).is_ok());
@@ -676,6 +676,8 @@ impl SemiRingUnitsSignature for RealAlgebraicCanonicalStructure {
     }
 }
 
+impl CommutativeRingSignature for RealAlgebraicCanonicalStructure {}
+
 impl IntegralDomainSignature for RealAlgebraicCanonicalStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         match self.inv(b) {
@@ -250,6 +250,16 @@ impl<
 {
 }
 
+impl<
+    Ring: IntegralDomainSignature,
+    RingB: BorrowedStructure<Ring>,
+    Ideals: IdealsArithmeticSignature<Ring, RingB>,
+    FoF: FieldSignature,
+    FoFInclusion: FieldOfFractionsInclusion<Ring, FoF>,
+> CommutativeRingSignature for LocalizedRingAtPrime<Ring, RingB, Ideals, FoF, FoFInclusion>
+{
+}
+
 impl<
     Ring: IntegralDomainSignature,
     RingB: BorrowedStructure<Ring>,
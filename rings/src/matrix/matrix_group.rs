@@ -0,0 +1,685 @@
+use super::*;
+use algebraeon_groups::composition_table::group::FiniteGroupMultiplicationTable;
+use algebraeon_groups::permutation::Permutation;
+use algebraeon_groups::stabilizer_chain::StabilizerChain;
+use algebraeon_groups::structure::Group;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// An invertible `N` by `N` matrix over a finite field.
+#[derive(Debug, Clone)]
+pub struct InvertibleMatrix<F: MetaType, const N: usize>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    matrix: Matrix<F>,
+}
+
+impl<F: MetaType, const N: usize> InvertibleMatrix<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    pub fn new(matrix: Matrix<F>) -> Result<Self, &'static str> {
+        if matrix.rows() != N || matrix.cols() != N {
+            return Err("matrix is not N by N");
+        }
+        if F::structure().is_zero(&matrix.det().unwrap()) {
+            return Err("matrix is not invertible");
+        }
+        Ok(Self { matrix })
+    }
+
+    pub fn new_unchecked(matrix: Matrix<F>) -> Self {
+        debug_assert!(Self::new(matrix.clone()).is_ok());
+        Self { matrix }
+    }
+
+    pub fn matrix(&self) -> &Matrix<F> {
+        &self.matrix
+    }
+
+    pub fn into_matrix(self) -> Matrix<F> {
+        self.matrix
+    }
+
+    pub fn det(&self) -> F {
+        self.matrix.det().unwrap()
+    }
+
+    //the image of the column vector v
+    pub fn apply(&self, v: &Vec<F>) -> Vec<F> {
+        self.matrix.apply_col(v)
+    }
+}
+
+impl<F: MetaType, const N: usize> PartialEq for InvertibleMatrix<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.matrix == other.matrix
+    }
+}
+
+impl<F: MetaType, const N: usize> Eq for InvertibleMatrix<F, N> where
+    F::Signature: FiniteFieldSignature
+{
+}
+
+impl<F: MetaType + Hash, const N: usize> Hash for InvertibleMatrix<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for r in 0..N {
+            for c in 0..N {
+                self.matrix.at(r, c).unwrap().hash(state);
+            }
+        }
+    }
+}
+
+impl<F: MetaType, const N: usize> Group for InvertibleMatrix<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    fn identity() -> Self {
+        Self {
+            matrix: Matrix::ident(N),
+        }
+    }
+
+    fn inverse(self) -> Self {
+        Self {
+            matrix: self.matrix.inv().unwrap(),
+        }
+    }
+
+    fn compose_mut(&mut self, other: &Self) {
+        self.matrix = Matrix::mul(&self.matrix, &other.matrix).unwrap();
+    }
+}
+
+/// A group of invertible `N` by `N` matrices over a finite field, given by a list of generators.
+///
+/// Computations with the whole group go through its faithful action on the vectors of `F^N`,
+/// which is handled as a permutation group using the Schreier-Sims algorithm.
+#[derive(Debug, Clone)]
+pub struct MatrixGroup<F: MetaType, const N: usize>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    generators: Vec<InvertibleMatrix<F, N>>,
+}
+
+impl<F: MetaType + Hash + Eq, const N: usize> MatrixGroup<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    pub fn new(generators: Vec<InvertibleMatrix<F, N>>) -> Self {
+        Self { generators }
+    }
+
+    pub fn generators(&self) -> &Vec<InvertibleMatrix<F, N>> {
+        &self.generators
+    }
+
+    /// All vectors of `F^N`. The action of the group on vectors permutes the indices of this list.
+    pub fn vectors() -> Vec<Vec<F>> {
+        let elements = F::structure().list_all_elements();
+        (0..N)
+            .map(|_| elements.clone())
+            .multi_cartesian_product()
+            .collect()
+    }
+
+    /// A representative of each one dimensional subspace of `F^N`, scaled so that the first non-zero entry is one.
+    pub fn projective_points() -> Vec<Vec<F>> {
+        Self::vectors()
+            .into_iter()
+            .filter(|v| is_normalized::<F>(v))
+            .collect()
+    }
+
+    fn permutation_of(
+        g: &InvertibleMatrix<F, N>,
+        points: &HashMap<Vec<F>, usize>,
+        projective: bool,
+    ) -> Permutation {
+        let mut perm = vec![0; points.len()];
+        for (v, i) in points {
+            let image = g.apply(v);
+            perm[*i] = if projective {
+                points[&normalize::<F>(image)]
+            } else {
+                points[&image]
+            };
+        }
+        Permutation::new_unchecked(perm)
+    }
+
+    /// The permutation of `vectors()` given by `v -> gv`.
+    pub fn vector_permutation(g: &InvertibleMatrix<F, N>) -> Permutation {
+        Self::permutation_of(g, &index_points(Self::vectors()), false)
+    }
+
+    /// The permutation of `projective_points()` induced by `g`.
+    pub fn projective_permutation(g: &InvertibleMatrix<F, N>) -> Permutation {
+        Self::permutation_of(g, &index_points(Self::projective_points()), true)
+    }
+
+    pub fn stabilizer_chain(&self) -> StabilizerChain {
+        let points = index_points(Self::vectors());
+        StabilizerChain::new(
+            self.generators
+                .iter()
+                .map(|g| Self::permutation_of(g, &points, false))
+                .collect(),
+        )
+    }
+
+    /// The stabilizer chain of the image of the group acting on one dimensional subspaces.
+    /// This image is the quotient of the group by its subgroup of scalar matrices.
+    pub fn projective_stabilizer_chain(&self) -> StabilizerChain {
+        let points = index_points(Self::projective_points());
+        StabilizerChain::new(
+            self.generators
+                .iter()
+                .map(|g| Self::permutation_of(g, &points, true))
+                .collect(),
+        )
+    }
+
+    pub fn order(&self) -> Natural {
+        self.stabilizer_chain().order()
+    }
+
+    pub fn projective_order(&self) -> Natural {
+        self.projective_stabilizer_chain().order()
+    }
+
+    pub fn contains(&self, g: &InvertibleMatrix<F, N>) -> bool {
+        self.stabilizer_chain()
+            .contains(&Self::vector_permutation(g))
+    }
+
+    /// The multiplication table of the group together with the matrix labelled by each element.
+    /// Every element is enumerated so this is only suitable for small groups.
+    pub fn composition_table(
+        &self,
+    ) -> (
+        FiniteGroupMultiplicationTable,
+        Vec<InvertibleMatrix<F, N>>,
+        HashMap<InvertibleMatrix<F, N>, usize>,
+    ) {
+        InvertibleMatrix::generated_finite_subgroup_table(self.generators.clone())
+    }
+
+    /// The multiplication table of the quotient of the group by its scalar matrices.
+    pub fn projective_composition_table(&self) -> FiniteGroupMultiplicationTable {
+        let points = index_points(Self::projective_points());
+        Permutation::generated_finite_subgroup_table(
+            self.generators
+                .iter()
+                .map(|g| Self::permutation_of(g, &points, true))
+                .collect(),
+        )
+        .0
+    }
+
+    /// The general linear group GL(N, q), generated by the two matrices `diag(w, 1, ..., 1)` for a
+    /// primitive element `w` of the field and `-E_{1,1} + E_{1,N} - E_{2,1} - ... - E_{N,N-1}`.
+    /// Over the field of two elements GL(N, 2) is SL(N, 2) and has its generators instead.
+    pub fn general_linear() -> Self {
+        let field = F::structure();
+        if N == 0 {
+            return Self::new(vec![]);
+        }
+        if field.list_all_elements().len() == 2 {
+            return Self::special_linear();
+        }
+        let mut diag = (0..N).map(|_| field.one()).collect::<Vec<_>>();
+        diag[0] = primitive_element::<F>();
+        let mut generators = vec![InvertibleMatrix::new_unchecked(Matrix::diag(&diag))];
+        if N > 1 {
+            generators.push(cycling_matrix::<F, N>());
+        }
+        Self::new(generators)
+    }
+
+    /// The special linear group SL(N, q), generated by the two matrices `diag(w, w^{-1}, 1, ..., 1)`
+    /// for a primitive element `w` of the field, or the transvection `I + E_{1,2}` when `q` is 2 or 3,
+    /// and `-E_{1,1} + E_{1,N} - E_{2,1} - ... - E_{N,N-1}`.
+    pub fn special_linear() -> Self {
+        let field = F::structure();
+        if N < 2 {
+            return Self::new(vec![]);
+        }
+        let first = if field.list_all_elements().len() <= 3 {
+            Matrix::construct(N, N, |r, c| {
+                if r == c || (r, c) == (0, 1) {
+                    field.one()
+                } else {
+                    field.zero()
+                }
+            })
+        } else {
+            let w = primitive_element::<F>();
+            let mut diag = (0..N).map(|_| field.one()).collect::<Vec<_>>();
+            diag[1] = field.inv(&w).unwrap();
+            diag[0] = w;
+            Matrix::diag(&diag)
+        };
+        Self::new(vec![
+            InvertibleMatrix::new_unchecked(first),
+            cycling_matrix::<F, N>(),
+        ])
+    }
+
+    /// The symplectic group Sp(N, q) preserving the form `sum_i x_i y_{i+N/2} - x_{i+N/2} y_i`,
+    /// generated by [`Self::symplectic_transvections`].
+    pub fn symplectic() -> Result<Self, &'static str> {
+        Ok(Self::new(Self::symplectic_transvections()?))
+    }
+
+    /// Every symplectic transvection `x -> x + a B(x, v) v` for the form `B` of [`Self::symplectic`],
+    /// with `v` a representative of each one dimensional subspace and `a` in a basis of the field over
+    /// its prime subfield. These generate Sp(N, q) but are far from a minimal generating set.
+    pub fn symplectic_transvections() -> Result<Vec<InvertibleMatrix<F, N>>, &'static str> {
+        if !N.is_multiple_of(2) {
+            return Err("symplectic groups only exist in even dimension");
+        }
+        let field = F::structure();
+        let m = N / 2;
+        //B(e_c, v)
+        let form_with_basis = |c: usize, v: &Vec<F>| {
+            if c < m {
+                v[c + m].clone()
+            } else {
+                field.neg(&v[c - m])
+            }
+        };
+        let mut transvections = vec![];
+        for v in Self::projective_points() {
+            for a in additive_basis::<F>() {
+                transvections.push(InvertibleMatrix::new_unchecked(Matrix::construct(
+                    N,
+                    N,
+                    |r, c| {
+                        let t = field.mul(&a, &field.mul(&form_with_basis(c, &v), &v[r]));
+                        if r == c {
+                            field.add(&field.one(), &t)
+                        } else {
+                            t
+                        }
+                    },
+                )));
+            }
+        }
+        Ok(transvections)
+    }
+
+    /// The orthogonal group O(N, q) preserving the form `sum_i x_i y_i` in odd characteristic,
+    /// generated by [`Self::orthogonal_reflections`].
+    pub fn orthogonal() -> Result<Self, &'static str> {
+        Ok(Self::new(Self::orthogonal_reflections()?))
+    }
+
+    /// The reflection `x -> x - 2 B(x, v) / B(v, v) v` in each anisotropic vector `v`, up to scaling,
+    /// for the form `B` of [`Self::orthogonal`]. These generate O(N, q) but are far from a minimal
+    /// generating set.
+    pub fn orthogonal_reflections() -> Result<Vec<InvertibleMatrix<F, N>>, &'static str> {
+        let field = F::structure();
+        let two = field.add(&field.one(), &field.one());
+        if field.is_zero(&two) {
+            return Err("orthogonal groups are only supported in odd characteristic");
+        }
+        let mut reflections = vec![];
+        for v in Self::projective_points() {
+            let norm = v
+                .iter()
+                .fold(field.zero(), |acc, x| field.add(&acc, &field.mul(x, x)));
+            if field.is_zero(&norm) {
+                continue;
+            }
+            //x -> x - 2 B(x, v) / B(v, v) v
+            let scale = field.neg(&field.mul(&two, &field.inv(&norm).unwrap()));
+            reflections.push(InvertibleMatrix::new_unchecked(Matrix::construct(
+                N,
+                N,
+                |r, c| {
+                    let t = field.mul(&scale, &field.mul(&v[r], &v[c]));
+                    if r == c {
+                        field.add(&field.one(), &t)
+                    } else {
+                        t
+                    }
+                },
+            )));
+        }
+        Ok(reflections)
+    }
+}
+
+//the matrix -E_{1,1} + E_{1,N} - E_{2,1} - ... - E_{N,N-1}, which has determinant one
+fn cycling_matrix<F: MetaType, const N: usize>() -> InvertibleMatrix<F, N>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    let field = F::structure();
+    InvertibleMatrix::new_unchecked(Matrix::construct(N, N, |r, c| {
+        if (r, c) == (0, N - 1) {
+            field.one()
+        } else if (r, c) == (0, 0) || r == c + 1 {
+            field.neg(&field.one())
+        } else {
+            field.zero()
+        }
+    }))
+}
+
+fn index_points<F: Hash + Eq>(points: Vec<Vec<F>>) -> HashMap<Vec<F>, usize> {
+    points
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| (v, i))
+        .collect()
+}
+
+fn is_normalized<F: MetaType>(v: &Vec<F>) -> bool
+where
+    F::Signature: FiniteFieldSignature,
+{
+    let field = F::structure();
+    match v.iter().find(|x| !field.is_zero(x)) {
+        Some(x) => field.equal(x, &field.one()),
+        None => false,
+    }
+}
+
+//scale a non-zero vector so that its first non-zero entry is one
+fn normalize<F: MetaType>(v: Vec<F>) -> Vec<F>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    let field = F::structure();
+    match v.iter().find(|x| !field.is_zero(x)) {
+        Some(x) => {
+            let s = field.inv(x).unwrap();
+            v.iter().map(|y| field.mul(&s, y)).collect()
+        }
+        None => v,
+    }
+}
+
+//a generator of the multiplicative group of the field
+fn primitive_element<F: MetaType>() -> F
+where
+    F::Signature: FiniteFieldSignature,
+{
+    let field = F::structure();
+    let units = field.all_units();
+    units
+        .iter()
+        .find(|u| {
+            let mut x = (*u).clone();
+            let mut order = 1;
+            while !field.equal(&x, &field.one()) {
+                x = field.mul(&x, u);
+                order += 1;
+            }
+            order == units.len()
+        })
+        .unwrap()
+        .clone()
+}
+
+//a basis of the field as a vector space over its prime subfield
+//the powers 1, w, w^2, ..., w^{k-1} of a primitive element w of a field of order p^k are such a basis
+fn additive_basis<F: MetaType>() -> Vec<F>
+where
+    F::Signature: FiniteFieldSignature,
+{
+    let field = F::structure();
+    let mut p = 1;
+    let mut x = field.one();
+    while !field.is_zero(&x) {
+        x = field.add(&x, &field.one());
+        p += 1;
+    }
+    let q = field.list_all_elements().len();
+    let mut k = 0;
+    let mut p_pow = 1;
+    while p_pow < q {
+        p_pow *= p;
+        k += 1;
+    }
+    let w = primitive_element::<F>();
+    let mut basis = vec![];
+    let mut power = field.one();
+    for _ in 0..k {
+        basis.push(power.clone());
+        power = field.mul(&power, &w);
+    }
+    basis
+}
+
+#[cfg(test)]
+mod matrix_group_tests {
+    use super::*;
+    use crate::finite_fields::modulo::Modulo;
+    use crate::finite_fields::quaternary_field::QuaternaryField;
+    use algebraeon_groups::composition_table::small_groups::SmallGroupId;
+
+    #[test]
+    fn test_linear_group_orders() {
+        assert_eq!(
+            MatrixGroup::<Modulo<2>, 2>::general_linear().order(),
+            Natural::from(6u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 2>::general_linear().order(),
+            Natural::from(48u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 2>::general_linear().order(),
+            Natural::from(480u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<2>, 3>::general_linear().order(),
+            Natural::from(168u32)
+        );
+        assert_eq!(
+            MatrixGroup::<QuaternaryField, 2>::general_linear().order(),
+            Natural::from(180u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 3>::general_linear().order(),
+            Natural::from(11232u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 1>::general_linear().order(),
+            Natural::from(4u32)
+        );
+
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 2>::special_linear().order(),
+            Natural::from(24u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 2>::special_linear().order(),
+            Natural::from(120u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 3>::special_linear().order(),
+            Natural::from(5616u32)
+        );
+        assert_eq!(
+            MatrixGroup::<QuaternaryField, 2>::special_linear().order(),
+            Natural::from(60u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<7>, 2>::special_linear().order(),
+            Natural::from(336u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<2>, 4>::special_linear().order(),
+            Natural::from(20160u32)
+        );
+        assert_eq!(
+            MatrixGroup::<QuaternaryField, 3>::special_linear().order(),
+            Natural::from(60480u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 3>::special_linear().order(),
+            Natural::from(372000u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 1>::special_linear().order(),
+            Natural::from(1u32)
+        );
+
+        //the standard generating sets have two elements
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 3>::general_linear()
+                .generators()
+                .len(),
+            2
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 3>::special_linear()
+                .generators()
+                .len(),
+            2
+        );
+        for g in MatrixGroup::<Modulo<5>, 3>::special_linear().generators() {
+            assert_eq!(g.det(), Modulo::from(1usize));
+        }
+    }
+
+    #[test]
+    fn test_projective_special_linear_group_orders() {
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 2>::special_linear().projective_order(),
+            Natural::from(12u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 2>::special_linear().projective_order(),
+            Natural::from(60u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<7>, 2>::special_linear().projective_order(),
+            Natural::from(168u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<2>, 3>::special_linear().projective_order(),
+            Natural::from(168u32)
+        );
+        assert_eq!(
+            MatrixGroup::<QuaternaryField, 2>::special_linear().projective_order(),
+            Natural::from(60u32)
+        );
+    }
+
+    #[test]
+    fn test_symplectic_group_orders() {
+        assert!(MatrixGroup::<Modulo<3>, 3>::symplectic().is_err());
+        //Sp(2, q) = SL(2, q)
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 2>::symplectic().unwrap().order(),
+            Natural::from(24u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<2>, 4>::symplectic().unwrap().order(),
+            Natural::from(720u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 4>::symplectic().unwrap().order(),
+            Natural::from(51840u32)
+        );
+    }
+
+    #[test]
+    fn test_orthogonal_group_orders() {
+        assert!(MatrixGroup::<Modulo<2>, 2>::orthogonal().is_err());
+        //x^2 + y^2 is anisotropic over F_3 and split over F_5
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 2>::orthogonal().unwrap().order(),
+            Natural::from(8u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 2>::orthogonal().unwrap().order(),
+            Natural::from(8u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<3>, 3>::orthogonal().unwrap().order(),
+            Natural::from(48u32)
+        );
+        assert_eq!(
+            MatrixGroup::<Modulo<5>, 3>::orthogonal().unwrap().order(),
+            Natural::from(240u32)
+        );
+    }
+
+    #[test]
+    fn test_membership() {
+        let sl = MatrixGroup::<Modulo<5>, 2>::special_linear();
+        let minus_one = InvertibleMatrix::new(Matrix::from_rows(vec![
+            vec![Modulo::<5>::from(4usize), Modulo::from(0usize)],
+            vec![Modulo::from(0usize), Modulo::from(4usize)],
+        ]))
+        .unwrap();
+        let two = InvertibleMatrix::new(Matrix::from_rows(vec![
+            vec![Modulo::<5>::from(2usize), Modulo::from(0usize)],
+            vec![Modulo::from(0usize), Modulo::from(1usize)],
+        ]))
+        .unwrap();
+        assert!(sl.contains(&minus_one));
+        assert!(!sl.contains(&two));
+        assert!(MatrixGroup::<Modulo<5>, 2>::general_linear().contains(&two));
+
+        assert!(
+            InvertibleMatrix::<Modulo<5>, 2>::new(Matrix::from_rows(vec![
+                vec![Modulo::<5>::from(1usize), Modulo::from(2usize)],
+                vec![Modulo::from(2usize), Modulo::from(4usize)],
+            ]))
+            .is_err()
+        );
+        assert_eq!(
+            InvertibleMatrix::compose_refs(&two, &two.inverse_ref()),
+            InvertibleMatrix::identity()
+        );
+    }
+
+    #[test]
+    fn test_composition_tables() {
+        //GL(2, 2) is S3
+        let (table, elems, _) = MatrixGroup::<Modulo<2>, 2>::general_linear().composition_table();
+        table.check_state().unwrap();
+        assert_eq!(elems.len(), 6);
+        assert_eq!(
+            table.small_group_id(),
+            Some(SmallGroupId { order: 6, index: 1 })
+        );
+
+        //SL(2, 3) is SmallGroup(24, 3)
+        let (table, _, _) = MatrixGroup::<Modulo<3>, 2>::special_linear().composition_table();
+        assert_eq!(
+            table.small_group_id(),
+            Some(SmallGroupId {
+                order: 24,
+                index: 3
+            })
+        );
+
+        //PSL(2, 3) is A4
+        let table = MatrixGroup::<Modulo<3>, 2>::special_linear().projective_composition_table();
+        assert_eq!(
+            table.small_group_id(),
+            Some(SmallGroupId {
+                order: 12,
+                index: 3
+            })
+        );
+    }
+}
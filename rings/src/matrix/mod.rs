@@ -8,6 +8,7 @@ mod gram_schmidt;
 mod hermite_reduction;
 mod jordan_normal_form;
 mod matrix;
+mod matrix_group;
 mod polynomial;
 mod primitive;
 mod row_operations;
@@ -15,6 +16,7 @@ mod smith_normal_form;
 
 pub use hermite_reduction::*;
 pub use matrix::*;
+pub use matrix_group::*;
 // pub use polynomial::*;
 use row_operations::*;
 // pub use smith_normal_form::*;
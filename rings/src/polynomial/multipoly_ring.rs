@@ -585,6 +585,11 @@ impl<RS: RingEqSignature, RSB: BorrowedStructure<RS>> RingSignature
 {
 }
 
+impl<RS: RingEqSignature + CommutativeRingSignature, RSB: BorrowedStructure<RS>>
+    CommutativeRingSignature for MultiPolynomialStructure<RS, RSB>
+{
+}
+
 impl<RS: CharacteristicSignature + RingEqSignature, RSB: BorrowedStructure<RS>>
     CharacteristicSignature for MultiPolynomialStructure<RS, RSB>
 {
@@ -273,6 +273,11 @@ impl<RS: RingEqSignature, RSB: BorrowedStructure<RS>> RingSignature
 {
 }
 
+impl<RS: RingEqSignature + CommutativeRingSignature, RSB: BorrowedStructure<RS>>
+    CommutativeRingSignature for PolynomialStructure<RS, RSB>
+{
+}
+
 impl<RS: RingEqSignature, RSB: BorrowedStructure<RS>> PolynomialStructure<RS, RSB> {
     pub fn reduce_poly(&self, a: Polynomial<RS::Set>) -> Polynomial<RS::Set> {
         self.coeff_ring().polynomial_semiring().reduce_poly(a)
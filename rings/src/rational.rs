@@ -56,6 +56,8 @@ impl SemiRingUnitsSignature for RationalCanonicalStructure {
     }
 }
 
+impl CommutativeRingSignature for RationalCanonicalStructure {}
+
 impl IntegralDomainSignature for RationalCanonicalStructure {
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError> {
         if b == &Rational::ZERO {
@@ -11,6 +11,7 @@ mod quotient;
 mod quotient_morphism;
 mod rings;
 mod ufd;
+mod units;
 
 pub use algebra::*;
 pub use ergonomic::*;
@@ -25,3 +26,4 @@ pub use quotient::*;
 pub use quotient_morphism::*;
 pub use rings::*;
 pub use ufd::*;
+pub use units::*;
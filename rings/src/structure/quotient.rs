@@ -218,6 +218,11 @@ impl<RS: EuclideanDomainSignature, RSB: BorrowedStructure<RS>, const IS_FIELD: b
     }
 }
 
+impl<RS: EuclideanDomainSignature, RSB: BorrowedStructure<RS>, const IS_FIELD: bool>
+    CommutativeRingSignature for EuclideanRemainderQuotientStructure<RS, RSB, IS_FIELD>
+{
+}
+
 impl<
     RS: EuclideanDomainSignature + FavoriteAssociateSignature,
     RSB: BorrowedStructure<RS>,
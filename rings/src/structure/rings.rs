@@ -228,6 +228,14 @@ pub trait SemiRingUnitsSignature: SemiRingSignature {
             Err(RingDivisionError::DivideByZero | RingDivisionError::NotDivisible) => false,
         }
     }
+
+    fn units<'a>(&'a self) -> MultiplicativeUnitsStructure<Self, &'a Self> {
+        MultiplicativeUnitsStructure::new(self)
+    }
+
+    fn into_units(self) -> MultiplicativeUnitsStructure<Self, Self> {
+        MultiplicativeUnitsStructure::new(self)
+    }
 }
 pub trait MetaSemiRingUnitsSignature: MetaType
 where
@@ -248,7 +256,12 @@ impl<R: MetaType> MetaSemiRingUnitsSignature for R where
 pub trait RingUnitsSignature: RingSignature + SemiRingUnitsSignature {}
 impl<Ring: RingSignature + SemiRingUnitsSignature> RingUnitsSignature for Ring {}
 
-pub trait IntegralDomainSignature: RingUnitsSignature + EqSignature {
+/// A ring whose multiplication is commutative.
+pub trait CommutativeRingSignature: RingSignature {}
+
+pub trait IntegralDomainSignature:
+    CommutativeRingSignature + RingUnitsSignature + EqSignature
+{
     fn div(&self, a: &Self::Set, b: &Self::Set) -> Result<Self::Set, RingDivisionError>;
 
     fn try_from_rat(&self, x: &Rational) -> Option<Self::Set> {
@@ -0,0 +1,163 @@
+use super::*;
+use algebraeon_groups::structure::{AbelianGroupSignature, GroupSignature, MonoidSignature};
+use algebraeon_sets::structure::*;
+use std::marker::PhantomData;
+
+/// The group of units of a ring under multiplication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiplicativeUnitsStructure<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> {
+    _ring: PhantomData<RS>,
+    ring: RSB,
+}
+
+impl<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> MultiplicativeUnitsStructure<RS, RSB> {
+    pub fn new(ring: RSB) -> Self {
+        Self {
+            _ring: PhantomData,
+            ring,
+        }
+    }
+
+    pub fn ring(&self) -> &RS {
+        self.ring.borrow()
+    }
+}
+
+impl<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> Signature
+    for MultiplicativeUnitsStructure<RS, RSB>
+{
+}
+
+impl<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> SetSignature
+    for MultiplicativeUnitsStructure<RS, RSB>
+{
+    type Set = RS::Set;
+
+    fn is_element(&self, x: &Self::Set) -> Result<(), String> {
+        self.ring().is_element(x)?;
+        if self.ring().is_unit(x) {
+            Ok(())
+        } else {
+            Err("not a unit".to_string())
+        }
+    }
+}
+
+impl<RS: SemiRingUnitsSignature + EqSignature, RSB: BorrowedStructure<RS>> EqSignature
+    for MultiplicativeUnitsStructure<RS, RSB>
+{
+    fn equal(&self, a: &Self::Set, b: &Self::Set) -> bool {
+        self.ring().equal(a, b)
+    }
+}
+
+impl<RS: SemiRingUnitsSignature + FiniteUnitsSignature, RSB: BorrowedStructure<RS>>
+    CountableSetSignature for MultiplicativeUnitsStructure<RS, RSB>
+{
+    fn generate_all_elements(&self) -> impl Iterator<Item = Self::Set> + Clone {
+        self.ring().all_units().into_iter()
+    }
+}
+
+impl<RS: SemiRingUnitsSignature + FiniteUnitsSignature, RSB: BorrowedStructure<RS>>
+    FiniteSetSignature for MultiplicativeUnitsStructure<RS, RSB>
+{
+}
+
+impl<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> MonoidSignature
+    for MultiplicativeUnitsStructure<RS, RSB>
+{
+    fn identity(&self) -> Self::Set {
+        self.ring().one()
+    }
+
+    fn compose(&self, a: &Self::Set, b: &Self::Set) -> Self::Set {
+        self.ring().mul(a, b)
+    }
+}
+
+impl<RS: SemiRingUnitsSignature, RSB: BorrowedStructure<RS>> GroupSignature
+    for MultiplicativeUnitsStructure<RS, RSB>
+{
+    fn inverse(&self, a: &Self::Set) -> Self::Set {
+        self.ring().inv(a).unwrap()
+    }
+}
+
+impl<RS: SemiRingUnitsSignature + CommutativeRingSignature, RSB: BorrowedStructure<RS>>
+    AbelianGroupSignature for MultiplicativeUnitsStructure<RS, RSB>
+{
+}
+
+#[cfg(test)]
+mod units_tests {
+    use super::*;
+    use crate::finite_fields::modulo::Modulo;
+    use algebraeon_groups::composition_table::small_groups::SmallGroupId;
+    use algebraeon_groups::structure::FiniteGroupSignature;
+    use algebraeon_nzq::{Integer, Natural};
+
+    fn abelian_group<G: AbelianGroupSignature>(group: G) -> G {
+        group
+    }
+
+    #[test]
+    fn test_integer_units() {
+        let units = Integer::structure().into_units();
+        assert!(units.is_element(&Integer::from(-1)).is_ok());
+        assert!(units.is_element(&Integer::from(2)).is_err());
+        assert_eq!(units.identity(), Integer::from(1));
+        assert_eq!(
+            units.compose(&Integer::from(-1), &Integer::from(-1)),
+            Integer::from(1)
+        );
+        assert_eq!(units.inverse(&Integer::from(-1)), Integer::from(-1));
+    }
+
+    #[test]
+    fn test_quotient_ring_units_are_abelian() {
+        //(Z/8Z)^* is abelian even though Z/8Z is not an integral domain
+        let units = abelian_group(
+            Integer::structure()
+                .into_quotient_ring(Integer::from(8))
+                .into_units(),
+        );
+        let elems = [1, 3, 5, 7].map(Integer::from);
+        assert!(units.is_element(&Integer::from(2)).is_err());
+        for a in &elems {
+            assert!(units.is_element(a).is_ok());
+            assert_eq!(units.compose(a, a), Integer::from(1));
+            for b in &elems {
+                assert_eq!(units.compose(a, b), units.compose(b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_finite_units() {
+        //(Z/8Z)^* is the Klein four group, abelian although Z/8Z is not an integral domain
+        let units = abelian_group(Modulo::<8>::structure().into_units());
+        assert_eq!(units.size(), 4);
+        assert!(FiniteGroupSignature::is_abelian(&units));
+        assert_eq!(
+            units.multiplication_table().small_group_id(),
+            Some(SmallGroupId { order: 4, index: 2 })
+        );
+
+        //the units of a finite field are cyclic
+        let units = Modulo::<13>::structure().into_units();
+        assert_eq!(units.size(), 12);
+        assert_eq!(units.element_order(&Modulo::<13>::from(2usize)), 12);
+        assert_eq!(
+            units.nat_pow(&Modulo::<13>::from(2usize), &Natural::from(12u32)),
+            Modulo::<13>::from(1usize)
+        );
+        assert_eq!(
+            units.multiplication_table().small_group_id(),
+            Some(SmallGroupId {
+                order: 12,
+                index: 2
+            })
+        );
+    }
+}